@@ -31,15 +31,14 @@ fn show_enemy_paths(
 
     // debug visualization of enemy paths
     for ai in query.iter() {
-        if let AiMovementState::Moving {
-            path,
-            index: _index,
-        } = ai
-        {
-            gizmos.linestrip(
-                path.clone().iter().map(|v| v.with_y(0.2)),
-                palettes::css::BLUE,
-            );
+        if let AiMovementState::Moving { path, partial, .. } = ai {
+            // paths that stop short of the player show up orange
+            let color = if *partial {
+                palettes::css::ORANGE
+            } else {
+                palettes::css::BLUE
+            };
+            gizmos.linestrip(path.clone().iter().map(|v| v.with_y(0.2)), color);
         }
     }
 }
@@ -1,6 +1,12 @@
+use crate::HotSystems;
 use crate::ai::pathfinding_service::PathfindingState;
+use crate::ai::strafe::StrafeBehavior;
 use crate::gameplay::Gameplay;
 use crate::gameplay::enemy::Enemy;
+use crate::gameplay::enemy::activity::{Activity, ActivityBubble};
+use crate::gameplay::enemy::flinch::Flinching;
+use crate::gameplay::enemy::spawning::Spawning;
+use crate::gameplay::enemy::squad::Alerted;
 use crate::gameplay::player::Player;
 use avian3d::prelude::LinearVelocity;
 use bevy::prelude::*;
@@ -44,7 +50,14 @@ impl Default for FollowPlayerBehavior {
 pub enum AiMovementState {
     Observing,
     FindingPath,
-    Moving { path: Vec<Vec3>, index: usize },
+    Moving {
+        path: Vec<Vec3>,
+        index: usize,
+        /// Where the player was when the path got calculated.
+        goal: Vec3,
+        /// The path stops short of the player, so stay put at its end instead of trying again.
+        partial: bool,
+    },
 }
 impl AiMovementState {
     pub fn plugin(app: &mut App) {
@@ -57,7 +70,9 @@ impl AiMovementState {
         );
         app.add_systems(
             Update,
-            Self::following_player_state_machine.run_if(in_state(Gameplay::Normal)),
+            Self::following_player_state_machine
+                .run_if(in_state(Gameplay::Normal))
+                .in_set(HotSystems::EnemyAi),
         );
         app.register_type::<AiMovementState>();
     }
@@ -72,18 +87,33 @@ impl AiMovementState {
                 &FollowPlayerBehavior,
                 &mut LinearVelocity,
                 Option<&PathfindingState>,
+                Option<&StrafeBehavior>,
+                Has<Alerted>,
+                &Activity,
+            ),
+            (
+                With<Enemy>,
+                Without<Player>,
+                Without<Spawning>,
+                Without<Flinching>,
             ),
-            (With<Enemy>, Without<Player>),
         >,
+        activity_bubble: ActivityBubble,
         mut commands: Commands,
     ) {
         let target = player.translation;
-        for (e, t, state, behavior, mut linear_velocity, pathfinding) in enemies.iter_mut() {
+        for (e, t, state, behavior, mut linear_velocity, pathfinding, strafe, alerted, activity) in
+            enemies.iter_mut()
+        {
+            if !activity_bubble.is_due(e, *activity) {
+                continue;
+            }
             let me = t.translation;
             let state = state.into_inner();
             match state {
                 AiMovementState::Observing => {
-                    if target.distance(me) < behavior.detection_range
+                    // an alerted squad comes from anywhere
+                    if (alerted || target.distance(me) < behavior.detection_range)
                         && target.distance(me) > behavior.distance_to_keep
                     {
                         commands
@@ -93,25 +123,42 @@ impl AiMovementState {
                     }
                 }
                 AiMovementState::FindingPath => {
-                    if let Some(PathfindingState::Completed(found_path)) = pathfinding {
+                    if let Some(PathfindingState::Completed { path, partial }) = pathfinding {
                         commands
                             .entity(e)
                             .insert(AiMovementState::Moving {
                                 index: 1,
-                                path: found_path.clone(),
+                                path: path.clone(),
+                                goal: target,
+                                partial: *partial,
                             })
                             .remove::<PathfindingState>();
                     }
                 }
-                AiMovementState::Moving { path, index } => {
+                AiMovementState::Moving {
+                    path,
+                    index,
+                    goal,
+                    partial,
+                } => {
                     // first, a staleness check - if player has moved too far from the original path we want to recompute it instead.
-                    let target_deviation = path.last().map(|v| v.distance(target)).unwrap_or(0.);
-                    if target_deviation > behavior.staleness_range {
+                    if goal.distance(target) > behavior.staleness_range {
                         info!("target moved! recalculating...");
                         commands.entity(e).insert(AiMovementState::Observing);
                         continue;
                     }
 
+                    // let the sidestep finish first, we'll pick up the path again afterwards
+                    if strafe.is_some_and(StrafeBehavior::is_stepping) {
+                        continue;
+                    }
+
+                    // as close as we can get, so hold position until the player moves
+                    if *partial && *index >= path.len() {
+                        *linear_velocity = LinearVelocity::ZERO;
+                        continue;
+                    }
+
                     let me = me.with_y(0.0); // our capsules' y are 1.0, while the pathfinding nodes are at 0.0
                     let next = path.get(*index).unwrap_or(&target);
                     let dist = (next - me).length();
@@ -124,7 +171,7 @@ impl AiMovementState {
                         *index += 1; // doesn't work
                     }
 
-                    if *index >= path.len() {
+                    if *index >= path.len() && !*partial {
                         commands.entity(e).insert(AiMovementState::Observing);
                         commands.entity(e).insert(LinearVelocity::ZERO);
                     }
@@ -1,6 +1,8 @@
 mod debug;
 pub mod enemy_ai;
 pub mod pathfinding_service;
+pub mod strafe;
+pub mod wander;
 
 use bevy::prelude::*;
 use oxidized_navigation::{
@@ -13,6 +15,8 @@ pub fn plugin(app: &mut App) {
         // navmesh_position::plugin,
         pathfinding_service::plugin,
         enemy_ai::plugin,
+        strafe::plugin,
+        wander::plugin,
         debug::plugin,
         OxidizedNavigationPlugin::<AvianCollider>::new(NavMeshSettings::from_agent_and_bounds(
             1.1, 1.9, 1000.0, -1.0,
@@ -1,21 +1,41 @@
 use bevy::prelude::*;
-use oxidized_navigation::query::{find_polygon_path, perform_string_pulling_on_path};
+use oxidized_navigation::query::{
+    find_polygon_path, get_closest_polygon_in_box, perform_string_pulling_on_path,
+};
+use oxidized_navigation::tiles::NavMeshTiles;
 use oxidized_navigation::{NavMesh, NavMeshSettings};
 
+use crate::HotSystems;
 use crate::gameplay::Gameplay;
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<PathfindingService>();
     app.add_systems(
         Update,
-        PathfindingService::run_blocking_pathfinding.run_if(in_state(Gameplay::Normal)),
+        PathfindingService::run_blocking_pathfinding
+            .run_if(in_state(Gameplay::Normal))
+            .in_set(HotSystems::Pathfinding),
     );
 }
 
+/// Cost multipliers per navmesh area, passed to every path query.
+const AREA_COST_MULTIPLIERS: [f32; 2] = [1.0, 0.5];
+
+/// When the target is off the navmesh (e.g. standing on a crate), look for the closest reachable
+/// spot in boxes of these half-extents around it, smallest first.
+const FALLBACK_SEARCH_EXTENTS: [f32; 3] = [2.0, 5.0, 10.0];
+
 #[derive(Component)]
 pub enum PathfindingState {
-    Requested { a: Vec3, b: Vec3 },
-    Completed(Vec<Vec3>),
+    Requested {
+        a: Vec3,
+        b: Vec3,
+    },
+    Completed {
+        path: Vec<Vec3>,
+        /// The target couldn't be reached, so the path ends at the closest spot we could get to.
+        partial: bool,
+    },
 }
 impl PathfindingState {
     pub fn new(a: Vec3, b: Vec3) -> Self {
@@ -36,40 +56,71 @@ impl PathfindingService {
         // Get the underlying nav_mesh.
         if let Ok(nav_mesh) = nav_mesh.get().read() {
             for (entity, state) in query.iter() {
-                match state {
-                    PathfindingState::Requested { a, b } => {
-                        // execute the sync pathfinding job
-                        let start_pos = *a;
-                        let end_pos = *b;
+                let PathfindingState::Requested { a, b } = state else {
+                    continue;
+                };
 
-                        // Run pathfinding to get a polygon path.
-                        match find_polygon_path(
-                            &nav_mesh,
-                            &nav_mesh_settings,
-                            start_pos,
-                            end_pos,
-                            None,
-                            Some(&[1.0, 0.5]),
-                        ) {
-                            Ok(path) => {
-                                // Convert polygon path to a path of Vec3s.
-                                match perform_string_pulling_on_path(
-                                    &nav_mesh, start_pos, end_pos, &path,
-                                ) {
-                                    Ok(string_path) => {
-                                        commands
-                                            .entity(entity)
-                                            .insert(PathfindingState::Completed(string_path));
-                                    }
-                                    Err(error) => error!("Error with string path: {:?}", error),
-                                };
-                            }
-                            Err(error) => error!("Error with pathfinding: {:?}", error),
+                // execute the sync pathfinding job
+                let completed = match find_path(&nav_mesh, &nav_mesh_settings, *a, *b) {
+                    Some(path) => PathfindingState::Completed {
+                        path,
+                        partial: false,
+                    },
+                    None => match find_partial_path(&nav_mesh, &nav_mesh_settings, *a, *b) {
+                        Some(path) => PathfindingState::Completed {
+                            path,
+                            partial: true,
+                        },
+                        None => {
+                            error!("No path from {a} towards {b}, not even a partial one");
+                            continue;
                         }
-                    }
-                    _ => continue,
-                }
+                    },
+                };
+                commands.entity(entity).insert(completed);
             }
         }
     }
 }
+
+/// Runs pathfinding to get a polygon path, then converts it to a path of Vec3s.
+fn find_path(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+) -> Option<Vec<Vec3>> {
+    let path = find_polygon_path(
+        nav_mesh,
+        nav_mesh_settings,
+        start_pos,
+        end_pos,
+        None,
+        Some(&AREA_COST_MULTIPLIERS),
+    )
+    .inspect_err(|error| debug!("Error with pathfinding: {:?}", error))
+    .ok()?;
+
+    perform_string_pulling_on_path(nav_mesh, start_pos, end_pos, &path)
+        .inspect_err(|error| error!("Error with string path: {:?}", error))
+        .ok()
+}
+
+/// Paths to the closest point on the navmesh around `end_pos` instead, widening the search
+/// until something reachable turns up.
+fn find_partial_path(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    start_pos: Vec3,
+    end_pos: Vec3,
+) -> Option<Vec<Vec3>> {
+    FALLBACK_SEARCH_EXTENTS.iter().find_map(|extents| {
+        let (_, closest) = get_closest_polygon_in_box(
+            nav_mesh,
+            nav_mesh_settings,
+            end_pos,
+            Vec3::splat(*extents),
+        )?;
+        find_path(nav_mesh, nav_mesh_settings, start_pos, closest)
+    })
+}
@@ -0,0 +1,153 @@
+use crate::HotSystems;
+use crate::gameplay::Gameplay;
+use crate::gameplay::enemy::activity::{Activity, ActivityBubble};
+use crate::gameplay::enemy::flinch::Flinching;
+use crate::gameplay::enemy::spawning::Spawning;
+use crate::gameplay::player::Player;
+use crate::physics_layers::GameLayer;
+use avian3d::prelude::{LinearVelocity, SpatialQuery, SpatialQueryFilter};
+use bevy::prelude::*;
+use oxidized_navigation::query::get_closest_polygon_in_box;
+use oxidized_navigation::tiles::NavMeshTiles;
+use oxidized_navigation::{NavMesh, NavMeshSettings};
+use rand::{Rng, thread_rng};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<StrafeBehavior>();
+    app.add_systems(
+        Update,
+        strafe
+            .run_if(in_state(Gameplay::Normal))
+            .in_set(HotSystems::EnemyAi),
+    );
+}
+
+/// Short sidesteps around the player while there's nothing better to do, e.g. while reloading.
+/// Backs off instead when the player gets too close. Every step has to end on the navmesh
+/// and not run into a wall, otherwise it tries the other way.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct StrafeBehavior {
+    /// Whether to strafe right now. Left to whoever owns this behavior.
+    pub active: bool,
+    pub movement_speed: f32,
+    /// How far a single step goes.
+    pub step_length: f32,
+    /// Back away from the player when they are closer than this.
+    pub keep_away: f32,
+    destination: Option<Vec3>,
+}
+
+impl StrafeBehavior {
+    pub fn new(movement_speed: f32, keep_away: f32) -> Self {
+        Self {
+            active: false,
+            movement_speed,
+            step_length: 1.5,
+            keep_away,
+            destination: None,
+        }
+    }
+
+    /// Whether a step is underway, in which case other movement should hold off.
+    pub fn is_stepping(&self) -> bool {
+        self.destination.is_some()
+    }
+}
+
+/// How close to a step's destination counts as arrived.
+const ARRIVAL_DISTANCE: f32 = 0.3;
+/// Steps may end at most this far (horizontally) from the navmesh.
+const MAX_NAVMESH_OFFSET: f32 = 0.5;
+/// Half-extents of the box the end of a step gets looked up in on the navmesh.
+const NAVMESH_SEARCH_EXTENTS: Vec3 = Vec3::new(1.0, 2.0, 1.0);
+
+fn strafe(
+    nav_mesh_settings: Res<NavMeshSettings>,
+    nav_mesh: Res<NavMesh>,
+    spatial_query: SpatialQuery,
+    player: Single<&Transform, With<Player>>,
+    activity_bubble: ActivityBubble,
+    mut strafers: Query<
+        (
+            Entity,
+            &Transform,
+            &mut StrafeBehavior,
+            &mut LinearVelocity,
+            Option<&Activity>,
+        ),
+        (Without<Spawning>, Without<Flinching>),
+    >,
+) {
+    let Ok(nav_mesh) = nav_mesh.get().read() else {
+        return;
+    };
+    let mut rng = thread_rng();
+    for (entity, transform, mut strafe, mut velocity, activity) in &mut strafers {
+        if !activity_bubble.is_due(entity, activity.copied().unwrap_or_default()) {
+            continue;
+        }
+        let position = transform.translation;
+        if !strafe.active {
+            if strafe.destination.take().is_some() {
+                velocity.x = 0.0;
+                velocity.z = 0.0;
+            }
+            continue;
+        }
+
+        if let Some(destination) = strafe.destination {
+            let offset = (destination - position).with_y(0.0);
+            if offset.length() < ARRIVAL_DISTANCE {
+                strafe.destination = None;
+                velocity.x = 0.0;
+                velocity.z = 0.0;
+            } else {
+                let step = offset.normalize() * strafe.movement_speed;
+                velocity.x = step.x;
+                velocity.z = step.z;
+            }
+            continue;
+        }
+
+        let Some(to_player) = (player.translation - position).with_y(0.0).try_normalize() else {
+            continue;
+        };
+        let directions = if player.translation.distance(position) < strafe.keep_away {
+            vec![-to_player]
+        } else {
+            // pick a side at random, so they don't all circle the same way
+            let side = to_player.cross(Vec3::Y);
+            if rng.gen_bool(0.5) {
+                vec![side, -side]
+            } else {
+                vec![-side, side]
+            }
+        };
+
+        strafe.destination = directions.into_iter().find_map(|direction| {
+            let length = strafe.step_length;
+            let filter = SpatialQueryFilter::from_mask([GameLayer::Terrain, GameLayer::Default]);
+            if spatial_query
+                .cast_ray(position, Dir3::new(direction).ok()?, length, true, &filter)
+                .is_some()
+            {
+                return None;
+            }
+            let on_nav_mesh =
+                closest_on_nav_mesh(&nav_mesh, &nav_mesh_settings, position + direction * length)?;
+            Some(on_nav_mesh.with_y(position.y))
+        });
+    }
+}
+
+/// The point on the navmesh right below `point`, if there is one.
+pub(crate) fn closest_on_nav_mesh(
+    nav_mesh: &NavMeshTiles,
+    nav_mesh_settings: &NavMeshSettings,
+    point: Vec3,
+) -> Option<Vec3> {
+    let (_, closest) =
+        get_closest_polygon_in_box(nav_mesh, nav_mesh_settings, point, NAVMESH_SEARCH_EXTENTS)?;
+    (closest.xz().distance(point.xz()) < MAX_NAVMESH_OFFSET).then_some(closest)
+}
@@ -0,0 +1,86 @@
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::physics_layers::GameLayer;
+use avian3d::prelude::{LinearVelocity, SpatialQuery, SpatialQueryFilter};
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<WanderBehavior>();
+    app.add_systems(Update, wander.run_if(in_state(Gameplay::Normal)));
+}
+
+/// Aimlessly strolls around its home, pausing every now and then.
+/// Walks in straight lines and stops short of walls, so it doesn't need the navmesh.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct WanderBehavior {
+    pub home: Vec3,
+    /// How far from home to wander.
+    pub radius: f32,
+    pub movement_speed: f32,
+    /// How long to stand around after reaching a destination.
+    pub pause: Timer,
+    destination: Option<Vec3>,
+}
+
+impl WanderBehavior {
+    pub fn new(home: Vec3, radius: f32, movement_speed: f32) -> Self {
+        Self {
+            home,
+            radius,
+            movement_speed,
+            pause: Timer::from_seconds(2.0, TimerMode::Repeating),
+            destination: None,
+        }
+    }
+}
+
+/// Stay this far away from walls.
+const WALL_MARGIN: f32 = 1.0;
+
+fn wander(
+    time: GameTime,
+    spatial_query: SpatialQuery,
+    mut wanderers: Query<(&Transform, &mut WanderBehavior, &mut LinearVelocity)>,
+) {
+    let mut rng = thread_rng();
+    for (transform, mut wander, mut velocity) in &mut wanderers {
+        let position = transform.translation;
+        match wander.destination {
+            Some(destination) => {
+                let offset = (destination - position).with_y(0.0);
+                if offset.length() < 0.5 {
+                    wander.destination = None;
+                    velocity.0 = Vec3::ZERO;
+                } else {
+                    velocity.0 = offset.normalize() * wander.movement_speed;
+                }
+            }
+            None => {
+                if !wander.pause.tick(time.delta()).just_finished() {
+                    continue;
+                }
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let distance = rng.gen_range(0.0..wander.radius);
+                let candidate = wander.home + Quat::from_rotation_y(angle) * Vec3::X * distance;
+
+                let Ok((direction, length)) =
+                    Dir3::new_and_length((candidate - position).with_y(0.0))
+                else {
+                    continue;
+                };
+                let filter =
+                    SpatialQueryFilter::from_mask([GameLayer::Terrain, GameLayer::Default]);
+                let reachable =
+                    match spatial_query.cast_ray(position, direction, length, true, &filter) {
+                        Some(hit) => hit.distance - WALL_MARGIN,
+                        None => length,
+                    };
+                if reachable > 0.5 {
+                    wander.destination = Some(position + direction * reachable);
+                }
+            }
+        }
+    }
+}
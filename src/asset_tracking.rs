@@ -1,8 +1,12 @@
 //! A high-level way to load collections of asset handles as resources.
 
 use std::collections::VecDeque;
+use std::marker::PhantomData;
 
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
 use bevy::prelude::*;
+use serde::de::DeserializeOwned;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<ResourceHandles>();
@@ -54,6 +58,19 @@ impl ResourceHandles {
     }
 }
 
+/// Inserts every resource requested via [`LoadResource`] right away, without waiting for its assets
+/// to finish loading. Headless tests have no loaders for models, audio or fonts, so they would
+/// otherwise wait forever.
+#[cfg(test)]
+pub fn insert_resources_without_loading(world: &mut World) {
+    world.resource_scope(|world, mut resource_handles: Mut<ResourceHandles>| {
+        while let Some((handle, insert_fn)) = resource_handles.waiting.pop_front() {
+            insert_fn(world, &handle);
+            resource_handles.finished.push(handle);
+        }
+    });
+}
+
 fn load_resource_assets(world: &mut World) {
     world.resource_scope(|world, mut resource_handles: Mut<ResourceHandles>| {
         world.resource_scope(|world, assets: Mut<AssetServer>| {
@@ -69,3 +86,68 @@ fn load_resource_assets(world: &mut World) {
         });
     });
 }
+
+/// An [`Asset`] that's read straight from a RON file, see [`RonAssetLoader`].
+pub trait RonAsset: Asset + DeserializeOwned {
+    /// The file extensions to load this asset from, e.g. `"credits.ron"`.
+    const EXTENSIONS: &'static [&'static str];
+}
+
+/// Loads any [`RonAsset`]. Register it with `app.init_asset_loader::<RonAssetLoader<T>>()`.
+pub struct RonAssetLoader<T>(PhantomData<T>);
+
+impl<T> Default for RonAssetLoader<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[derive(Debug)]
+pub enum RonAssetLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for RonAssetLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RonAssetLoaderError::Io(error) => write!(f, "could not read file: {error}"),
+            RonAssetLoaderError::Ron(error) => write!(f, "could not parse file: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RonAssetLoaderError {}
+
+impl From<std::io::Error> for RonAssetLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for RonAssetLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+impl<T: RonAsset> AssetLoader for RonAssetLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = RonAssetLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        T::EXTENSIONS
+    }
+}
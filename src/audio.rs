@@ -1,12 +1,27 @@
-use avian3d::prelude::{Physics, PhysicsTime};
+use crate::clock::{GameTime, UiTime};
+use crate::settings::{Settings, SettingsApplied};
+use avian3d::prelude::PhysicsTime;
 use bevy::asset::Handle;
 use bevy::audio::{
-    AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, PlaybackSettings, Volume,
+    AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, GlobalVolume, PlaybackSettings, Volume,
 };
-use bevy::ecs::system::{Query, Res};
-use bevy::prelude::{Bundle, Component};
+use bevy::prelude::*;
 use bevy::time::Time;
 
+pub fn plugin(app: &mut App) {
+    app.init_resource::<MusicDucking>()
+        .register_type::<MusicDucking>();
+    app.add_observer(duck_music)
+        .add_observer(apply_volume_setting);
+    app.add_systems(
+        Update,
+        (
+            (attenuate_by_distance, update_sfx_speed).chain(),
+            update_music_ducking,
+        ),
+    );
+}
+
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
 /// general "music" category (e.g. global background music, soundtrack).
 ///
@@ -45,8 +60,219 @@ pub fn sound_effect_non_dilated(handle: Handle<AudioSource>, decibels: f32) -> i
 #[derive(Component)]
 pub struct TimeDilatedPitch(pub f32);
 
-pub fn update_sfx_speed(time: Res<Time<Physics>>, query: Query<(&AudioSink, &TimeDilatedPitch)>) {
-    for (sink, sfx) in &query {
-        sink.set_speed(time.relative_speed() * sfx.0);
+pub fn update_sfx_speed(
+    time: GameTime,
+    query: Query<(&AudioSink, &TimeDilatedPitch, Option<&DistanceAttenuation>)>,
+) {
+    for (sink, sfx, attenuation) in &query {
+        let doppler = attenuation.map_or(1.0, |attenuation| attenuation.pitch);
+        sink.set_speed(time.relative_speed() * sfx.0 * doppler);
+    }
+}
+
+// ===== DISTANCE =====
+
+/// Speed of sound in world units per second, used for the doppler shift. A lot slower than the
+/// real thing, otherwise boomerangs would hardly sound any different.
+const SPEED_OF_SOUND: f32 = 60.0;
+/// The doppler shift never goes further than this, up or down.
+const MAX_DOPPLER_SHIFT: f32 = 0.3;
+/// How quickly the doppler shift follows changes in speed, per second. The distance is measured
+/// every frame, which makes for a jittery speed.
+const DOPPLER_SMOOTHING: f32 = 10.0;
+
+/// Where sounds with a [`DistanceAttenuation`] are heard from. With several listeners, e.g. in
+/// co-op, the closest one counts.
+#[derive(Component, Debug, Default)]
+pub struct SfxListener;
+
+/// Makes a sound quieter the further it is from the closest [`SfxListener`], and shifts its pitch
+/// up while it comes closer and down while it moves away.
+/// Works on top of [`TimeDilatedPitch`] and the volume in the sound's [`PlaybackSettings`].
+#[derive(Component, Debug, Clone)]
+pub struct DistanceAttenuation {
+    /// Full volume up to this distance.
+    pub near: f32,
+    /// Silent from this distance on.
+    pub far: f32,
+    /// Multiplier on the playback speed, from the doppler shift.
+    pitch: f32,
+    previous_distance: Option<f32>,
+}
+
+impl DistanceAttenuation {
+    pub fn new(near: f32, far: f32) -> Self {
+        Self {
+            near,
+            far,
+            pitch: 1.0,
+            previous_distance: None,
+        }
+    }
+
+    fn gain(&self, distance: f32) -> f32 {
+        let t = ((distance - self.near) / (self.far - self.near).max(f32::EPSILON)).clamp(0.0, 1.0);
+        // falls off quickly at first, like real sounds do
+        (1.0 - t).powi(2)
+    }
+}
+
+fn attenuate_by_distance(
+    // game time, the doppler shift is about how fast things move in the game
+    time: GameTime,
+    global_volume: Res<GlobalVolume>,
+    listeners: Query<&GlobalTransform, With<SfxListener>>,
+    mut sounds: Query<(
+        &GlobalTransform,
+        &mut DistanceAttenuation,
+        Option<(&mut AudioSink, &PlaybackSettings)>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    for (transform, mut attenuation, sink) in &mut sounds {
+        let position = transform.translation();
+        let Some(distance) = listeners
+            .iter()
+            .map(|listener| listener.translation().distance(position))
+            .min_by(f32::total_cmp)
+        else {
+            attenuation.previous_distance = None;
+            continue;
+        };
+
+        if let Some(previous) = attenuation.previous_distance.filter(|_| delta > 0.0) {
+            // positive while moving away
+            let receding_speed = (distance - previous) / delta;
+            let target = (SPEED_OF_SOUND / (SPEED_OF_SOUND + receding_speed).max(f32::EPSILON))
+                .clamp(1.0 - MAX_DOPPLER_SHIFT, 1.0 + MAX_DOPPLER_SHIFT);
+            let blend = (DOPPLER_SMOOTHING * delta).min(1.0);
+            attenuation.pitch += (target - attenuation.pitch) * blend;
+        }
+        attenuation.previous_distance = Some(distance);
+
+        if let Some((mut sink, settings)) = sink {
+            let base = global_volume.volume.to_linear() * settings.volume.to_linear();
+            sink.set_volume(Volume::Linear(base * attenuation.gain(distance)));
+        }
+    }
+}
+
+fn apply_volume_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+) {
+    global_volume.volume = Volume::Linear(settings.volume);
+}
+
+// ===== DUCKING =====
+
+/// Why the music is getting quieter. Later variants win over earlier ones, so a scream can't cut
+/// a player death short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+pub enum DuckPriority {
+    DeathScream,
+    FinalKill,
+    PlayerDeath,
+}
+
+impl DuckPriority {
+    /// How loud the music gets while ducked.
+    fn volume(self) -> f32 {
+        match self {
+            DuckPriority::DeathScream => 0.6,
+            DuckPriority::FinalKill => 0.35,
+            DuckPriority::PlayerDeath => 0.15,
+        }
+    }
+
+    /// How long the music stays ducked before it fades back in.
+    fn hold_seconds(self) -> f32 {
+        match self {
+            DuckPriority::DeathScream => 0.8,
+            DuckPriority::FinalKill => 1.5,
+            DuckPriority::PlayerDeath => 2.5,
+        }
+    }
+}
+
+/// Trigger to make room for an important sound.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DuckMusic(pub DuckPriority);
+
+/// Seconds it takes the music to get fully quiet.
+const DUCK_FADE_OUT: f32 = 0.1;
+/// Seconds it takes the music to get back to full volume.
+const DUCK_FADE_IN: f32 = 0.6;
+
+/// Keeps track of the most important reason to duck the music right now and how far the music is
+/// currently turned down.
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct MusicDucking {
+    active: Option<(DuckPriority, Timer)>,
+    /// Multiplier on the music volume, 1.0 when nothing is ducking it.
+    volume: f32,
+}
+
+impl Default for MusicDucking {
+    fn default() -> Self {
+        Self {
+            active: None,
+            volume: 1.0,
+        }
+    }
+}
+
+fn duck_music(trigger: Trigger<DuckMusic>, mut ducking: ResMut<MusicDucking>) {
+    let priority = trigger.0;
+    if ducking
+        .active
+        .as_ref()
+        .is_some_and(|(active, _)| *active > priority)
+    {
+        return;
+    }
+    ducking.active = Some((
+        priority,
+        Timer::from_seconds(priority.hold_seconds(), TimerMode::Once),
+    ));
+}
+
+fn update_music_ducking(
+    // real time, slow-mo shouldn't drag the fades out
+    time: UiTime,
+    global_volume: Res<GlobalVolume>,
+    mut ducking: ResMut<MusicDucking>,
+    mut music: Query<(&mut AudioSink, &PlaybackSettings), With<Music>>,
+) {
+    if ducking.active.is_none() && ducking.volume >= 1.0 {
+        return;
+    }
+
+    let target = match &mut ducking.active {
+        Some((priority, hold)) => {
+            let volume = priority.volume();
+            if hold.tick(time.delta()).finished() {
+                ducking.active = None;
+            }
+            volume
+        }
+        None => 1.0,
+    };
+    let step = if target < ducking.volume {
+        time.delta_secs() / DUCK_FADE_OUT
+    } else {
+        time.delta_secs() / DUCK_FADE_IN
+    };
+    ducking.volume = if target < ducking.volume {
+        (ducking.volume - step).max(target)
+    } else {
+        (ducking.volume + step).min(target)
+    };
+
+    for (mut sink, settings) in &mut music {
+        let base = global_volume.volume.to_linear() * settings.volume.to_linear();
+        sink.set_volume(Volume::Linear(base * ducking.volume));
     }
 }
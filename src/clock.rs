@@ -0,0 +1,65 @@
+//! Which clock to read for what, decided in one place.
+//!
+//! Slow motion (see [`crate::gameplay::aim_mode`]) and sequences only ever change
+//! `Time<Physics>`, by setting its relative speed or pausing it. Bullets and outlaws are physics
+//! bodies, so they follow along on their own. Everything else that happens in the level has to
+//! follow along too, otherwise it keeps going at full speed while the world around it crawls, like
+//! enemies rising out of the ground in no time during slow motion. Menus, the HUD and the camera on
+//! the other hand have to stay snappy no matter what.
+//!
+//! - [`GameTime`]: everything in the level. Movement, boomerangs, cooldowns, spawning, particles,
+//!   fuses.
+//! - [`UiTime`]: menus, HUD, toasts, hints, the camera and input that has to feel the same in
+//!   slow motion, like debouncing a button.
+//!
+//! Sounds follow [`GameTime`] through [`crate::audio::TimeDilatedPitch`].
+//! Plain `Res<Time>` is the virtual clock in `Update`, which nothing slows down, don't use it for
+//! either. Don't read `Time<Physics>` in `Update` either: it only advances in fixed steps, so its
+//! delta is that of the last fixed step, not of the frame. In dev builds, press 't' to see all
+//! clocks side by side.
+
+use avian3d::prelude::{Physics, PhysicsTime};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// The clock of everything happening in the level, slowed down by slow motion: the frame's delta,
+/// scaled by how fast physics runs right now. Stands still while physics is paused.
+#[derive(SystemParam)]
+pub struct GameTime<'w> {
+    frame: Res<'w, Time<Virtual>>,
+    physics: Res<'w, Time<Physics>>,
+}
+
+impl GameTime<'_> {
+    /// How fast time goes in the level, 1.0 being normal speed. Doesn't care whether it's paused.
+    pub fn relative_speed(&self) -> f32 {
+        self.physics.relative_speed()
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.frame.delta().mul_f32(self.running_speed())
+    }
+
+    pub fn delta_secs(&self) -> f32 {
+        self.frame.delta_secs() * self.running_speed()
+    }
+
+    /// How much time has passed in physics. Only good for things like wobbling back and forth, it
+    /// only moves forward in fixed steps.
+    pub fn elapsed_secs(&self) -> f32 {
+        self.physics.elapsed_secs()
+    }
+
+    fn running_speed(&self) -> f32 {
+        if self.physics.is_paused() {
+            0.0
+        } else {
+            self.relative_speed()
+        }
+    }
+}
+
+/// The clock of everything the player looks at or interacts with outside of the level itself,
+/// never slowed down.
+pub type UiTime<'w> = Res<'w, Time<Real>>;
@@ -0,0 +1,313 @@
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::{
+    BoomerangTargetLostEvent, BounceBoomerangEvent, ThrowBoomerangEvent,
+};
+use crate::gameplay::bullets::{FireBulletsEvent, LobDynamiteEvent};
+use crate::gameplay::health_and_damage::{
+    DeathEvent, Health, HealthEvent, HitAbsorbedEvent, HurtEvent, ShieldBlockEvent,
+};
+use crate::gameplay::level::LevelAssets;
+use crate::screens::Screen;
+use avian3d::prelude::LinearVelocity;
+use bevy::ecs::query::QueryData;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// BUG REPORT
+/// press 'F12' to capture one
+///
+/// - writes a folder `bug_reports/<unix timestamp>` into the working directory
+/// - `screenshot.png`: what the player was looking at
+/// - `entities.txt`: every named entity with a position, its velocity, health and components
+/// - `combat_log.txt`: the last [`COMBAT_LOG_LENGTH`] combat events, like throws, bounces, shots
+///   and hits, with the real time they happened at
+///
+/// Zip the folder and attach it to the issue.
+pub fn plugin(app: &mut App) {
+    app.init_resource::<CombatLog>();
+    app.add_observer(log_damage)
+        .add_observer(log_hurt)
+        .add_observer(log_death)
+        .add_observer(log_shield_block)
+        .add_observer(log_hit_absorbed)
+        .add_observer(log_bullets)
+        .add_observer(log_dynamite);
+    app.add_systems(Update, log_boomerang_events);
+    app.add_systems(
+        Update,
+        capture_bug_report.run_if(input_just_pressed(KeyCode::F12)),
+    );
+}
+
+/// How many combat events are kept around for the next bug report.
+const COMBAT_LOG_LENGTH: usize = 200;
+const BUG_REPORT_DIRECTORY: &str = "bug_reports";
+
+/// The most recent combat events, oldest first.
+#[derive(Resource, Debug, Default)]
+struct CombatLog(VecDeque<String>);
+
+impl CombatLog {
+    fn push(&mut self, time: &Time<Real>, entry: impl AsRef<str>) {
+        if self.0.len() == COMBAT_LOG_LENGTH {
+            self.0.pop_front();
+        }
+        self.0
+            .push_back(format!("{:>9.3}s {}", time.elapsed_secs(), entry.as_ref()));
+    }
+}
+
+fn log_damage(
+    trigger: Trigger<HealthEvent>,
+    names: Query<NameOrEntity>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let HealthEvent::Damage(damage, bounces, source) = trigger.event();
+    let target = describe(&names, trigger.target());
+    let source = source.map_or_else(String::new, |source| format!(" from {source}"));
+    log.push(
+        &time,
+        format!("{target} takes {damage} damage after {bounces} bounces{source}"),
+    );
+}
+
+fn log_hurt(
+    trigger: Trigger<HurtEvent>,
+    names: Query<NameOrEntity>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let target = describe(&names, trigger.target());
+    log.push(&time, format!("{target} is hurt"));
+}
+
+fn log_death(
+    trigger: Trigger<DeathEvent>,
+    names: Query<NameOrEntity>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let target = describe(&names, trigger.target());
+    log.push(
+        &time,
+        format!("{target} dies after {} bounces", trigger.event().0),
+    );
+}
+
+fn log_shield_block(
+    trigger: Trigger<ShieldBlockEvent>,
+    names: Query<NameOrEntity>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let target = describe(&names, trigger.target());
+    log.push(&time, format!("{target} blocks a hit with their shield"));
+}
+
+fn log_hit_absorbed(
+    trigger: Trigger<HitAbsorbedEvent>,
+    names: Query<NameOrEntity>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let target = describe(&names, trigger.target());
+    log.push(&time, format!("{target} absorbs a hit"));
+}
+
+fn log_bullets(
+    trigger: Trigger<FireBulletsEvent>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let bullets = trigger.event();
+    log.push(
+        &time,
+        format!(
+            "{} bullet(s) fired from {} towards {} for {} damage",
+            bullets.pellets, bullets.origin, bullets.direction, bullets.damage
+        ),
+    );
+}
+
+fn log_dynamite(
+    trigger: Trigger<LobDynamiteEvent>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    let dynamite = trigger.event();
+    log.push(
+        &time,
+        format!(
+            "dynamite lobbed from {} at {}, fuse {}s",
+            dynamite.origin, dynamite.target, dynamite.fuse
+        ),
+    );
+}
+
+fn log_boomerang_events(
+    mut throws: EventReader<ThrowBoomerangEvent>,
+    mut bounces: EventReader<BounceBoomerangEvent>,
+    mut lost_targets: EventReader<BoomerangTargetLostEvent>,
+    names: Query<NameOrEntity>,
+    time: Res<Time<Real>>,
+    mut log: ResMut<CombatLog>,
+) {
+    for throw in throws.read() {
+        let thrower = describe(&names, throw.thrower_entity);
+        log.push(
+            &time,
+            format!(
+                "{thrower} throws a boomerang (slot {:?}, charge {:.2}) at {:?}",
+                throw.slot, throw.charge, throw.target
+            ),
+        );
+    }
+    for bounce in bounces.read() {
+        let boomerang = describe(&names, bounce.boomerang_entity);
+        log.push(
+            &time,
+            format!("{boomerang} bounces off {:?}", bounce._bounce_on),
+        );
+    }
+    for lost in lost_targets.read() {
+        let boomerang = describe(&names, lost.boomerang_entity);
+        let target = describe(&names, lost.lost_entity);
+        log.push(&time, format!("{boomerang} lost its target {target}"));
+    }
+}
+
+fn describe(names: &Query<NameOrEntity>, entity: Entity) -> String {
+    names
+        .get(entity)
+        .map_or_else(|_| format!("{entity} (gone)"), |name| format!("{name}"))
+}
+
+fn capture_bug_report(world: &mut World) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let directory = Path::new(BUG_REPORT_DIRECTORY).join(timestamp.to_string());
+    if let Err(error) = std::fs::create_dir_all(&directory) {
+        error!(
+            "bug report: couldn't create {}: {error}",
+            directory.display()
+        );
+        return;
+    }
+
+    world
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(directory.join("screenshot.png")));
+
+    let files = [
+        ("entities.txt", dump_entities(world)),
+        ("combat_log.txt", dump_combat_log(world)),
+    ];
+    for (file, contents) in files {
+        let path = directory.join(file);
+        if let Err(error) = std::fs::write(&path, contents) {
+            error!("bug report: couldn't write {}: {error}", path.display());
+        }
+    }
+    info!("bug report: captured to {}", directory.display());
+}
+
+#[derive(QueryData)]
+struct DumpedEntity {
+    entity: Entity,
+    name: &'static Name,
+    transform: &'static GlobalTransform,
+    velocity: Option<&'static LinearVelocity>,
+    health: Option<&'static Health>,
+}
+
+fn dump_entities(world: &mut World) -> String {
+    let mut dump = String::new();
+    let screen = world
+        .get_resource::<State<Screen>>()
+        .map(|state| state.get().clone());
+    let gameplay = world
+        .get_resource::<State<Gameplay>>()
+        .map(|state| state.get().clone());
+    let level = world
+        .get_resource::<LevelAssets>()
+        .map(|level_assets| level_assets.current_level);
+    let _ = writeln!(
+        dump,
+        "screen: {screen:?}, gameplay: {gameplay:?}, level: {level:?}"
+    );
+
+    let mut entities = world.query::<DumpedEntity>();
+    let mut rows: Vec<_> = entities
+        .iter(world)
+        .map(|item| {
+            (
+                item.entity,
+                item.name.to_string(),
+                item.transform.translation(),
+                item.velocity.map(|velocity| velocity.0),
+                item.health.map(|health| health.0),
+            )
+        })
+        .collect();
+    rows.sort_by(|a, b| a.1.cmp(&b.1));
+
+    for (entity, name, position, velocity, health) in rows {
+        let _ = writeln!(dump, "\n{name} ({entity})");
+        let _ = writeln!(dump, "  position: {position}");
+        if let Some(velocity) = velocity {
+            let _ = writeln!(
+                dump,
+                "  velocity: {velocity} ({:.2} m/s)",
+                velocity.length()
+            );
+        }
+        if let Some(health) = health {
+            let _ = writeln!(dump, "  health: {health}");
+        }
+        if let Ok(components) = world.inspect_entity(entity) {
+            let components: Vec<_> = components
+                .map(|component| short_type_name(component.name()))
+                .collect();
+            let _ = writeln!(dump, "  components: {}", components.join(", "));
+        }
+    }
+    dump
+}
+
+fn dump_combat_log(world: &World) -> String {
+    world
+        .resource::<CombatLog>()
+        .0
+        .iter()
+        .fold(String::new(), |mut log, entry| {
+            let _ = writeln!(log, "{entry}");
+            log
+        })
+}
+
+/// Strips the module paths from a type name, including the ones of its generic parameters,
+/// e.g. `bevy_state::state_scoped::StateScoped<game::screens::Screen>` becomes
+/// `StateScoped<Screen>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment = String::new();
+    for character in name.chars() {
+        if character.is_alphanumeric() || character == '_' || character == ':' {
+            segment.push(character);
+        } else {
+            short.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            short.push(character);
+        }
+    }
+    short.push_str(segment.rsplit("::").next().unwrap_or_default());
+    short
+}
@@ -0,0 +1,203 @@
+use crate::physics_layers::GameLayer;
+use avian3d::prelude::{CollisionLayers, PhysicsLayer};
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+/// COLLISION LAYERS
+/// press 'l' to show/hide
+///
+/// While visible:
+/// - every collider gets its layers written above it: what it is on top, what it collides with
+///   below, one color per [`GameLayer`]
+/// - a matrix window shows which layers actually end up colliding, worked out from the
+///   [`CollisionLayers`] of everything that's currently spawned.
+///   ✔ means both sides agree, ½ means only one of them lists the other, which is usually a bug
+pub fn plugin(app: &mut App) {
+    app.init_resource::<CollisionLayerOverlay>();
+    app.add_systems(
+        Update,
+        toggle_overlay.run_if(input_just_pressed(KeyCode::KeyL)),
+    );
+    app.add_systems(
+        EguiContextPass,
+        (draw_entity_layers, layer_matrix_ui)
+            .run_if(|overlay: Res<CollisionLayerOverlay>| overlay.visible),
+    );
+}
+
+const LAYERS: [(GameLayer, &str, egui::Color32); 8] = [
+    (GameLayer::Default, "Default", egui::Color32::GRAY),
+    (GameLayer::Enemy, "Enemy", egui::Color32::RED),
+    (GameLayer::Player, "Player", egui::Color32::GREEN),
+    (GameLayer::Bullet, "Bullet", egui::Color32::YELLOW),
+    (GameLayer::Terrain, "Terrain", egui::Color32::BROWN),
+    (GameLayer::Boomerang, "Boomerang", egui::Color32::LIGHT_BLUE),
+    (GameLayer::DeadEnemy, "DeadEnemy", egui::Color32::DARK_RED),
+    (GameLayer::Civilian, "Civilian", egui::Color32::GOLD),
+];
+
+#[derive(Resource, Debug, Default)]
+struct CollisionLayerOverlay {
+    visible: bool,
+}
+
+fn toggle_overlay(mut overlay: ResMut<CollisionLayerOverlay>) {
+    overlay.visible = !overlay.visible;
+    info!("collision layer overlay: {}", overlay.visible);
+}
+
+fn has_layer(bits: u32, layer: &GameLayer) -> bool {
+    bits & layer.to_bits() != 0
+}
+
+/// The layers in `bits` as colored, comma separated names.
+fn layer_names(bits: u32, font: egui::FontId) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    let mut first = true;
+    for (layer, name, color) in &LAYERS {
+        if !has_layer(bits, layer) {
+            continue;
+        }
+        if !first {
+            job.append(", ", 0.0, text_format(&font, egui::Color32::WHITE));
+        }
+        job.append(name, 0.0, text_format(&font, *color));
+        first = false;
+    }
+    if first {
+        job.append("none", 0.0, text_format(&font, egui::Color32::WHITE));
+    }
+    job
+}
+
+fn text_format(font: &egui::FontId, color: egui::Color32) -> egui::TextFormat {
+    egui::TextFormat {
+        font_id: font.clone(),
+        color,
+        background: egui::Color32::from_black_alpha(160),
+        ..default()
+    }
+}
+
+fn draw_entity_layers(
+    mut contexts: EguiContexts,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    colliders: Query<(&CollisionLayers, &GlobalTransform)>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    let (camera, camera_transform) = camera.into_inner();
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    let font = egui::FontId::monospace(10.0);
+
+    for (layers, transform) in &colliders {
+        let Ok(position) =
+            camera.world_to_viewport(camera_transform, transform.translation() + Vec3::Y * 1.5)
+        else {
+            continue;
+        };
+        let position = egui::pos2(position.x, position.y);
+        let memberships =
+            ctx.fonts(|fonts| fonts.layout_job(layer_names(layers.memberships.0, font.clone())));
+        let filters =
+            ctx.fonts(|fonts| fonts.layout_job(layer_names(layers.filters.0, font.clone())));
+        let line_height = memberships.rect.height();
+        painter.galley(
+            position - egui::vec2(memberships.rect.width() / 2.0, line_height),
+            memberships,
+            egui::Color32::WHITE,
+        );
+        painter.galley(
+            position - egui::vec2(filters.rect.width() / 2.0, 0.0),
+            filters,
+            egui::Color32::WHITE,
+        );
+    }
+}
+
+/// Whether colliders on layer `a` and colliders on layer `b` collide, judging by everything
+/// that's spawned right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayerInteraction {
+    Both,
+    /// One side has the other in its filters, but not the other way around.
+    OneSided,
+}
+
+fn layer_matrix_ui(mut contexts: EguiContexts, colliders: Query<&CollisionLayers>) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    // lots of colliders share the same layers, only look at every combination once
+    let mut configurations: HashMap<(u32, u32), usize> = HashMap::default();
+    for layers in &colliders {
+        *configurations
+            .entry((layers.memberships.0, layers.filters.0))
+            .or_default() += 1;
+    }
+
+    let mut matrix: HashMap<(usize, usize), LayerInteraction> = HashMap::default();
+    for &(a_memberships, a_filters) in configurations.keys() {
+        for &(b_memberships, b_filters) in configurations.keys() {
+            let a_accepts_b = a_filters & b_memberships != 0;
+            let b_accepts_a = b_filters & a_memberships != 0;
+            let interaction = match (a_accepts_b, b_accepts_a) {
+                (true, true) => LayerInteraction::Both,
+                (false, false) => continue,
+                _ => LayerInteraction::OneSided,
+            };
+            for (i, (a_layer, ..)) in LAYERS.iter().enumerate() {
+                for (j, (b_layer, ..)) in LAYERS.iter().enumerate() {
+                    if !has_layer(a_memberships, a_layer) || !has_layer(b_memberships, b_layer) {
+                        continue;
+                    }
+                    // colliding for real wins over a one-sided configuration elsewhere
+                    let cell = matrix.entry((i, j)).or_insert(interaction);
+                    if interaction == LayerInteraction::Both {
+                        *cell = LayerInteraction::Both;
+                    }
+                }
+            }
+        }
+    }
+
+    egui::Window::new("Collision Layers")
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "{} colliders in {} layer configurations",
+                colliders.iter().count(),
+                configurations.len()
+            ));
+            ui.separator();
+
+            egui::Grid::new("collision_layer_matrix")
+                .num_columns(LAYERS.len() + 1)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    for (_, name, color) in &LAYERS {
+                        ui.colored_label(*color, &name[..3]);
+                    }
+                    ui.end_row();
+
+                    for (i, (_, name, color)) in LAYERS.iter().enumerate() {
+                        ui.colored_label(*color, *name);
+                        for j in 0..LAYERS.len() {
+                            match matrix.get(&(i, j)) {
+                                Some(LayerInteraction::Both) => ui.label("✔"),
+                                Some(LayerInteraction::OneSided) => {
+                                    ui.colored_label(egui::Color32::YELLOW, "½")
+                                }
+                                None => ui.label("·"),
+                            };
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
@@ -0,0 +1,79 @@
+use crate::gameplay::enemy::difficulty::{
+    AdaptiveDifficulty, DifficultyLevel, LevelPerformance, RecentPerformance,
+};
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+/// ADAPTIVE DIFFICULTY
+/// press 'k' to show/hide
+///
+/// - shows the current difficulty level and what it does to enemies
+/// - the level can be dragged around with the slider, applies to enemies spawning from then on
+/// - below that, how the current level is going and the ratings of the recent ones
+pub fn plugin(app: &mut App) {
+    app.init_resource::<DifficultyPanel>();
+    app.add_systems(
+        Update,
+        toggle_difficulty_panel.run_if(input_just_pressed(KeyCode::KeyK)),
+    );
+    app.add_systems(
+        EguiContextPass,
+        difficulty_panel_ui.run_if(|panel: Res<DifficultyPanel>| panel.visible),
+    );
+}
+
+#[derive(Resource, Debug, Default)]
+struct DifficultyPanel {
+    visible: bool,
+}
+
+fn toggle_difficulty_panel(mut panel: ResMut<DifficultyPanel>) {
+    panel.visible = !panel.visible;
+    info!("difficulty panel: {}", panel.visible);
+}
+
+fn difficulty_panel_ui(
+    mut contexts: EguiContexts,
+    difficulty: Res<AdaptiveDifficulty>,
+    performance: Res<LevelPerformance>,
+    recent: Res<RecentPerformance>,
+    mut level: ResMut<DifficultyLevel>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Adaptive Difficulty")
+        .default_width(280.0)
+        .show(ctx, |ui| {
+            if !difficulty.enabled {
+                ui.colored_label(egui::Color32::YELLOW, "turned off in the settings");
+            }
+            ui.add(egui::Slider::new(&mut level.0, -1.0..=1.0).text("level"));
+            ui.label(format!(
+                "attack delay x{:.2}",
+                difficulty.attack_delay(level.0)
+            ));
+            ui.label(format!(
+                "bullet speed x{:.2}",
+                difficulty.bullet_speed(level.0)
+            ));
+            ui.label(format!(
+                "skip chance {:.0}%",
+                difficulty.skip_chance(level.0) * 100.0
+            ));
+            ui.separator();
+
+            ui.label(format!(
+                "this level: {}/{} throws hit, {} hits taken",
+                performance.hits, performance.throws, performance.hits_taken
+            ));
+            let ratings: Vec<_> = recent
+                .0
+                .iter()
+                .map(|level| format!("{:.2}", level.rating()))
+                .collect();
+            ui.label(format!("recent ratings: [{}]", ratings.join(", ")));
+        });
+}
@@ -0,0 +1,95 @@
+use crate::gameplay::boomerang::BoomerangFlightHistory;
+use bevy::color::palettes;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+/// FLIGHT HISTORY
+/// press 'h' to show/hide
+///
+/// While visible:
+/// - boomerangs in the air show the path they took so far
+/// - the last boomerang that landed keeps showing its full path
+/// - bounces are marked with their normals, entities it hit get a circle around them
+pub fn plugin(app: &mut App) {
+    app.init_resource::<FlightHistoryOverlay>();
+    app.add_observer(remember_last_flight);
+    app.add_systems(
+        Update,
+        (
+            toggle_overlay.run_if(input_just_pressed(KeyCode::KeyH)),
+            draw_flight_histories.run_if(|overlay: Res<FlightHistoryOverlay>| overlay.visible),
+        ),
+    );
+}
+
+#[derive(Resource, Debug, Default)]
+struct FlightHistoryOverlay {
+    visible: bool,
+    last_flight: Option<BoomerangFlightHistory>,
+}
+
+fn toggle_overlay(mut overlay: ResMut<FlightHistoryOverlay>) {
+    overlay.visible = !overlay.visible;
+    info!("flight history overlay: {}", overlay.visible);
+}
+
+fn remember_last_flight(
+    trigger: Trigger<OnRemove, BoomerangFlightHistory>,
+    histories: Query<&BoomerangFlightHistory>,
+    mut overlay: ResMut<FlightHistoryOverlay>,
+) {
+    if let Ok(history) = histories.get(trigger.target()) {
+        overlay.last_flight = Some(history.clone());
+    }
+}
+
+fn draw_flight_histories(
+    overlay: Res<FlightHistoryOverlay>,
+    histories: Query<(&BoomerangFlightHistory, &Transform)>,
+    transforms: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    for (history, transform) in &histories {
+        draw_history(
+            history,
+            Some(transform.translation),
+            &transforms,
+            &mut gizmos,
+        );
+    }
+    if let Some(history) = &overlay.last_flight {
+        draw_history(history, None, &transforms, &mut gizmos);
+    }
+}
+
+fn draw_history(
+    history: &BoomerangFlightHistory,
+    current_position: Option<Vec3>,
+    transforms: &Query<&GlobalTransform>,
+    gizmos: &mut Gizmos,
+) {
+    let path = history.waypoints.iter().copied().chain(current_position);
+    gizmos.linestrip(path, palettes::css::YELLOW);
+
+    for (waypoint, normal) in history
+        .waypoints
+        .iter()
+        .skip(1)
+        .zip(&history.bounce_normals)
+    {
+        gizmos.arrow(*waypoint, *waypoint + *normal * 1.5, palettes::css::ORANGE);
+    }
+
+    for hit in &history.hits {
+        if let Ok(transform) = transforms.get(*hit) {
+            gizmos.circle(
+                Isometry3d::new(
+                    transform.translation(),
+                    Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+                ),
+                1.0,
+                palettes::css::RED,
+            );
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use crate::gameplay::ammo::{
+    AddBoomerangSlot, BoomerangInventory, BoomerangKind, BoomerangSlotChanged, UpgradeBoomerangSlot,
+};
 use crate::gameplay::enemy::Enemy;
 use crate::gameplay::health_and_damage::{DeathEvent, Health, HealthEvent};
 use crate::gameplay::player::{MovementSettings, Player};
@@ -11,8 +14,11 @@ use bevy::prelude::*;
 /// While in it:
 /// - 1 kills all enemies
 /// - 2 kills player
+/// - 3 hands the player an iron boomerang
+/// - 4 upgrades the player's first boomerang
 pub fn plugin(app: &mut App) {
     app.init_state::<GodModeState>();
+    app.add_observer(log_boomerang_slot_changes);
 
     app.add_systems(
         Update,
@@ -33,6 +39,15 @@ pub fn plugin(app: &mut App) {
             .run_if(in_state(GodModeState::God)),
     );
 
+    app.add_systems(
+        Update,
+        (
+            give_iron_boomerang.run_if(input_just_pressed(KeyCode::Digit3)),
+            upgrade_first_boomerang.run_if(input_just_pressed(KeyCode::Digit4)),
+        )
+            .run_if(in_state(GodModeState::God)),
+    );
+
     app.add_systems(OnEnter(GodModeState::God), enable_god_mode);
     app.add_systems(OnEnter(GodModeState::Normal), disable_god_mode);
 }
@@ -59,7 +74,10 @@ fn enable_god_mode(player: Single<Entity, With<Player>>, mut commands: Commands)
     commands
         .entity(player.into_inner())
         .insert(RigidBody::Kinematic)
-        .insert(MovementSettings { walk_speed: 40. })
+        .insert(MovementSettings {
+            walk_speed: 40.,
+            ..default()
+        })
         .insert(Health(9000));
 }
 fn disable_god_mode(player: Single<Entity, With<Player>>, mut commands: Commands) {
@@ -73,7 +91,9 @@ fn disable_god_mode(player: Single<Entity, With<Player>>, mut commands: Commands
 fn kill_all_enemies(enemies: Query<Entity, (With<Enemy>, With<Health>)>, mut commands: Commands) {
     info!("kill {} enemies:", enemies.iter().len());
     for e in enemies.iter() {
-        commands.entity(e).trigger(HealthEvent::Damage(100, 1));
+        commands
+            .entity(e)
+            .trigger(HealthEvent::Damage(100, 1, None));
     }
 }
 fn kill_player(player: Single<Entity, (With<Player>, With<Health>)>, mut commands: Commands) {
@@ -81,3 +101,33 @@ fn kill_player(player: Single<Entity, (With<Player>, With<Health>)>, mut command
     info!("kill player: {}", p);
     commands.entity(p).trigger(DeathEvent(1));
 }
+
+fn give_iron_boomerang(player: Single<Entity, With<Player>>, mut commands: Commands) {
+    commands
+        .entity(player.into_inner())
+        .trigger(AddBoomerangSlot(BoomerangKind::IRON));
+}
+
+fn upgrade_first_boomerang(player: Single<Entity, With<Player>>, mut commands: Commands) {
+    commands
+        .entity(player.into_inner())
+        .trigger(UpgradeBoomerangSlot(0));
+}
+
+fn log_boomerang_slot_changes(
+    trigger: Trigger<BoomerangSlotChanged>,
+    inventories: Query<&BoomerangInventory>,
+) {
+    if let Some(slot) = inventories
+        .get(trigger.target())
+        .ok()
+        .and_then(|inventory| inventory.slot(trigger.slot))
+    {
+        debug!(
+            "boomerang slot {} of {}: {:?}",
+            trigger.slot,
+            trigger.target(),
+            slot
+        );
+    }
+}
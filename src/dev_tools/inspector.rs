@@ -0,0 +1,157 @@
+use crate::dev_tools::god_mode::GodModeState;
+use crate::dev_tools::orbit_camera::OrbitCameraState;
+use avian3d::prelude::{ColliderAabb, SpatialQuery, SpatialQueryFilter};
+use bevy::color::palettes;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy::window::PrimaryWindow;
+use bevy_inspector_egui::bevy_egui::{EguiContext, EguiContextPass, EguiContexts, egui};
+use bevy_inspector_egui::bevy_inspector::hierarchy::{SelectedEntities, hierarchy_ui};
+use bevy_inspector_egui::bevy_inspector::ui_for_entity;
+
+/// INSPECTOR
+/// visible in god mode and while using the orbit camera
+///
+/// - left click anything in the 3D view to select it
+/// - or pick it from the hierarchy
+/// - the current selection gets a box drawn around it
+pub fn plugin(app: &mut App) {
+    app.init_resource::<DevSelection>();
+
+    app.add_systems(
+        Update,
+        (
+            select_entity_under_cursor.run_if(input_just_pressed(MouseButton::Left)),
+            draw_selection_bounds,
+        )
+            .run_if(inspector_visible),
+    );
+    app.add_systems(EguiContextPass, inspector_ui.run_if(inspector_visible));
+}
+
+/// The entity currently selected with the dev tools.
+#[derive(Resource, Debug, Default)]
+pub struct DevSelection(pub Option<Entity>);
+
+fn inspector_visible(
+    god_mode: Res<State<GodModeState>>,
+    orbit_camera: Res<State<OrbitCameraState>>,
+) -> bool {
+    god_mode.get() == &GodModeState::God || orbit_camera.get() == &OrbitCameraState::Orbiting
+}
+
+fn select_entity_under_cursor(
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera3d>>,
+    spatial_query: SpatialQuery,
+    names: Query<&Name>,
+    mut egui_contexts: EguiContexts,
+    mut selection: ResMut<DevSelection>,
+) {
+    // Clicks on the inspector itself shouldn't change the selection
+    if egui_contexts
+        .try_ctx_mut()
+        .is_some_and(|ctx| ctx.is_pointer_over_area())
+    {
+        return;
+    }
+
+    let (camera, camera_transform) = camera.into_inner();
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
+        return;
+    };
+    let Some(hit) = spatial_query.cast_ray(
+        ray.origin,
+        ray.direction,
+        1000.0,
+        true,
+        &SpatialQueryFilter::default(),
+    ) else {
+        selection.0 = None;
+        return;
+    };
+
+    info!(
+        "selected {} ({})",
+        hit.entity,
+        names.get(hit.entity).map(Name::as_str).unwrap_or("unnamed")
+    );
+    selection.0 = Some(hit.entity);
+}
+
+fn draw_selection_bounds(
+    selection: Res<DevSelection>,
+    collider_bounds: Query<&ColliderAabb>,
+    mesh_bounds: Query<(&Aabb, &GlobalTransform)>,
+    transforms: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    let Some(entity) = selection.0 else {
+        return;
+    };
+    let color = palettes::css::LIME;
+
+    // Colliders already know their world space bounds, meshes only know their local ones
+    let bounds = if let Ok(aabb) = collider_bounds.get(entity) {
+        Transform::from_translation(aabb.center()).with_scale(aabb.size())
+    } else if let Ok((aabb, transform)) = mesh_bounds.get(entity) {
+        let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+        Transform {
+            translation: translation + rotation * (Vec3::from(aabb.center) * scale),
+            rotation,
+            scale: Vec3::from(aabb.half_extents) * 2.0 * scale,
+        }
+    } else if let Ok(transform) = transforms.get(entity) {
+        Transform::from_translation(transform.translation())
+    } else {
+        return;
+    };
+    gizmos.cuboid(bounds, color);
+}
+
+fn inspector_ui(world: &mut World, mut selected: Local<SelectedEntities>) {
+    let Ok(egui_context) = world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>()
+        .single(world)
+    else {
+        return;
+    };
+    let mut egui_context = egui_context.clone();
+
+    // Pull in what was picked in the 3D view
+    let mut picked = world.resource::<DevSelection>().0;
+    if picked.is_some_and(|entity| world.get_entity(entity).is_err()) {
+        picked = None;
+    }
+    if picked != selected.as_slice().first().copied() {
+        match picked {
+            Some(entity) => selected.select_replace(entity),
+            None => selected.clear(),
+        }
+    }
+
+    egui::Window::new("Inspector")
+        .default_size((320.0, 480.0))
+        .show(egui_context.get_mut(), |ui| {
+            egui::ScrollArea::both().show(ui, |ui| {
+                ui.heading("Hierarchy");
+                hierarchy_ui(world, ui, &mut selected);
+                ui.separator();
+                if let Some(entity) = selected.as_slice().first().copied() {
+                    ui_for_entity(world, entity, ui);
+                } else {
+                    ui.label("Click an entity to inspect it");
+                }
+            });
+        });
+
+    // Push back what was picked in the hierarchy
+    let chosen = selected.as_slice().first().copied();
+    if world.resource::<DevSelection>().0 != chosen {
+        world.resource_mut::<DevSelection>().0 = chosen;
+    }
+}
@@ -0,0 +1,355 @@
+use crate::clock::UiTime;
+use crate::gameplay::civilian::CivilianSpawnPoint;
+use crate::gameplay::collectibles::GoldenHorseshoe;
+use crate::gameplay::enemy::archetype::EnemyArchetypeRegistry;
+use crate::gameplay::horse::HorseSpawnPoint;
+use crate::gameplay::input::PlayerActions;
+use crate::gameplay::inspectable::Inspectable;
+use crate::gameplay::level::{LevelAddition, LevelAssets};
+use crate::gameplay::level_exit::ExitZone;
+use crate::gameplay::mouse_position::MousePosition;
+use crate::gameplay::pickups::PickupRegistry;
+use crate::gameplay::player::Player;
+use crate::screens::Screen;
+use bevy::color::palettes;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::scene::SceneFilter;
+use bevy_enhanced_input::prelude::Actions;
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+use std::any::TypeId;
+use std::path::Path;
+
+/// LEVEL EDITOR
+/// press 'b' to open/close during a level
+///
+/// While open the player can't move or throw, instead:
+/// - pick something from the palette and left click the ground to place it
+/// - with "select & move", left click an addition to select it and drag it around
+/// - q/e turn the selection, delete removes it
+/// - "export & reload" saves every addition to `assets/levels/<level>.scn.ron` and restarts the
+///   level, which spawns them along with the level's glTF scene from then on
+///
+/// Only additions can be edited, whatever was placed in Blender stays where it is.
+pub fn plugin(app: &mut App) {
+    app.init_resource::<LevelEditor>();
+    app.add_systems(
+        Update,
+        toggle_editor
+            .run_if(input_just_pressed(KeyCode::KeyB))
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        (
+            edit_with_mouse,
+            turn_selection,
+            delete_selection.run_if(input_just_pressed(KeyCode::Delete)),
+            draw_additions,
+            export_additions.run_if(|editor: Res<LevelEditor>| editor.export_requested),
+        )
+            .run_if(editor_open),
+    );
+    app.add_systems(EguiContextPass, editor_ui.run_if(editor_open));
+    app.add_systems(OnExit(Screen::Gameplay), close_editor);
+}
+
+/// How close to an addition a click has to be to select it.
+const PICK_RADIUS: f32 = 1.5;
+/// How far q/e turn the selection, in radians per second.
+const TURN_RATE: f32 = 3.0;
+
+#[derive(Resource, Debug, Default)]
+struct LevelEditor {
+    open: bool,
+    /// What a click places, or [None] to select and move additions instead.
+    brush: Option<Brush>,
+    selected: Option<Entity>,
+    dragging: bool,
+    export_requested: bool,
+    /// How the last export went.
+    status: String,
+}
+
+fn editor_open(editor: Res<LevelEditor>) -> bool {
+    editor.open
+}
+
+/// Something that can be placed in the level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Brush {
+    /// The spawn point of an archetype from the [`EnemyArchetypeRegistry`].
+    Enemy(TypeId),
+    Civilian,
+    Horse,
+    Note,
+    Horseshoe,
+    Exit,
+}
+
+impl Brush {
+    /// Everything but the enemies, those come from the [`EnemyArchetypeRegistry`].
+    const OTHERS: [Brush; 5] = [
+        Brush::Civilian,
+        Brush::Horse,
+        Brush::Note,
+        Brush::Horseshoe,
+        Brush::Exit,
+    ];
+
+    fn label(self, archetypes: &EnemyArchetypeRegistry) -> &'static str {
+        match self {
+            Brush::Enemy(type_id) => archetypes
+                .get(type_id)
+                .map_or("Unknown enemy", |archetype| archetype.name),
+            Brush::Civilian => "Civilian",
+            Brush::Horse => "Horse",
+            Brush::Note => "Note",
+            Brush::Horseshoe => "Golden horseshoe",
+            Brush::Exit => "Exit",
+        }
+    }
+
+    fn place(
+        self,
+        transform: Transform,
+        archetypes: &EnemyArchetypeRegistry,
+        commands: &mut Commands,
+    ) -> Entity {
+        let mut entity = commands.spawn((
+            Name::new(format!("{} (addition)", self.label(archetypes))),
+            transform,
+            Visibility::default(),
+            LevelAddition,
+            StateScoped(Screen::Gameplay),
+        ));
+        let id = entity.id();
+        match self {
+            Brush::Enemy(type_id) => {
+                if let Some(archetype) = archetypes.get(type_id) {
+                    archetype.insert_default(&mut entity);
+                }
+                &mut entity
+            }
+            Brush::Civilian => entity.insert(CivilianSpawnPoint::default()),
+            Brush::Horse => entity.insert(HorseSpawnPoint::default()),
+            Brush::Note => entity.insert(Inspectable {
+                text: "Edit me in the inspector".to_string(),
+                ..default()
+            }),
+            Brush::Horseshoe => entity.insert(GoldenHorseshoe {
+                id: format!("addition {id}"),
+            }),
+            Brush::Exit => entity.insert(ExitZone::default()),
+        };
+        id
+    }
+}
+
+fn toggle_editor(
+    mut editor: ResMut<LevelEditor>,
+    player: Query<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    editor.open = !editor.open;
+    editor.dragging = false;
+    info!("level editor: {}", editor.open);
+    // the mouse is busy with editing
+    for player in &player {
+        if editor.open {
+            commands.entity(player).remove::<Actions<PlayerActions>>();
+        } else {
+            commands
+                .entity(player)
+                .insert(Actions::<PlayerActions>::default());
+        }
+    }
+}
+
+fn close_editor(mut editor: ResMut<LevelEditor>) {
+    editor.open = false;
+    editor.selected = None;
+    editor.dragging = false;
+}
+
+fn edit_with_mouse(
+    mouse: Res<ButtonInput<MouseButton>>,
+    mouse_position: Res<MousePosition>,
+    mut egui_contexts: EguiContexts,
+    mut additions: Query<(Entity, &mut Transform), With<LevelAddition>>,
+    mut editor: ResMut<LevelEditor>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    mut commands: Commands,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        editor.dragging = false;
+        return;
+    }
+    let Some(cursor) = mouse_position.global else {
+        return;
+    };
+    if editor.dragging {
+        if let Some(Ok((_, mut transform))) =
+            editor.selected.map(|entity| additions.get_mut(entity))
+        {
+            transform.translation = cursor;
+        }
+        return;
+    }
+    // clicks on the editor window itself
+    if !mouse.just_pressed(MouseButton::Left)
+        || egui_contexts
+            .try_ctx_mut()
+            .is_some_and(|ctx| ctx.is_pointer_over_area())
+    {
+        return;
+    }
+
+    if let Some(brush) = editor.brush {
+        editor.selected = Some(brush.place(
+            Transform::from_translation(cursor),
+            &archetypes,
+            &mut commands,
+        ));
+        return;
+    }
+    editor.selected = additions
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation.distance(cursor)))
+        .filter(|(_, distance)| *distance < PICK_RADIUS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+    editor.dragging = editor.selected.is_some();
+}
+
+fn turn_selection(
+    time: UiTime,
+    keys: Res<ButtonInput<KeyCode>>,
+    editor: Res<LevelEditor>,
+    mut additions: Query<&mut Transform, With<LevelAddition>>,
+) {
+    let direction = keys.pressed(KeyCode::KeyQ) as i32 - keys.pressed(KeyCode::KeyE) as i32;
+    let Some(Ok(mut transform)) = editor.selected.map(|entity| additions.get_mut(entity)) else {
+        return;
+    };
+    transform.rotate_y(direction as f32 * TURN_RATE * time.delta_secs());
+}
+
+fn delete_selection(mut editor: ResMut<LevelEditor>, mut commands: Commands) {
+    if let Some(entity) = editor.selected.take() {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+fn draw_additions(
+    editor: Res<LevelEditor>,
+    additions: Query<(Entity, &GlobalTransform), With<LevelAddition>>,
+    mut gizmos: Gizmos,
+) {
+    for (entity, transform) in &additions {
+        let color = if editor.selected == Some(entity) {
+            palettes::css::LIME
+        } else {
+            palettes::css::FUCHSIA
+        };
+        let position = transform.translation().with_y(0.05);
+        gizmos.circle(
+            Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            PICK_RADIUS * 0.5,
+            color,
+        );
+        gizmos.arrow(
+            position,
+            position + transform.forward().with_y(0.0) * PICK_RADIUS,
+            color,
+        );
+    }
+}
+
+fn editor_ui(
+    mut contexts: EguiContexts,
+    mut editor: ResMut<LevelEditor>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    additions: Query<&Name, With<LevelAddition>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Level Editor")
+        .default_width(220.0)
+        .show(ctx, |ui| {
+            ui.selectable_value(&mut editor.brush, None, "Select & move");
+            ui.separator();
+            let enemies = archetypes
+                .iter()
+                .map(|archetype| Brush::Enemy(archetype.type_id));
+            for brush in enemies.chain(Brush::OTHERS) {
+                ui.selectable_value(&mut editor.brush, Some(brush), brush.label(&archetypes));
+            }
+            ui.separator();
+
+            ui.label(format!("{} additions", additions.iter().count()));
+            let selected = editor
+                .selected
+                .and_then(|entity| additions.get(entity).ok())
+                .map_or("nothing selected", Name::as_str);
+            ui.label(selected);
+            if ui.button("Export & reload").clicked() {
+                editor.export_requested = true;
+            }
+            if !editor.status.is_empty() {
+                ui.label(&editor.status);
+            }
+        });
+}
+
+/// Writes every [`LevelAddition`] to the level's additions scene and restarts the level, which
+/// spawns it again from the file.
+fn export_additions(world: &mut World) {
+    world.resource_mut::<LevelEditor>().export_requested = false;
+
+    let additions: Vec<Entity> = world
+        .query_filtered::<Entity, With<LevelAddition>>()
+        .iter(world)
+        .collect();
+    // only what was authored, everything the observers add on top of it gets added again on load
+    let mut filter = SceneFilter::deny_all()
+        .allow::<Name>()
+        .allow::<Transform>()
+        .allow::<LevelAddition>()
+        .allow::<CivilianSpawnPoint>()
+        .allow::<HorseSpawnPoint>()
+        .allow::<Inspectable>()
+        .allow::<ExitZone>();
+    let archetypes = world.resource::<EnemyArchetypeRegistry>().iter();
+    let pickups = world.resource::<PickupRegistry>().iter();
+    for type_id in archetypes
+        .map(|archetype| archetype.type_id)
+        .chain(pickups.map(|pickup| pickup.type_id))
+    {
+        filter = filter.allow_by_id(type_id);
+    }
+    let scene = DynamicSceneBuilder::from_world(world)
+        .deny_all_resources()
+        .with_component_filter(filter)
+        .extract_entities(additions.into_iter())
+        .build();
+
+    let level_assets = world.resource::<LevelAssets>();
+    let path = Path::new("assets").join(level_assets.additions_path(level_assets.current_level));
+    let serialized = scene.serialize(&world.resource::<AppTypeRegistry>().read());
+    let result = serialized
+        .map_err(|error| error.to_string())
+        .and_then(|ron| std::fs::write(&path, ron).map_err(|error| error.to_string()));
+
+    let status = match result {
+        Ok(()) => {
+            world.resource_mut::<NextState<Screen>>().set(Screen::Retry);
+            format!("exported to {}", path.display())
+        }
+        Err(error) => format!("export failed: {error}"),
+    };
+    info!("level editor: {status}");
+    world.resource_mut::<LevelEditor>().status = status;
+}
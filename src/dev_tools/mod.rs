@@ -1,16 +1,23 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
+#[cfg(not(target_family = "wasm"))]
+mod bug_report;
+mod collision_layers;
+mod difficulty;
+mod flight_history;
 mod god_mode;
+mod inspector;
+mod level_editor;
+mod orbit_camera;
+mod system_budget;
+mod time_scales;
 
-use crate::dev_tools::god_mode::GodModeState;
 use crate::screens::Screen;
 use avian3d::prelude::PhysicsGizmos;
-use bevy::audio::Volume;
 use bevy::color::palettes;
 use bevy::dev_tools::states::log_transitions;
 use bevy::prelude::*;
 use bevy_inspector_egui::bevy_egui::EguiPlugin;
-use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use iyes_perf_ui::PerfUiPlugin;
 use iyes_perf_ui::entries::{PerfUiFramerateEntries, PerfUiWindowEntries};
 use iyes_perf_ui::prelude::{PerfUiPosition, PerfUiRoot};
@@ -28,10 +35,17 @@ pub(super) fn plugin(app: &mut App) {
         EguiPlugin {
             enable_multipass_for_primary_context: true,
         },
-        WorldInspectorPlugin::new().run_if(in_state(GodModeState::God)),
+        inspector::plugin,
         // boomerang_dev_tools_plugin,
         #[cfg(feature = "dev")]
         god_mode::plugin,
+        orbit_camera::plugin,
+        system_budget::plugin,
+        time_scales::plugin,
+        flight_history::plugin,
+        collision_layers::plugin,
+        difficulty::plugin,
+        level_editor::plugin,
     ))
     .insert_gizmo_config(
         PhysicsGizmos {
@@ -42,19 +56,19 @@ pub(super) fn plugin(app: &mut App) {
         GizmoConfig::default(),
     );
 
+    // there's no file system to write bug reports to on web
+    #[cfg(not(target_family = "wasm"))]
+    app.add_plugins(bug_report::plugin);
+
     // Log `Screen` state transitions.
     app.add_systems(Update, log_transitions::<Screen>);
 
-    app.add_systems(Startup, (setup_perf_ui, lower_starting_audio_volume));
+    app.add_systems(Startup, setup_perf_ui);
 }
 
 #[derive(Component)]
 pub struct PerfUiMarker;
 
-fn lower_starting_audio_volume(mut global_volume: ResMut<GlobalVolume>) {
-    global_volume.volume = Volume::Linear(0.5);
-}
-
 fn setup_perf_ui(mut commands: Commands) {
     commands.spawn((
         Name::from("PerfUi"),
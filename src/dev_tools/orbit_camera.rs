@@ -0,0 +1,139 @@
+use crate::dev_tools::inspector::DevSelection;
+use crate::gameplay::camera::CameraDetached;
+use bevy::input::common_conditions::{input_just_pressed, input_pressed};
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use bevy::prelude::*;
+
+/// ORBIT CAMERA
+/// press 'o' to enter/exit, works independently of god mode
+///
+/// While in it:
+/// - hold right mouse to orbit around the focus point
+/// - hold middle mouse to pan
+/// - scroll to zoom
+/// - left click an entity to focus on it and show it in the inspector
+pub fn plugin(app: &mut App) {
+    app.init_state::<OrbitCameraState>();
+
+    app.add_systems(
+        Update,
+        toggle_orbit_camera.run_if(input_just_pressed(KeyCode::KeyO)),
+    );
+    app.add_systems(OnEnter(OrbitCameraState::Orbiting), detach_camera);
+    app.add_systems(OnExit(OrbitCameraState::Orbiting), reattach_camera);
+    app.add_systems(
+        Update,
+        (
+            focus_on_selection.run_if(resource_changed::<DevSelection>),
+            orbit.run_if(input_pressed(MouseButton::Right)),
+            pan.run_if(input_pressed(MouseButton::Middle)),
+            zoom,
+            update_orbit_camera_transform,
+        )
+            .chain()
+            .run_if(in_state(OrbitCameraState::Orbiting)),
+    );
+}
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq, States)]
+pub enum OrbitCameraState {
+    #[default]
+    Following,
+    Orbiting,
+}
+
+#[derive(Component, Debug)]
+struct OrbitCamera {
+    focus: Vec3,
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+}
+
+const ORBIT_SENSITIVITY: f32 = 0.005;
+const PAN_SENSITIVITY: f32 = 0.002;
+const ZOOM_SENSITIVITY: f32 = 0.1;
+
+fn toggle_orbit_camera(
+    state: Res<State<OrbitCameraState>>,
+    mut next_state: ResMut<NextState<OrbitCameraState>>,
+) {
+    let next = match state.get() {
+        OrbitCameraState::Following => OrbitCameraState::Orbiting,
+        OrbitCameraState::Orbiting => OrbitCameraState::Following,
+    };
+    info!("orbit camera: {:?}", next);
+    next_state.set(next);
+}
+
+fn detach_camera(camera: Single<(Entity, &Transform), With<Camera3d>>, mut commands: Commands) {
+    let (entity, transform) = camera.into_inner();
+
+    // Orbit around the point on the ground the camera is currently looking at
+    let forward = transform.forward();
+    let distance = if forward.y < -f32::EPSILON {
+        transform.translation.y / -forward.y
+    } else {
+        30.0
+    };
+    let focus = transform.translation + forward * distance;
+    let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
+    commands.entity(entity).insert((
+        CameraDetached,
+        OrbitCamera {
+            focus,
+            yaw,
+            pitch,
+            distance,
+        },
+    ));
+}
+
+fn reattach_camera(camera: Single<Entity, With<Camera3d>>, mut commands: Commands) {
+    commands
+        .entity(camera.into_inner())
+        .remove::<(CameraDetached, OrbitCamera)>();
+}
+
+fn orbit(motion: Res<AccumulatedMouseMotion>, mut orbit_camera: Single<&mut OrbitCamera>) {
+    orbit_camera.yaw -= motion.delta.x * ORBIT_SENSITIVITY;
+    orbit_camera.pitch = (orbit_camera.pitch - motion.delta.y * ORBIT_SENSITIVITY).clamp(
+        -std::f32::consts::FRAC_PI_2 + 0.05,
+        std::f32::consts::FRAC_PI_2 - 0.05,
+    );
+}
+
+fn pan(motion: Res<AccumulatedMouseMotion>, orbit_camera: Single<(&mut OrbitCamera, &Transform)>) {
+    let (mut orbit_camera, transform) = orbit_camera.into_inner();
+    let scale = orbit_camera.distance * PAN_SENSITIVITY;
+    let offset =
+        transform.right() * -motion.delta.x * scale + transform.up() * motion.delta.y * scale;
+    orbit_camera.focus += offset;
+}
+
+fn zoom(scroll: Res<AccumulatedMouseScroll>, mut orbit_camera: Single<&mut OrbitCamera>) {
+    if scroll.delta.y == 0.0 {
+        return;
+    }
+    orbit_camera.distance =
+        (orbit_camera.distance * (1.0 - scroll.delta.y * ZOOM_SENSITIVITY)).clamp(2.0, 300.0);
+}
+
+fn update_orbit_camera_transform(camera: Single<(&OrbitCamera, &mut Transform)>) {
+    let (orbit_camera, mut transform) = camera.into_inner();
+    let rotation = Quat::from_euler(EulerRot::YXZ, orbit_camera.yaw, orbit_camera.pitch, 0.0);
+    transform.rotation = rotation;
+    transform.translation = orbit_camera.focus + rotation * Vec3::Z * orbit_camera.distance;
+}
+
+fn focus_on_selection(
+    selection: Res<DevSelection>,
+    transforms: Query<&GlobalTransform>,
+    mut orbit_camera: Single<&mut OrbitCamera>,
+) {
+    let Some(transform) = selection.0.and_then(|entity| transforms.get(entity).ok()) else {
+        return;
+    };
+    orbit_camera.focus = transform.translation();
+}
@@ -0,0 +1,175 @@
+use crate::HotSystems;
+use crate::theme::fx_budget::{FX_ALIVE, FX_RECYCLED};
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::platform::collections::HashMap;
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+/// SYSTEM BUDGET
+/// press 'p' to show/hide
+///
+/// - lists how long each group of [`HotSystems`] took, averaged over the last couple of seconds
+/// - turns a row red when that group is over the per-frame budget on average,
+///   yellow when it only spiked over it
+/// - the budget can be adjusted with the slider
+/// - below that, how many effects are alive and how many the FX budget had to cut short
+///
+/// Timings are wall-clock time from just before a set starts until it's done, so anything the
+/// scheduler runs in parallel in the meantime is included too. Good enough to spot regressions.
+pub fn plugin(app: &mut App) {
+    app.init_resource::<SystemBudget>()
+        .init_resource::<SpanStarts>();
+
+    for set in ALL_HOT_SYSTEMS {
+        app.register_diagnostic(
+            Diagnostic::new(diagnostic_path(set))
+                .with_suffix("ms")
+                .with_max_history_length(HISTORY_LENGTH),
+        );
+        app.add_systems(
+            Update,
+            (
+                (move |mut starts: ResMut<SpanStarts>| {
+                    starts.0.insert(set, Instant::now());
+                })
+                .before(set),
+                (move |starts: Res<SpanStarts>, mut diagnostics: Diagnostics| {
+                    if let Some(start) = starts.0.get(&set) {
+                        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+                        diagnostics.add_measurement(&diagnostic_path(set), || elapsed_ms);
+                    }
+                })
+                .after(set),
+            ),
+        );
+    }
+
+    app.add_systems(
+        Update,
+        toggle_budget_panel.run_if(input_just_pressed(KeyCode::KeyP)),
+    );
+    app.add_systems(
+        EguiContextPass,
+        budget_panel_ui.run_if(|budget: Res<SystemBudget>| budget.visible),
+    );
+}
+
+const ALL_HOT_SYSTEMS: [HotSystems; 4] = [
+    HotSystems::Pathfinding,
+    HotSystems::EnemyAi,
+    HotSystems::BoomerangMovement,
+    HotSystems::Particles,
+];
+
+/// Roughly two seconds worth of frames.
+const HISTORY_LENGTH: usize = 120;
+
+const PATHFINDING: DiagnosticPath = DiagnosticPath::const_new("hot_systems/pathfinding");
+const ENEMY_AI: DiagnosticPath = DiagnosticPath::const_new("hot_systems/enemy_ai");
+const BOOMERANG_MOVEMENT: DiagnosticPath =
+    DiagnosticPath::const_new("hot_systems/boomerang_movement");
+const PARTICLES: DiagnosticPath = DiagnosticPath::const_new("hot_systems/particles");
+
+fn diagnostic_path(set: HotSystems) -> DiagnosticPath {
+    match set {
+        HotSystems::Pathfinding => PATHFINDING,
+        HotSystems::EnemyAi => ENEMY_AI,
+        HotSystems::BoomerangMovement => BOOMERANG_MOVEMENT,
+        HotSystems::Particles => PARTICLES,
+    }
+}
+
+#[derive(Resource, Debug)]
+struct SystemBudget {
+    /// How many milliseconds a single group of systems may take per frame.
+    per_system_ms: f64,
+    visible: bool,
+}
+
+impl Default for SystemBudget {
+    fn default() -> Self {
+        Self {
+            per_system_ms: 1.0,
+            visible: false,
+        }
+    }
+}
+
+/// When each set last started running this frame.
+#[derive(Resource, Debug, Default)]
+struct SpanStarts(HashMap<HotSystems, Instant>);
+
+fn toggle_budget_panel(mut budget: ResMut<SystemBudget>) {
+    budget.visible = !budget.visible;
+    info!("system budget panel: {}", budget.visible);
+}
+
+fn budget_panel_ui(
+    mut contexts: EguiContexts,
+    diagnostics: Res<DiagnosticsStore>,
+    mut budget: ResMut<SystemBudget>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("System Budget")
+        .default_width(280.0)
+        .show(ctx, |ui| {
+            ui.add(
+                egui::Slider::new(&mut budget.per_system_ms, 0.1..=8.0)
+                    .text("budget (ms)")
+                    .logarithmic(true),
+            );
+            ui.separator();
+
+            egui::Grid::new("system_budget_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("systems");
+                    ui.strong("avg");
+                    ui.strong("max");
+                    ui.end_row();
+
+                    for set in ALL_HOT_SYSTEMS {
+                        let Some(diagnostic) = diagnostics.get(&diagnostic_path(set)) else {
+                            continue;
+                        };
+                        let average = diagnostic.average().unwrap_or_default();
+                        let max = diagnostic.values().copied().fold(0.0, f64::max);
+                        let color = if average > budget.per_system_ms {
+                            egui::Color32::RED
+                        } else if max > budget.per_system_ms {
+                            egui::Color32::YELLOW
+                        } else {
+                            ui.visuals().text_color()
+                        };
+
+                        ui.colored_label(color, format!("{set:?}"));
+                        ui.colored_label(color, format!("{average:.2} ms"));
+                        ui.colored_label(color, format!("{max:.2} ms"));
+                        ui.end_row();
+                    }
+                });
+            ui.separator();
+
+            let alive = diagnostics
+                .get_measurement(&FX_ALIVE)
+                .map_or(0.0, |measurement| measurement.value);
+            let recycled = diagnostics
+                .get(&FX_RECYCLED)
+                .map_or(0.0, |diagnostic| diagnostic.values().sum::<f64>());
+            let color = if recycled > 0.0 {
+                egui::Color32::YELLOW
+            } else {
+                ui.visuals().text_color()
+            };
+            ui.label(format!("effects alive: {alive}"));
+            ui.colored_label(color, format!("effects recycled (last 2s): {recycled}"));
+        });
+}
@@ -0,0 +1,127 @@
+use avian3d::prelude::{Physics, PhysicsTime};
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+/// TIME SCALES
+/// press 't' to show/hide
+///
+/// - lists every clock with how fast it runs, whether it's paused, its last delta and how much
+///   time has passed on it
+/// - the game clock is the one slow motion changes, see [`crate::clock`]
+/// - a clock that's not running at full speed is highlighted
+///
+/// Handy to check that something slowed down (or didn't) for the right reason.
+pub fn plugin(app: &mut App) {
+    app.init_resource::<TimeScalesPanel>();
+    app.add_systems(
+        Update,
+        toggle_time_scales_panel.run_if(input_just_pressed(KeyCode::KeyT)),
+    );
+    app.add_systems(
+        EguiContextPass,
+        time_scales_ui.run_if(|panel: Res<TimeScalesPanel>| panel.visible),
+    );
+}
+
+#[derive(Resource, Debug, Default)]
+struct TimeScalesPanel {
+    visible: bool,
+}
+
+fn toggle_time_scales_panel(mut panel: ResMut<TimeScalesPanel>) {
+    panel.visible = !panel.visible;
+    info!("time scales panel: {}", panel.visible);
+}
+
+/// One row of the panel.
+struct ClockRow {
+    name: &'static str,
+    used_by: &'static str,
+    relative_speed: f32,
+    paused: bool,
+    delta: f32,
+    elapsed: f32,
+}
+
+fn time_scales_ui(
+    mut contexts: EguiContexts,
+    real: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    physics: Res<Time<Physics>>,
+    fixed: Res<Time<Fixed>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let rows = [
+        ClockRow {
+            name: "Real",
+            used_by: "UiTime",
+            relative_speed: 1.0,
+            paused: false,
+            delta: real.delta_secs(),
+            elapsed: real.elapsed_secs(),
+        },
+        ClockRow {
+            name: "Virtual",
+            used_by: "Res<Time>, don't",
+            relative_speed: virtual_time.relative_speed(),
+            paused: virtual_time.is_paused(),
+            delta: virtual_time.delta_secs(),
+            elapsed: virtual_time.elapsed_secs(),
+        },
+        ClockRow {
+            name: "Physics",
+            used_by: "physics bodies, GameTime's speed",
+            relative_speed: physics.relative_speed(),
+            paused: physics.is_paused(),
+            delta: physics.delta_secs(),
+            elapsed: physics.elapsed_secs(),
+        },
+        ClockRow {
+            name: "Fixed",
+            used_by: "FixedUpdate",
+            relative_speed: 1.0,
+            paused: false,
+            delta: fixed.delta_secs(),
+            elapsed: fixed.elapsed_secs(),
+        },
+    ];
+
+    egui::Window::new("Time Scales")
+        .default_width(360.0)
+        .show(ctx, |ui| {
+            egui::Grid::new("time_scales_grid")
+                .num_columns(6)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("clock");
+                    ui.strong("read via");
+                    ui.strong("speed");
+                    ui.strong("paused");
+                    ui.strong("delta");
+                    ui.strong("elapsed");
+                    ui.end_row();
+
+                    for row in rows {
+                        let color = if row.paused {
+                            egui::Color32::RED
+                        } else if row.relative_speed != 1.0 {
+                            egui::Color32::YELLOW
+                        } else {
+                            ui.visuals().text_color()
+                        };
+
+                        ui.colored_label(color, row.name);
+                        ui.colored_label(color, row.used_by);
+                        ui.colored_label(color, format!("{:.2}x", row.relative_speed));
+                        ui.colored_label(color, if row.paused { "yes" } else { "no" });
+                        ui.colored_label(color, format!("{:.1} ms", row.delta * 1000.0));
+                        ui.colored_label(color, format!("{:.1} s", row.elapsed));
+                        ui.end_row();
+                    }
+                });
+        });
+}
@@ -1,44 +1,62 @@
 use crate::audio::sound_effect_non_dilated;
+use crate::clock::UiTime;
+use crate::gameplay::assists::Assists;
 use crate::gameplay::boomerang::{
-    BoomerangHittable, BoomerangTargetKind, CurrentBoomerangThrowOrigin, ThrowBoomerangEvent,
-    get_raycast_target,
+    BoomerangHittable, BoomerangSettings, BoomerangTargetKind, CurrentBoomerangThrowOrigin,
+    ThrowBoomerangEvent, get_raycast_target,
 };
-use crate::gameplay::input::AimModeAction;
+use crate::gameplay::bullets::Bullet;
+use crate::gameplay::deadeye::Deflected;
+use crate::gameplay::input::{AimModeAction, ConfirmAimAction, CycleTargetAction};
+use crate::gameplay::kill_cam::KillCam;
 use crate::gameplay::mouse_position::MousePosition;
+use crate::gameplay::mutators::Mutators;
 use crate::gameplay::player::Player;
+use crate::gameplay::practice_range::TargetDummy;
+use crate::gameplay::throwing_plane::ThrowingPlane;
 use crate::physics_layers::GameLayer;
 use avian3d::prelude::{
-    Collider, Physics, PhysicsTime, ShapeCastConfig, SpatialQuery, SpatialQueryFilter,
+    Collider, Physics, PhysicsTime, ShapeCastConfig, SpatialQuery, SpatialQueryFilter, SubstepCount,
 };
 use bevy::asset::{Asset, AssetServer, Handle};
 use bevy::audio::AudioSource;
 use bevy::color::{Color, palettes};
 use bevy::ecs::entity::EntityHashSet;
-use bevy::math::{Dir3, Isometry3d, Quat};
+use bevy::math::{Dir3, Quat};
 use bevy::prelude::{
-    Commands, Component, Entity, Event, EventWriter, FromWorld, Gizmos, NextState, Query, Reflect,
-    Res, ResMut, Resource, Single, State, States, Transform, Trigger, With, World,
+    Commands, Component, Entity, Event, EventWriter, FromWorld, Gizmos, Has, NextState, Query,
+    Reflect, Res, ResMut, Resource, Single, State, States, Transform, Trigger, With, World,
 };
-use bevy_enhanced_input::events::{Completed, Fired};
+use bevy_enhanced_input::events::{Completed, Fired, Started};
 use rand::{Rng, thread_rng};
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 
 // ===================
 // AIM MODE
 // ==================
-use crate::gameplay::ammo::{GiveAmmo, HasLimitedAmmo};
+use crate::gameplay::ammo::{
+    BoomerangInventory, ConsumeBoomerangSlot, ThrowCooldown, ThrowRejected,
+};
 use crate::gameplay::enemy::Enemy;
+use crate::settings::{Settings, SettingsApplied};
 use crate::theme::film_grain::FilmGrainSettingsTween;
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-/// The "minimum possible" speed time can go. We never fully pause the game during slo-mo.
+/// The "minimum possible" speed time can go, before the stronger slow-mo assist. We never fully
+/// pause the game during slo-mo.
 pub const SLOW_MO_SCALING_FACTOR: f32 = 0.1;
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<AimModeAssets>();
     app.add_systems(
         Update,
-        (draw_crosshair, draw_target_circles, draw_target_lines)
+        (
+            update_target_path_segments,
+            highlight_provisional_target,
+            draw_cursor_preview_line,
+            update_energy_meter,
+        )
             .run_if(in_state(AimModeState::Aiming)),
     );
     app.add_systems(Update, record_target_near_mouse);
@@ -46,6 +64,8 @@ pub fn plugin(app: &mut App) {
         OnEnter(AimModeState::Aiming),
         (
             initialize_target_list,
+            spawn_provisional_marker,
+            spawn_energy_meter,
             FilmGrainSettingsTween::tween_tunnel_vision_focus,
         ),
     );
@@ -59,20 +79,32 @@ pub fn plugin(app: &mut App) {
     );
 
     app.init_state::<AimModeState>();
-    app.add_observer(enter_aim_mode).add_observer(exit_aim_mode);
+    app.init_resource::<AimButtonReleaseRequired>();
+    app.add_observer(enter_aim_mode)
+        .add_observer(confirm_aim)
+        .add_observer(cancel_aim)
+        .add_observer(throw_at_painted_targets)
+        .add_observer(log_cancelled_aim);
+    app.add_observer(cycle_provisional_target);
 
     // slowdown time while in aim mode
-    app.add_systems(
-        OnEnter(AimModeState::Aiming),
-        |mut t: ResMut<Time<Physics>>| t.set_relative_speed(SLOW_MO_SCALING_FACTOR),
-    );
-    app.add_systems(
-        OnExit(AimModeState::Aiming),
-        |mut t: ResMut<Time<Physics>>| t.set_relative_speed(1.0),
-    );
+    app.init_resource::<SlowMoVeto>()
+        .init_resource::<SlowMoPhysicsSettings>()
+        .register_type::<SlowMoPhysicsSettings>();
+    app.add_systems(Update, apply_slow_mo);
 
     app.add_observer(play_enemy_targeted_sound_effect);
+    app.init_resource::<TargetMarkerAssets>()
+        .add_observer(spawn_target_markers);
     app.register_type::<AimModeTargets>();
+    app.init_resource::<AimAssist>()
+        .register_type::<AimAssist>()
+        .add_observer(apply_aim_assist_setting);
+    app.init_resource::<TargetPriority>()
+        .register_type::<TargetPriority>();
+
+    app.init_gizmo_group::<OverBudgetGizmos>();
+    app.add_systems(Startup, setup_over_budget_gizmos);
 }
 
 // =====================
@@ -86,19 +118,96 @@ pub enum AimModeState {
     Aiming,
 }
 
+/// Triggered right before aim mode ends, while the painted targets are still around.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AimModeExit {
+    /// The player pressed [`ConfirmAimAction`], the boomerang flies at the painted targets.
+    Confirmed,
+    /// The player let go of [`AimModeAction`] without confirming, nothing gets thrown.
+    Cancelled,
+}
+
+/// Set after confirming a throw while the aim button is still held, so holding it doesn't go
+/// straight back into aim mode.
+#[derive(Resource, Debug, Default)]
+struct AimButtonReleaseRequired(bool);
+
+/// Set while someone else (e.g. a local co-op partner) doesn't agree to slowing down time.
+/// Aim mode still works, just at full speed.
+#[derive(Resource, Debug, Default)]
+pub struct SlowMoVeto(pub bool);
+
+/// How physics gets stepped while time is slowed down.
+/// The fixed timestep stays the same, so nothing that reads the clocks gets out of step with
+/// physics. Every step gets split into more substeps instead, so collisions stay just as accurate.
+/// Costs more CPU the higher it goes.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct SlowMoPhysicsSettings {
+    /// How many substeps each physics step is split into while slowed down.
+    pub substeps: u32,
+}
+
+impl Default for SlowMoPhysicsSettings {
+    fn default() -> Self {
+        Self {
+            substeps: SubstepCount::default().0 * 2,
+        }
+    }
+}
+
+fn apply_slow_mo(
+    state: Res<State<AimModeState>>,
+    veto: Res<SlowMoVeto>,
+    mutators: Res<Mutators>,
+    assists: Res<Assists>,
+    kill_cam: Res<KillCam>,
+    settings: Res<SlowMoPhysicsSettings>,
+    mut time: ResMut<Time<Physics>>,
+    mut substeps: ResMut<SubstepCount>,
+    mut normal_substeps: Local<Option<u32>>,
+) {
+    let allowed = !veto.0 && !mutators.no_slow_mo;
+    let speed = if !allowed {
+        1.0
+    } else if state.get() == &AimModeState::Aiming {
+        SLOW_MO_SCALING_FACTOR * assists.slow_mo_multiplier()
+    } else {
+        kill_cam.speed()
+    };
+    let slowed_down = speed < 1.0;
+    if time.relative_speed() == speed && !(slowed_down && settings.is_changed()) {
+        return;
+    }
+    time.set_relative_speed(speed);
+
+    let normal_substeps = *normal_substeps.get_or_insert(substeps.0);
+    substeps.0 = if slowed_down {
+        settings.substeps.max(normal_substeps)
+    } else {
+        normal_substeps
+    };
+}
+
 pub fn enter_aim_mode(
     _trigger: Trigger<Fired<AimModeAction>>,
     state: Res<State<AimModeState>>,
-    has_limited_ammo: Single<Option<&HasLimitedAmmo>, With<Player>>,
+    player: Single<(Entity, Option<&BoomerangInventory>, Has<ThrowCooldown>), With<Player>>,
+    release_required: Res<AimButtonReleaseRequired>,
     mut next_state: ResMut<NextState<AimModeState>>,
+    mut commands: Commands,
 ) {
     // don't enter aim mode if we're already in it
-    if state.get() == &AimModeState::Aiming {
+    if state.get() == &AimModeState::Aiming || release_required.0 {
         return;
     }
 
-    if !has_limited_ammo.map(|o| o.0 > 0).unwrap_or(false) {
-        // info!("No ammo!");
+    let (player, inventory, cooling_down) = player.into_inner();
+    if cooling_down {
+        return;
+    }
+    if !inventory.is_some_and(BoomerangInventory::can_throw) {
+        commands.entity(player).trigger(ThrowRejected);
         return;
     }
 
@@ -106,17 +215,36 @@ pub fn enter_aim_mode(
     next_state.set(AimModeState::Aiming);
 }
 
-pub fn exit_aim_mode(
-    _trigger: Trigger<Completed<AimModeAction>>,
+fn confirm_aim(
+    _trigger: Trigger<Started<ConfirmAimAction>>,
     state: Res<State<AimModeState>>,
+    mut release_required: ResMut<AimButtonReleaseRequired>,
     mut next_state: ResMut<NextState<AimModeState>>,
+    mut commands: Commands,
 ) {
-    // we can only exit aim mode if we're in it
     if state.get() != &AimModeState::Aiming {
         return;
     }
+    info!("Confirmed aim");
+    release_required.0 = true;
+    commands.trigger(AimModeExit::Confirmed);
+    next_state.set(AimModeState::Normal);
+}
 
-    info!("Exiting aim mode");
+fn cancel_aim(
+    _trigger: Trigger<Completed<AimModeAction>>,
+    state: Res<State<AimModeState>>,
+    mut release_required: ResMut<AimButtonReleaseRequired>,
+    mut next_state: ResMut<NextState<AimModeState>>,
+    mut commands: Commands,
+) {
+    release_required.0 = false;
+    // already confirmed, or never aimed in the first place
+    if state.get() != &AimModeState::Aiming {
+        return;
+    }
+    info!("Cancelled aim");
+    commands.trigger(AimModeExit::Cancelled);
     next_state.set(AimModeState::Normal);
 }
 
@@ -174,6 +302,72 @@ pub fn play_enemy_targeted_sound_effect(
 // TARGETING
 // ===================
 const AUTOTARGETING_RADIUS: f32 = 2.0;
+/// Radius of the sphere swept to find bullets, which only get painted with the cursor right on
+/// them, see [`crate::gameplay::deadeye`].
+const BULLET_AUTOTARGETING_RADIUS: f32 = 0.4;
+
+/// How much the game helps with painting targets, picked in the settings menu.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum AimAssist {
+    Off,
+    #[default]
+    Low,
+    High,
+}
+
+impl AimAssist {
+    /// Radius of the sphere swept from the thrower to the cursor to find targets.
+    fn autotargeting_radius(self) -> f32 {
+        match self {
+            AimAssist::Off => AUTOTARGETING_RADIUS * 0.25,
+            AimAssist::Low => AUTOTARGETING_RADIUS,
+            AimAssist::High => AUTOTARGETING_RADIUS * 1.5,
+        }
+    }
+
+    /// How far the cursor gets pulled towards a nearby enemy while aiming with a gamepad,
+    /// from 0.0 (not at all) to 1.0 (right onto it).
+    fn magnetism(self) -> f32 {
+        match self {
+            AimAssist::Off => 0.0,
+            AimAssist::Low => 0.3,
+            AimAssist::High => 0.6,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            AimAssist::Off => AimAssist::Low,
+            AimAssist::Low => AimAssist::High,
+            AimAssist::High => AimAssist::Off,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            AimAssist::Off => AimAssist::High,
+            AimAssist::Low => AimAssist::Off,
+            AimAssist::High => AimAssist::Low,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AimAssist::Off => "Off",
+            AimAssist::Low => "Low",
+            AimAssist::High => "High",
+        }
+    }
+}
+
+fn apply_aim_assist_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut aim_assist: ResMut<AimAssist>,
+) {
+    *aim_assist = settings.aim_assist;
+}
 
 #[derive(Component, Default, Debug, Clone, Reflect)]
 #[reflect(Component)]
@@ -182,134 +376,178 @@ pub struct AimModeTargets {
     // todo when aim mode exits, despawn this entity and fire a single boomerang with the list of targets we painted
 }
 
+impl AimModeTargets {
+    /// Whether nothing has been painted yet.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Length of the painted path, starting at `start` and visiting every target in order.
+    fn path_length(&self, start: Vec3, transforms: &Query<&GlobalTransform>) -> f32 {
+        let mut length = 0.0;
+        let mut previous = start;
+        for target in &self.targets {
+            let Ok(transform) = transforms.get(*target) else {
+                continue;
+            };
+            let position = transform.translation();
+            length += previous.distance(position);
+            previous = position;
+        }
+        length
+    }
+}
+
+/// The enemy under the cursor that's about to be painted. When there's a crowd under the cursor
+/// the player can cycle through it with [CycleTargetAction] before the paint goes through.
+#[derive(Component, Default, Debug)]
+pub struct ProvisionalTarget {
+    target: Option<Entity>,
+    /// How many steps the player cycled away from the best scoring candidate.
+    offset: isize,
+    /// How long `target` has been the provisional target, in real time.
+    picked_for: f32,
+}
+
 pub fn initialize_target_list(mut commands: Commands) {
-    commands.spawn((Name::from("AimModeTargets"), AimModeTargets::default()));
+    commands.spawn((
+        Name::from("AimModeTargets"),
+        AimModeTargets::default(),
+        ProvisionalTarget::default(),
+    ));
 }
 
-pub fn cleanup_target_list(
-    mut commands: Commands,
-    query: Single<(Entity, &AimModeTargets)>,
-    player_single: Single<Entity, With<Player>>,
+fn throw_at_painted_targets(
+    trigger: Trigger<AimModeExit>,
+    target_list: Single<&AimModeTargets>,
+    player_single: Single<(Entity, Option<&BoomerangInventory>), With<Player>>,
     mut event_writer: EventWriter<ThrowBoomerangEvent>,
+    mut commands: Commands,
 ) {
-    let (target_list_entity, target_list) = query.into_inner();
+    if *trigger.event() != AimModeExit::Confirmed {
+        return;
+    }
     let v: Vec<_> = target_list
         .targets
         .iter()
         .map(|e| BoomerangTargetKind::Entity(*e))
         .collect();
     // todo not why we nee this or how to handle multiple such entities. just assuming throws always originate from the player for now.
-    let player = player_single.into_inner();
-    if !v.is_empty() {
+    let (player, inventory) = player_single.into_inner();
+    let slot = inventory.and_then(BoomerangInventory::ready_slot);
+    if let Some(slot) = slot.filter(|_| !v.is_empty()) {
         event_writer.write(ThrowBoomerangEvent {
             thrower_entity: player,
             target: v,
+            slot: Some(slot),
+            charge: 0.0,
         });
-        commands.entity(player).trigger(GiveAmmo(-1));
+        commands.entity(player).trigger(ConsumeBoomerangSlot(slot));
     }
-    commands.entity(target_list_entity).despawn();
 }
 
-pub fn draw_crosshair(mut gizmos: Gizmos, mouse_position: Res<MousePosition>) {
-    let Some(mouse_position) = mouse_position.boomerang_throwing_plane else {
-        debug!("No mouse position found");
+fn log_cancelled_aim(trigger: Trigger<AimModeExit>, target_list: Single<&AimModeTargets>) {
+    if *trigger.event() != AimModeExit::Cancelled {
         return;
-    };
-
-    // Create a rotation that rotates 90 degrees (PI/2 radians) around the X-axis
-    let rotation = Quat::from_rotation_x(std::f32::consts::FRAC_PI_2);
-    let isometry = Isometry3d::new(mouse_position, rotation);
+    }
+    info!(
+        "Aim cancelled, dropping {} painted target(s)",
+        target_list.targets.len()
+    );
+}
 
-    gizmos.circle(isometry, 2.0, Color::srgb(0.9, 0.1, 0.1));
+pub fn cleanup_target_list(mut commands: Commands, query: Single<Entity, With<AimModeTargets>>) {
+    commands.entity(query.into_inner()).despawn();
 }
 
-pub fn draw_target_circles(
-    mut gizmos: Gizmos,
-    hittables: Query<&Transform, With<BoomerangHittable>>,
-    query: Single<&AimModeTargets>,
-) {
-    let targets = query.into_inner();
-    let x = &targets.targets;
+const MAX_TARGETS_SELECTABLE: usize = 300;
+/// How many enemies along the sweep get considered for painting at once.
+const MAX_TARGET_CANDIDATES: u32 = 16;
 
-    for e in x.iter() {
-        if let Ok(t) = hittables.get(*e) {
-            // Create a rotation that rotates 90 degrees (PI/2 radians) around the X-axis
-            let rotation = Quat::from_rotation_x(std::f32::consts::FRAC_PI_2);
-            let isometry = Isometry3d::new(t.translation, rotation);
+/// Decides which enemy gets painted when several of them are under the cursor.
+/// Every candidate gets a score, lower is better.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct TargetPriority {
+    /// Score per unit of distance between the enemy and the line from the thrower to the cursor.
+    pub cursor_ray_weight: f32,
+    /// Score per unit of distance between the enemy and the previous target (or the player), so
+    /// short hops win over reaching past someone.
+    pub previous_target_weight: f32,
+    /// Whether enemies that are already painted get skipped, so they don't shadow the ones behind.
+    pub skip_painted: bool,
+    /// With more than one enemy under the cursor, the provisional target only gets painted once
+    /// it's been picked for this long (in real time), leaving a moment to cycle to another one.
+    pub crowd_confirm_seconds: f32,
+}
 
-            // todo use retained mode gizmos to be more efficient (or an instanced mesh of a cool looking crosshair)
-            gizmos.circle(isometry, 1.5, Color::srgb(0.9, 0.1, 0.1));
+impl Default for TargetPriority {
+    fn default() -> Self {
+        Self {
+            cursor_ray_weight: 1.0,
+            previous_target_weight: 0.1,
+            skip_painted: true,
+            crowd_confirm_seconds: 0.3,
         }
     }
 }
 
-pub fn draw_target_lines(
-    mut gizmos: Gizmos,
-    hittables: Query<&Transform, With<BoomerangHittable>>,
-    query: Single<&AimModeTargets>,
-    player_single: Single<(Entity, &Transform), With<Player>>,
-    spatial_query: SpatialQuery,
-) -> Result {
-    let targets = query.into_inner();
-    let x = &targets.targets;
-
-    let (mut last_entity_found, mut last_transform_found) = player_single.into_inner();
-
-    for e in x.iter() {
-        if let Ok(t) = hittables.get(*e) {
-            let (mut target_entity, target_location) = match get_raycast_target(
-                &spatial_query,
-                t.translation,
-                last_entity_found,
-                last_transform_found.translation,
-            ) {
-                Ok(value) => value,
-                Err(_value) => continue,
-            };
-
-            if let Some(te) = target_entity {
-                if hittables.get(te).is_err() {
-                    // If the entity hit isn't one of the targetable ones, we hit a wall.
-                    target_entity = None;
-                }
-            }
-
-            let color = match target_entity {
-                Some(_entity) => Color::srgb(0.2, 0.7, 0.2),
-                None => Color::srgb(0.5, 0.1, 0.1),
-            };
-
-            // todo use retained mode gizmos to be more efficient (or an instanced mesh of a cool looking crosshair)
-            gizmos.line(last_transform_found.translation, target_location, color);
-
-            last_transform_found = t;
-            last_entity_found = *e;
-        }
+impl TargetPriority {
+    fn score(&self, enemy: Vec3, origin: Vec3, cursor: Vec3) -> f32 {
+        let enemy = enemy.with_y(cursor.y);
+        let origin = origin.with_y(cursor.y);
+        let leg = cursor - origin;
+        let along =
+            ((enemy - origin).dot(leg) / leg.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+        let distance_to_ray = enemy.distance(origin + leg * along);
+        self.cursor_ray_weight * distance_to_ray
+            + self.previous_target_weight * enemy.distance(origin)
     }
-
-    Ok(())
 }
 
-const MAX_TARGETS_SELECTABLE: usize = 300;
-
 pub fn record_target_near_mouse(
+    real_time: UiTime,
     mouse_position: Res<MousePosition>,
     spatial_query: SpatialQuery,
-    mut current_target_list: Single<&mut AimModeTargets>,
+    target_list: Single<(&mut AimModeTargets, &mut ProvisionalTarget)>,
     current_throw_origin: Single<(Entity, &Transform), With<CurrentBoomerangThrowOrigin>>,
-    enemies_query: Query<Entity, With<Enemy>>,
+    // target dummies on the practice range can be aimed at like outlaws, and so can bullets
+    enemies_query: Query<
+        Entity,
+        Or<(
+            With<Enemy>,
+            With<TargetDummy>,
+            (With<Bullet>, Without<Deflected>),
+        )>,
+    >,
+    player: Single<&GlobalTransform, With<Player>>,
+    transforms: Query<&GlobalTransform>,
+    boomerang_settings: Res<BoomerangSettings>,
+    aim_assist: Res<AimAssist>,
+    target_priority: Res<TargetPriority>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
     mut commands: Commands,
     mut gizmos: Gizmos,
 ) -> Result {
+    let (mut current_target_list, mut provisional) = target_list.into_inner();
     // target list is full, don't add any more targets
     if current_target_list.targets.len() >= MAX_TARGETS_SELECTABLE {
         return Ok(());
     }
 
-    let Some(mouse_position) = mouse_position.boomerang_throwing_plane else {
+    let Some(mut mouse_position) = mouse_position.boomerang_throwing_plane else {
         warn!("No mouse position found");
         return Ok(());
     };
+
+    // Sweeping a cursor over enemies with a stick is a lot harder than with a mouse, so help out
+    // players aiming on a gamepad. Aim mode is held on player one's own binding, so if it isn't
+    // the mouse button it's their gamepad, whatever the partner is doing with theirs.
+    let aiming_with_gamepad = !mouse_buttons.pressed(MouseButton::Left);
+    if aiming_with_gamepad {
+        mouse_position = magnetize_cursor(mouse_position, *aim_assist, &spatial_query, &transforms);
+    }
+    let autotargeting_radius = aim_assist.autotargeting_radius();
     let (origin_entity, origin_transform) = current_throw_origin.into_inner();
 
     let Ok(direction_from_thrower_to_cursor) =
@@ -318,69 +556,95 @@ pub fn record_target_near_mouse(
         return Ok(());
     };
 
-    // Cast a sphere from the thrower to the cursor, returning the first enemy hit (this is what we're targeting).
+    // Sweep a sphere from the thrower to the cursor, collecting every enemy along the way.
     // The reason it's a sphere is to allow for some "auto-aim" functionality - you don't need to mouse over the target exactly.
-    let Some(target_near_cursor) = spatial_query.cast_shape_predicate(
-        &Collider::sphere(AUTOTARGETING_RADIUS), // Shape
+    let mut excluded = vec![origin_entity];
+    if target_priority.skip_painted {
+        excluded.extend(current_target_list.targets.iter().copied());
+    }
+    let hits = spatial_query.shape_hits(
+        &Collider::sphere(autotargeting_radius), // Shape
         origin_transform.translation,            // Shape position
         Quat::default(),                         // Shape rotation
         direction_from_thrower_to_cursor,
+        MAX_TARGET_CANDIDATES,
         &ShapeCastConfig::from_max_distance(
-            origin_transform.translation.distance(mouse_position) + AUTOTARGETING_RADIUS / 2.,
+            origin_transform.translation.distance(mouse_position) + autotargeting_radius / 2.,
         ),
-        &SpatialQueryFilter::from_mask(GameLayer::Enemy)
-            .with_excluded_entities(vec![origin_entity]),
-        &|e| enemies_query.contains(e),
-    ) else {
-        // info!("record_target_near_mouse:: no target near cursor at {:?}", mouse_position);
+        &SpatialQueryFilter::from_mask(GameLayer::Enemy).with_excluded_entities(excluded.clone()),
+    );
+    // Bullets get a much smaller sphere, painting one is supposed to take a steady hand.
+    let bullet_hits = spatial_query.shape_hits(
+        &Collider::sphere(BULLET_AUTOTARGETING_RADIUS),
+        origin_transform.translation,
+        Quat::default(),
+        direction_from_thrower_to_cursor,
+        MAX_TARGET_CANDIDATES,
+        &ShapeCastConfig::from_max_distance(
+            origin_transform.translation.distance(mouse_position)
+                + BULLET_AUTOTARGETING_RADIUS / 2.,
+        ),
+        &SpatialQueryFilter::from_mask(GameLayer::Bullet).with_excluded_entities(excluded),
+    );
+
+    // In a crowd the sweep hits several enemies at once, the best scoring one the thrower can
+    // actually see is what we're targeting.
+    let mut candidates: Vec<_> = hits
+        .into_iter()
+        .chain(bullet_hits)
+        .filter(|hit| enemies_query.contains(hit.entity))
+        .filter_map(|hit| {
+            let position = transforms.get(hit.entity).ok()?.translation();
+            let score =
+                target_priority.score(position, origin_transform.translation, mouse_position);
+            Some((hit, score))
+        })
+        .collect();
+    candidates.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    let visible: Vec<_> = candidates
+        .into_iter()
+        .map(|(hit, _)| hit)
+        .filter(|hit| {
+            in_line_of_sight(
+                &spatial_query,
+                origin_entity,
+                origin_transform.translation,
+                hit.entity,
+                hit.point1,
+            )
+        })
+        .collect();
+    if visible.is_empty() {
+        *provisional = ProvisionalTarget::default();
         return Ok(());
-    };
+    }
 
-    {
-        let _dist = target_near_cursor
-            .point1
-            .distance(origin_transform.translation);
-        // info!("record_target_near_mouse:: target near cursor {:?} away from origin of throw", dist);
+    // The player may have cycled away from the best scoring one.
+    let index = provisional.offset.rem_euclid(visible.len() as isize) as usize;
+    let target_near_cursor = &visible[index];
+    if provisional.target == Some(target_near_cursor.entity) {
+        provisional.picked_for += real_time.delta_secs();
+    } else {
+        provisional.target = Some(target_near_cursor.entity);
+        provisional.picked_for = 0.0;
+    }
+    // A lone enemy gets painted right away, so sweeping over them works like it always did.
+    if visible.len() > 1 && provisional.picked_for < target_priority.crowd_confirm_seconds {
+        return Ok(());
     }
+    gizmos.line(
+        origin_transform.translation,
+        target_near_cursor.point1,
+        palettes::css::BLUE_VIOLET,
+    );
 
-    // Check for intervening walls with a ray cast. This time, we don't filter to
-    // Enemies only - if we hit a wall before hitting our target, we don't add
-    // it to the list of targeted entities.
-    {
-        let Ok(ray_direction) = Dir3::new(
-            (target_near_cursor.point1 - origin_transform.translation).normalize_or_zero(),
-        ) else {
-            // info!("record_target_near_mouse:: couldn't raycast to painted target");
-            return Ok(());
-        };
-        let line_of_sight_ray = spatial_query.cast_ray_predicate(
-            origin_transform.translation,
-            ray_direction,
-            900.,
-            true,
-            &SpatialQueryFilter {
-                excluded_entities: EntityHashSet::from([origin_entity]),
-                ..Default::default()
-            },
-            &|e| origin_entity != e,
-        );
-        // info!("record_target_near_mouse:: cast ray from {:?} to {:?}. Direction {:?}", origin_transform.translation, target_near_cursor.point1, ray_direction);
-        gizmos.line(
-            origin_transform.translation,
-            target_near_cursor.point1,
-            palettes::css::BLUE_VIOLET,
-        );
-        let Some(ray_hit) = line_of_sight_ray else {
-            // info!("record_target_near_mouse:: no ray hits");
-            return Ok(());
-        };
-        if ray_hit.entity != target_near_cursor.entity {
-            // info!(
-            //     "record_target_near_mouse:: ray hit a different target {:?} than the mouse cursor: {:?}",
-            //     ray_hit.entity, target_near_cursor.entity
-            // );
-            return Ok(());
-        }
+    // Reject targets which would make the path longer than the boomerang has energy for.
+    let path_length = current_target_list.path_length(player.translation(), &transforms)
+        + origin_transform
+            .translation
+            .distance(target_near_cursor.point1);
+    if path_length > boomerang_settings.energy {
+        return Ok(());
     }
 
     // Finally, check if the targeted entity has already been targeted
@@ -397,12 +661,349 @@ pub fn record_target_near_mouse(
             commands.reborrow(),
         );
         current_target_list.targets.push(target_near_cursor.entity);
+        *provisional = ProvisionalTarget::default();
         commands.trigger(PlayEnemyTargetedSound); // play a sound when an enemy is targeted
+        commands.trigger(TargetPainted {
+            target: target_near_cursor.entity,
+            previous: origin_entity,
+        });
     }
 
     Ok(())
 }
 
+fn cycle_provisional_target(
+    trigger: Trigger<Started<CycleTargetAction>>,
+    mut provisional: Query<&mut ProvisionalTarget>,
+) {
+    // only around while aiming
+    let Ok(mut provisional) = provisional.single_mut() else {
+        return;
+    };
+    provisional.offset += if trigger.value > 0.0 { 1 } else { -1 };
+}
+
+/// Check for intervening walls with a ray cast. This time, we don't filter to Enemies only - if
+/// we hit a wall before hitting the target, it can't be painted.
+fn in_line_of_sight(
+    spatial_query: &SpatialQuery,
+    origin_entity: Entity,
+    origin: Vec3,
+    target: Entity,
+    target_point: Vec3,
+) -> bool {
+    let Ok(ray_direction) = Dir3::new((target_point - origin).normalize_or_zero()) else {
+        return false;
+    };
+    let line_of_sight_ray = spatial_query.cast_ray_predicate(
+        origin,
+        ray_direction,
+        900.,
+        true,
+        &SpatialQueryFilter {
+            excluded_entities: EntityHashSet::from([origin_entity]),
+            ..Default::default()
+        },
+        &|e| origin_entity != e,
+    );
+    line_of_sight_ray.is_some_and(|ray_hit| ray_hit.entity == target)
+}
+
+/// Pulls the cursor towards the closest enemy around it, depending on the [AimAssist] setting.
+fn magnetize_cursor(
+    cursor: Vec3,
+    aim_assist: AimAssist,
+    spatial_query: &SpatialQuery,
+    transforms: &Query<&GlobalTransform>,
+) -> Vec3 {
+    let magnetism = aim_assist.magnetism();
+    if magnetism <= 0.0 {
+        return cursor;
+    }
+
+    let pull_range = aim_assist.autotargeting_radius() * 2.0;
+    let closest_enemy = spatial_query
+        .shape_intersections(
+            &Collider::sphere(pull_range),
+            cursor,
+            Quat::default(),
+            &SpatialQueryFilter::from_mask(GameLayer::Enemy),
+        )
+        .into_iter()
+        .filter_map(|entity| transforms.get(entity).ok())
+        .map(|transform| transform.translation().with_y(cursor.y))
+        .min_by(|a, b| {
+            a.distance_squared(cursor)
+                .total_cmp(&b.distance_squared(cursor))
+        });
+
+    closest_enemy.map_or(cursor, |enemy| cursor.lerp(enemy, magnetism))
+}
+
+// ===================
+// TARGET MARKERS
+// ===================
+
+/// Sent when a target gets added to the path. `previous` is where the boomerang comes from, i.e.
+/// the player or the target painted before.
+#[derive(Event, Debug, Clone, Copy)]
+struct TargetPainted {
+    target: Entity,
+    previous: Entity,
+}
+
+/// Shared by all markers, so painting a target doesn't create any new assets.
+#[derive(Resource)]
+struct TargetMarkerAssets {
+    ring: Handle<Mesh>,
+    segment: Handle<Mesh>,
+    ring_material: Handle<StandardMaterial>,
+    provisional_material: Handle<StandardMaterial>,
+    clear_path_material: Handle<StandardMaterial>,
+    blocked_path_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for TargetMarkerAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let ring = meshes.add(Annulus::new(1.35, 1.5));
+        // one unit long along Z, stretched to the length of each leg
+        let segment = meshes.add(Cuboid::new(0.08, 0.08, 1.0));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let mut unlit = |color: Color| {
+            materials.add(StandardMaterial {
+                base_color: color,
+                unlit: true,
+                ..default()
+            })
+        };
+        Self {
+            ring,
+            segment,
+            ring_material: unlit(Color::srgb(0.9, 0.1, 0.1)),
+            provisional_material: unlit(Color::srgb(1.0, 0.843, 0.0)),
+            clear_path_material: unlit(Color::srgb(0.2, 0.7, 0.2)),
+            blocked_path_material: unlit(Color::srgb(0.5, 0.1, 0.1)),
+        }
+    }
+}
+
+/// Sits under the [ProvisionalTarget] while it waits to be painted.
+#[derive(Component, Debug)]
+struct ProvisionalMarker;
+
+fn spawn_provisional_marker(assets: Res<TargetMarkerAssets>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Provisional Target Marker"),
+        ProvisionalMarker,
+        Mesh3d(assets.ring.clone()),
+        MeshMaterial3d(assets.provisional_material.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+        StateScoped(AimModeState::Aiming),
+    ));
+}
+
+fn highlight_provisional_target(
+    provisional: Single<&ProvisionalTarget>,
+    targets: Query<&GlobalTransform>,
+    marker: Single<(&mut Transform, &mut Visibility), With<ProvisionalMarker>>,
+) {
+    let (mut transform, mut visibility) = marker.into_inner();
+    let Some(target) = provisional
+        .target
+        .and_then(|target| targets.get(target).ok())
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    // flat on the ground like the painted rings, a bit smaller so both show when they overlap
+    *transform = Transform::from_translation(target.translation())
+        .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2))
+        .with_scale(Vec3::splat(0.8));
+    *visibility = Visibility::Inherited;
+}
+
+/// One leg of the painted path, from one target (or the player) to the next.
+#[derive(Component, Debug)]
+struct TargetPathSegment {
+    from: Entity,
+    to: Entity,
+}
+
+/// Puts a ring under the newly painted target and connects it to the previous one. Everything is
+/// scoped to aim mode, so it all goes away once the boomerang is thrown.
+fn spawn_target_markers(
+    trigger: Trigger<TargetPainted>,
+    assets: Res<TargetMarkerAssets>,
+    mut commands: Commands,
+) {
+    let painted = trigger.event();
+    commands.spawn((
+        Name::new("Target Marker"),
+        Mesh3d(assets.ring.clone()),
+        MeshMaterial3d(assets.ring_material.clone()),
+        // the ring is built facing +Z, lay it flat on the ground
+        Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ChildOf(painted.target),
+        StateScoped(AimModeState::Aiming),
+    ));
+    commands.spawn((
+        Name::new("Target Path Segment"),
+        TargetPathSegment {
+            from: painted.previous,
+            to: painted.target,
+        },
+        Mesh3d(assets.segment.clone()),
+        MeshMaterial3d(assets.blocked_path_material.clone()),
+        Transform::default(),
+        // placed once we know where its ends are
+        Visibility::Hidden,
+        StateScoped(AimModeState::Aiming),
+    ));
+}
+
+/// Stretches the path segments between their ends, turning them red where a wall is in the way.
+/// Only segments with an end that moved get updated.
+fn update_target_path_segments(
+    mut segments: Query<(
+        &TargetPathSegment,
+        &mut Transform,
+        &mut MeshMaterial3d<StandardMaterial>,
+        &mut Visibility,
+    )>,
+    ends: Query<Ref<GlobalTransform>>,
+    hittables: Query<(), With<BoomerangHittable>>,
+    spatial_query: SpatialQuery,
+    throwing_plane: Res<ThrowingPlane>,
+    assets: Res<TargetMarkerAssets>,
+) {
+    for (segment, mut transform, mut material, mut visibility) in &mut segments {
+        let (Ok(from), Ok(to)) = (ends.get(segment.from), ends.get(segment.to)) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        if !from.is_changed() && !to.is_changed() && *visibility != Visibility::Hidden {
+            continue;
+        }
+
+        let Ok(hit) = get_raycast_target(
+            &spatial_query,
+            &throwing_plane,
+            to.translation(),
+            segment.from,
+            from.translation(),
+        ) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        // If the entity hit isn't one of the targetable ones, we hit a wall.
+        let path_is_clear = hit.entity.is_some_and(|entity| hittables.contains(entity));
+
+        let (start, end) = (from.translation(), hit.position);
+        *transform = Transform::from_translation((start + end) / 2.0)
+            .looking_at(end, Vec3::Y)
+            .with_scale(Vec3::new(1.0, 1.0, start.distance(end)));
+        material.0 = if path_is_clear {
+            assets.clear_path_material.clone()
+        } else {
+            assets.blocked_path_material.clone()
+        };
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// ===================
+// ENERGY BUDGET
+// ===================
+
+/// Used to draw the part of the preview line the boomerang doesn't have enough energy for.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct OverBudgetGizmos;
+
+fn setup_over_budget_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<OverBudgetGizmos>();
+    config.line.width = 5.;
+    config.line.style = GizmoLineStyle::Dotted;
+}
+
+/// Previews the next leg from the current throw origin to the cursor, turning red and dotted past
+/// the point where the boomerang would run out of energy.
+fn draw_cursor_preview_line(
+    mut gizmos: Gizmos,
+    mut over_budget_gizmos: Gizmos<OverBudgetGizmos>,
+    mouse_position: Res<MousePosition>,
+    targets: Single<&AimModeTargets>,
+    current_throw_origin: Single<&GlobalTransform, With<CurrentBoomerangThrowOrigin>>,
+    player: Single<&GlobalTransform, With<Player>>,
+    transforms: Query<&GlobalTransform>,
+    boomerang_settings: Res<BoomerangSettings>,
+) {
+    let Some(cursor) = mouse_position.boomerang_throwing_plane else {
+        return;
+    };
+    let origin = current_throw_origin.translation().with_y(cursor.y);
+    let remaining_energy = (boomerang_settings.energy
+        - targets.path_length(player.translation(), &transforms))
+    .max(0.0);
+
+    let leg = cursor - origin;
+    if leg.length() <= remaining_energy {
+        gizmos.line(origin, cursor, Color::srgba(1.0, 1.0, 1.0, 0.5));
+        return;
+    }
+
+    let budget_end = origin + leg.normalize_or_zero() * remaining_energy;
+    gizmos.line(origin, budget_end, Color::srgba(1.0, 1.0, 1.0, 0.5));
+    over_budget_gizmos.line(budget_end, cursor, Color::srgb(0.9, 0.1, 0.1));
+}
+
+#[derive(Component)]
+struct EnergyMeterFill;
+
+fn spawn_energy_meter(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Energy Meter"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(30.0),
+            left: Val::Percent(35.0),
+            width: Val::Percent(30.0),
+            height: Val::Px(16.0),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor(Color::BLACK),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        Pickable::IGNORE,
+        StateScoped(AimModeState::Aiming),
+        children![(
+            Name::new("Energy Meter Fill"),
+            EnergyMeterFill,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(1.0, 0.843, 0.0)),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn update_energy_meter(
+    targets: Single<&AimModeTargets>,
+    player: Single<&GlobalTransform, With<Player>>,
+    transforms: Query<&GlobalTransform>,
+    boomerang_settings: Res<BoomerangSettings>,
+    mut fill: Single<&mut Node, With<EnergyMeterFill>>,
+) {
+    let used = targets.path_length(player.translation(), &transforms);
+    let remaining = (1.0 - used / boomerang_settings.energy).clamp(0.0, 1.0);
+    fill.width = Val::Percent(remaining * 100.0);
+}
+
 fn reset_current_boomerang_throw_origin_to_player(
     player: Single<Entity, With<Player>>,
     current_throw_origin: Single<Entity, With<CurrentBoomerangThrowOrigin>>,
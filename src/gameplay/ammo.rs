@@ -1,36 +1,456 @@
+//! The boomerangs a thrower carries, one per slot. Throwing one empties its slot until it comes
+//! back, after which some kinds need to cool down before they can be thrown again.
+//!
+//! Other systems don't touch [`BoomerangInventory`] directly but trigger [`ConsumeBoomerangSlot`],
+//! [`RestoreBoomerangSlot`], [`AddBoomerangSlot`] or [`UpgradeBoomerangSlot`] on the thrower.
+//! Whenever a slot changes, [`BoomerangSlotChanged`] gets triggered on the thrower in turn.
+//!
+//! Kinds of boomerangs are looked up in the [`BoomerangKindRegistry`], more of them can be added
+//! with [`RegisterBoomerangKind::register_boomerang_kind`].
+//!
+//! Every throw puts a short [`ThrowCooldown`] on the thrower so a single press can't throw twice.
+//! Trying to throw without a ready boomerang should trigger [`ThrowRejected`] instead, which
+//! clicks and flashes the HUD.
+
+use crate::asset_tracking::LoadResource;
+use crate::audio::sound_effect_non_dilated;
+use crate::clock::{GameTime, UiTime};
+use crate::gameplay::Gameplay;
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_observer(give_ammo)
-        .register_type::<HasLimitedAmmo>();
+    app.register_type::<BoomerangInventory>()
+        .init_resource::<BoomerangKindRegistry>()
+        .register_type::<ThrownFromSlot>()
+        .register_type::<AmmoAssets>()
+        .load_resource::<AmmoAssets>();
+    app.add_observer(consume_slot)
+        .add_observer(restore_slot)
+        .add_observer(add_slot)
+        .add_observer(upgrade_slot)
+        .add_observer(reject_throw);
+    app.add_systems(
+        Update,
+        (cool_down_slots, cool_down_throws, fade_empty_flash).run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// Minimum time between two throws of the same thrower.
+const THROW_COOLDOWN_SECONDS: f32 = 0.25;
+/// Minimum time between two rejected throws, so holding the button doesn't click non-stop.
+const REJECT_COOLDOWN_SECONDS: f32 = 0.5;
+const EMPTY_FLASH_SECONDS: f32 = 0.3;
+const EMPTY_FLASH_COLOR: Color = Color::srgba(0.8, 0.1, 0.1, 0.6);
+
+/// A kind of boomerang, see [`BoomerangKindRegistry`] for what it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub struct BoomerangKind(usize);
+
+impl BoomerangKind {
+    pub const WOODEN: Self = Self(0);
+    /// Hits harder, but needs a moment after catching it before it can be thrown again.
+    pub const IRON: Self = Self(1);
+}
+
+/// What sets a kind of boomerang apart.
+#[derive(Debug, Clone)]
+pub struct BoomerangKindInfo {
+    pub name: &'static str,
+    pub damage: u32,
+    /// How long it needs after being caught before it can be thrown again.
+    pub cooldown_seconds: f32,
+}
+
+/// Every [`BoomerangKind`] the game knows about. The built-in ones are always there, others get
+/// added with [`RegisterBoomerangKind::register_boomerang_kind`].
+#[derive(Resource, Debug)]
+pub struct BoomerangKindRegistry {
+    kinds: Vec<BoomerangKindInfo>,
+}
+
+impl Default for BoomerangKindRegistry {
+    fn default() -> Self {
+        Self {
+            kinds: vec![
+                BoomerangKindInfo {
+                    name: "Wooden",
+                    damage: 1,
+                    cooldown_seconds: 0.0,
+                },
+                BoomerangKindInfo {
+                    name: "Iron",
+                    damage: 2,
+                    cooldown_seconds: 1.5,
+                },
+            ],
+        }
+    }
+}
+
+impl BoomerangKindRegistry {
+    /// Falls back to a wooden boomerang for kinds that were never registered.
+    pub fn get(&self, kind: BoomerangKind) -> &BoomerangKindInfo {
+        self.kinds
+            .get(kind.0)
+            .unwrap_or(&self.kinds[BoomerangKind::WOODEN.0])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (BoomerangKind, &BoomerangKindInfo)> {
+        self.kinds
+            .iter()
+            .enumerate()
+            .map(|(index, info)| (BoomerangKind(index), info))
+    }
+}
+
+pub trait RegisterBoomerangKind {
+    /// Adds a new kind of boomerang, which can then be handed out with [`AddBoomerangSlot`].
+    fn register_boomerang_kind(&mut self, info: BoomerangKindInfo) -> BoomerangKind;
+}
+
+impl RegisterBoomerangKind for App {
+    fn register_boomerang_kind(&mut self, info: BoomerangKindInfo) -> BoomerangKind {
+        self.init_resource::<BoomerangKindRegistry>();
+        let mut registry = self.world_mut().resource_mut::<BoomerangKindRegistry>();
+        registry.kinds.push(info);
+        BoomerangKind(registry.kinds.len() - 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Reflect)]
+pub enum SlotState {
+    #[default]
+    Ready,
+    /// The boomerang is out there somewhere.
+    Thrown,
+    CoolingDown(Timer),
+}
+
+#[derive(Debug, Clone, Default, Reflect)]
+pub struct BoomerangSlot {
+    pub kind: BoomerangKind,
+    /// Every upgrade adds one damage on top of what the kind does.
+    pub upgrades: u32,
+    pub state: SlotState,
 }
-#[derive(Component, Debug, Reflect)]
+
+impl BoomerangSlot {
+    pub fn new(kind: BoomerangKind) -> Self {
+        Self { kind, ..default() }
+    }
+
+    pub fn damage(&self, kinds: &BoomerangKindRegistry) -> u32 {
+        kinds.get(self.kind).damage + self.upgrades
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.state == SlotState::Ready
+    }
+}
+
+#[derive(Component, Debug, Clone, Default, Reflect)]
 #[reflect(Component)]
-pub struct HasLimitedAmmo(pub i32);
-impl HasLimitedAmmo {
-    pub fn _can_shoot(&self) -> bool {
-        self.0 > 0
+pub struct BoomerangInventory {
+    slots: Vec<BoomerangSlot>,
+}
+
+impl BoomerangInventory {
+    /// `count` wooden boomerangs, ready to go.
+    pub fn new(count: usize) -> Self {
+        Self {
+            slots: vec![BoomerangSlot::default(); count],
+        }
+    }
+
+    pub fn slot(&self, slot: usize) -> Option<&BoomerangSlot> {
+        self.slots.get(slot)
+    }
+
+    /// The first slot that can be thrown right now.
+    pub fn ready_slot(&self) -> Option<usize> {
+        self.slots.iter().position(BoomerangSlot::is_ready)
+    }
+
+    pub fn ready_count(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_ready()).count()
+    }
+
+    pub fn can_throw(&self) -> bool {
+        self.ready_slot().is_some()
+    }
+
+    /// Marks the boomerang in `slot` as thrown. Returns false if it wasn't ready.
+    pub fn consume(&mut self, slot: usize) -> bool {
+        match self.slots.get_mut(slot) {
+            Some(slot) if slot.is_ready() => {
+                slot.state = SlotState::Thrown;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Puts a thrown boomerang back into `slot`, starting its cooldown if it has one.
+    /// Returns false if nothing was thrown from that slot.
+    pub fn restore(&mut self, slot: usize, kinds: &BoomerangKindRegistry) -> bool {
+        let Some(slot) = self.slots.get_mut(slot) else {
+            return false;
+        };
+        if slot.state != SlotState::Thrown {
+            return false;
+        }
+        let cooldown = kinds.get(slot.kind).cooldown_seconds;
+        slot.state = if cooldown > 0.0 {
+            SlotState::CoolingDown(Timer::from_seconds(cooldown, TimerMode::Once))
+        } else {
+            SlotState::Ready
+        };
+        true
     }
 }
 
+/// Remembers which slot a boomerang came out of, so it goes back into the same one.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ThrownFromSlot(pub usize);
+
+/// Trigger on a thrower to mark a slot as thrown.
+#[derive(Event, Debug)]
+pub struct ConsumeBoomerangSlot(pub usize);
+
+/// Trigger on a thrower when a boomerang made it back to them.
 #[derive(Event, Debug)]
-pub struct GiveAmmo(pub i32);
+pub struct RestoreBoomerangSlot(pub usize);
 
-fn give_ammo(trigger: Trigger<GiveAmmo>, mut query: Query<&mut HasLimitedAmmo>) {
-    let Ok(mut e) = query.get_mut(trigger.target()) else {
+/// Trigger on a thrower to hand them another boomerang, e.g. from a pickup or the shop.
+#[derive(Event, Debug)]
+pub struct AddBoomerangSlot(pub BoomerangKind);
+
+/// Trigger on a thrower to upgrade the boomerang in a slot.
+#[derive(Event, Debug)]
+pub struct UpgradeBoomerangSlot(pub usize);
+
+/// Trigger on a thrower that tried to throw without a ready boomerang.
+#[derive(Event, Debug)]
+pub struct ThrowRejected;
+
+/// Throwers with this component ignore throw inputs until it runs out.
+#[derive(Component, Debug)]
+pub struct ThrowCooldown(Timer);
+
+impl ThrowCooldown {
+    fn new(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+/// Throwers with this component never run out: throwing leaves the slot ready, only the
+/// [`ThrowCooldown`] applies. Used on the practice range.
+#[derive(Component, Debug)]
+pub struct InfiniteAmmo;
+
+/// Gets triggered on the thrower whenever one of their slots changed.
+#[derive(Event, Debug)]
+pub struct BoomerangSlotChanged {
+    pub slot: usize,
+}
+
+fn consume_slot(
+    trigger: Trigger<ConsumeBoomerangSlot>,
+    mut inventories: Query<(&mut BoomerangInventory, Has<InfiniteAmmo>)>,
+    mut commands: Commands,
+) {
+    let slot = trigger.0;
+    let Ok((mut inventory, infinite)) = inventories.get_mut(trigger.target()) else {
         info!(
-            "Tried to give ammo to entity that doesn't track it! {:?}",
+            "Tried to take a boomerang from an entity that doesn't carry any! {:?}",
             trigger
         );
         return;
     };
+    if infinite {
+        commands
+            .entity(trigger.target())
+            .insert(ThrowCooldown::new(THROW_COOLDOWN_SECONDS));
+        return;
+    }
+    if !inventory.consume(slot) {
+        warn!("Tried to throw boomerang slot {slot}, but it isn't ready");
+        return;
+    }
+    commands
+        .entity(trigger.target())
+        .insert(ThrowCooldown::new(THROW_COOLDOWN_SECONDS))
+        .trigger(BoomerangSlotChanged { slot });
+}
 
-    let amount = trigger.0;
-    e.0 += amount;
+fn restore_slot(
+    trigger: Trigger<RestoreBoomerangSlot>,
+    mut inventories: Query<&mut BoomerangInventory>,
+    kinds: Res<BoomerangKindRegistry>,
+    mut commands: Commands,
+) {
+    let slot = trigger.0;
+    let Ok(mut inventory) = inventories.get_mut(trigger.target()) else {
+        info!(
+            "Tried to give a boomerang back to an entity that doesn't carry any! {:?}",
+            trigger
+        );
+        return;
+    };
+    if inventory.restore(slot, &kinds) {
+        commands
+            .entity(trigger.target())
+            .trigger(BoomerangSlotChanged { slot });
+    }
+}
+
+fn add_slot(
+    trigger: Trigger<AddBoomerangSlot>,
+    mut inventories: Query<&mut BoomerangInventory>,
+    kinds: Res<BoomerangKindRegistry>,
+    mut commands: Commands,
+) {
+    let Ok(mut inventory) = inventories.get_mut(trigger.target()) else {
+        return;
+    };
+    inventory.slots.push(BoomerangSlot::new(trigger.0));
+    let slot = inventory.slots.len() - 1;
+    info!(
+        "Adding a {} boomerang in slot {slot}",
+        kinds.get(trigger.0).name
+    );
+    commands
+        .entity(trigger.target())
+        .trigger(BoomerangSlotChanged { slot });
+}
+
+fn upgrade_slot(
+    trigger: Trigger<UpgradeBoomerangSlot>,
+    mut inventories: Query<&mut BoomerangInventory>,
+    mut commands: Commands,
+) {
+    let slot = trigger.0;
+    let Some(boomerang) = inventories
+        .get_mut(trigger.target())
+        .ok()
+        .and_then(|inventory| inventory.into_inner().slots.get_mut(slot))
+    else {
+        return;
+    };
+    boomerang.upgrades += 1;
+    commands
+        .entity(trigger.target())
+        .trigger(BoomerangSlotChanged { slot });
+}
+
+fn cool_down_slots(
+    time: GameTime,
+    mut inventories: Query<(Entity, &mut BoomerangInventory)>,
+    mut commands: Commands,
+) {
+    for (entity, mut inventory) in &mut inventories {
+        // only touch inventories with a timer to tick, so the rest don't show up as changed
+        let cooling_down = inventory
+            .slots
+            .iter()
+            .any(|boomerang| matches!(boomerang.state, SlotState::CoolingDown(_)));
+        if !cooling_down {
+            continue;
+        }
+        for (slot, boomerang) in inventory.slots.iter_mut().enumerate() {
+            let SlotState::CoolingDown(timer) = &mut boomerang.state else {
+                continue;
+            };
+            if timer.tick(time.delta()).finished() {
+                boomerang.state = SlotState::Ready;
+                commands
+                    .entity(entity)
+                    .trigger(BoomerangSlotChanged { slot });
+            }
+        }
+    }
+}
+
+fn cool_down_throws(
+    time: UiTime,
+    mut throwers: Query<(Entity, &mut ThrowCooldown)>,
+    mut commands: Commands,
+) {
+    for (entity, mut cooldown) in &mut throwers {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<ThrowCooldown>();
+        }
+    }
+}
+
+// ===== REJECTION FEEDBACK =====
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct AmmoAssets {
+    #[dependency]
+    empty_click: Handle<AudioSource>,
+}
+
+impl FromWorld for AmmoAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            empty_click: asset_server.load("audio/sound_effects/button_click.ogg"),
+        }
+    }
+}
+
+/// Red frame around the screen, shown for a moment after a rejected throw.
+#[derive(Component, Debug)]
+struct EmptyFlash(Timer);
+
+fn reject_throw(
+    trigger: Trigger<ThrowRejected>,
+    assets: Res<AmmoAssets>,
+    flashes: Query<Entity, With<EmptyFlash>>,
+    mut commands: Commands,
+) {
+    commands
+        .entity(trigger.target())
+        .insert(ThrowCooldown::new(REJECT_COOLDOWN_SECONDS));
+    commands.spawn((
+        Name::new("Empty Click"),
+        sound_effect_non_dilated(assets.empty_click.clone(), -6.0),
+    ));
+
+    for flash in &flashes {
+        commands.entity(flash).despawn();
+    }
+    commands.spawn((
+        Name::new("Empty Flash"),
+        EmptyFlash(Timer::from_seconds(EMPTY_FLASH_SECONDS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            border: UiRect::all(Val::Px(12.0)),
+            ..default()
+        },
+        BorderColor(EMPTY_FLASH_COLOR),
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    ));
+}
 
-    if amount < 0 {
-        info!("Deducting {:?} ammos", amount);
-    } else {
-        info!("Adding {:?} ammos", amount);
+fn fade_empty_flash(
+    // real time, the flash shouldn't linger while aiming in slow motion
+    time: UiTime,
+    mut flashes: Query<(Entity, &mut EmptyFlash, &mut BorderColor)>,
+    mut commands: Commands,
+) {
+    for (entity, mut flash, mut color) in &mut flashes {
+        if flash.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let alpha = EMPTY_FLASH_COLOR.alpha() * flash.0.fraction_remaining();
+        color.0 = EMPTY_FLASH_COLOR.with_alpha(alpha);
     }
 }
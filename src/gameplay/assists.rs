@@ -0,0 +1,136 @@
+//! Assists: toggles that take the edge off the game for players who want it, picked on their own
+//! screen reachable from the settings. Unlike [`crate::gameplay::mutators`], they're available from
+//! the start and don't touch the bounties, nobody should feel bad about switching them on.
+//!
+//! Every assist is a tuning multiplier the module it affects applies on top of its own tuning, see
+//! the constants below for how strong they are.
+//!
+//! The assists picked are part of the [`Settings`] and are copied into [`Assists`] whenever those
+//! change.
+
+use crate::gameplay::health_and_damage::Health;
+use crate::gameplay::player::Player;
+use crate::settings::{Settings, SettingsApplied};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Assists>().init_resource::<Assists>();
+    app.add_observer(apply_assist_settings)
+        .add_observer(toughen_player);
+}
+
+/// What outlaws' bullet speed is multiplied with while [`Assist::SlowerBullets`] is on.
+const BULLET_SPEED_MULTIPLIER: f32 = 0.6;
+/// What the speed of time while aiming is multiplied with while [`Assist::StrongerSlowMo`] is on.
+const SLOW_MO_MULTIPLIER: f32 = 0.5;
+/// What the player's health is multiplied with while [`Assist::ExtraHealth`] is on.
+const HEALTH_MULTIPLIER: i32 = 3;
+
+/// One of the assists that can be switched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Assist {
+    /// Outlaws' bullets fly slower, so there's more time to get out of their way.
+    SlowerBullets,
+    /// Time slows down even more while aiming.
+    StrongerSlowMo,
+    /// Returning boomerangs are always caught, no matter where the player is facing.
+    AutoCatch,
+    /// The player takes more hits before going down.
+    ExtraHealth,
+}
+
+impl Assist {
+    pub const ALL: [Assist; 4] = [
+        Assist::SlowerBullets,
+        Assist::StrongerSlowMo,
+        Assist::AutoCatch,
+        Assist::ExtraHealth,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Assist::SlowerBullets => "Slower Bullets",
+            Assist::StrongerSlowMo => "Stronger Slow-Mo",
+            Assist::AutoCatch => "Auto-Catch",
+            Assist::ExtraHealth => "Extra Health",
+        }
+    }
+}
+
+/// Which [`Assist`]s are switched on.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Assists {
+    pub slower_bullets: bool,
+    pub stronger_slow_mo: bool,
+    pub auto_catch: bool,
+    pub extra_health: bool,
+}
+
+impl Assists {
+    pub fn is_on(&self, assist: Assist) -> bool {
+        match assist {
+            Assist::SlowerBullets => self.slower_bullets,
+            Assist::StrongerSlowMo => self.stronger_slow_mo,
+            Assist::AutoCatch => self.auto_catch,
+            Assist::ExtraHealth => self.extra_health,
+        }
+    }
+
+    pub fn toggle(&mut self, assist: Assist) {
+        let on = match assist {
+            Assist::SlowerBullets => &mut self.slower_bullets,
+            Assist::StrongerSlowMo => &mut self.stronger_slow_mo,
+            Assist::AutoCatch => &mut self.auto_catch,
+            Assist::ExtraHealth => &mut self.extra_health,
+        };
+        *on = !*on;
+    }
+
+    /// What the speed of outlaws' bullets is multiplied with.
+    pub fn bullet_speed_multiplier(&self) -> f32 {
+        if self.slower_bullets {
+            BULLET_SPEED_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    /// What the speed of time while aiming is multiplied with.
+    pub fn slow_mo_multiplier(&self) -> f32 {
+        if self.stronger_slow_mo {
+            SLOW_MO_MULTIPLIER
+        } else {
+            1.0
+        }
+    }
+
+    /// What the player's health is multiplied with.
+    pub fn health_multiplier(&self) -> i32 {
+        if self.extra_health {
+            HEALTH_MULTIPLIER
+        } else {
+            1
+        }
+    }
+}
+
+fn apply_assist_settings(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut assists: ResMut<Assists>,
+) {
+    assists.set_if_neq(settings.assists);
+}
+
+fn toughen_player(
+    trigger: Trigger<OnAdd, Health>,
+    assists: Res<Assists>,
+    mut players: Query<&mut Health, With<Player>>,
+) {
+    if let Ok(mut health) = players.get_mut(trigger.target()) {
+        health.0 *= assists.health_multiplier();
+    }
+}
@@ -0,0 +1,111 @@
+//! Round shadows right under things flying through the air, like boomerangs, bullets and dynamite.
+//! From the top-down camera it's hard to tell where something at a fixed height above the ground
+//! actually is, the blob on the ground below it makes that obvious.
+//! The shadow is placed wherever a ray straight down hits the level, and gets smaller the higher up
+//! its caster is.
+
+use crate::gameplay::boomerang::Boomerang;
+use crate::gameplay::bullets::Bullet;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use avian3d::prelude::{SpatialQuery, SpatialQueryFilter};
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<BlobShadowAssets>();
+    app.register_required_components::<Boomerang, CastsBlobShadow>()
+        .register_required_components::<Bullet, CastsBlobShadow>();
+    app.add_observer(spawn_blob_shadow);
+    app.add_systems(Update, follow_casters.run_if(in_state(Screen::Gameplay)));
+}
+
+/// How far down to look for ground.
+const MAX_HEIGHT: f32 = 20.0;
+/// Put the shadow this far above the ground, so it doesn't z-fight.
+const GROUND_OFFSET: f32 = 0.02;
+const RADIUS: f32 = 0.4;
+/// Height above the ground at which the shadow shrinks to half its size.
+const HALF_SIZE_HEIGHT: f32 = 6.0;
+
+/// Gets a blob shadow on the ground below it for as long as it's around.
+#[derive(Component, Debug, Default)]
+pub struct CastsBlobShadow;
+
+#[derive(Component, Debug)]
+struct BlobShadow {
+    caster: Entity,
+}
+
+/// Shared by all blob shadows.
+#[derive(Resource)]
+struct BlobShadowAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for BlobShadowAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Circle::new(RADIUS));
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::srgba(0.0, 0.0, 0.0, 0.4),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            });
+        Self { mesh, material }
+    }
+}
+
+fn spawn_blob_shadow(
+    trigger: Trigger<OnAdd, CastsBlobShadow>,
+    assets: Res<BlobShadowAssets>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Name::new("Blob Shadow"),
+        BlobShadow {
+            caster: trigger.target(),
+        },
+        Mesh3d(assets.mesh.clone()),
+        MeshMaterial3d(assets.material.clone()),
+        Transform::default(),
+        // placed once we know where the ground is
+        Visibility::Hidden,
+        NotShadowCaster,
+        NotShadowReceiver,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn follow_casters(
+    mut shadows: Query<(Entity, &BlobShadow, &mut Transform, &mut Visibility)>,
+    casters: Query<&GlobalTransform, With<CastsBlobShadow>>,
+    spatial_query: SpatialQuery,
+    mut commands: Commands,
+) {
+    let ground = SpatialQueryFilter::from_mask([GameLayer::Default, GameLayer::Terrain]);
+    for (entity, shadow, mut transform, mut visibility) in &mut shadows {
+        let Ok(caster) = casters.get(shadow.caster) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let origin = caster.translation();
+        let Some(hit) = spatial_query.cast_ray(origin, Dir3::NEG_Y, MAX_HEIGHT, true, &ground)
+        else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let height = hit.distance;
+        let size = HALF_SIZE_HEIGHT / (HALF_SIZE_HEIGHT + height);
+        // lying flat on the ground
+        *transform = Transform::from_translation(origin - Vec3::Y * (height - GROUND_OFFSET))
+            .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2))
+            .with_scale(Vec3::splat(size));
+        *visibility = Visibility::Inherited;
+    }
+}
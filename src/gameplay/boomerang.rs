@@ -1,24 +1,70 @@
-use crate::audio::TimeDilatedPitch;
+use crate::HotSystems;
+use crate::audio::{DistanceAttenuation, TimeDilatedPitch};
+use crate::clock::GameTime;
 use crate::gameplay::Gameplay;
-use crate::gameplay::ammo::GiveAmmo;
-use crate::gameplay::enemy::Enemy;
-use crate::gameplay::health_and_damage::CanDamage;
-use crate::gameplay::input::FireBoomerangAction;
+use crate::gameplay::ammo::{
+    BoomerangInventory, BoomerangKindRegistry, ConsumeBoomerangSlot, RestoreBoomerangSlot,
+    ThrowCooldown, ThrowRejected, ThrownFromSlot,
+};
+use crate::gameplay::assists::Assists;
+use crate::gameplay::bullets::Bullet;
+use crate::gameplay::charge_throw::ChargingThrow;
+use crate::gameplay::enemy::{Enemy, intercept_time};
+use crate::gameplay::health_and_damage::{CanDamage, HealthEvent};
+use crate::gameplay::input::{FireBoomerangAction, SteerBoomerangAction};
 use crate::gameplay::mouse_position::MousePosition;
-use crate::gameplay::player::Player;
+use crate::gameplay::player::{Player, Staggered};
+use crate::gameplay::throwing_plane::ThrowingPlane;
 use crate::physics_layers::GameLayer;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use crate::theme::toast::Toast;
 use avian3d::prelude::{
-    AngularVelocity, Collider, CollisionEventsEnabled, CollisionLayers, LinearVelocity, Physics,
-    RigidBody,
+    AngularVelocity, Collider, ColliderAabb, CollisionEventsEnabled, CollisionLayers,
+    CollisionStarted, LinearVelocity, RigidBody,
 };
-use avian3d::spatial_query::{SpatialQuery, SpatialQueryFilter};
+use avian3d::spatial_query::{RayHitData, SpatialQuery, SpatialQueryFilter};
 use bevy::color;
 use bevy::ecs::entity::EntityHashSet;
 use bevy::prelude::*;
-use bevy_enhanced_input::prelude::Fired;
+use bevy_enhanced_input::prelude::{Completed, Fired, Started};
 use rand::{Rng, thread_rng};
 
-pub const BOOMERANG_FLYING_HEIGHT: f32 = 1.5;
+/// Boomerangs closer than this to their thrower don't clash with other boomerangs.
+const CLASH_GRACE_DISTANCE: f32 = 2.0;
+/// Boomerangs are at full volume up to this distance from the player.
+const SFX_NEAR_DISTANCE: f32 = 4.0;
+/// Boomerangs further than this from the player can't be heard.
+const SFX_FAR_DISTANCE: f32 = 40.0;
+/// How often a boomerang tries to fly around walls on its way home before giving up.
+const MAX_RETURN_DETOURS: u32 = 2;
+/// How far to either side of a wall boomerangs look for a way around it.
+const DETOUR_OFFSETS: [f32; 4] = [2.0, 4.0, 6.0, 8.0];
+/// How far out from an entity target boomerangs start coming down (or up) to its hit height.
+const FINAL_APPROACH_DISTANCE: f32 = 3.0;
+/// How fast boomerangs get back to their flying height after hitting something low, in world
+/// units per second.
+const CLIMB_SPEED: f32 = 4.0;
+/// How far from its middle a boomerang touches things: its collider's radius, scaled up with the
+/// mesh.
+const BOOMERANG_REACH: f32 = 0.75;
+/// How far boomerangs ricochet off a wall that was thrown at when nothing's in the way.
+const RICOCHET_RANGE: f32 = 10.0;
+/// How far off a wall the reflected leg of a ricochet starts.
+const RICOCHET_SURFACE_OFFSET: f32 = 0.05;
+/// How long a boomerang that didn't make it home stays there before it's back in its slot.
+pub const STRANDED_SECONDS: f32 = 8.0;
+/// How far into a wall stuck boomerangs go, from their middle.
+const STUCK_DEPTH: f32 = 0.3;
+/// How far the blade of a stuck boomerang points down into the wall, in radians.
+const STUCK_TILT: f32 = 0.35;
+/// How long a boomerang keeps wobbling after getting stuck.
+const WOBBLE_SECONDS: f32 = 0.8;
+/// How far stuck boomerangs wobble to either side at first, in radians.
+const WOBBLE_ANGLE: f32 = 0.3;
+/// How often stuck boomerangs wobble back and forth per second.
+const WOBBLE_FREQUENCY: f32 = 9.0;
+/// How close the player has to get to a stuck boomerang to pull it out of the wall.
+pub const RETRIEVE_DISTANCE: f32 = 1.5;
 
 /// Component used to describe boomerang entities.
 #[derive(Component, Debug, Default, Reflect)]
@@ -28,13 +74,179 @@ pub struct Boomerang {
     path: Vec<BoomerangTargetKind>,
     pub path_index: usize,
     progress_on_current_segment: f32, // value from 0.0 to 1.0
+    /// The plane this boomerang flies on, the [ThrowingPlane] it was thrown on.
+    plane: ThrowingPlane,
+    /// Where the current segment started, in case the entity it started at is gone.
+    segment_start: Vec3,
+    /// Where the current target entity was last seen, in case it gets despawned mid-flight.
+    last_known_target: Option<Vec3>,
+    /// How far this boomerang may fly to its painted targets. Starts out at
+    /// [BoomerangSettings::energy] and goes down whenever it clashes with another boomerang or
+    /// gets steered.
+    energy: f32,
+    /// How often this boomerang had to fly around a wall to get home.
+    return_detours: u32,
+    /// Multiplier on [BoomerangSettings::tween_movement_speed], higher for charged throws.
+    speed: f32,
 }
 impl Boomerang {
-    fn new(path: Vec<BoomerangTargetKind>) -> Self {
+    fn new(
+        path: Vec<BoomerangTargetKind>,
+        plane: ThrowingPlane,
+        start: Vec3,
+        energy: f32,
+        speed: f32,
+    ) -> Self {
         Self {
             path,
             path_index: 0,
             progress_on_current_segment: 0.0,
+            plane,
+            segment_start: start,
+            last_known_target: None,
+            energy,
+            return_detours: 0,
+            speed,
+        }
+    }
+
+    /// How fast this boomerang is flying right now, in world units per second.
+    pub fn speed(&self, settings: &BoomerangSettings) -> f32 {
+        settings.tween_movement_speed(self.progress_on_current_segment) * self.speed
+    }
+
+    /// Ricochets off something in the middle of a segment, e.g. another boomerang.
+    /// The boomerang takes a detour along `direction` before carrying on along its path, which
+    /// costs [BoomerangSettings::clash_energy_cost]. Painted targets it can't afford anymore are
+    /// dropped from the end of the path, but it always makes it back to the thrower.
+    fn deflect(
+        &mut self,
+        history: &mut BoomerangFlightHistory,
+        position: Vec3,
+        direction: Vec3,
+        settings: &BoomerangSettings,
+        transforms: &Query<&Transform, Without<Boomerang>>,
+    ) {
+        self.energy -= settings.clash_energy_cost;
+
+        let detour = self
+            .plane
+            .project(position + direction * settings.clash_deflect_distance);
+        let index = self.path_index + 1;
+        self.path
+            .insert(index, BoomerangTargetKind::Position(detour));
+        self.path
+            .insert(index, BoomerangTargetKind::Position(position));
+        self.path_index = index;
+        self.segment_start = position;
+        self.last_known_target = None;
+        self.progress_on_current_segment = 0.0;
+        history.record_bounce(position);
+
+        let flown = history
+            .waypoints
+            .windows(2)
+            .map(|leg| leg[0].distance(leg[1]))
+            .sum::<f32>();
+        // keep the clash point, the detour and the way home, drop painted targets from the back
+        while flown + self.remaining_painted_length(transforms) > self.energy
+            && self.path.len() > self.path_index + 3
+        {
+            self.path.remove(self.path.len() - 2);
+        }
+    }
+
+    /// Bends the current segment towards `cursor` by at most `max_angle` radians, keeping its
+    /// length. Only segments that end at a position can be bent, the way to a painted outlaw or
+    /// back home stays as it is. Returns where the segment ends now, if it was bent.
+    fn steer_towards(&mut self, position: Vec3, cursor: Vec3, max_angle: f32) -> Option<Vec3> {
+        let index = self.path_index + 1;
+        let Some(BoomerangTargetKind::Position(target)) = self.path.get(index).copied() else {
+            return None;
+        };
+        let to_target = (target - position).xz();
+        let to_cursor = (cursor - position).xz();
+        if to_target == Vec2::ZERO || to_cursor == Vec2::ZERO {
+            return None;
+        }
+        let angle = to_target.angle_to(to_cursor).clamp(-max_angle, max_angle);
+        if angle.abs() < f32::EPSILON {
+            return None;
+        }
+        let bent = Vec2::from_angle(angle).rotate(to_target);
+        let steered = Vec3::new(position.x + bent.x, target.y, position.z + bent.y);
+        self.path[index] = BoomerangTargetKind::Position(steered);
+        Some(steered)
+    }
+
+    /// Turns towards `waypoint` right where the boomerang is, before carrying on along its path.
+    fn turn_towards(
+        &mut self,
+        history: &mut BoomerangFlightHistory,
+        position: Vec3,
+        waypoint: Vec3,
+    ) {
+        let index = self.path_index + 1;
+        self.path
+            .insert(index, BoomerangTargetKind::Position(waypoint));
+        self.path
+            .insert(index, BoomerangTargetKind::Position(position));
+        self.path_index = index;
+        self.segment_start = position;
+        self.last_known_target = None;
+        self.progress_on_current_segment = 0.0;
+        history.record_bounce(position);
+    }
+
+    /// Whether the boomerang is still close to whoever threw it, i.e. just thrown or about to be
+    /// caught.
+    fn is_near_thrower(
+        &self,
+        position: Vec3,
+        transforms: &Query<&Transform, Without<Boomerang>>,
+    ) -> bool {
+        let Some(BoomerangTargetKind::Entity(thrower)) = self.path.first() else {
+            return false;
+        };
+        transforms.get(*thrower).is_ok_and(|thrower| {
+            self.plane.project(thrower.translation).distance(position) < CLASH_GRACE_DISTANCE
+        })
+    }
+
+    /// Length of the rest of the path from the current origin, not counting the way home.
+    fn remaining_painted_length(&self, transforms: &Query<&Transform, Without<Boomerang>>) -> f32 {
+        let Some((_, painted)) = self.path[self.path_index..].split_last() else {
+            return 0.0;
+        };
+        painted
+            .iter()
+            .filter_map(|node| match node {
+                BoomerangTargetKind::Position(position) => Some(*position),
+                BoomerangTargetKind::Entity(entity) => transforms
+                    .get(*entity)
+                    .ok()
+                    .map(|transform| self.plane.project(transform.translation)),
+            })
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|leg| leg[0].distance(leg[1]))
+            .sum()
+    }
+
+    /// Where the boomerang started its current segment, i.e. the thing it last bounced off of.
+    pub fn current_origin(&self) -> Option<BoomerangTargetKind> {
+        self.path.get(self.path_index).copied()
+    }
+
+    /// The last thing painted for this throw, if it's an entity rather than a spot on a wall.
+    pub fn last_painted_entity(&self) -> Option<Entity> {
+        // the thrower is both the first and the last node on the path
+        match self.path.len().checked_sub(2) {
+            Some(index) if index > 0 => match self.path[index] {
+                BoomerangTargetKind::Entity(entity) => Some(entity),
+                BoomerangTargetKind::Position(_) => None,
+            },
+            _ => None,
         }
     }
 
@@ -43,6 +255,65 @@ impl Boomerang {
     }
 }
 
+/// Records what happened to a boomerang during its flight, for scoring, trails and debugging.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct BoomerangFlightHistory {
+    /// Where the boomerang was thrown from, followed by every point it bounced at.
+    pub waypoints: Vec<Vec3>,
+    /// One per bounce, points away from whatever was bounced off. Walls get their actual surface
+    /// normal, everything else gets the direction halfway between the incoming and outgoing path
+    /// once the boomerang leaves the bounce point again.
+    pub bounce_normals: Vec<Vec3>,
+    /// Every entity the boomerang collided with, in order. Includes hits blocked by shields.
+    pub hits: Vec<Entity>,
+    /// How long each finished segment took, in physics time.
+    pub segment_durations: Vec<f32>,
+    current_segment_time: f32,
+}
+
+impl BoomerangFlightHistory {
+    fn new(start: Vec3) -> Self {
+        Self {
+            waypoints: vec![start],
+            ..default()
+        }
+    }
+
+    /// How often the boomerang has bounced so far.
+    pub fn bounces(&self) -> usize {
+        self.waypoints.len().saturating_sub(1)
+    }
+
+    pub fn record_hit(&mut self, entity: Entity) {
+        self.hits.push(entity);
+    }
+
+    fn record_bounce(&mut self, position: Vec3) {
+        self.waypoints.push(position);
+        self.segment_durations.push(self.current_segment_time);
+        self.current_segment_time = 0.0;
+    }
+
+    /// Sets the normal of the last bounce to that of the surface it bounced off.
+    fn record_surface_normal(&mut self, normal: Dir3) {
+        if self.bounce_normals.len() + 1 == self.bounces() {
+            self.bounce_normals.push(*normal);
+        }
+    }
+
+    /// Completes the normal of the last bounce once we know which way the boomerang left it.
+    fn record_departure(&mut self, direction: Dir3) {
+        let bounces = self.bounces();
+        if bounces == 0 || self.bounce_normals.len() >= bounces {
+            return;
+        }
+        let incoming = (self.waypoints[bounces] - self.waypoints[bounces - 1]).normalize_or_zero();
+        self.bounce_normals
+            .push((*direction - incoming).normalize_or_zero());
+    }
+}
+
 /// Component used to mark boomerangs which are midair.
 #[derive(Component)]
 struct Flying;
@@ -51,6 +322,21 @@ struct Flying;
 #[derive(Component)]
 struct Falling;
 
+/// Component used to mark boomerangs which couldn't make it back to their thrower because of a
+/// wall. They stay where they ended up for a while before they count as returned.
+#[derive(Component)]
+struct Stranded(Timer);
+
+/// Put on stranded boomerangs that fly into the wall in their way, rather than dropping in front of
+/// it. Once they hit it, they stick in it, wobbling, until the player comes and pulls them out or
+/// [STRANDED_SECONDS] are up.
+#[derive(Component, Debug)]
+struct StuckInWall {
+    /// The wall's normal, flattened onto the throwing plane.
+    normal: Dir3,
+    wobble: Timer,
+}
+
 /// Component used to mark anything that can be hit by the boomerang.
 /// By default, the Boomerang will just bounce off of the marked surface (like a wall), add other components like [PotentialBoomerangOrigin] to add more functionality.
 #[derive(Component, Default)]
@@ -72,6 +358,11 @@ pub struct CurrentBoomerangThrowOrigin;
 pub struct ThrowBoomerangEvent {
     pub thrower_entity: Entity,
     pub target: Vec<BoomerangTargetKind>,
+    /// Which of the thrower's [BoomerangInventory] slots the boomerang came out of, if any.
+    pub slot: Option<usize>,
+    /// How much the throw was charged up, from 0.0 (not at all) to 1.0 (fully), see
+    /// [ChargingThrow].
+    pub charge: f32,
 }
 
 // An event which gets fired whenever a boomerang reaches the end of its current path.
@@ -83,6 +374,27 @@ pub struct BounceBoomerangEvent {
     pub _bounce_on: BoomerangTargetKind,
 }
 
+/// An event which gets fired whenever an entity a boomerang was flying towards has disappeared.
+/// The boomerang flies on to where the entity was last seen, or skips it if it never saw it.
+#[derive(Event, Debug)]
+pub struct BoomerangTargetLostEvent {
+    pub boomerang_entity: Entity,
+    pub lost_entity: Entity,
+}
+
+/// Triggered on a painted entity when a boomerang gets to where it was flying to meet it, whether
+/// or not they actually touch.
+#[derive(Event, Debug)]
+pub struct BoomerangArrivedEvent {
+    pub boomerang: Entity,
+    /// Where the boomerang got to.
+    pub position: Vec3,
+}
+
+/// Triggered on a thrower whose boomerang came back at them from behind and clipped them.
+#[derive(Event)]
+pub struct FumbledCatchEvent;
+
 // An event which gets fired whenever a boomerang falls to the ground, thus ceasing all movement.
 #[derive(Event)]
 struct BoomerangHasFallenOnGroundEvent {
@@ -107,6 +419,11 @@ pub struct WeaponTarget {
     pub target_entity: Option<Entity>,
 }
 
+/// Next to the [WeaponTarget] on the preview entity: where a boomerang thrown at the wall under the
+/// preview ricochets off to. Its entity is only set for things the boomerang can fly on from.
+#[derive(Component, Debug, Default)]
+pub struct RicochetPreview(pub Option<RaycastTarget>);
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 struct BoomerangAssets {
@@ -114,12 +431,7 @@ struct BoomerangAssets {
     toss_sfx: Vec<Handle<AudioSource>>,
     loop_sfx: Handle<AudioSource>,
     bounce_sfx: Handle<AudioSource>,
-}
-
-impl BoomerangAssets {
-    fn get_bounce_pitch(&self, bounces: usize) -> f32 {
-        1.0.lerp(4.0, bounces as f32 / 10.0)
-    }
+    clang_sfx: Handle<AudioSource>,
 }
 
 impl FromWorld for BoomerangAssets {
@@ -136,6 +448,7 @@ impl FromWorld for BoomerangAssets {
             loop_sfx: asset_server
                 .load("audio/sound_effects/boomerang_sfx/boomerang_loop_single_short.ogg"),
             bounce_sfx: asset_server.load("audio/sound_effects/boomerang_sfx/ding.ogg"),
+            clang_sfx: asset_server.load("audio/sound_effects/213925__diboz__pistol_riccochet.ogg"),
         }
     }
 }
@@ -144,10 +457,12 @@ pub fn plugin(app: &mut App) {
     app.init_resource::<BoomerangSettings>();
     app.register_type::<BoomerangSettings>();
     app.register_type::<WeaponTarget>();
+    app.register_type::<BoomerangFlightHistory>();
 
     app.init_gizmo_group::<BoomerangPreviewGizmos>();
     app.add_event::<ThrowBoomerangEvent>();
     app.add_event::<BounceBoomerangEvent>();
+    app.add_event::<BoomerangTargetLostEvent>();
     app.add_event::<BoomerangHasFallenOnGroundEvent>();
     app.init_resource::<BoomerangAssets>();
 
@@ -166,96 +481,241 @@ pub fn plugin(app: &mut App) {
             (
                 move_flying_boomerangs,
                 on_boomerang_bounce_advance_to_next_pathing_step_or_fall_down,
+                keep_way_home_clear,
             )
-                .chain(),
-            move_falling_boomerangs,
+                .chain()
+                .in_set(HotSystems::BoomerangMovement),
+            move_falling_boomerangs.in_set(HotSystems::BoomerangMovement),
             on_boomerang_fallen_despawn_boomerang.after(move_falling_boomerangs),
+            (
+                recover_stranded_boomerangs,
+                wobble_stuck_boomerangs,
+                retrieve_stuck_boomerangs,
+            ),
+            on_boomerang_clash_deflect.after(HotSystems::BoomerangMovement),
         )
             .run_if(in_state(Gameplay::Normal)),
     );
 
     app.add_observer(on_fire_action_throw_boomerang)
-        .add_observer(handle_boomerang_sfx);
+        .add_observer(start_steering)
+        .add_observer(stop_steering)
+        .add_observer(handle_boomerang_sfx)
+        .add_observer(on_fumbled_catch);
 }
 
 /// Moves boomerangs along their paths.
 /// Fires a [BounceBoomerangEvent] in case that the next path destination was reached.
+/// Targets which don't exist anymore are dealt with in [resolve_next_target].
 fn move_flying_boomerangs(
-    mut flying_boomerangs: Query<(Entity, &mut Boomerang, &mut Transform), With<Flying>>,
+    mut flying_boomerangs: Query<
+        (
+            Entity,
+            &mut Boomerang,
+            &mut BoomerangFlightHistory,
+            &mut Transform,
+        ),
+        With<Flying>,
+    >,
     all_other_transforms: Query<&Transform, Without<Boomerang>>,
+    aabbs: Query<&ColliderAabb>,
+    bullets: Query<&LinearVelocity, With<Bullet>>,
+    steering: Query<(), With<SteeringBoomerangs>>,
+    mouse_position: Res<MousePosition>,
     boomerang_settings: Res<BoomerangSettings>,
-    time: Res<Time<Physics>>,
+    time: GameTime,
     mut bounce_event_writer: EventWriter<BounceBoomerangEvent>,
-) -> Result {
-    for (boomerang_entity, mut boomerang, mut transform) in flying_boomerangs.iter_mut() {
-        let target = &boomerang
-            .path
-            .get(boomerang.path_index + 1)
-            .ok_or(format!("No path for boomerang {boomerang:?}"))?
-            .clone();
-
-        let target_position = match target {
-            BoomerangTargetKind::Entity(entity) => all_other_transforms
-                .get(*entity)?
-                .translation
-                .with_y(BOOMERANG_FLYING_HEIGHT),
-            BoomerangTargetKind::Position(position) => position.with_y(BOOMERANG_FLYING_HEIGHT),
+    mut lost_event_writer: EventWriter<BoomerangTargetLostEvent>,
+) {
+    for (boomerang_entity, mut boomerang, mut history, mut transform) in
+        flying_boomerangs.iter_mut()
+    {
+        let plane = boomerang.plane;
+        history.current_segment_time += time.delta_secs();
+        let Some((target, mut target_position)) = resolve_next_target(
+            boomerang_entity,
+            &mut boomerang,
+            &all_other_transforms,
+            &mut lost_event_writer,
+        ) else {
+            // Nothing left to fly to, drop it right here
+            let here = transform.translation;
+            history.record_bounce(here);
+            send_boomerang_bounce_event(
+                &mut bounce_event_writer,
+                boomerang_entity,
+                &mut transform,
+                BoomerangTargetKind::Position(here),
+                here,
+            );
+            continue;
         };
 
-        let Ok((direction, remaining_distance)) = Dir3::new_and_length(
-            target_position - transform.translation.with_y(BOOMERANG_FLYING_HEIGHT),
-        ) else {
+        // bullets are too fast to chase, fly to where they're going to be instead
+        if let BoomerangTargetKind::Entity(entity) = target {
+            if let Ok(velocity) = bullets.get(entity) {
+                let velocity = velocity.0.with_y(0.0);
+                let speed = boomerang.speed(&boomerang_settings);
+                if let Some(time) =
+                    intercept_time(transform.translation, target_position, velocity, speed)
+                {
+                    target_position = plane.project(target_position + velocity * time);
+                }
+            }
+        }
+
+        let thrower_steering = matches!(
+            boomerang.path.first(),
+            Some(BoomerangTargetKind::Entity(thrower)) if steering.contains(*thrower)
+        );
+        if let Some(cursor) = mouse_position
+            .boomerang_throwing_plane
+            .filter(|_| thrower_steering && boomerang.energy > 0.0)
+        {
+            let max_angle = boomerang_settings.steer_turn_rate * time.delta_secs();
+            if let Some(steered) = boomerang.steer_towards(transform.translation, cursor, max_angle)
+            {
+                target_position = plane.project(steered);
+                boomerang.energy -= boomerang_settings.steer_energy_cost * time.delta_secs();
+            }
+        }
+
+        let Ok((direction, remaining_distance)) =
+            Dir3::new_and_length(target_position - plane.project(transform.translation))
+        else {
+            boomerang.segment_start = target_position;
+            history.record_bounce(target_position);
             send_boomerang_bounce_event(
                 &mut bounce_event_writer,
                 boomerang_entity,
                 &mut transform,
-                *target,
+                target,
                 target_position,
             );
             continue;
         };
 
+        history.record_departure(direction);
+
         // todo make this a util fn
-        let origin_position = match boomerang
-            .path
-            .get(boomerang.path_index)
-            .ok_or(format!("No Origin for boomerang: {boomerang:?}"))?
-        {
-            BoomerangTargetKind::Entity(entity) => all_other_transforms
-                .get(*entity)?
-                .translation
-                .with_y(BOOMERANG_FLYING_HEIGHT),
-            BoomerangTargetKind::Position(position) => position.with_y(BOOMERANG_FLYING_HEIGHT),
+        let origin_position = match boomerang.current_origin() {
+            Some(BoomerangTargetKind::Entity(entity)) => all_other_transforms
+                .get(entity)
+                .map_or(boomerang.segment_start, |origin| {
+                    plane.project(origin.translation)
+                }),
+            Some(BoomerangTargetKind::Position(position)) => plane.project(position),
+            None => boomerang.segment_start,
         };
 
         let total_path_length = (target_position - origin_position).length();
         let progress = 1. - (remaining_distance / total_path_length);
         boomerang.progress_on_current_segment = progress; // convenience hack; cache this value so we don't have to recalculate in other systems.
-        let velocity = boomerang_settings.tween_movement_speed(progress);
+        let velocity = boomerang_settings.tween_movement_speed(progress) * boomerang.speed;
+
+        // low targets get hit from their own height, everything else from the flying height
+        let flying_height = plane.project(transform.translation).y;
+        let target_height = match target {
+            BoomerangTargetKind::Entity(entity) => {
+                hit_height(flying_height, aabbs.get(entity).ok())
+            }
+            BoomerangTargetKind::Position(_) => flying_height,
+        };
 
         let distance_travelled_this_frame = velocity * time.delta_secs();
         if remaining_distance <= distance_travelled_this_frame {
+            boomerang.segment_start = target_position;
+            history.record_bounce(target_position);
             send_boomerang_bounce_event(
                 &mut bounce_event_writer,
                 boomerang_entity,
                 &mut transform,
-                *target,
-                target_position,
+                target,
+                target_position.with_y(target_height),
             );
             continue;
         }
 
+        let remaining_distance = remaining_distance - distance_travelled_this_frame;
+        let approach = (remaining_distance / FINAL_APPROACH_DISTANCE).min(1.0);
+        let height = target_height.lerp(flying_height, approach);
+        let current_height = transform.translation.y;
         transform.translation += direction * distance_travelled_this_frame;
+        // dives right onto the target, but takes a moment to climb back up after it
+        transform.translation.y =
+            if (height - flying_height).abs() < (current_height - flying_height).abs() {
+                let climb = CLIMB_SPEED * time.delta_secs();
+                current_height + (height - current_height).clamp(-climb, climb)
+            } else {
+                height
+            };
     }
+}
 
-    Ok(())
+/// The height a boomerang has to be at to hit a target with the given bounds. That's its flying
+/// height, unless it would fly right over (or under) the target. Then it's the target's middle.
+fn hit_height(flying_height: f32, bounds: Option<&ColliderAabb>) -> f32 {
+    let Some(bounds) = bounds else {
+        return flying_height;
+    };
+    if (bounds.min.y - BOOMERANG_REACH..=bounds.max.y + BOOMERANG_REACH).contains(&flying_height) {
+        flying_height
+    } else {
+        (bounds.min.y + bounds.max.y) / 2.0
+    }
+}
+
+/// Finds the next node on the boomerang's path and where it is.
+/// If that node is an entity which has been despawned, it's replaced by the position it was last
+/// seen at. If the boomerang never saw it, the node is skipped instead.
+/// Returns [None] once there is nothing left to fly to.
+fn resolve_next_target(
+    boomerang_entity: Entity,
+    boomerang: &mut Boomerang,
+    transforms: &Query<&Transform, Without<Boomerang>>,
+    lost_event_writer: &mut EventWriter<BoomerangTargetLostEvent>,
+) -> Option<(BoomerangTargetKind, Vec3)> {
+    let plane = boomerang.plane;
+    loop {
+        let index = boomerang.path_index + 1;
+        let entity = match *boomerang.path.get(index)? {
+            BoomerangTargetKind::Position(position) => {
+                return Some((
+                    BoomerangTargetKind::Position(position),
+                    plane.project(position),
+                ));
+            }
+            BoomerangTargetKind::Entity(entity) => entity,
+        };
+
+        if let Ok(transform) = transforms.get(entity) {
+            let position = plane.project(transform.translation);
+            boomerang.last_known_target = Some(position);
+            return Some((BoomerangTargetKind::Entity(entity), position));
+        }
+
+        lost_event_writer.write(BoomerangTargetLostEvent {
+            boomerang_entity,
+            lost_entity: entity,
+        });
+        match boomerang.last_known_target {
+            Some(position) => {
+                let target = BoomerangTargetKind::Position(position);
+                boomerang.path[index] = target;
+                return Some((target, position));
+            }
+            None => {
+                boomerang.path.remove(index);
+            }
+        }
+    }
 }
 
 /// Lets boomerangs fall to the ground.
 /// Fires a [BoomerangHasFallenOnGroundEvent] in case that the next path destination was reached.
 fn move_falling_boomerangs(
     mut falling_boomerangs: Query<(Entity, &mut Transform), (With<Boomerang>, With<Falling>)>,
-    time: Res<Time>,
+    time: GameTime,
     mut fallen_event_writer: EventWriter<BoomerangHasFallenOnGroundEvent>,
     boomerang_stats: Res<BoomerangSettings>,
 ) -> Result {
@@ -276,20 +736,255 @@ fn move_falling_boomerangs(
 
 fn on_boomerang_fallen_despawn_boomerang(
     mut fallen_events: EventReader<BoomerangHasFallenOnGroundEvent>,
+    boomerangs: Query<(&Boomerang, Option<&ThrownFromSlot>, Has<Stranded>)>,
     player: Single<Entity, With<Player>>,
     mut commands: Commands,
 ) -> Result {
     let player_entity = player.into_inner();
     for event in fallen_events.read() {
-        commands.entity(event.boomerang_entity).despawn();
-
-        // TODO this assumes booms only fall next to player (they always return, no picking up)
-        commands.entity(player_entity).trigger(GiveAmmo(1));
+        let Ok((boomerang, slot, stranded)) = boomerangs.get(event.boomerang_entity) else {
+            continue;
+        };
+        if stranded {
+            // stays on the ground for a bit, see recover_stranded_boomerangs
+            commands.entity(event.boomerang_entity).remove::<Falling>();
+            continue;
+        }
+        return_to_thrower(
+            event.boomerang_entity,
+            boomerang,
+            slot,
+            player_entity,
+            &mut commands,
+        );
     }
 
     Ok(())
 }
 
+/// Gives the boomerang back to whoever threw it.
+fn return_to_thrower(
+    entity: Entity,
+    boomerang: &Boomerang,
+    slot: Option<&ThrownFromSlot>,
+    player: Entity,
+    commands: &mut Commands,
+) {
+    let thrower = match boomerang.path.first() {
+        Some(BoomerangTargetKind::Entity(thrower)) => *thrower,
+        _ => player,
+    };
+    commands.entity(entity).despawn();
+
+    // TODO this assumes booms only fall next to their thrower (they always return, no picking up)
+    if let (Ok(mut thrower), Some(ThrownFromSlot(slot))) = (commands.get_entity(thrower), slot) {
+        thrower.trigger(RestoreBoomerangSlot(*slot));
+    }
+}
+
+impl StuckInWall {
+    /// Blade first into the wall, tilted down a little, as if it came in at a slight angle.
+    fn rest_rotation(&self) -> Quat {
+        Transform::default()
+            .looking_to(-self.normal, Vec3::Y)
+            .rotation
+            * Quat::from_rotation_x(-STUCK_TILT)
+    }
+}
+
+/// Stuck boomerangs wobble from side to side for a moment, less and less until they're still.
+fn wobble_stuck_boomerangs(
+    time: GameTime,
+    mut boomerangs: Query<(&mut StuckInWall, &mut Transform), Without<Flying>>,
+) {
+    for (mut stuck, mut transform) in &mut boomerangs {
+        if stuck.wobble.finished() {
+            continue;
+        }
+        stuck.wobble.tick(time.delta());
+        let elapsed = stuck.wobble.elapsed_secs();
+        let angle = WOBBLE_ANGLE
+            * stuck.wobble.fraction_remaining()
+            * (elapsed * WOBBLE_FREQUENCY * std::f32::consts::TAU).sin();
+        // around the axis along the wall's surface, sideways
+        let axis = stuck.normal.cross(Vec3::Y).normalize_or(Vec3::X);
+        transform.rotation = Quat::from_axis_angle(axis, angle) * stuck.rest_rotation();
+    }
+}
+
+/// Players pull stuck boomerangs out of the wall by walking up to them, rather than waiting for
+/// [STRANDED_SECONDS].
+fn retrieve_stuck_boomerangs(
+    boomerangs: Query<
+        (Entity, &Boomerang, Option<&ThrownFromSlot>, &Transform),
+        (With<StuckInWall>, Without<Flying>),
+    >,
+    players: Query<(Entity, &Transform), (With<Player>, Without<Boomerang>)>,
+    mut commands: Commands,
+) {
+    for (entity, boomerang, slot, transform) in &boomerangs {
+        let position = transform.translation.xz();
+        let retriever = players
+            .iter()
+            .find(|(_, player)| player.translation.xz().distance(position) <= RETRIEVE_DISTANCE);
+        if let Some((player, _)) = retriever {
+            return_to_thrower(entity, boomerang, slot, player, &mut commands);
+        }
+    }
+}
+
+fn recover_stranded_boomerangs(
+    time: GameTime,
+    mut boomerangs: Query<
+        (Entity, &Boomerang, Option<&ThrownFromSlot>, &mut Stranded),
+        Without<Falling>,
+    >,
+    player: Single<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    for (entity, boomerang, slot, mut stranded) in &mut boomerangs {
+        if stranded.0.tick(time.delta()).finished() {
+            return_to_thrower(entity, boomerang, slot, *player, &mut commands);
+        }
+    }
+}
+
+/// Boomerangs don't fly through walls on their way home. With a wall in the way they try to fly
+/// around it, and if there's no simple way around, they get stuck in it, see [StuckInWall]. Walls
+/// they can't stick in, like ones sloped so much they're almost a floor, drop them to the ground
+/// in front of them instead.
+fn keep_way_home_clear(
+    mut boomerangs: Query<
+        (
+            Entity,
+            &mut Boomerang,
+            &mut BoomerangFlightHistory,
+            &Transform,
+        ),
+        (With<Flying>, Without<Stranded>),
+    >,
+    transforms: Query<&Transform, Without<Boomerang>>,
+    spatial_query: SpatialQuery,
+    boomerang_settings: Res<BoomerangSettings>,
+    boomerang_assets: Res<BoomerangAssets>,
+    mut commands: Commands,
+) {
+    let walls = SpatialQueryFilter::from_mask([GameLayer::Default, GameLayer::Terrain]);
+    for (entity, mut boomerang, mut history, transform) in &mut boomerangs {
+        // only the last leg goes home
+        if boomerang.path_index + 2 != boomerang.path.len() {
+            continue;
+        }
+        let Some(BoomerangTargetKind::Entity(thrower)) = boomerang.path.last().copied() else {
+            continue;
+        };
+        let Ok(thrower_transform) = transforms.get(thrower) else {
+            continue;
+        };
+        let from = transform.translation;
+        let home = boomerang.plane.project(thrower_transform.translation);
+        let Some(wall) = wall_hit(&spatial_query, &walls, from, home) else {
+            continue;
+        };
+        let wall_distance = wall.distance;
+
+        if boomerang.return_detours < MAX_RETURN_DETOURS {
+            if let Some(detour) = find_detour(&spatial_query, &walls, from, home, wall_distance) {
+                boomerang.return_detours += 1;
+                boomerang.turn_towards(&mut history, from, detour);
+                continue;
+            }
+        }
+
+        if boomerang_settings.never_fall {
+            // no way around, so straight through it
+            continue;
+        }
+
+        // no way around, stick it into the wall, or drop it right in front of it
+        let direction = (home - from).normalize_or_zero();
+        let stuck = boomerang
+            .plane
+            .flatten(wall.normal)
+            .map(|normal| StuckInWall {
+                normal,
+                wobble: Timer::from_seconds(WOBBLE_SECONDS, TimerMode::Once),
+            });
+        let stop_distance = match stuck {
+            Some(_) => wall_distance + STUCK_DEPTH,
+            None => (wall_distance - 0.5).max(0.0),
+        };
+        let stop = from + direction * stop_distance;
+        let home_index = boomerang.path.len() - 1;
+        boomerang.path.truncate(home_index);
+        boomerang.turn_towards(&mut history, from, stop);
+        commands.entity(entity).insert(Stranded(Timer::from_seconds(
+            STRANDED_SECONDS,
+            TimerMode::Once,
+        )));
+        match stuck {
+            // thunks once it's in the wall
+            Some(stuck) => {
+                commands.entity(entity).insert(stuck);
+                commands.trigger(Toast::new("Yer 'rang is stuck in a wall, go fetch it"));
+            }
+            None => {
+                commands.trigger(Toast::new("Yer 'rang didn't make it back"));
+                commands.spawn((
+                    Name::new("Boomerang Clatter SFX"),
+                    AudioPlayer::new(boomerang_assets.clang_sfx.clone()),
+                    PlaybackSettings::DESPAWN,
+                    TimeDilatedPitch(0.7),
+                ));
+            }
+        }
+    }
+}
+
+/// How far from `from` the first wall between `from` and `to` is, if there is one.
+fn distance_to_wall(
+    spatial_query: &SpatialQuery,
+    walls: &SpatialQueryFilter,
+    from: Vec3,
+    to: Vec3,
+) -> Option<f32> {
+    wall_hit(spatial_query, walls, from, to).map(|hit| hit.distance)
+}
+
+/// The first wall between `from` and `to`, if there is one.
+fn wall_hit(
+    spatial_query: &SpatialQuery,
+    walls: &SpatialQueryFilter,
+    from: Vec3,
+    to: Vec3,
+) -> Option<RayHitData> {
+    let (direction, distance) = Dir3::new_and_length(to - from).ok()?;
+    spatial_query.cast_ray(from, direction, distance, true, walls)
+}
+
+/// A point to the side of the wall from which both the boomerang and its thrower can be reached
+/// in a straight line.
+fn find_detour(
+    spatial_query: &SpatialQuery,
+    walls: &SpatialQueryFilter,
+    from: Vec3,
+    home: Vec3,
+    wall_distance: f32,
+) -> Option<Vec3> {
+    let direction = (home - from).normalize_or_zero();
+    let side = direction.cross(Vec3::Y).normalize_or_zero();
+    // just in front of the wall
+    let in_front = from + direction * (wall_distance - 1.0).max(0.0);
+    DETOUR_OFFSETS
+        .into_iter()
+        .flat_map(|offset| [offset, -offset])
+        .map(|offset| in_front + side * offset)
+        .find(|detour| {
+            distance_to_wall(spatial_query, walls, from, *detour).is_none()
+                && distance_to_wall(spatial_query, walls, *detour, home).is_none()
+        })
+}
+
 fn send_boomerang_bounce_event(
     bounce_event_writer: &mut EventWriter<BounceBoomerangEvent>,
     boomerang_entity: Entity,
@@ -306,34 +1001,223 @@ fn send_boomerang_bounce_event(
 
 fn on_boomerang_bounce_advance_to_next_pathing_step_or_fall_down(
     mut bounce_events: EventReader<BounceBoomerangEvent>,
+    boomerang_settings: Res<BoomerangSettings>,
+    assists: Res<Assists>,
+    mut boomerangs: Query<
+        (
+            &mut Boomerang,
+            &mut BoomerangFlightHistory,
+            &mut Transform,
+            &mut AngularVelocity,
+            Option<&StuckInWall>,
+        ),
+        With<Flying>,
+    >,
+    throwers: Query<&Transform, Without<Boomerang>>,
+    spatial_query: SpatialQuery,
     boomerang_assets: Res<BoomerangAssets>,
-    mut boomerangs: Query<&mut Boomerang, With<Flying>>,
     mut commands: Commands,
 ) -> Result {
     for event in bounce_events.read() {
-        let mut boomerang = boomerangs.get_mut(event.boomerang_entity)?;
+        let (mut boomerang, mut history, mut transform, mut spin, stuck) =
+            boomerangs.get_mut(event.boomerang_entity)?;
+        match event._bounce_on {
+            BoomerangTargetKind::Position(_) => {
+                let normal = wall_normal(&spatial_query, &boomerang.plane, &history);
+                if let Some(normal) = normal {
+                    history.record_surface_normal(normal);
+                }
+                commands.trigger(
+                    SpawnVfxEvent::new(Vfx::ImpactDust, transform.translation)
+                        .toward(normal.map_or(Vec3::ZERO, Vec3::from)),
+                );
+            }
+            BoomerangTargetKind::Entity(entity) => {
+                commands.entity(entity).trigger(BoomerangArrivedEvent {
+                    boomerang: event.boomerang_entity,
+                    position: transform.translation,
+                });
+            }
+        }
 
         boomerang.path_index += 1;
-
-        if boomerang.path_index >= boomerang.path.len() - 1 {
+        boomerang.last_known_target = None;
+
+        if boomerang.path_index + 1 >= boomerang.path.len() {
+            if let Some(thrower) = fumbled_catch(
+                &boomerang,
+                &history,
+                &throwers,
+                &boomerang_settings,
+                &assists,
+            ) {
+                commands.entity(thrower).trigger(FumbledCatchEvent);
+            }
             commands
                 .entity(event.boomerang_entity)
                 .remove::<Flying>()
-                .remove::<BoomerangSfx>()
-                .insert(Falling);
+                .remove::<BoomerangSfx>();
+            if let Some(stuck) = stuck {
+                spin.0 = Vec3::ZERO;
+                transform.rotation = stuck.rest_rotation();
+                commands.spawn((
+                    Name::new("Boomerang Thunk SFX"),
+                    AudioPlayer::new(boomerang_assets.clang_sfx.clone()),
+                    PlaybackSettings::DESPAWN,
+                    TimeDilatedPitch(0.5),
+                ));
+                info!("stuck in a wall");
+                continue;
+            }
+            commands.entity(event.boomerang_entity).insert(Falling);
             info!("falling");
-        } else {
-            commands.spawn((
-                AudioPlayer::new(boomerang_assets.bounce_sfx.clone()),
-                PlaybackSettings::DESPAWN,
-                TimeDilatedPitch(boomerang_assets.get_bounce_pitch(boomerang.path_index)),
-            ));
         }
     }
 
     Ok(())
 }
 
+/// The normal of the wall the boomerang just bounced off, if there is one right where it bounced.
+/// Painted positions usually are on a wall, see [get_raycast_target].
+fn wall_normal(
+    spatial_query: &SpatialQuery,
+    plane: &ThrowingPlane,
+    history: &BoomerangFlightHistory,
+) -> Option<Dir3> {
+    let [.., from, to] = history.waypoints.as_slice() else {
+        return None;
+    };
+    let (ray, length) = plane.ray_between(*from, *to)?;
+    let walls = SpatialQueryFilter::from_mask([GameLayer::Default, GameLayer::Terrain]);
+    let hit = spatial_query.cast_ray(
+        ray.origin,
+        ray.direction,
+        length + BOOMERANG_REACH,
+        true,
+        &walls,
+    )?;
+    // something closer in the way doesn't count, the boomerang didn't bounce off that
+    if hit.distance < length - BOOMERANG_REACH {
+        return None;
+    }
+    plane.flatten(hit.normal)
+}
+
+/// The thrower the boomerang just got back to, if they weren't facing it and it clipped them
+/// instead of being caught, see [BoomerangSettings::fumble_catches].
+fn fumbled_catch(
+    boomerang: &Boomerang,
+    history: &BoomerangFlightHistory,
+    throwers: &Query<&Transform, Without<Boomerang>>,
+    settings: &BoomerangSettings,
+    assists: &Assists,
+) -> Option<Entity> {
+    if !settings.fumble_catches || settings.never_fall || assists.auto_catch {
+        return None;
+    }
+    let (Some(BoomerangTargetKind::Entity(thrower)), Some(BoomerangTargetKind::Entity(caught_by))) =
+        (boomerang.path.first(), boomerang.path.last())
+    else {
+        return None;
+    };
+    // it was headed for someone else, e.g. a stranded boomerang flying home after a detour
+    if thrower != caught_by {
+        return None;
+    }
+    let [.., from, to] = history.waypoints[..] else {
+        return None;
+    };
+    let towards_boomerang = (from - to).with_y(0.0).try_normalize()?;
+    let facing = throwers
+        .get(*thrower)
+        .ok()?
+        .forward()
+        .with_y(0.0)
+        .try_normalize()?;
+    (facing.dot(towards_boomerang) < settings.catch_half_angle.cos()).then_some(*thrower)
+}
+
+/// Deals [BoomerangSettings::fumble_damage] and staggers the thrower for a moment.
+fn on_fumbled_catch(
+    trigger: Trigger<FumbledCatchEvent>,
+    boomerang_settings: Res<BoomerangSettings>,
+    boomerang_assets: Res<BoomerangAssets>,
+    mut commands: Commands,
+) {
+    let mut thrower = commands.entity(trigger.target());
+    thrower.insert(Staggered::new(boomerang_settings.fumble_stagger_seconds));
+    if boomerang_settings.fumble_damage > 0 {
+        thrower.trigger(HealthEvent::Damage(
+            boomerang_settings.fumble_damage,
+            0,
+            None,
+        ));
+    }
+    // a dull thud instead of a clean catch
+    commands.spawn((
+        Name::new("Fumbled Catch SFX"),
+        AudioPlayer::new(boomerang_assets.bounce_sfx.clone()),
+        PlaybackSettings::DESPAWN,
+        TimeDilatedPitch(0.5),
+    ));
+}
+
+/// Boomerangs that run into each other mid-air both ricochet off, see [Boomerang::deflect].
+fn on_boomerang_clash_deflect(
+    mut collisions: EventReader<CollisionStarted>,
+    mut boomerangs: Query<(&mut Boomerang, &mut BoomerangFlightHistory, &Transform), With<Flying>>,
+    transforms: Query<&Transform, Without<Boomerang>>,
+    settings: Res<BoomerangSettings>,
+    boomerang_assets: Res<BoomerangAssets>,
+    mut commands: Commands,
+) {
+    for CollisionStarted(entity1, entity2) in collisions.read() {
+        let Ok(
+            [
+                (mut first, mut first_history, first_transform),
+                (mut second, mut second_history, second_transform),
+            ],
+        ) = boomerangs.get_many_mut([*entity1, *entity2])
+        else {
+            continue;
+        };
+        let first_position = first_transform.translation;
+        let second_position = second_transform.translation;
+        // throwing several boomerangs in a row shouldn't make them knock each other out of the air
+        if first.is_near_thrower(first_position, &transforms)
+            || second.is_near_thrower(second_position, &transforms)
+        {
+            continue;
+        }
+
+        // reflect both flight directions off the plane between the two boomerangs
+        let normal = (first_position - second_position)
+            .with_y(0.0)
+            .normalize_or(Vec3::X);
+        for (boomerang, history, position, normal) in [
+            (&mut first, &mut first_history, first_position, normal),
+            (&mut second, &mut second_history, second_position, -normal),
+        ] {
+            let incoming = history
+                .waypoints
+                .last()
+                .map_or(Vec3::ZERO, |from| (position - *from).with_y(0.0))
+                .normalize_or(-normal);
+            let outgoing = incoming.reflect(normal).normalize_or(normal);
+            boomerang.deflect(history, position, outgoing, &settings, &transforms);
+        }
+
+        let contact = (first_position + second_position) / 2.0;
+        commands.trigger(SpawnVfxEvent::new(Vfx::Sparks, contact));
+        commands.spawn((
+            Name::new("Boomerang Clash SFX"),
+            AudioPlayer::new(boomerang_assets.clang_sfx.clone()),
+            PlaybackSettings::DESPAWN,
+            TimeDilatedPitch(1.6),
+        ));
+    }
+}
+
 /// Rotates our boomerangs at constant speed.
 fn set_boomerang_rotation_speed_based_on_velocity(
     mut boomerangs: Query<(&mut AngularVelocity, &Boomerang), With<Flying>>,
@@ -348,10 +1232,11 @@ fn set_boomerang_rotation_speed_based_on_velocity(
 fn update_boomerang_preview_position(
     boomerang_origins: Single<(Entity, &GlobalTransform), With<CurrentBoomerangThrowOrigin>>,
     potential_origins: Query<(), With<PotentialBoomerangOrigin>>,
-    mut previews: Query<(&mut WeaponTarget, &mut Transform), Without<Enemy>>,
+    mut previews: Query<(&mut WeaponTarget, &mut RicochetPreview, &mut Transform), Without<Enemy>>,
     mouse_position: Res<MousePosition>,
     mut commands: Commands,
     spatial_query: SpatialQuery,
+    throwing_plane: Res<ThrowingPlane>,
 ) -> Result {
     let Some(mouse_position) = mouse_position.boomerang_throwing_plane else {
         // Mouse is probably not inside the game window right now
@@ -360,32 +1245,38 @@ fn update_boomerang_preview_position(
 
     let (origin_entity, origin_transform) = boomerang_origins.into_inner();
 
-    let (mut target_entity, target_location) = match get_raycast_target(
+    let Ok(hit) = get_raycast_target(
         &spatial_query,
+        &throwing_plane,
         mouse_position,
         origin_entity,
         origin_transform.translation(),
-    ) {
-        Ok(value) => value,
-        Err(_value) => return Ok(()),
+    ) else {
+        return Ok(());
     };
-
-    if let Some(te) = target_entity {
-        if potential_origins.get(te).is_err() {
-            // If the entity hit isn't one of the targetable ones, we hit a wall.
-            target_entity = None;
-        }
+    // If the entity hit isn't one of the targetable ones, we hit a wall.
+    let targetable = |hit: &RaycastTarget| hit.entity.filter(|te| potential_origins.contains(*te));
+    let target_entity = targetable(&hit);
+    let ricochet = match target_entity {
+        Some(_) => None,
+        None => get_ricochet_target(&spatial_query, &throwing_plane, &hit, origin_entity),
     }
+    .map(|leg| RaycastTarget {
+        entity: targetable(&leg),
+        ..leg
+    });
 
-    if let Ok((mut preview, mut transform)) = previews.single_mut() {
+    if let Ok((mut preview, mut preview_ricochet, mut transform)) = previews.single_mut() {
         preview.target_entity = target_entity;
-        transform.translation = target_location;
+        preview_ricochet.0 = ricochet;
+        transform.translation = hit.position;
     } else {
         // TODO: Preview needs to be despawned after throw
         commands.spawn((
             Name::from("WeaponTarget"),
             WeaponTarget { target_entity },
-            Transform::from_translation(target_location),
+            RicochetPreview(ricochet),
+            Transform::from_translation(hit.position),
         ));
     }
     Ok(())
@@ -393,59 +1284,209 @@ fn update_boomerang_preview_position(
 
 pub fn get_raycast_target(
     spatial_query: &SpatialQuery,
+    throwing_plane: &ThrowingPlane,
     target_position: Vec3,
     origin_entity: Entity,
     origin_transform: Vec3,
-) -> Result<(Option<Entity>, Vec3), Result> {
-    let origin = origin_transform.with_y(BOOMERANG_FLYING_HEIGHT);
-
-    let Ok(direction) = Dir3::new(target_position - origin) else {
+) -> Result<RaycastTarget, Result> {
+    let Some((ray, _)) = throwing_plane.ray_between(origin_transform, target_position) else {
         // We are probably just pointing right at the ThrowOrigin
         return Err(Ok(()));
     };
 
     let max_distance = 50.0;
+    Ok(cast_on_plane(
+        spatial_query,
+        throwing_plane,
+        ray,
+        max_distance,
+        EntityHashSet::from([origin_entity]),
+    ))
+}
+
+/// Where a boomerang carries on to after hitting the wall at `hit`: along its travel direction,
+/// mirrored around the wall's normal, up to the next thing in the way or [RICOCHET_RANGE] further.
+/// [None] if nothing was hit in the first place. Flies right past the thrower, it's on its way home
+/// after the ricochet anyway.
+pub fn get_ricochet_target(
+    spatial_query: &SpatialQuery,
+    throwing_plane: &ThrowingPlane,
+    hit: &RaycastTarget,
+    thrower: Entity,
+) -> Option<RaycastTarget> {
+    let (normal, direction) = (hit.normal?, hit.ricochet_direction()?);
+    // off the surface, so the wall itself isn't the first thing the ray runs into
+    let origin = hit.position + normal * RICOCHET_SURFACE_OFFSET;
+    Some(cast_on_plane(
+        spatial_query,
+        throwing_plane,
+        Ray3d::new(origin, direction),
+        RICOCHET_RANGE,
+        EntityHashSet::from([thrower]),
+    ))
+}
+
+/// What [get_raycast_target] or [get_ricochet_target] found along the throwing plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RaycastTarget {
+    /// Whatever was hit first, if anything was.
+    pub entity: Option<Entity>,
+    /// Where it was hit, or as far as the ray goes if nothing was.
+    pub position: Vec3,
+    /// Which way the boomerang would be flying to get there.
+    pub direction: Dir3,
+    /// The normal of the surface that was hit, flattened onto the throwing plane. [None] if nothing
+    /// was hit.
+    pub normal: Option<Dir3>,
+}
+
+impl RaycastTarget {
+    /// Where a boomerang flies to for this hit: the entity if there is one, the position otherwise.
+    pub fn target_kind(&self) -> BoomerangTargetKind {
+        match self.entity {
+            Some(entity) => BoomerangTargetKind::Entity(entity),
+            None => BoomerangTargetKind::Position(self.position),
+        }
+    }
+
+    /// The travel direction mirrored around the surface normal, which is where a boomerang goes
+    /// after ricocheting off the hit.
+    pub fn ricochet_direction(&self) -> Option<Dir3> {
+        Dir3::new(self.direction.reflect(*self.normal?)).ok()
+    }
+}
+
+fn cast_on_plane(
+    spatial_query: &SpatialQuery,
+    throwing_plane: &ThrowingPlane,
+    ray: Ray3d,
+    max_distance: f32,
+    excluded_entities: EntityHashSet,
+) -> RaycastTarget {
     let solid = true;
     let filter = SpatialQueryFilter {
-        excluded_entities: EntityHashSet::from([origin_entity]),
+        excluded_entities,
         ..Default::default()
     };
-    let (distance_to_target, target_entity) = if let Some(first_hit) =
-        spatial_query.cast_ray(origin, direction, max_distance, solid, &filter)
-    {
-        (first_hit.distance, Some(first_hit.entity))
-    } else {
-        (max_distance, None)
-    };
+    let hit = spatial_query.cast_ray(ray.origin, ray.direction, max_distance, solid, &filter);
+    RaycastTarget {
+        entity: hit.map(|hit| hit.entity),
+        position: ray.get_point(hit.map_or(max_distance, |hit| hit.distance)),
+        direction: ray.direction,
+        normal: hit.and_then(|hit| throwing_plane.flatten(hit.normal)),
+    }
+}
+
+/// On whoever is holding [`SteerBoomerangAction`], their boomerangs bend towards the cursor while
+/// they're on their way to a painted position, see [`Boomerang::steer_towards`].
+#[derive(Component, Debug, Default)]
+pub struct SteeringBoomerangs;
+
+fn start_steering(trigger: Trigger<Started<SteerBoomerangAction>>, mut commands: Commands) {
+    commands.entity(trigger.target()).insert(SteeringBoomerangs);
+}
 
-    let target_location = origin + direction * distance_to_target;
-    Ok((target_entity, target_location))
+fn stop_steering(trigger: Trigger<Completed<SteerBoomerangAction>>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .try_remove::<SteeringBoomerangs>();
 }
 
+/// Throws once the fire button is let go, as charged up as it got, see [ChargingThrow].
 fn on_fire_action_throw_boomerang(
+    // the fire action is bound with a `Release` condition, so this only fires once it's let go
     _trigger: Trigger<Fired<FireBoomerangAction>>,
-    boomerang_holders: Query<Entity, With<CurrentBoomerangThrowOrigin>>,
-    boomerang_previews: Query<(&WeaponTarget, &GlobalTransform), Without<Enemy>>,
+    boomerang_holders: Query<
+        (
+            Entity,
+            &BoomerangInventory,
+            Has<ThrowCooldown>,
+            Option<&ChargingThrow>,
+        ),
+        With<CurrentBoomerangThrowOrigin>,
+    >,
+    boomerang_previews: Query<(&WeaponTarget, &RicochetPreview, &GlobalTransform), Without<Enemy>>,
+    enemies: Query<(Entity, &GlobalTransform), With<Enemy>>,
+    transforms: Query<&GlobalTransform>,
+    settings: Res<BoomerangSettings>,
     mut event_writer: EventWriter<ThrowBoomerangEvent>,
+    mut commands: Commands,
 ) {
-    let Ok(thrower_entity) = boomerang_holders.single() else {
+    let Ok((thrower_entity, inventory, cooling_down, charging)) = boomerang_holders.single() else {
         error!("Was unable to find a single thrower! (multiple ain't supported yet)");
         return;
     };
-    let Ok((preview, preview_position)) = boomerang_previews.single() else {
+    // wasn't ready when the button went down, see `start_charging`
+    let Some(charging) = charging else {
+        return;
+    };
+    let charge = charging.charge(&settings);
+    commands.entity(thrower_entity).remove::<ChargingThrow>();
+    if cooling_down {
+        return;
+    }
+    let Some(slot) = inventory.ready_slot() else {
+        commands.entity(thrower_entity).trigger(ThrowRejected);
+        return;
+    };
+    let Ok((preview, ricochet, preview_position)) = boomerang_previews.single() else {
         error!("Was unable to find a single target preview! (multiple ain't supported yet)");
         return;
     };
 
-    let target = match preview.target_entity {
+    let mut target = vec![match preview.target_entity {
         None => BoomerangTargetKind::Position(preview_position.translation()),
         Some(entity) => BoomerangTargetKind::Entity(entity),
-    };
+    }];
+    // thrown at a wall, so it carries on the way the preview showed
+    target.extend(ricochet.0.as_ref().map(RaycastTarget::target_kind));
+    if let (Some(first), Ok(thrower)) = (preview.target_entity, transforms.get(thrower_entity)) {
+        if charge >= 1.0 {
+            target.extend(
+                outlaw_behind(first, thrower.translation(), &enemies, &settings)
+                    .map(BoomerangTargetKind::Entity),
+            );
+        }
+    }
 
     event_writer.write(ThrowBoomerangEvent {
         thrower_entity,
-        target: vec![target],
+        target,
+        slot: Some(slot),
+        charge,
     });
+    commands
+        .entity(thrower_entity)
+        .trigger(ConsumeBoomerangSlot(slot));
+}
+
+/// The closest outlaw behind `target`, as seen from `from`, for a fully charged throw to fly on to.
+fn outlaw_behind(
+    target: Entity,
+    from: Vec3,
+    enemies: &Query<(Entity, &GlobalTransform), With<Enemy>>,
+    settings: &BoomerangSettings,
+) -> Option<Entity> {
+    let (_, target_transform) = enemies.get(target).ok()?;
+    let target_position = target_transform.translation().with_y(0.0);
+    let direction = (target_position - from.with_y(0.0)).normalize_or_zero();
+    enemies
+        .iter()
+        .filter(|(entity, _)| *entity != target)
+        .map(|(entity, transform)| {
+            let offset = transform.translation().with_y(0.0) - target_position;
+            (
+                entity,
+                offset.length(),
+                offset.normalize_or_zero().dot(direction),
+            )
+        })
+        // roughly the same direction the boomerang is already flying in
+        .filter(|(_, distance, alignment)| {
+            *distance <= settings.charge_pierce_range && *alignment > 0.7
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _, _)| entity)
 }
 
 #[derive(Component)]
@@ -455,7 +1496,11 @@ fn on_throw_boomerang_spawn_boomerang(
     mut event_reader: EventReader<ThrowBoomerangEvent>,
     mut commands: Commands,
     all_transforms: Query<&Transform>,
+    inventories: Query<&BoomerangInventory>,
+    boomerang_kinds: Res<BoomerangKindRegistry>,
     boomerang_assets: Res<BoomerangAssets>,
+    throwing_plane: Res<ThrowingPlane>,
+    settings: Res<BoomerangSettings>,
 ) -> Result {
     let mut rng = thread_rng();
     for event in event_reader.read() {
@@ -467,35 +1512,47 @@ fn on_throw_boomerang_spawn_boomerang(
 
         let random_index = rng.gen_range(0..boomerang_assets.toss_sfx.len());
         let random_sfx = &boomerang_assets.toss_sfx[random_index];
+        let start = throwing_plane.project(all_transforms.get(event.thrower_entity)?.translation);
+        let damage = event
+            .slot
+            .and_then(|slot| inventories.get(event.thrower_entity).ok()?.slot(slot))
+            .map_or(1, |slot| slot.damage(&boomerang_kinds));
         // spawn the 'rang
-        commands
-            .spawn((
-                Name::new("Boomerang"),
-                Boomerang::new(path),
-                Transform::from_translation(
-                    all_transforms
-                        .get(event.thrower_entity)?
-                        .translation
-                        .with_y(BOOMERANG_FLYING_HEIGHT),
-                )
-                .with_scale(Vec3::splat(1.5)),
-                StateScoped(Gameplay::Normal),
-                Flying,
-                SceneRoot(boomerang_assets.mesh.clone()),
-                Collider::sphere(0.5),
-                CollisionLayers::new(GameLayer::Boomerang, GameLayer::Enemy),
-                RigidBody::Kinematic,
-                CanDamage(1),
-                CollisionEventsEnabled,
-                LinearVelocity(Vec3::ZERO),
-                AngularVelocity(Vec3::ZERO),
-            ))
-            .insert((
-                AudioPlayer::new(random_sfx.clone()),
-                PlaybackSettings::REMOVE,
-                BoomerangSfx,
-                TimeDilatedPitch(1.0),
-            ));
+        let mut boomerang = commands.spawn((
+            Name::new("Boomerang"),
+            Boomerang::new(
+                path,
+                *throwing_plane,
+                start,
+                settings.energy * (1.0 + event.charge * settings.charge_energy_bonus),
+                1.0 + event.charge * settings.charge_speed_bonus,
+            ),
+            BoomerangFlightHistory::new(start),
+            Transform::from_translation(start).with_scale(Vec3::splat(1.5)),
+            StateScoped(Gameplay::Normal),
+            Flying,
+            SceneRoot(boomerang_assets.mesh.clone()),
+            Collider::sphere(0.5),
+            CollisionLayers::new(
+                GameLayer::Boomerang,
+                [GameLayer::Enemy, GameLayer::Civilian, GameLayer::Boomerang],
+            ),
+            RigidBody::Kinematic,
+            CanDamage(damage),
+            CollisionEventsEnabled,
+            LinearVelocity(Vec3::ZERO),
+            AngularVelocity(Vec3::ZERO),
+        ));
+        boomerang.insert((
+            AudioPlayer::new(random_sfx.clone()),
+            PlaybackSettings::REMOVE,
+            BoomerangSfx,
+            TimeDilatedPitch(1.0),
+            DistanceAttenuation::new(SFX_NEAR_DISTANCE, SFX_FAR_DISTANCE),
+        ));
+        if let Some(slot) = event.slot {
+            boomerang.insert(ThrownFromSlot(slot));
+        }
     }
 
     Ok(())
@@ -525,15 +1582,20 @@ struct BoomerangPreviewGizmos;
 fn draw_preview_gizmo(
     mut gizmos: Gizmos<BoomerangPreviewGizmos>,
     boomerang_holders: Query<&GlobalTransform, With<CurrentBoomerangThrowOrigin>>,
-    boomerang_target_preview: Query<&GlobalTransform, (With<WeaponTarget>, Without<Enemy>)>,
+    boomerang_target_preview: Query<
+        (&GlobalTransform, &RicochetPreview),
+        (With<WeaponTarget>, Without<Enemy>),
+    >,
+    throwing_plane: Res<ThrowingPlane>,
 ) {
+    let color = color::palettes::css::BLACK.with_alpha(0.5);
     for from in boomerang_holders {
-        for to in boomerang_target_preview {
-            gizmos.line(
-                from.translation().with_y(BOOMERANG_FLYING_HEIGHT),
-                to.translation().with_y(BOOMERANG_FLYING_HEIGHT),
-                color::palettes::css::BLACK.with_alpha(0.5),
-            );
+        for (to, ricochet) in boomerang_target_preview {
+            let to = throwing_plane.project(to.translation());
+            gizmos.line(throwing_plane.project(from.translation()), to, color);
+            if let Some(ricochet) = &ricochet.0 {
+                gizmos.line(to, ricochet.position, color);
+            }
         }
     }
 }
@@ -551,23 +1613,68 @@ pub fn _boomerang_dev_tools_plugin(app: &mut App) {
 /// Current set of stats of our boomerang
 #[derive(Resource, Debug, Reflect)]
 #[reflect(Resource)]
-struct BoomerangSettings {
+pub struct BoomerangSettings {
+    /// Total distance a single throw may cover along its painted path (not counting the way back).
+    pub energy: f32,
     pub min_movement_speed: f32,
     pub max_movement_speed: f32,
     pub min_rotation_speed: f32,
     pub max_rotation_speed: f32,
     pub falling_speed: f32,
+    /// How much energy a boomerang loses when it clashes with another one mid-air.
+    pub clash_energy_cost: f32,
+    /// How far boomerangs ricochet off each other before carrying on along their paths.
+    pub clash_deflect_distance: f32,
+    /// How fast a boomerang can be steered towards the cursor, in radians per second.
+    pub steer_turn_rate: f32,
+    /// How much energy steering takes, per second.
+    pub steer_energy_cost: f32,
+    /// How long the fire button has to be held for a fully charged throw, in seconds.
+    pub charge_seconds: f32,
+    /// How much faster a fully charged throw flies, e.g. 0.5 for 50% faster.
+    pub charge_speed_bonus: f32,
+    /// How much more energy a fully charged throw has, e.g. 0.5 for 50% more.
+    pub charge_energy_bonus: f32,
+    /// How far behind its target a fully charged throw looks for one more outlaw to fly on to.
+    pub charge_pierce_range: f32,
+    /// Whether boomerangs coming back at their thrower from behind clip them instead of being
+    /// caught.
+    pub fumble_catches: bool,
+    /// Boomerangs coming back at most this far (in radians) from where the thrower is facing are
+    /// caught. The rest are fumbled.
+    pub catch_half_angle: f32,
+    /// Damage a fumbled catch deals, players only have one health so this is off by default.
+    pub fumble_damage: u32,
+    /// How long a fumbled catch keeps the thrower from moving, in seconds.
+    pub fumble_stagger_seconds: f32,
+    /// Boomerangs always make it back to their thrower: they fly through walls on the way home
+    /// rather than being stranded, and are never fumbled. Set by [`crate::gameplay::mutators`].
+    pub never_fall: bool,
     pub easing_function: EaseFunction, // see https://bevyengine.org/examples/animation/easing-functions/
 }
 
 impl Default for BoomerangSettings {
     fn default() -> Self {
         Self {
+            energy: 60.,
             min_movement_speed: 8.,
             max_movement_speed: 18.,
             min_rotation_speed: 10.,
             max_rotation_speed: 25.,
             falling_speed: 5.0,
+            clash_energy_cost: 10.0,
+            clash_deflect_distance: 4.0,
+            steer_turn_rate: 1.5,
+            steer_energy_cost: 15.0,
+            charge_seconds: 1.0,
+            charge_speed_bonus: 0.6,
+            charge_energy_bonus: 0.5,
+            charge_pierce_range: 12.0,
+            fumble_catches: true,
+            catch_half_angle: 110f32.to_radians(),
+            fumble_damage: 0,
+            fumble_stagger_seconds: 0.5,
+            never_fall: false,
             easing_function: EaseFunction::BackOut,
         }
     }
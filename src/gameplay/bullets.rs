@@ -0,0 +1,511 @@
+//! Enemy projectiles.
+//! Trigger a [`FireBulletsEvent`] to spawn one or more bullets, e.g. a single pistol shot or a
+//! fan of shotgun pellets. Trigger a [`LobDynamiteEvent`] to throw a stick of dynamite in an arc,
+//! which blows up everything with [`Health`] around it once its fuse runs out.
+//! Bullets can be knocked back with a boomerang, see [`crate::gameplay::deadeye`].
+//!
+//! So they're easy to read and dodge, bullets drag a glowing [`Tracer`] behind them and every shot
+//! lights up a muzzle flash where it was fired from.
+
+use crate::asset_tracking::LoadResource;
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::assists::Assists;
+use crate::gameplay::blob_shadows::CastsBlobShadow;
+use crate::gameplay::boomerang::BoomerangHittable;
+use crate::gameplay::camera::AddTrauma;
+use crate::gameplay::health_and_damage::{CanDamage, Health, HealthEvent};
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use crate::theme::fx_budget::Fx;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use avian3d::prelude::{
+    Collider, CollisionEventsEnabled, CollisionLayers, LinearVelocity, RigidBody,
+};
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Bullet>()
+        .register_type::<DamageFalloff>()
+        .register_type::<Tracer>()
+        .register_type::<BulletAssets>()
+        .load_resource::<BulletAssets>();
+
+    app.add_observer(fire_bullets).add_observer(flash_muzzle);
+    app.add_observer(lob_dynamite);
+    app.add_systems(
+        Update,
+        (
+            apply_damage_falloff,
+            move_arcing_projectiles,
+            burn_dynamite_fuses,
+            fade_explosions,
+            stretch_tracers,
+            fade_muzzle_flashes,
+        )
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+const DYNAMITE_GRAVITY: f32 = 20.0;
+const DYNAMITE_LANDING_HEIGHT: f32 = 0.2;
+const EXPLOSION_FLASH_SECS: f32 = 0.3;
+/// Bullet models are tiny, they get scaled up by this much.
+const BULLET_SCALE: f32 = 3.0;
+/// How long tracers get once the bullet is far enough from the muzzle, in world units.
+const TRACER_LENGTH: f32 = 2.5;
+const TRACER_WIDTH: f32 = 0.12;
+const MUZZLE_FLASH_SECS: f32 = 0.08;
+const MUZZLE_FLASH_INTENSITY: f32 = 300_000.0;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Bullet;
+
+/// Bullets with this component deal less damage the further they travel, and disappear once
+/// they've reached their max range.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct DamageFalloff {
+    /// Bullets deal their full damage up to this distance.
+    pub full_damage_range: f32,
+    /// Bullets deal this much damage after leaving their full damage range.
+    pub reduced_damage: u32,
+    /// Bullets are removed after travelling this far.
+    pub max_range: f32,
+}
+
+/// The streak of light behind a bullet, pointing the way it came from. Grows out of the muzzle
+/// until it's [`TRACER_LENGTH`] long.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Tracer;
+
+/// The flash of light at a gun's muzzle right when it fires.
+#[derive(Component, Debug)]
+struct MuzzleFlash(Timer);
+
+/// Where a bullet was fired from, used for damage falloff and to show the player where a hit came
+/// from.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BulletOrigin(pub Vec3);
+
+#[derive(Event, Debug, Clone)]
+pub struct FireBulletsEvent {
+    pub origin: Vec3,
+    /// Direction of the center bullet. Other pellets are fanned out evenly around it.
+    pub direction: Vec3,
+    pub speed: f32,
+    pub damage: u32,
+    /// How many bullets to fire at once.
+    pub pellets: usize,
+    /// The angle (in radians) between the outermost pellets.
+    pub spread: f32,
+    pub falloff: Option<DamageFalloff>,
+}
+
+impl FireBulletsEvent {
+    /// A single bullet flying straight in `direction`.
+    pub fn single(origin: Vec3, direction: Vec3, speed: f32, damage: u32) -> Self {
+        Self {
+            origin,
+            direction,
+            speed,
+            damage,
+            pellets: 1,
+            spread: 0.0,
+            falloff: None,
+        }
+    }
+
+    /// Directions of all pellets, fanned out on the horizontal plane.
+    fn pellet_directions(&self) -> impl Iterator<Item = Vec3> + '_ {
+        let step = if self.pellets > 1 {
+            self.spread / (self.pellets - 1) as f32
+        } else {
+            0.0
+        };
+        let first_angle = -self.spread / 2.0;
+        (0..self.pellets).map(move |i| {
+            let angle = if self.pellets > 1 {
+                first_angle + step * i as f32
+            } else {
+                0.0
+            };
+            Quat::from_rotation_y(angle) * self.direction
+        })
+    }
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct LobDynamiteEvent {
+    pub origin: Vec3,
+    /// Where the dynamite lands. Only the horizontal position is used, it always lands on the
+    /// ground.
+    pub target: Vec3,
+    /// How long the dynamite is in the air.
+    pub flight_time: f32,
+    /// How long after being thrown the dynamite explodes. Should be longer than the flight time,
+    /// otherwise it goes off in mid-air.
+    pub fuse: f32,
+    pub radius: f32,
+    pub damage: u32,
+}
+
+/// Follows a parabola under its own gravity, instead of the straight line of a [`Bullet`].
+#[derive(Component, Debug, Clone)]
+struct ArcingProjectile {
+    velocity: Vec3,
+    remaining_flight: f32,
+    landing: Vec3,
+}
+
+impl ArcingProjectile {
+    fn new(origin: Vec3, landing: Vec3, flight_time: f32) -> Self {
+        let flight_time = flight_time.max(0.1);
+        let offset = landing - origin;
+        let horizontal = offset.with_y(0.0) / flight_time;
+        let vertical =
+            (offset.y + 0.5 * DYNAMITE_GRAVITY * flight_time * flight_time) / flight_time;
+        Self {
+            velocity: horizontal.with_y(vertical),
+            remaining_flight: flight_time,
+            landing,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct Dynamite {
+    fuse: Timer,
+    radius: f32,
+    damage: u32,
+    /// The decal showing where the dynamite is going to land.
+    indicator: Entity,
+}
+
+#[derive(Component, Debug)]
+struct ExplosionFlash {
+    timer: Timer,
+    radius: f32,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct BulletAssets {
+    #[dependency]
+    bullet: Handle<Scene>,
+}
+
+impl FromWorld for BulletAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            bullet: asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/bullet.glb")),
+        }
+    }
+}
+
+fn fire_bullets(
+    trigger: Trigger<FireBulletsEvent>,
+    bullet_assets: Res<BulletAssets>,
+    assists: Res<Assists>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    // only outlaws shoot, so the assist slows down every bullet
+    let speed = event.speed * assists.bullet_speed_multiplier();
+    let material = materials.add(Color::srgb_u8(50, 0, 0));
+    // lying flat, so it's seen from above, and 1 long so it can be scaled to any length
+    let tracer_mesh = meshes.add(
+        Plane3d::default()
+            .mesh()
+            .size(TRACER_WIDTH / BULLET_SCALE, 1.0),
+    );
+    let tracer_material = materials.add(StandardMaterial {
+        base_color: Color::srgba(1.0, 0.85, 0.5, 0.8),
+        emissive: LinearRgba::rgb(6.0, 3.0, 0.8),
+        alpha_mode: AlphaMode::Add,
+        unlit: true,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+    for direction in event.pellet_directions() {
+        let mut bullet_transform =
+            Transform::from_translation(event.origin).with_scale(Vec3::splat(BULLET_SCALE));
+        bullet_transform.look_to(direction, Vec3::Y);
+        let mut bullet = commands.spawn((
+            Name::new("Bullet"),
+            bullet_transform,
+            Bullet,
+            BulletOrigin(event.origin),
+            SceneRoot(bullet_assets.bullet.clone()),
+            MeshMaterial3d(material.clone()),
+            Collider::sphere(0.1),
+            CollisionLayers::new(
+                GameLayer::Bullet,
+                [
+                    GameLayer::Player,
+                    GameLayer::Terrain,
+                    GameLayer::Enemy,
+                    GameLayer::Default,
+                ],
+            ),
+            RigidBody::Kinematic,
+            LinearVelocity(direction * speed),
+            CanDamage(event.damage),
+            // can be painted in aim mode, see crate::gameplay::deadeye
+            BoomerangHittable,
+            CollisionEventsEnabled,
+            StateScoped(Screen::Gameplay),
+            children![(
+                Name::new("Tracer"),
+                Tracer,
+                Mesh3d(tracer_mesh.clone()),
+                MeshMaterial3d(tracer_material.clone()),
+                Transform::from_scale(Vec3::ZERO),
+                NotShadowCaster,
+                NotShadowReceiver,
+            )],
+        ));
+        if let Some(falloff) = event.falloff {
+            bullet.insert(falloff);
+        }
+    }
+}
+
+fn flash_muzzle(
+    trigger: Trigger<FireBulletsEvent>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    commands.spawn((
+        Name::new("Muzzle Flash"),
+        // stretched out in the direction of the shot
+        Transform::from_translation(event.origin)
+            .looking_to(event.direction, Vec3::Y)
+            .with_scale(Vec3::new(1.0, 1.0, 2.0)),
+        Mesh3d(meshes.add(Sphere::new(0.2))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(1.0, 0.8, 0.4),
+            emissive: LinearRgba::rgb(10.0, 6.0, 1.5),
+            unlit: true,
+            ..default()
+        })),
+        PointLight {
+            color: Color::srgb(1.0, 0.7, 0.3),
+            intensity: MUZZLE_FLASH_INTENSITY,
+            range: 8.0,
+            shadows_enabled: false,
+            ..default()
+        },
+        MuzzleFlash(Timer::from_seconds(MUZZLE_FLASH_SECS, TimerMode::Once)),
+        Fx::MuzzleFlash,
+        NotShadowCaster,
+        NotShadowReceiver,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Tracers stay behind their bullet, so they'd stick out of the gun if they started out at full
+/// length.
+fn stretch_tracers(
+    bullets: Query<(&Transform, &BulletOrigin, &Children), With<Bullet>>,
+    mut tracers: Query<&mut Transform, (With<Tracer>, Without<Bullet>)>,
+) {
+    for (transform, origin, children) in &bullets {
+        let length = transform.translation.distance(origin.0).min(TRACER_LENGTH) / BULLET_SCALE;
+        for child in children {
+            if let Ok(mut tracer) = tracers.get_mut(*child) {
+                // bullets fly towards -Z, so behind them is +Z
+                tracer.translation = Vec3::Z * length / 2.0;
+                tracer.scale = Vec3::new(1.0, 1.0, length);
+            }
+        }
+    }
+}
+
+fn fade_muzzle_flashes(
+    time: GameTime,
+    mut flashes: Query<(Entity, &mut MuzzleFlash, &mut PointLight, &mut Transform)>,
+    mut commands: Commands,
+) {
+    for (entity, mut flash, mut light, mut transform) in &mut flashes {
+        flash.0.tick(time.delta());
+        if flash.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let remaining = 1.0 - flash.0.fraction();
+        light.intensity = MUZZLE_FLASH_INTENSITY * remaining;
+        transform.scale = Vec3::new(1.0, 1.0, 2.0) * remaining;
+    }
+}
+
+fn apply_damage_falloff(
+    mut bullets: Query<(
+        Entity,
+        &Transform,
+        &BulletOrigin,
+        &DamageFalloff,
+        &mut CanDamage,
+    )>,
+    mut commands: Commands,
+) {
+    for (entity, transform, origin, falloff, mut damage) in &mut bullets {
+        let travelled = transform.translation.distance(origin.0);
+        if travelled > falloff.max_range {
+            commands.entity(entity).despawn();
+        } else if travelled > falloff.full_damage_range && damage.0 != falloff.reduced_damage {
+            damage.0 = falloff.reduced_damage;
+        }
+    }
+}
+
+fn lob_dynamite(
+    trigger: Trigger<LobDynamiteEvent>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let landing = event.target.with_y(DYNAMITE_LANDING_HEIGHT);
+
+    let indicator = commands
+        .spawn((
+            Name::new("Dynamite Landing Indicator"),
+            Mesh3d(meshes.add(Circle::new(event.radius))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgba(1.0, 0.2, 0.0, 0.35),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            })),
+            Transform::from_translation(event.target.with_y(0.02))
+                .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+            NotShadowCaster,
+            NotShadowReceiver,
+            StateScoped(Screen::Gameplay),
+        ))
+        .id();
+
+    commands.spawn((
+        Name::new("Dynamite"),
+        Transform::from_translation(event.origin),
+        Mesh3d(meshes.add(Cylinder::new(0.08, 0.5))),
+        MeshMaterial3d(materials.add(Color::srgb_u8(180, 30, 20))),
+        ArcingProjectile::new(event.origin, landing, event.flight_time),
+        Dynamite {
+            fuse: Timer::from_seconds(event.fuse, TimerMode::Once),
+            radius: event.radius,
+            damage: event.damage,
+            indicator,
+        },
+        CastsBlobShadow,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn move_arcing_projectiles(
+    time: GameTime,
+    mut projectiles: Query<(Entity, &mut Transform, &mut ArcingProjectile)>,
+    mut commands: Commands,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut transform, mut projectile) in &mut projectiles {
+        projectile.remaining_flight -= dt;
+        if projectile.remaining_flight <= 0.0 {
+            transform.translation = projectile.landing;
+            transform.rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+            commands.entity(entity).remove::<ArcingProjectile>();
+            continue;
+        }
+        projectile.velocity.y -= DYNAMITE_GRAVITY * dt;
+        transform.translation += projectile.velocity * dt;
+        transform.rotate_local_x(dt * 10.0);
+    }
+}
+
+fn burn_dynamite_fuses(
+    time: GameTime,
+    mut dynamites: Query<(Entity, &Transform, &mut Dynamite)>,
+    targets: Query<(Entity, &GlobalTransform), With<Health>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    for (entity, transform, mut dynamite) in &mut dynamites {
+        dynamite.fuse.tick(time.delta());
+        if !dynamite.fuse.finished() {
+            continue;
+        }
+
+        let position = transform.translation;
+        for (target, target_transform) in &targets {
+            let distance = target_transform
+                .translation()
+                .with_y(0.0)
+                .distance(position.with_y(0.0));
+            if distance <= dynamite.radius {
+                commands.entity(target).trigger(HealthEvent::Damage(
+                    dynamite.damage,
+                    0,
+                    Some(position),
+                ));
+            }
+        }
+
+        commands.trigger(SpawnVfxEvent::new(Vfx::GunSmoke, position).toward(Vec3::Y));
+        commands.trigger(AddTrauma::Explosion);
+        commands.spawn((
+            Name::new("Explosion"),
+            Mesh3d(meshes.add(Sphere::new(1.0))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgba(1.0, 0.6, 0.1, 0.8),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            })),
+            Transform::from_translation(position).with_scale(Vec3::ZERO),
+            ExplosionFlash {
+                timer: Timer::from_seconds(EXPLOSION_FLASH_SECS, TimerMode::Once),
+                radius: dynamite.radius,
+            },
+            Fx::Explosion,
+            NotShadowCaster,
+            StateScoped(Screen::Gameplay),
+        ));
+        commands.entity(dynamite.indicator).despawn();
+        commands.entity(entity).despawn();
+    }
+}
+
+fn fade_explosions(
+    time: GameTime,
+    mut explosions: Query<(
+        Entity,
+        &mut Transform,
+        &mut ExplosionFlash,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    for (entity, mut transform, mut flash, material) in &mut explosions {
+        flash.timer.tick(time.delta());
+        let progress = flash.timer.fraction();
+        transform.scale = Vec3::splat(flash.radius * progress);
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color.set_alpha(0.8 * (1.0 - progress));
+        }
+        if flash.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -1,21 +1,27 @@
+use crate::clock::UiTime;
 use crate::gameplay::Gameplay;
 use crate::gameplay::boomerang::BounceBoomerangEvent;
+use crate::gameplay::health_and_damage::HealthEvent;
+use crate::gameplay::kill_cam::KillCam;
+use crate::gameplay::player::Player;
+use crate::settings::{Settings, SettingsApplied};
 use crate::theme::film_grain::FilmGrainSettings;
 use bevy::app::{App, Startup, Update};
 use bevy::color::Color;
 use bevy::core_pipeline::bloom::Bloom;
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::ecs::schedule::IntoScheduleConfigs;
-use bevy::math::{Vec2, Vec3};
+use bevy::math::Vec3;
 use bevy::prelude::{
-    Camera, Camera3d, Commands, Component, Entity, EventReader, GizmoLineStyle, IsDefaultUiCamera,
-    Msaa, Name, PerspectiveProjection, Projection, Query, Real, Reflect, Res, Single, Time, Timer,
-    TimerMode, Transform, Window, With, Without, default,
+    Camera, Camera3d, Commands, Component, Event, EventReader, GizmoLineStyle, IsDefaultUiCamera,
+    Msaa, Name, PerspectiveProjection, Projection, Query, Real, Reflect, Res, Resource, Single,
+    Time, Transform, Trigger, With, Without, default,
+};
+use bevy::prelude::{
+    DefaultGizmoConfigGroup, GizmoConfigStore, ReflectComponent, ReflectResource, ResMut,
 };
-use bevy::prelude::{DefaultGizmoConfigGroup, GizmoConfigStore, ReflectComponent, ResMut};
 use bevy::render::camera::Exposure;
 use bevy::state::condition::in_state;
-use rand::{Rng, thread_rng};
 
 pub fn plugin(app: &mut App) {
     // systems
@@ -24,16 +30,25 @@ pub fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
+            remove_screen_shake,
             camera_follow,
-            start_shake_on_boomerang_bounce,
-            update_screen_shake,
-            tick_shake_timers,
+            add_trauma_on_boomerang_bounce,
+            apply_screen_shake,
         )
+            .chain()
             .run_if(in_state(Gameplay::Normal)),
     );
+    app.init_resource::<Trauma>()
+        .init_resource::<ScreenShakeSettings>();
+    app.add_observer(on_add_trauma)
+        .add_observer(add_trauma_on_player_hit)
+        .add_observer(apply_screen_shake_setting);
 
     // reflection
-    app.register_type::<CameraProperties>();
+    app.register_type::<CameraProperties>()
+        .register_type::<CameraZoomOut>()
+        .register_type::<Trauma>()
+        .register_type::<ScreenShakeSettings>();
 }
 
 #[derive(Component)]
@@ -43,12 +58,35 @@ pub struct SceneCamera;
 #[reflect(Component)]
 pub struct CameraFollowTarget;
 
+/// While the camera has this component, it stops following the [CameraFollowTarget], e.g. while a
+/// dev tool takes control of it.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct CameraDetached;
+
+/// Put this next to the [CameraFollowTarget] to pull the camera further back, e.g. 0.3 to get 30%
+/// more distance.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct CameraZoomOut(pub f32);
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
 pub struct CameraProperties {
     camera_follow_snappiness: f32,
 }
 
 const INITIAL_Z_OFFSET: f32 = 25.0;
+const INITIAL_HEIGHT: f32 = 40.0;
+/// How far follow targets can be from their center before the camera starts zooming out.
+const TARGET_SPREAD_BEFORE_ZOOM: f32 = 10.0;
+/// How far the camera moves from its targets towards the final kill of a throw, see [KillCam].
+const KILL_CAM_PULL: f32 = 0.5;
+/// How much closer the camera gets during the kill cam, 0.3 is 30% less distance.
+const KILL_CAM_ZOOM_IN: f32 = 0.3;
+
+/// Where the camera sits when it's centered on `target` without any zoom, e.g. for cutscenes.
+pub fn framing_position(target: Vec3) -> Vec3 {
+    Vec3::new(target.x, INITIAL_HEIGHT, target.z + INITIAL_Z_OFFSET)
+}
 
 pub fn spawn_camera(mut commands: Commands) {
     commands.spawn((
@@ -59,7 +97,7 @@ pub fn spawn_camera(mut commands: Commands) {
         CameraProperties {
             camera_follow_snappiness: 7.0,
         },
-        Transform::from_xyz(0., 40., INITIAL_Z_OFFSET).looking_at(Vec3::ZERO, Vec3::Y),
+        Transform::from_xyz(0., INITIAL_HEIGHT, INITIAL_Z_OFFSET).looking_at(Vec3::ZERO, Vec3::Y),
         Camera {
             hdr: true,
             clear_color: Color::srgb_u8(15, 9, 20).into(),
@@ -76,6 +114,7 @@ pub fn spawn_camera(mut commands: Commands) {
         Tonemapping::TonyMcMapface,
         Bloom::NATURAL,
         FilmGrainSettings::default(),
+        CameraShakeOffset::default(),
     ));
 }
 
@@ -89,25 +128,53 @@ fn setup_gizmos_config(mut config_store: ResMut<GizmoConfigStore>) {
 }
 
 fn camera_follow(
-    camera: Single<(&mut Transform, &CameraProperties), With<Camera>>,
-    target: Single<&Transform, (With<CameraFollowTarget>, Without<Camera>)>,
-    time: Res<Time<Real>>,
+    camera: Single<(&mut Transform, &CameraProperties), (With<Camera>, Without<CameraDetached>)>,
+    targets: Query<
+        (&Transform, Option<&CameraZoomOut>),
+        (With<CameraFollowTarget>, Without<Camera>),
+    >,
+    kill_cam: Res<KillCam>,
+    time: UiTime,
 ) -> bevy::prelude::Result {
-    let target_transform = target.into_inner();
     let (mut camera_transform, properties) = camera.into_inner();
 
+    // With multiple targets (local co-op), frame all of them
+    let target_count = targets.iter().count();
+    if target_count == 0 {
+        return Ok(());
+    }
+    let center = targets.iter().map(|(t, _)| t.translation).sum::<Vec3>() / target_count as f32;
+    let spread = targets
+        .iter()
+        .map(|(t, _)| t.translation.distance(center))
+        .fold(0.0, f32::max);
+    let spread_zoom = ((spread - TARGET_SPREAD_BEFORE_ZOOM) / TARGET_SPREAD_BEFORE_ZOOM).max(0.0);
+    let target_zoom = targets
+        .iter()
+        .filter_map(|(_, zoom)| zoom.map(|z| z.0))
+        .fold(0.0, f32::max);
+
+    // punching in on the final kill of a throw
+    let punch = kill_cam.strength();
+    let center = center.lerp(kill_cam.focus, punch * KILL_CAM_PULL);
+
+    // zooming out moves the camera back along its viewing direction
+    let zoom = 1.0 + target_zoom.max(spread_zoom) - punch * KILL_CAM_ZOOM_IN;
+    let z_offset = INITIAL_Z_OFFSET * zoom;
+    let height = INITIAL_HEIGHT * zoom;
+
     //calculate bounds
     let level_width = 200.0f32;
     let level_height = 50.0f32;
     let min_x = -level_width / 2.0;
     let max_x = level_width / 2.0;
-    let min_z = -level_height / 2.0 + INITIAL_Z_OFFSET;
-    let max_z = level_height / 2.0 + INITIAL_Z_OFFSET;
+    let min_z = -level_height / 2.0 + z_offset;
+    let max_z = level_height / 2.0 + z_offset;
 
     let bounded_target_position = Vec3::new(
-        target_transform.translation.x.clamp(min_x, max_x),
-        camera_transform.translation.y,
-        (target_transform.translation.z + INITIAL_Z_OFFSET).clamp(min_z, max_z),
+        center.x.clamp(min_x, max_x),
+        height,
+        (center.z + z_offset).clamp(min_z, max_z),
     );
 
     //smoothly interpolate camera position to target position
@@ -119,7 +186,7 @@ fn camera_follow(
     //and hard clam that camera's position if it is out of bounds
     camera_transform.translation = Vec3::new(
         translation.x.clamp(min_x, max_x),
-        camera_transform.translation.y,
+        translation.y,
         translation.z.clamp(min_z, max_z),
     );
 
@@ -130,71 +197,151 @@ fn camera_follow(
 // SCREEN SHAKE
 // ===============
 
-#[derive(Component, Debug, Reflect, Default)]
-struct ScreenShake {
-    intensity: f32, // 0.0 - 1.0
-    timer: Timer,
+/// Trigger this whenever something should shake the screen.
+/// How much each kind of event shakes things up is configured in [ScreenShakeSettings].
+#[derive(Event, Debug, Clone, Copy)]
+pub enum AddTrauma {
+    BoomerangBounce,
+    PlayerHit,
+    Explosion,
+    /// A one-off amount of trauma, from 0.0 to 1.0.
+    Custom(f32),
 }
 
-impl ScreenShake {
-    pub fn default() -> Self {
+/// How shaken up the camera currently is, from 0.0 to 1.0.
+/// Events stack trauma, which then decays over time. The camera shakes by trauma², so small
+/// bumps barely register while several big ones in a row really rattle the screen.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Trauma(f32);
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ScreenShakeSettings {
+    /// Turned off in the settings menu by players who don't like screen shake.
+    pub enabled: bool,
+    /// How much trauma is lost per second.
+    pub decay: f32,
+    /// How far the camera moves at full trauma, in world units.
+    pub max_offset: f32,
+    /// How far the camera rolls at full trauma, in radians.
+    pub max_roll: f32,
+    /// How fast the shake wobbles.
+    pub frequency: f32,
+    pub boomerang_bounce_trauma: f32,
+    pub player_hit_trauma: f32,
+    pub explosion_trauma: f32,
+}
+
+impl Default for ScreenShakeSettings {
+    fn default() -> Self {
         Self {
-            intensity: 0.01,
-            timer: Timer::from_seconds(0.1, TimerMode::Once),
+            enabled: true,
+            decay: 1.5,
+            max_offset: 1.0,
+            max_roll: 0.05,
+            frequency: 15.0,
+            boomerang_bounce_trauma: 0.2,
+            player_hit_trauma: 0.5,
+            explosion_trauma: 0.7,
+        }
+    }
+}
+
+fn apply_screen_shake_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut screen_shake: ResMut<ScreenShakeSettings>,
+) {
+    screen_shake.enabled = settings.screen_shake;
+}
+
+impl ScreenShakeSettings {
+    fn trauma_for(&self, event: AddTrauma) -> f32 {
+        match event {
+            AddTrauma::BoomerangBounce => self.boomerang_bounce_trauma,
+            AddTrauma::PlayerHit => self.player_hit_trauma,
+            AddTrauma::Explosion => self.explosion_trauma,
+            AddTrauma::Custom(amount) => amount,
         }
     }
 }
-fn start_shake_on_boomerang_bounce(
+
+/// The shake that was applied to the camera last frame, so it can be taken back out before the
+/// camera follows its targets again.
+#[derive(Component, Debug, Default)]
+struct CameraShakeOffset {
+    translation: Vec3,
+    roll: f32,
+}
+
+fn on_add_trauma(
+    trigger: Trigger<AddTrauma>,
+    settings: Res<ScreenShakeSettings>,
+    mut trauma: ResMut<Trauma>,
+) {
+    trauma.0 = (trauma.0 + settings.trauma_for(*trigger.event())).clamp(0.0, 1.0);
+}
+
+fn add_trauma_on_boomerang_bounce(
     mut event_reader: EventReader<BounceBoomerangEvent>,
     mut commands: Commands,
 ) {
     for _ in event_reader.read() {
-        commands.spawn((Name::new("ScreenShake"), ScreenShake::default()));
+        commands.trigger(AddTrauma::BoomerangBounce);
     }
 }
 
-fn update_screen_shake(
-    query: Query<&ScreenShake>,
-    mut camera_query: Single<&mut Transform, With<Camera>>,
-    windows: Query<&Window>,
+fn add_trauma_on_player_hit(
+    trigger: Trigger<HealthEvent>,
+    players: Query<(), With<Player>>,
+    mut commands: Commands,
 ) {
-    let mut rng = thread_rng();
-
-    // Get viewport dimensions for scaling
-    let viewport_size = windows
-        .single()
-        .map(|w| Vec2::new(w.width(), w.height()))
-        .unwrap_or(Vec2::new(1024.0, 768.0));
-    // Maximum shake offset as a small percentage of viewport (e.g., 1% of viewport size)
-    let max_shake_percentage = 0.01;
-    let max_offset = viewport_size * max_shake_percentage;
-
-    // we only care about one shake, maybe adjust this to pick the most intense one at any given time.
-    let Some(shake) = query.iter().next() else {
-        return;
-    };
-
-    // Calculate shake progress (1.0 at start, 0.0 at end for decay)
-    let progress = 1.0 - shake.timer.fraction();
-
-    // Generate random offset scaled by intensity and progress
-    let offset_x = rng.gen_range(-1.0..1.0) * max_offset.x * shake.intensity * progress;
-    let offset_z = rng.gen_range(-1.0..1.0) * max_offset.y * shake.intensity * progress;
-
-    let total_offset = Vec3::new(offset_x, 0.0, offset_z);
+    if players.contains(trigger.target()) {
+        commands.trigger(AddTrauma::PlayerHit);
+    }
+}
 
-    camera_query.translation += total_offset;
+fn remove_screen_shake(
+    camera: Single<
+        (&mut Transform, &mut CameraShakeOffset),
+        (With<Camera>, Without<CameraDetached>),
+    >,
+) {
+    let (mut transform, mut offset) = camera.into_inner();
+    transform.translation -= offset.translation;
+    transform.rotate_local_z(-offset.roll);
+    *offset = CameraShakeOffset::default();
 }
 
-fn tick_shake_timers(
-    time: Res<Time<Real>>,
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut ScreenShake)>,
+fn apply_screen_shake(
+    camera: Single<
+        (&mut Transform, &mut CameraShakeOffset),
+        (With<Camera>, Without<CameraDetached>),
+    >,
+    settings: Res<ScreenShakeSettings>,
+    mut trauma: ResMut<Trauma>,
+    time: UiTime,
 ) {
-    for (e, mut shake) in query.iter_mut() {
-        shake.timer.tick(time.delta());
-        if shake.timer.finished() {
-            commands.entity(e).despawn();
-        }
+    trauma.0 = (trauma.0 - settings.decay * time.delta_secs()).max(0.0);
+    if !settings.enabled || trauma.0 <= 0.0 {
+        return;
     }
+
+    let shake = trauma.0 * trauma.0;
+    let t = time.elapsed_secs() * settings.frequency;
+    let (mut transform, mut offset) = camera.into_inner();
+    offset.translation =
+        Vec3::new(smooth_noise(t, 0.0), 0.0, smooth_noise(t, 10.0)) * settings.max_offset * shake;
+    offset.roll = smooth_noise(t, 20.0) * settings.max_roll * shake;
+    transform.translation += offset.translation;
+    transform.rotate_local_z(offset.roll);
+}
+
+/// Cheap smooth noise in -1.0..1.0, different seeds give unrelated curves.
+fn smooth_noise(t: f32, seed: f32) -> f32 {
+    ((t + seed).sin() * 0.5
+        + (t * 2.3 + seed * 1.7).sin() * 0.3
+        + (t * 4.1 + seed * 2.9).sin() * 0.2)
+        .clamp(-1.0, 1.0)
 }
@@ -0,0 +1,198 @@
+//! Charged throws: holding the fire button winds up the next throw, releasing it lets go.
+//! The longer the wind-up, the faster and farther the boomerang flies, see
+//! [`BoomerangSettings::charge_seconds`]. A full charge also carries it on through its target to
+//! one more outlaw behind it. The price is time, outlaws don't stop shooting during the wind-up.
+//!
+//! The fire action is bound with a [`Release`](bevy_enhanced_input::prelude::Release) condition,
+//! so it starts when the button goes down and fires once it's let go. The throw itself happens in
+//! the boomerang module, this only keeps track of the charge, the gauge and the wind-up sound.
+
+use crate::audio::TimeDilatedPitch;
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::aim_mode::AimModeState;
+use crate::gameplay::ammo::{BoomerangInventory, ThrowCooldown, ThrowRejected};
+use crate::gameplay::boomerang::BoomerangSettings;
+use crate::gameplay::input::FireBoomerangAction;
+use bevy::color::palettes::css::{BLACK, GOLD, ORANGE_RED};
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Started;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<ChargingThrow>()
+        .init_resource::<ChargeThrowAssets>();
+    app.add_observer(start_charging).add_observer(stop_charging);
+    app.add_systems(
+        Update,
+        (charge_throws, update_charge_gauges).run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// The wind-up sound starts out this much lower and ends up this much higher than usual.
+const PITCH_RANGE: (f32, f32) = (0.6, 1.8);
+/// How far above the thrower the gauge sits, in world units.
+const GAUGE_HEIGHT: f32 = 2.5;
+const GAUGE_WIDTH: f32 = 60.0;
+
+/// Put on a thrower while they hold the fire button.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ChargingThrow {
+    /// How long the button has been held, in game time.
+    pub seconds: f32,
+}
+
+impl ChargingThrow {
+    /// From 0.0 (just pressed) to 1.0 (fully charged).
+    pub fn charge(&self, settings: &BoomerangSettings) -> f32 {
+        (self.seconds / settings.charge_seconds.max(f32::EPSILON)).min(1.0)
+    }
+}
+
+/// The gauge and the sound belonging to a thrower's [`ChargingThrow`].
+#[derive(Component, Debug)]
+struct ChargeGauge {
+    thrower: Entity,
+    sfx: Entity,
+}
+
+#[derive(Component, Debug)]
+struct ChargeGaugeFill;
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct ChargeThrowAssets {
+    #[dependency]
+    wind_up: Handle<AudioSource>,
+}
+
+impl FromWorld for ChargeThrowAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            wind_up: assets
+                .load("audio/sound_effects/boomerang_sfx/boomerang_loop_single_short.ogg"),
+        }
+    }
+}
+
+fn start_charging(
+    trigger: Trigger<Started<FireBoomerangAction>>,
+    throwers: Query<(Option<&BoomerangInventory>, Has<ThrowCooldown>)>,
+    aim_state: Res<State<AimModeState>>,
+    assets: Res<ChargeThrowAssets>,
+    mut commands: Commands,
+) {
+    // while aiming the same button confirms the painted throw instead, see `aim_mode`
+    if aim_state.get() == &AimModeState::Aiming {
+        return;
+    }
+    let thrower = trigger.target();
+    let Ok((inventory, cooling_down)) = throwers.get(thrower) else {
+        return;
+    };
+    if cooling_down {
+        return;
+    }
+    if !inventory.is_some_and(BoomerangInventory::can_throw) {
+        commands.entity(thrower).trigger(ThrowRejected);
+        return;
+    }
+
+    commands.entity(thrower).insert(ChargingThrow::default());
+    let sfx = commands
+        .spawn((
+            Name::new("Charge Throw SFX"),
+            AudioPlayer::new(assets.wind_up.clone()),
+            PlaybackSettings::LOOP,
+            TimeDilatedPitch(PITCH_RANGE.0),
+            StateScoped(Gameplay::Normal),
+        ))
+        .id();
+    commands.spawn((
+        Name::new("Charge Gauge"),
+        ChargeGauge { thrower, sfx },
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(GAUGE_WIDTH),
+            height: Val::Px(8.0),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor(BLACK.into()),
+        BackgroundColor(BLACK.with_alpha(0.5).into()),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+        children![(
+            ChargeGaugeFill,
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(GOLD.into()),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn stop_charging(
+    trigger: Trigger<OnRemove, ChargingThrow>,
+    gauges: Query<(Entity, &ChargeGauge)>,
+    mut commands: Commands,
+) {
+    for (entity, gauge) in &gauges {
+        if gauge.thrower == trigger.target() {
+            commands.entity(gauge.sfx).try_despawn();
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn charge_throws(time: GameTime, mut throwers: Query<&mut ChargingThrow>) {
+    for mut charging in &mut throwers {
+        charging.seconds += time.delta_secs();
+    }
+}
+
+fn update_charge_gauges(
+    settings: Res<BoomerangSettings>,
+    throwers: Query<(&ChargingThrow, &GlobalTransform)>,
+    mut gauges: Query<(&ChargeGauge, &mut Node, &mut Visibility, &Children)>,
+    mut fills: Query<
+        (&mut Node, &mut BackgroundColor),
+        (With<ChargeGaugeFill>, Without<ChargeGauge>),
+    >,
+    mut pitches: Query<&mut TimeDilatedPitch>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    for (gauge, mut node, mut visibility, children) in &mut gauges {
+        let Ok((charging, transform)) = throwers.get(gauge.thrower) else {
+            continue;
+        };
+        let charge = charging.charge(&settings);
+        if let Ok(mut pitch) = pitches.get_mut(gauge.sfx) {
+            pitch.0 = PITCH_RANGE.0.lerp(PITCH_RANGE.1, charge);
+        }
+        for child in children.iter() {
+            if let Ok((mut fill, mut color)) = fills.get_mut(child) {
+                fill.width = Val::Percent(charge * 100.0);
+                // turns red once it's fully charged
+                color.0 = if charge >= 1.0 { ORANGE_RED } else { GOLD }.into();
+            }
+        }
+
+        let Ok(screen_position) = camera.world_to_viewport(
+            camera_transform,
+            transform.translation() + Vec3::Y * GAUGE_HEIGHT,
+        ) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        node.left = Val::Px(screen_position.x - GAUGE_WIDTH / 2.0);
+        node.top = Val::Px(screen_position.y);
+        *visibility = Visibility::Inherited;
+    }
+}
@@ -0,0 +1,180 @@
+//! Innocent townsfolk wandering around the level.
+//! Place a [`CivilianSpawnPoint`] in the level scene. Civilians can't be targeted in aim mode, but a
+//! stray boomerang can still hit them: that costs bounty, and killing one fails the level if the
+//! [`LevelObjective`] is about protecting people.
+
+use crate::ai::wander::WanderBehavior;
+use crate::asset_tracking::LoadResource;
+use crate::audio::TimeDilatedPitch;
+use crate::gameplay::boomerang::BoomerangHittable;
+use crate::gameplay::health_and_damage::{DeathEvent, Health, HealthEvent, NoHealthHat};
+use crate::gameplay::score::ScoreEvent;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use avian3d::prelude::{
+    AngularDamping, AngularVelocity, Collider, CollisionLayers, LinearDamping, LinearVelocity,
+    LockedAxes, PhysicsLayer, RigidBody,
+};
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+
+#[cfg(doc)]
+use crate::gameplay::objective::LevelObjective;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<CivilianSpawnPoint>()
+        .register_type::<Civilian>()
+        .register_type::<CivilianAssets>()
+        .load_resource::<CivilianAssets>();
+    app.add_observer(spawn_civilians_on_spawn_points);
+}
+
+const HIT_PENALTY: f32 = -200.0;
+const KILL_PENALTY: f32 = -500.0;
+
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct Civilian;
+
+#[derive(Component, Reflect, Copy, Clone, Debug, PartialEq)]
+#[reflect(Default, Component)]
+pub struct CivilianSpawnPoint {
+    wander_radius: f32,
+    movement_speed: f32,
+}
+
+impl Default for CivilianSpawnPoint {
+    fn default() -> Self {
+        Self {
+            wander_radius: 6.0,
+            movement_speed: 1.5,
+        }
+    }
+}
+
+/// Fired whenever a civilian dies.
+#[derive(Event, Debug)]
+pub struct CivilianKilledEvent;
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct CivilianAssets {
+    #[dependency]
+    distress_sfx: Vec<Handle<AudioSource>>,
+}
+
+impl FromWorld for CivilianAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            distress_sfx: vec![
+                asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 1.ogg"),
+                asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 3.ogg"),
+                asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 5.ogg"),
+            ],
+        }
+    }
+}
+
+impl CivilianAssets {
+    fn random_distress_sfx(&self) -> Handle<AudioSource> {
+        let index = thread_rng().gen_range(0..self.distress_sfx.len());
+        self.distress_sfx[index].clone()
+    }
+}
+
+fn spawn_civilians_on_spawn_points(
+    trigger: Trigger<OnAdd, CivilianSpawnPoint>,
+    spawn_points: Query<(&Transform, &CivilianSpawnPoint)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) -> Result {
+    let (position, spawn_point) = spawn_points.get(trigger.target())?;
+
+    commands
+        .spawn((
+            Civilian,
+            Name::new("Civilian"),
+            *position,
+            Mesh3d(meshes.add(Capsule3d::default())),
+            MeshMaterial3d(materials.add(Color::srgb_u8(90, 140, 200))),
+            StateScoped(Screen::Gameplay),
+            WanderBehavior::new(
+                position.translation,
+                spawn_point.wander_radius,
+                spawn_point.movement_speed,
+            ),
+            BoomerangHittable,
+            Collider::capsule(0.5, 1.),
+            CollisionLayers::new(
+                GameLayer::Civilian,
+                [GameLayer::Player, GameLayer::Boomerang],
+            ),
+            LinearVelocity::ZERO,
+            LockedAxes::ROTATION_LOCKED.lock_translation_y(),
+            RigidBody::Kinematic,
+            // Survives one stray hit, the second one is fatal
+            Health(2),
+            NoHealthHat,
+        ))
+        .observe(on_civilian_hit)
+        .observe(on_civilian_death);
+
+    Ok(())
+}
+
+fn on_civilian_hit(
+    trigger: Trigger<HealthEvent>,
+    civilians: Query<(&Transform, &Health), With<Civilian>>,
+    assets: Res<CivilianAssets>,
+    mut commands: Commands,
+) {
+    let Ok((transform, health)) = civilians.get(trigger.target()) else {
+        return;
+    };
+    // Fatal hits are handled on death
+    if health.0 <= 0 {
+        return;
+    }
+    commands.trigger(ScoreEvent::AddScore(HIT_PENALTY, transform.translation));
+    commands.spawn((
+        Name::new("Civilian Distress SFX"),
+        AudioPlayer::new(assets.random_distress_sfx()),
+        PlaybackSettings::DESPAWN,
+        TimeDilatedPitch(1.4),
+    ));
+}
+
+fn on_civilian_death(
+    trigger: Trigger<DeathEvent>,
+    civilians: Query<&Transform, With<Civilian>>,
+    assets: Res<CivilianAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let Ok(transform) = civilians.get(trigger.target()) else {
+        return;
+    };
+    commands
+        .entity(trigger.target())
+        .remove::<WanderBehavior>()
+        .remove::<LockedAxes>()
+        .insert((
+            RigidBody::Dynamic,
+            MeshMaterial3d(materials.add(Color::srgb_u8(200, 210, 240))),
+            LinearVelocity::from(Vec3::new(3., 3., 3.)),
+            AngularVelocity::from(Vec3::new(3., 3., 3.)),
+            LinearDamping(0.5),
+            AngularDamping(0.5),
+            CollisionLayers::new(GameLayer::DeadEnemy, GameLayer::all_bits()),
+        ));
+    commands.trigger(ScoreEvent::AddScore(KILL_PENALTY, transform.translation));
+    commands.trigger(CivilianKilledEvent);
+    commands.spawn((
+        Name::new("Civilian Death SFX"),
+        AudioPlayer::new(assets.random_distress_sfx()),
+        PlaybackSettings::DESPAWN,
+        TimeDilatedPitch(1.2),
+    ));
+}
@@ -0,0 +1,140 @@
+//! Secret golden horseshoes hidden in the levels.
+//! Place a [`GoldenHorseshoe`] on an entity in the level scene. Found horseshoes are stored in the
+//! save file and won't show up again. Each one is worth a bounty bonus, and finding all of them in
+//! a level pays out an extra bonus and unlocks an achievement.
+
+use crate::asset_tracking::LoadResource;
+use crate::audio::sound_effect_non_dilated;
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::level::LevelAssets;
+use crate::gameplay::pickups::{PickedUpEvent, Pickup, RegisterPickup};
+use crate::gameplay::score::ScoreEvent;
+use crate::save::SaveData;
+use crate::theme::toast::Toast;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_pickup::<GoldenHorseshoe>()
+        .register_type::<CollectibleAssets>()
+        .load_resource::<CollectibleAssets>()
+        .init_resource::<LevelHorseshoes>();
+
+    app.add_systems(OnEnter(Gameplay::Normal), reset_level_horseshoes);
+    app.add_systems(Update, spin_horseshoes.run_if(in_state(Gameplay::Normal)));
+    app.add_observer(register_horseshoe)
+        .add_observer(collect_horseshoe);
+}
+
+const HORSESHOE_BOUNTY: f32 = 250.0;
+const ALL_HORSESHOES_BOUNTY: f32 = 1000.0;
+
+/// A hidden collectible. The `id` needs to be unique within its level, if left empty the entity's
+/// [`Name`] is used instead.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct GoldenHorseshoe {
+    pub id: String,
+}
+
+impl Pickup for GoldenHorseshoe {
+    const NAME: &'static str = "Golden Horseshoe";
+}
+
+/// How many horseshoes the current level contains, found or not.
+#[derive(Resource, Debug, Default)]
+struct LevelHorseshoes {
+    total: usize,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct CollectibleAssets {
+    #[dependency]
+    pickup_sfx: Handle<AudioSource>,
+}
+
+impl FromWorld for CollectibleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            pickup_sfx: asset_server.load("audio/sound_effects/boomerang_sfx/ding.ogg"),
+        }
+    }
+}
+
+fn reset_level_horseshoes(mut level_horseshoes: ResMut<LevelHorseshoes>) {
+    level_horseshoes.total = 0;
+}
+
+fn register_horseshoe(
+    trigger: Trigger<OnAdd, GoldenHorseshoe>,
+    mut horseshoes: Query<(&mut GoldenHorseshoe, Option<&Name>)>,
+    mut level_horseshoes: ResMut<LevelHorseshoes>,
+    level_assets: Res<LevelAssets>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    let Ok((mut horseshoe, name)) = horseshoes.get_mut(trigger.target()) else {
+        return;
+    };
+    if horseshoe.id.is_empty() {
+        horseshoe.id = name
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| format!("horseshoe_{}", level_horseshoes.total));
+    }
+
+    level_horseshoes.total += 1;
+    let progress = save_data.level_mut(level_assets.current_level);
+    progress.horseshoes_total = level_horseshoes.total;
+
+    if progress.horseshoes_found.contains(&horseshoe.id) {
+        commands.entity(trigger.target()).despawn();
+    }
+}
+
+fn spin_horseshoes(time: GameTime, mut horseshoes: Query<&mut Transform, With<GoldenHorseshoe>>) {
+    for mut transform in &mut horseshoes {
+        transform.rotate_y(time.delta_secs() * 2.0);
+    }
+}
+
+fn collect_horseshoe(
+    trigger: Trigger<PickedUpEvent>,
+    horseshoes: Query<(&GlobalTransform, &GoldenHorseshoe)>,
+    level_horseshoes: Res<LevelHorseshoes>,
+    level_assets: Res<LevelAssets>,
+    collectible_assets: Res<CollectibleAssets>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    let Ok((transform, horseshoe)) = horseshoes.get(trigger.target()) else {
+        return;
+    };
+    let position = transform.translation();
+
+    commands.spawn((
+        Name::new("Horseshoe SFX"),
+        sound_effect_non_dilated(collectible_assets.pickup_sfx.clone(), 0.0),
+    ));
+    commands.trigger(ScoreEvent::AddScore(HORSESHOE_BOUNTY, position));
+
+    let level = level_assets.current_level;
+    let progress = save_data.level_mut(level);
+    progress.horseshoes_found.insert(horseshoe.id.clone());
+    let found = progress.horseshoes_found.len();
+    commands.trigger(Toast::new(format!(
+        "Golden horseshoe {found}/{}",
+        level_horseshoes.total
+    )));
+
+    if found >= level_horseshoes.total {
+        commands.trigger(ScoreEvent::AddScore(ALL_HORSESHOES_BOUNTY, position));
+        if save_data.unlock_achievement(format!("all_horseshoes_level_{}", level + 1)) {
+            commands.trigger(Toast::new(format!(
+                "Achievement: Lucky Streak (all horseshoes in level {})",
+                level + 1
+            )));
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Local co-op. If two gamepads are connected when a level starts, a second player joins at the
+//! player spawn point. Player one keeps keyboard, mouse and the first gamepad, the partner plays on
+//! the second gamepad.
+//! The partner throws their own boomerangs straight ahead instead of painting paths, and has to hold
+//! their aim button to agree before player one's aim mode slows down time. Since they always throw
+//! from where they stand, they don't get a
+//! [`CurrentBoomerangThrowOrigin`](crate::gameplay::boomerang::CurrentBoomerangThrowOrigin) of their
+//! own, that one stays with player one's aim mode.
+//! Outlaws shoot at whichever player is closer, see [`crate::gameplay::enemy`].
+//! The camera frames both players (see [`crate::gameplay::camera`]).
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::aim_mode::SlowMoVeto;
+use crate::gameplay::ammo::{
+    BoomerangInventory, ConsumeBoomerangSlot, ThrowCooldown, ThrowRejected,
+};
+use crate::gameplay::boomerang::{BoomerangTargetKind, ThrowBoomerangEvent, get_raycast_target};
+use crate::gameplay::camera::CameraFollowTarget;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::{DeathEvent, Health};
+use crate::gameplay::input::{
+    PartnerActions, PartnerAimAction, PartnerMoveAction, PartnerThrowAction, PlayerActions,
+};
+use crate::gameplay::player::{MovementSettings, Player, WalkInput};
+use crate::gameplay::throwing_plane::ThrowingPlane;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use crate::theme::toast::Toast;
+use avian3d::prelude::{
+    CoefficientCombine, Collider, CollisionLayers, Friction, LockedAxes, RigidBody, SpatialQuery,
+};
+use bevy::prelude::*;
+use bevy_enhanced_input::events::{Completed, Started};
+use bevy_enhanced_input::prelude::{Actions, Fired};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<CoopPartner>()
+        .init_resource::<PartnerAgreesToSlowMo>();
+    app.add_observer(spawn_partner_with_player);
+    app.add_systems(
+        Update,
+        (assign_gamepads, update_slow_mo_veto).run_if(in_state(Gameplay::Normal)),
+    );
+    app.add_systems(OnExit(Gameplay::Normal), clear_slow_mo_veto);
+}
+
+/// How far ahead the partner throws their boomerang.
+const PARTNER_THROW_RANGE: f32 = 20.0;
+
+/// The second player in local co-op.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CoopPartner {
+    /// The direction the partner last moved in, boomerangs are thrown this way.
+    facing: Vec3,
+}
+
+impl Default for CoopPartner {
+    fn default() -> Self {
+        Self {
+            facing: Vec3::NEG_Z,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+struct PartnerAgreesToSlowMo(bool);
+
+fn spawn_partner_with_player(
+    trigger: Trigger<OnAdd, Player>,
+    players: Query<&Transform, With<Player>>,
+    gamepads: Query<(), With<Gamepad>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    if gamepads.iter().count() < 2 {
+        return;
+    }
+    let Ok(player_transform) = players.get(trigger.target()) else {
+        return;
+    };
+
+    commands
+        .spawn((
+            Name::new("Player 2"),
+            CoopPartner::default(),
+            Transform::from_translation(player_transform.translation + Vec3::X * 1.5),
+            Visibility::Inherited,
+            Mesh3d(meshes.add(Capsule3d::default())),
+            MeshMaterial3d(materials.add(Color::srgb_u8(0, 124, 124))),
+            StateScoped(Screen::Gameplay),
+            MovementSettings::default(),
+            CameraFollowTarget,
+            Actions::<PartnerActions>::default(),
+        ))
+        .insert((
+            Collider::capsule(0.5, 1.),
+            RigidBody::Dynamic,
+            LockedAxes::ROTATION_LOCKED.lock_translation_y(),
+            CollisionLayers::new(
+                GameLayer::Player,
+                [
+                    GameLayer::Enemy,
+                    GameLayer::Civilian,
+                    GameLayer::Bullet,
+                    GameLayer::Terrain,
+                    GameLayer::Default,
+                ],
+            ),
+            Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+        ))
+        .insert((Health::default(), BoomerangInventory::new(1)))
+        .observe(move_partner)
+        .observe(stop_partner)
+        .observe(partner_throw)
+        .observe(partner_agrees_to_slow_mo)
+        .observe(partner_disagrees_to_slow_mo)
+        .observe(on_partner_death);
+}
+
+/// Splits the gamepads between the players once the partner has joined.
+fn assign_gamepads(
+    new_partners: Query<Entity, Added<CoopPartner>>,
+    mut partner_actions: Query<&mut Actions<PartnerActions>>,
+    mut player_actions: Query<&mut Actions<PlayerActions>, With<Player>>,
+    gamepads: Query<Entity, With<Gamepad>>,
+) {
+    let Some(partner) = new_partners.iter().next() else {
+        return;
+    };
+    let mut gamepads: Vec<_> = gamepads.iter().collect();
+    gamepads.sort();
+    let [first, second, ..] = gamepads[..] else {
+        return;
+    };
+    if let Ok(mut actions) = player_actions.single_mut() {
+        actions.set_gamepad(first);
+    }
+    if let Ok(mut actions) = partner_actions.get_mut(partner) {
+        actions.set_gamepad(second);
+    }
+}
+
+fn move_partner(
+    trigger: Trigger<Fired<PartnerMoveAction>>,
+    mut partners: Query<(&mut WalkInput, &mut CoopPartner)>,
+    camera: Single<&Transform, With<Camera3d>>,
+) {
+    let Ok((mut walk_input, mut partner)) = partners.get_mut(trigger.target()) else {
+        return;
+    };
+    // Rotate input to be on the ground and aligned with camera, same as player one
+    let input = Vec3::new(trigger.value.x, 0.0, -trigger.value.y);
+    let direction = (camera.rotation * input).with_y(0.).normalize_or_zero();
+    if direction != Vec3::ZERO {
+        partner.facing = direction;
+    }
+    walk_input.0 = direction;
+}
+
+fn stop_partner(
+    trigger: Trigger<Completed<PartnerMoveAction>>,
+    mut partners: Query<&mut WalkInput, With<CoopPartner>>,
+) {
+    if let Ok(mut walk_input) = partners.get_mut(trigger.target()) {
+        walk_input.0 = Vec3::ZERO;
+    }
+}
+
+fn partner_throw(
+    trigger: Trigger<Started<PartnerThrowAction>>,
+    partners: Query<(
+        &GlobalTransform,
+        &CoopPartner,
+        &BoomerangInventory,
+        Has<ThrowCooldown>,
+    )>,
+    enemies: Query<(), With<Enemy>>,
+    spatial_query: SpatialQuery,
+    throwing_plane: Res<ThrowingPlane>,
+    mut event_writer: EventWriter<ThrowBoomerangEvent>,
+    mut commands: Commands,
+) {
+    let partner_entity = trigger.target();
+    let Ok((transform, partner, inventory, cooling_down)) = partners.get(partner_entity) else {
+        return;
+    };
+    if cooling_down {
+        return;
+    }
+    let Some(slot) = inventory.ready_slot() else {
+        commands.entity(partner_entity).trigger(ThrowRejected);
+        return;
+    };
+
+    let origin = transform.translation();
+    let Ok(hit) = get_raycast_target(
+        &spatial_query,
+        &throwing_plane,
+        origin + partner.facing * PARTNER_THROW_RANGE,
+        partner_entity,
+        origin,
+    ) else {
+        return;
+    };
+    let target = match hit.entity {
+        Some(enemy) if enemies.contains(enemy) => BoomerangTargetKind::Entity(enemy),
+        _ => BoomerangTargetKind::Position(hit.position),
+    };
+
+    event_writer.write(ThrowBoomerangEvent {
+        thrower_entity: partner_entity,
+        target: vec![target],
+        slot: Some(slot),
+        charge: 0.0,
+    });
+    commands
+        .entity(partner_entity)
+        .trigger(ConsumeBoomerangSlot(slot));
+}
+
+fn partner_agrees_to_slow_mo(
+    _trigger: Trigger<Started<PartnerAimAction>>,
+    mut agrees: ResMut<PartnerAgreesToSlowMo>,
+) {
+    agrees.0 = true;
+}
+
+fn partner_disagrees_to_slow_mo(
+    _trigger: Trigger<Completed<PartnerAimAction>>,
+    mut agrees: ResMut<PartnerAgreesToSlowMo>,
+) {
+    agrees.0 = false;
+}
+
+fn update_slow_mo_veto(
+    partners: Query<(), With<CoopPartner>>,
+    agrees: Res<PartnerAgreesToSlowMo>,
+    mut veto: ResMut<SlowMoVeto>,
+) {
+    let vetoed = !partners.is_empty() && !agrees.0;
+    if veto.0 != vetoed {
+        veto.0 = vetoed;
+    }
+}
+
+fn clear_slow_mo_veto(mut veto: ResMut<SlowMoVeto>, mut agrees: ResMut<PartnerAgreesToSlowMo>) {
+    veto.0 = false;
+    agrees.0 = false;
+}
+
+/// The partner going down doesn't end the level, player one carries on alone.
+fn on_partner_death(trigger: Trigger<DeathEvent>, mut commands: Commands) {
+    commands.entity(trigger.target()).despawn();
+    commands.trigger(Toast::new("Player 2 is down!"));
+}
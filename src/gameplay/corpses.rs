@@ -0,0 +1,170 @@
+//! Keeps dead outlaws from bogging down big fights. Every outlaw turns into a ragdoll when it dies,
+//! and every ragdoll is a dynamic body the physics engine has to keep simulating.
+//!
+//! Only so many ragdolls are allowed at once, see [`CorpseBudget`]. Past that, the oldest ones
+//! freeze in whatever pose they're in, as static bodies. Past [`CorpseBudget::max_corpses`], the
+//! oldest frozen ones disappear in a puff of smoke.
+//! How many ragdolls are allowed follows the frame time: it shrinks while frames take longer than
+//! [`CorpseBudget::target_frame_seconds`], and grows back once they're quick again.
+
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::DeathEvent;
+use crate::screens::Screen;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use avian3d::prelude::{AngularVelocity, LinearVelocity, RigidBody};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<CorpseBudget>()
+        .init_resource::<CorpseBudget>()
+        .init_resource::<RagdollAllowance>();
+    app.add_observer(mark_corpse);
+    app.add_systems(OnEnter(Screen::Gameplay), reset_allowance);
+    app.add_systems(
+        Update,
+        (adjust_allowance, enforce_corpse_budget)
+            .chain()
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How quickly the measured frame time follows the actual one, higher is quicker.
+const FRAME_TIME_SMOOTHING: f32 = 2.0;
+/// How many ragdolls per second the allowance changes by while frames are too slow or quick enough.
+const ALLOWANCE_RATE: f32 = 4.0;
+/// Frames have to be this much quicker than the target before more ragdolls are allowed again, so
+/// the allowance doesn't flip back and forth right at the target.
+const HEADROOM: f32 = 0.8;
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CorpseBudget {
+    /// Never fewer ragdolls than this, no matter how slow the frames get.
+    pub min_ragdolls: usize,
+    /// Never more ragdolls than this, no matter how quick the frames are.
+    pub max_ragdolls: usize,
+    /// Ragdolls and frozen corpses together.
+    pub max_corpses: usize,
+    /// Frames taking longer than this mean fewer ragdolls.
+    pub target_frame_seconds: f32,
+}
+
+impl Default for CorpseBudget {
+    fn default() -> Self {
+        // the web build runs physics on a single thread
+        let max_ragdolls = if cfg!(target_family = "wasm") { 6 } else { 16 };
+        Self {
+            min_ragdolls: 2,
+            max_ragdolls,
+            max_corpses: 40,
+            target_frame_seconds: 1.0 / 50.0,
+        }
+    }
+}
+
+/// Outlaws that bit the dust, numbered in the order they did.
+#[derive(Component, Debug)]
+pub struct Corpse(u64);
+
+/// A corpse that doesn't move anymore.
+#[derive(Component, Debug)]
+pub struct Frozen;
+
+/// How many ragdolls are allowed right now, and the smoothed frame time that decided it.
+#[derive(Resource, Debug)]
+struct RagdollAllowance {
+    ragdolls: f32,
+    frame_seconds: f32,
+}
+
+impl Default for RagdollAllowance {
+    fn default() -> Self {
+        let budget = CorpseBudget::default();
+        Self {
+            ragdolls: budget.max_ragdolls as f32,
+            frame_seconds: budget.target_frame_seconds,
+        }
+    }
+}
+
+fn mark_corpse(
+    trigger: Trigger<DeathEvent>,
+    enemies: Query<(), With<Enemy>>,
+    mut died: Local<u64>,
+    mut commands: Commands,
+) {
+    if enemies.contains(trigger.target()) {
+        commands.entity(trigger.target()).insert(Corpse(*died));
+        *died += 1;
+    }
+}
+
+fn reset_allowance(budget: Res<CorpseBudget>, mut allowance: ResMut<RagdollAllowance>) {
+    allowance.ragdolls = budget.max_ragdolls as f32;
+    allowance.frame_seconds = budget.target_frame_seconds;
+}
+
+fn adjust_allowance(
+    // real time, slow motion doesn't make frames any cheaper
+    time: UiTime,
+    budget: Res<CorpseBudget>,
+    mut allowance: ResMut<RagdollAllowance>,
+) {
+    let delta = time.delta_secs();
+    allowance.frame_seconds = allowance
+        .frame_seconds
+        .lerp(delta, (FRAME_TIME_SMOOTHING * delta).min(1.0));
+    let change = if allowance.frame_seconds > budget.target_frame_seconds {
+        -ALLOWANCE_RATE * delta
+    } else if allowance.frame_seconds < budget.target_frame_seconds * HEADROOM {
+        ALLOWANCE_RATE * delta
+    } else {
+        0.0
+    };
+    allowance.ragdolls = (allowance.ragdolls + change).clamp(
+        budget.min_ragdolls as f32,
+        budget.max_ragdolls.max(budget.min_ragdolls) as f32,
+    );
+}
+
+fn enforce_corpse_budget(
+    budget: Res<CorpseBudget>,
+    allowance: Res<RagdollAllowance>,
+    corpses: Query<(Entity, &Corpse, &GlobalTransform, Has<Frozen>)>,
+    mut commands: Commands,
+) {
+    let mut ragdolls: Vec<_> = corpses
+        .iter()
+        .filter(|(.., frozen)| !frozen)
+        .map(|(entity, corpse, ..)| (corpse.0, entity))
+        .collect();
+    let allowed = allowance.ragdolls.floor() as usize;
+    if ragdolls.len() > allowed {
+        ragdolls.sort_unstable();
+        for (_, entity) in &ragdolls[..ragdolls.len() - allowed] {
+            commands.entity(*entity).insert((
+                Frozen,
+                RigidBody::Static,
+                LinearVelocity::ZERO,
+                AngularVelocity::ZERO,
+            ));
+        }
+    }
+
+    let total = corpses.iter().count();
+    if total <= budget.max_corpses {
+        return;
+    }
+    let mut frozen: Vec<_> = corpses
+        .iter()
+        .filter(|(.., frozen)| *frozen)
+        .map(|(entity, corpse, transform, _)| (corpse.0, entity, transform.translation()))
+        .collect();
+    frozen.sort_unstable_by_key(|(died, ..)| *died);
+    for (_, entity, position) in frozen.into_iter().take(total - budget.max_corpses) {
+        commands.trigger(SpawnVfxEvent::new(Vfx::Puff, position));
+        commands.entity(entity).despawn();
+    }
+}
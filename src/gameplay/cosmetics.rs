@@ -0,0 +1,242 @@
+//! Cosmetics: what the player looks like. Both the [`PlayerHat`] and the [`PlayerSkin`] are
+//! unlocked by playing, picked in the wardrobe on the title screen and kept in the [`SaveData`].
+//! They're put on the player when it spawns, the hat by swapping the stetson for meshes of its own
+//! and the skin by giving the player a different material.
+
+use crate::gameplay::health_and_damage::HealthUi;
+use crate::gameplay::player::Player;
+use crate::save::SaveData;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(dress_player).add_observer(paint_player);
+}
+
+/// What the player picked in the wardrobe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cosmetics {
+    pub hat: PlayerHat,
+    pub skin: PlayerSkin,
+}
+
+/// What the player wears on their head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum PlayerHat {
+    #[default]
+    Stetson,
+    Bowler,
+    TopHat,
+    Sombrero,
+}
+
+impl PlayerHat {
+    pub const ALL: [PlayerHat; 4] = [
+        PlayerHat::Stetson,
+        PlayerHat::Bowler,
+        PlayerHat::TopHat,
+        PlayerHat::Sombrero,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlayerHat::Stetson => "Stetson",
+            PlayerHat::Bowler => "Bowler",
+            PlayerHat::TopHat => "Top Hat",
+            PlayerHat::Sombrero => "Sombrero",
+        }
+    }
+
+    /// How many hats have to be collected before this one can be worn.
+    pub fn hats_needed(self) -> u32 {
+        match self {
+            PlayerHat::Stetson => 0,
+            PlayerHat::Bowler => 10,
+            PlayerHat::TopHat => 25,
+            PlayerHat::Sombrero => 50,
+        }
+    }
+
+    pub fn is_unlocked(self, save_data: &SaveData) -> bool {
+        save_data.hats_collected >= self.hats_needed()
+    }
+
+    /// The next unlocked hat after this one, wrapping around.
+    pub fn next(self, save_data: &SaveData) -> Self {
+        self.cycle(save_data, 1)
+    }
+
+    /// The previous unlocked hat before this one, wrapping around.
+    pub fn previous(self, save_data: &SaveData) -> Self {
+        self.cycle(save_data, Self::ALL.len() - 1)
+    }
+
+    fn cycle(self, save_data: &SaveData, step: usize) -> Self {
+        cycle(&Self::ALL, self, step, |hat| hat.is_unlocked(save_data))
+    }
+
+    /// The meshes the hat is built from and where they go. Empty for the stetson, which is a
+    /// model of its own.
+    fn parts(self) -> Vec<(Mesh, Vec3)> {
+        match self {
+            PlayerHat::Stetson => Vec::new(),
+            PlayerHat::Bowler => vec![
+                (Cylinder::new(0.6, 0.05).into(), Vec3::ZERO),
+                (Sphere::new(0.42).into(), Vec3::Y * 0.05),
+            ],
+            PlayerHat::TopHat => vec![
+                (Cylinder::new(0.7, 0.05).into(), Vec3::ZERO),
+                (Cylinder::new(0.42, 0.9).into(), Vec3::Y * 0.45),
+            ],
+            PlayerHat::Sombrero => vec![
+                (Cylinder::new(1.2, 0.05).into(), Vec3::ZERO),
+                (Cone::new(0.5, 0.7).into(), Vec3::Y * 0.35),
+            ],
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            PlayerHat::Stetson | PlayerHat::Bowler => Color::srgb_u8(70, 45, 30),
+            PlayerHat::TopHat => Color::srgb_u8(25, 25, 30),
+            PlayerHat::Sombrero => Color::srgb_u8(210, 180, 110),
+        }
+    }
+}
+
+/// The color of the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum PlayerSkin {
+    #[default]
+    Drifter,
+    Dusty,
+    Midnight,
+    Gilded,
+}
+
+impl PlayerSkin {
+    pub const ALL: [PlayerSkin; 4] = [
+        PlayerSkin::Drifter,
+        PlayerSkin::Dusty,
+        PlayerSkin::Midnight,
+        PlayerSkin::Gilded,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlayerSkin::Drifter => "Drifter",
+            PlayerSkin::Dusty => "Dusty",
+            PlayerSkin::Midnight => "Midnight",
+            PlayerSkin::Gilded => "Gilded",
+        }
+    }
+
+    /// What has to be done before this skin can be worn.
+    pub fn unlock_hint(self) -> &'static str {
+        match self {
+            PlayerSkin::Drifter => "",
+            PlayerSkin::Dusty => "Collect 5 hats",
+            PlayerSkin::Midnight => "Finish the campaign",
+            PlayerSkin::Gilded => "Find every horseshoe in a level",
+        }
+    }
+
+    pub fn is_unlocked(self, save_data: &SaveData) -> bool {
+        match self {
+            PlayerSkin::Drifter => true,
+            PlayerSkin::Dusty => save_data.hats_collected >= 5,
+            PlayerSkin::Midnight => save_data.campaign_finished,
+            PlayerSkin::Gilded => save_data
+                .achievements
+                .iter()
+                .any(|achievement| achievement.starts_with("all_horseshoes_level_")),
+        }
+    }
+
+    /// The next unlocked skin after this one, wrapping around.
+    pub fn next(self, save_data: &SaveData) -> Self {
+        self.cycle(save_data, 1)
+    }
+
+    /// The previous unlocked skin before this one, wrapping around.
+    pub fn previous(self, save_data: &SaveData) -> Self {
+        self.cycle(save_data, Self::ALL.len() - 1)
+    }
+
+    fn cycle(self, save_data: &SaveData, step: usize) -> Self {
+        cycle(&Self::ALL, self, step, |skin| skin.is_unlocked(save_data))
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            PlayerSkin::Drifter => Color::srgb_u8(124, 124, 0),
+            PlayerSkin::Dusty => Color::srgb_u8(170, 130, 90),
+            PlayerSkin::Midnight => Color::srgb_u8(40, 40, 70),
+            PlayerSkin::Gilded => Color::srgb_u8(230, 180, 40),
+        }
+    }
+}
+
+/// Steps through `all` from `current` until it finds one that's unlocked, `step` at a time.
+/// Ends up back at `current` if nothing else is.
+fn cycle<T: Copy + PartialEq>(
+    all: &[T],
+    current: T,
+    step: usize,
+    is_unlocked: impl Fn(T) -> bool,
+) -> T {
+    let index = all.iter().position(|item| *item == current).unwrap_or(0);
+    (1..=all.len())
+        .map(|offset| all[(index + offset * step) % all.len()])
+        .find(|item| is_unlocked(*item))
+        .unwrap_or(current)
+}
+
+/// Swaps the stetson over the player's head for the hat they picked.
+fn dress_player(
+    trigger: Trigger<OnAdd, HealthUi>,
+    hats: Query<&HealthUi>,
+    players: Query<(), With<Player>>,
+    save_data: Res<SaveData>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let Ok(hat) = hats.get(trigger.target()) else {
+        return;
+    };
+    let worn = save_data.cosmetics.hat;
+    if !players.contains(hat.0) || worn == PlayerHat::Stetson || !worn.is_unlocked(&save_data) {
+        return;
+    }
+    let material = materials.add(worn.color());
+    commands
+        .entity(trigger.target())
+        .remove::<SceneRoot>()
+        .with_children(|hat| {
+            for (mesh, offset) in worn.parts() {
+                hat.spawn((
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_translation(offset),
+                ));
+            }
+        });
+}
+
+/// Gives the player the color of the skin they picked.
+fn paint_player(
+    trigger: Trigger<OnAdd, Player>,
+    save_data: Res<SaveData>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let skin = save_data.cosmetics.skin;
+    if skin == PlayerSkin::Drifter || !skin.is_unlocked(&save_data) {
+        return;
+    }
+    commands
+        .entity(trigger.target())
+        .insert(MeshMaterial3d(materials.add(skin.color())));
+}
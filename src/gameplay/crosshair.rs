@@ -0,0 +1,178 @@
+//! The reticle that follows the cursor while in aim mode. It sits open around the cursor and
+//! snaps shut whenever an enemy gets painted, then slowly opens up again.
+
+use crate::asset_tracking::LoadResource;
+use crate::clock::UiTime;
+use crate::gameplay::aim_mode::{AimModeState, PlayEnemyTargetedSound};
+use crate::gameplay::mouse_position::MousePosition;
+use crate::settings::{Settings, SettingsApplied};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<CrosshairStyle>()
+        .register_type::<CrosshairStyle>()
+        .register_type::<CrosshairAssets>()
+        .load_resource::<CrosshairAssets>();
+    app.add_systems(OnEnter(AimModeState::Aiming), spawn_crosshair);
+    app.add_systems(
+        Update,
+        update_crosshair.run_if(in_state(AimModeState::Aiming)),
+    );
+    app.add_observer(snap_crosshair_shut)
+        .add_observer(apply_crosshair_setting);
+}
+
+/// Size of the reticle in pixels when it's fully shut.
+const CROSSHAIR_SIZE: f32 = 48.0;
+/// How much bigger the reticle is while it's open.
+const OPEN_SCALE: f32 = 1.5;
+/// How long it takes to open up again after snapping shut.
+const REOPEN_SECONDS: f32 = 0.35;
+
+const OPEN_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+const SHUT_COLOR: Color = Color::srgb(0.9, 0.1, 0.1);
+
+/// Which reticle to show, picked in the settings menu.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum CrosshairStyle {
+    #[default]
+    Ring,
+    Brackets,
+    Dot,
+}
+
+impl CrosshairStyle {
+    pub fn next(self) -> Self {
+        match self {
+            CrosshairStyle::Ring => CrosshairStyle::Brackets,
+            CrosshairStyle::Brackets => CrosshairStyle::Dot,
+            CrosshairStyle::Dot => CrosshairStyle::Ring,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            CrosshairStyle::Ring => CrosshairStyle::Dot,
+            CrosshairStyle::Brackets => CrosshairStyle::Ring,
+            CrosshairStyle::Dot => CrosshairStyle::Brackets,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CrosshairStyle::Ring => "Ring",
+            CrosshairStyle::Brackets => "Brackets",
+            CrosshairStyle::Dot => "Dot",
+        }
+    }
+}
+
+fn apply_crosshair_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut style: ResMut<CrosshairStyle>,
+) {
+    *style = settings.crosshair;
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CrosshairAssets {
+    #[dependency]
+    ring: Handle<Image>,
+    #[dependency]
+    brackets: Handle<Image>,
+    #[dependency]
+    dot: Handle<Image>,
+}
+
+impl CrosshairAssets {
+    fn image(&self, style: CrosshairStyle) -> Handle<Image> {
+        match style {
+            CrosshairStyle::Ring => self.ring.clone(),
+            CrosshairStyle::Brackets => self.brackets.clone(),
+            CrosshairStyle::Dot => self.dot.clone(),
+        }
+    }
+}
+
+impl FromWorld for CrosshairAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            ring: assets.load("images/crosshair/ring.png"),
+            brackets: assets.load("images/crosshair/brackets.png"),
+            dot: assets.load("images/crosshair/dot.png"),
+        }
+    }
+}
+
+#[derive(Component, Debug, Default)]
+struct Crosshair {
+    /// 1.0 right after an enemy got painted, going back down to 0.0 as the reticle opens up.
+    shut: f32,
+}
+
+fn spawn_crosshair(
+    style: Res<CrosshairStyle>,
+    assets: Res<CrosshairAssets>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Name::new("Crosshair"),
+        Crosshair::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(CROSSHAIR_SIZE * OPEN_SCALE),
+            height: Val::Px(CROSSHAIR_SIZE * OPEN_SCALE),
+            ..default()
+        },
+        ImageNode::new(assets.image(*style)).with_color(OPEN_COLOR),
+        // hidden until it knows where the cursor is
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(AimModeState::Aiming),
+    ));
+}
+
+fn snap_crosshair_shut(
+    _trigger: Trigger<PlayEnemyTargetedSound>,
+    mut crosshair: Query<&mut Crosshair>,
+) {
+    for mut crosshair in &mut crosshair {
+        crosshair.shut = 1.0;
+    }
+}
+
+fn update_crosshair(
+    time: UiTime,
+    mouse_position: Res<MousePosition>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    crosshair: Single<(&mut Crosshair, &mut Node, &mut ImageNode, &mut Visibility)>,
+) {
+    let (mut crosshair, mut node, mut image, mut visibility) = crosshair.into_inner();
+    let (camera, camera_transform) = camera.into_inner();
+
+    // project the point the boomerang is actually aimed at, not the raw cursor
+    let Some(screen_position) = mouse_position
+        .boomerang_throwing_plane
+        .and_then(|aim| camera.world_to_viewport(camera_transform, aim).ok())
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    crosshair.shut = (crosshair.shut - time.delta_secs() / REOPEN_SECONDS).max(0.0);
+    // ease out, so it snaps shut fast and lingers before opening up fully
+    let shut = crosshair.shut * crosshair.shut;
+    let size = CROSSHAIR_SIZE * (OPEN_SCALE + (1.0 - OPEN_SCALE) * shut);
+
+    node.width = Val::Px(size);
+    node.height = Val::Px(size);
+    node.left = Val::Px(screen_position.x - size / 2.0);
+    node.top = Val::Px(screen_position.y - size / 2.0);
+    image.color = OPEN_COLOR.mix(&SHUT_COLOR, shut);
+    *visibility = Visibility::Inherited;
+}
@@ -0,0 +1,115 @@
+//! Arcs at the edge of the screen that point towards whatever just hurt the player, so shooters
+//! outside of the view don't go unnoticed. Every hit with a known source (see [`HealthEvent`]) gets
+//! its own arc, which fades away over [`FADE_SECONDS`].
+
+use crate::clock::UiTime;
+use crate::gameplay::health_and_damage::HealthEvent;
+use crate::gameplay::player::Player;
+use crate::screens::Screen;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(show_damage_indicator);
+    app.add_systems(
+        Update,
+        update_damage_indicators.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+const FADE_SECONDS: f32 = 1.0;
+const ARC_WIDTH: f32 = 180.0;
+const ARC_HEIGHT: f32 = 40.0;
+const ARC_THICKNESS: f32 = 6.0;
+/// How far the arcs are from the edge of the screen, as a fraction of half its size.
+const EDGE_INSET: f32 = 0.85;
+
+const ARC_COLOR: Color = Color::srgb(0.9, 0.1, 0.05);
+
+#[derive(Component, Debug)]
+struct DamageIndicator {
+    source: Vec3,
+    fade: Timer,
+}
+
+fn show_damage_indicator(
+    trigger: Trigger<HealthEvent>,
+    players: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let HealthEvent::Damage(_, _, Some(source)) = trigger.event() else {
+        return;
+    };
+    if !players.contains(trigger.target()) {
+        return;
+    }
+    commands.spawn((
+        Name::new("Damage Indicator"),
+        DamageIndicator {
+            source: *source,
+            fade: Timer::from_seconds(FADE_SECONDS, TimerMode::Once),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(ARC_WIDTH),
+            height: Val::Px(ARC_HEIGHT),
+            border: UiRect::top(Val::Px(ARC_THICKNESS)),
+            ..default()
+        },
+        BorderColor(ARC_COLOR),
+        BorderRadius::top(Val::Percent(50.0)),
+        // hidden until it's been turned towards the source
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn update_damage_indicators(
+    time: UiTime,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    player: Option<Single<&GlobalTransform, With<Player>>>,
+    mut indicators: Query<(
+        Entity,
+        &mut DamageIndicator,
+        &mut Node,
+        &mut Transform,
+        &mut BorderColor,
+        &mut Visibility,
+    )>,
+    mut commands: Commands,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    let Some(screen_size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let player_position = player.map(|player| player.translation());
+    // the screen's up and right, flattened onto the ground
+    let up = camera_transform.up().with_y(0.0).normalize_or_zero();
+    let right = camera_transform.right().with_y(0.0).normalize_or_zero();
+
+    for (entity, mut indicator, mut node, mut transform, mut color, mut visibility) in
+        &mut indicators
+    {
+        if indicator.fade.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let Some(player_position) = player_position else {
+            continue;
+        };
+        let offset = (indicator.source - player_position).with_y(0.0);
+        if offset.length_squared() < f32::EPSILON {
+            continue;
+        }
+        // clockwise from the top of the screen
+        let angle = offset.dot(right).atan2(offset.dot(up));
+        let half_size = screen_size / 2.0 * EDGE_INSET;
+        let center = screen_size / 2.0 + Vec2::new(angle.sin(), -angle.cos()) * half_size;
+        node.left = Val::Px(center.x - ARC_WIDTH / 2.0);
+        node.top = Val::Px(center.y - ARC_HEIGHT / 2.0);
+        // the UI's y points down, so this turns clockwise too
+        transform.rotation = Quat::from_rotation_z(angle);
+        color.0 = ARC_COLOR.with_alpha(indicator.fade.fraction_remaining());
+        *visibility = Visibility::Inherited;
+    }
+}
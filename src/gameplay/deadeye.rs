@@ -0,0 +1,67 @@
+//! Deadeye shots: in aim mode, outlaws' bullets can be painted like any other target, as long as
+//! the cursor gets right onto them. A boomerang that gets to a painted bullet in time knocks it
+//! back the way it came, where it can hit whoever fired it, and pays a bounty for the trick.
+//!
+//! Painting bullets is up to [`crate::gameplay::aim_mode`], boomerangs lead them so they actually
+//! meet, see [`crate::gameplay::boomerang`].
+
+use crate::gameplay::boomerang::BoomerangArrivedEvent;
+use crate::gameplay::bullets::{Bullet, BulletOrigin};
+use crate::gameplay::score::ScoreEvent;
+use crate::physics_layers::GameLayer;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use crate::theme::toast::Toast;
+use avian3d::prelude::{CollisionLayers, LinearVelocity};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Deflected>();
+    app.add_observer(deflect_bullet);
+}
+
+/// Extra bounty for every bullet knocked back, on top of whatever it hits afterwards.
+const DEADEYE_BOUNTY: f32 = 75.0;
+/// How close a boomerang has to get to a painted bullet to knock it back.
+const DEFLECT_REACH: f32 = 1.5;
+
+/// A bullet a boomerang knocked back. It can't hurt players anymore and can't be painted again.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Deflected;
+
+fn deflect_bullet(
+    trigger: Trigger<BoomerangArrivedEvent>,
+    mut bullets: Query<
+        (&mut Transform, &mut LinearVelocity, &mut BulletOrigin),
+        (With<Bullet>, Without<Deflected>),
+    >,
+    mut commands: Commands,
+) {
+    let Ok((mut transform, mut velocity, mut origin)) = bullets.get_mut(trigger.target()) else {
+        return;
+    };
+    let position = trigger.event().position;
+    // the boomerang flew to where it expected the bullet, it may have missed it by a bit
+    if transform.translation.distance(position) > DEFLECT_REACH {
+        return;
+    }
+
+    // straight back at whoever fired it
+    let direction = (origin.0 - transform.translation)
+        .with_y(0.0)
+        .normalize_or(-velocity.0.normalize_or_zero());
+    velocity.0 = direction * velocity.0.length();
+    transform.look_to(direction, Vec3::Y);
+    origin.0 = transform.translation;
+    commands.entity(trigger.target()).insert((
+        Deflected,
+        CollisionLayers::new(
+            GameLayer::Bullet,
+            [GameLayer::Terrain, GameLayer::Enemy, GameLayer::Default],
+        ),
+    ));
+
+    commands.trigger(ScoreEvent::AddScore(DEADEYE_BOUNTY, position));
+    commands.trigger(SpawnVfxEvent::new(Vfx::Sparks, position));
+    commands.trigger(Toast::new("Deadeye!"));
+}
@@ -0,0 +1,100 @@
+//! What it looks like when an outlaw bites the dust. There's a separate effect for every
+//! [`ViolenceLevel`], picked in the settings menu:
+//! - [`ViolenceLevel::Comic`] (the default): a cloud of dust, and the outlaw keels over
+//! - [`ViolenceLevel::OverTheTop`]: the outlaw goes flying, in a burst of confetti and hats
+
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::DeathEvent;
+use crate::settings::{Settings, SettingsApplied};
+use crate::theme::particles::{SpawnConfettiEvent, SpawnVfxEvent, Vfx};
+use avian3d::prelude::{AngularVelocity, LinearVelocity};
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ViolenceLevel>()
+        .register_type::<ViolenceLevel>();
+    app.add_observer(apply_violence_setting)
+        .add_observer(play_death_fx);
+}
+
+/// How far outlaws fly when they get launched.
+const LAUNCH_SPEED: f32 = 12.0;
+const LAUNCH_SPIN: f32 = 15.0;
+
+/// How graphic deaths are, picked in the settings menu.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum ViolenceLevel {
+    #[default]
+    Comic,
+    OverTheTop,
+}
+
+impl ViolenceLevel {
+    pub fn next(self) -> Self {
+        match self {
+            ViolenceLevel::Comic => ViolenceLevel::OverTheTop,
+            ViolenceLevel::OverTheTop => ViolenceLevel::Comic,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        // only two of them for now
+        self.next()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ViolenceLevel::Comic => "Comic",
+            ViolenceLevel::OverTheTop => "Over the Top",
+        }
+    }
+}
+
+fn apply_violence_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut violence: ResMut<ViolenceLevel>,
+) {
+    *violence = settings.violence;
+}
+
+/// Enemies are already ragdolls once they're dead, see the enemy death handling. This only decides
+/// how they get sent off.
+fn play_death_fx(
+    trigger: Trigger<DeathEvent>,
+    enemies: Query<&GlobalTransform, With<Enemy>>,
+    violence: Res<ViolenceLevel>,
+    mut commands: Commands,
+) {
+    let Ok(transform) = enemies.get(trigger.target()) else {
+        return;
+    };
+    let position = transform.translation();
+    match *violence {
+        ViolenceLevel::Comic => comic_death(trigger.target(), position, &mut commands),
+        ViolenceLevel::OverTheTop => over_the_top_death(trigger.target(), position, &mut commands),
+    }
+}
+
+fn comic_death(enemy: Entity, position: Vec3, commands: &mut Commands) {
+    commands.trigger(SpawnVfxEvent::new(Vfx::DeathPoof, position));
+    // just a little nudge to tip them over
+    let side = Vec3::new(thread_rng().gen_range(-1.0..1.0), 0.0, 1.0).normalize();
+    commands
+        .entity(enemy)
+        .insert((LinearVelocity(Vec3::ZERO), AngularVelocity(side * 2.0)));
+}
+
+fn over_the_top_death(enemy: Entity, position: Vec3, commands: &mut Commands) {
+    commands.trigger(SpawnConfettiEvent { position });
+    let mut rng = thread_rng();
+    let direction = Vec3::new(rng.gen_range(-1.0..1.0), 1.5, rng.gen_range(-1.0..1.0)).normalize();
+    let spin = Vec3::new(rng.r#gen(), rng.r#gen(), rng.r#gen()) * LAUNCH_SPIN;
+    commands.entity(enemy).insert((
+        LinearVelocity(direction * LAUNCH_SPEED),
+        AngularVelocity(spin),
+    ));
+}
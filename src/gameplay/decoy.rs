@@ -0,0 +1,222 @@
+//! The decoy trick: the player leaves a shimmering copy of themselves behind, and outlaws shoot at
+//! it instead of the player for a few seconds. It's unlocked once the player has made it to
+//! [`DECOY_UNLOCK_LEVEL`], before that the button does nothing.
+//!
+//! Outlaws aim at the closest [`Decoy`] they can see, see [`Decoys::closest`], and only go back to
+//! the player once it's gone. A decoy vanishes when its time is up or as soon as anything hits it.
+//! After dropping one, the player has to wait [`DecoySettings::cooldown_seconds`] for the next.
+
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::health_and_damage::{AbsorbNextHit, Health, HitAbsorbedEvent, NoHealthHat};
+use crate::gameplay::input::{DecoyAction, LastInputKind};
+use crate::gameplay::player::Player;
+use crate::physics_layers::GameLayer;
+use crate::save::SaveData;
+use crate::screens::Screen;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use crate::theme::toast::Toast;
+use avian3d::prelude::{Collider, CollisionLayers, RigidBody};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Started;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Decoy>()
+        .register_type::<DecoySettings>()
+        .init_resource::<DecoySettings>();
+    app.add_observer(drop_decoy).add_observer(announce_decoy);
+    app.add_systems(
+        Update,
+        (cool_down_decoys, dispel_expired_decoys, shimmer_decoys)
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// Index of the first level in which the player can drop decoys.
+pub const DECOY_UNLOCK_LEVEL: usize = 2;
+/// Stored in the achievements of the [`SaveData`] once the player was told about decoys.
+const DECOY_ANNOUNCED: &str = "decoy_unlocked";
+/// How many times per second the decoy shimmers.
+const SHIMMER_FREQUENCY: f32 = 3.0;
+/// The decoy's opacity shimmers between these two.
+const SHIMMER_ALPHA: (f32, f32) = (0.25, 0.6);
+/// Decoys fade out over their last this many seconds.
+const FADE_SECONDS: f32 = 0.5;
+const DECOY_COLOR: Color = Color::srgb(0.6, 0.8, 1.0);
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct DecoySettings {
+    /// How long a decoy lasts if nothing hits it.
+    pub seconds: f32,
+    /// How long the player has to wait after dropping a decoy before they can drop the next.
+    pub cooldown_seconds: f32,
+}
+
+impl Default for DecoySettings {
+    fn default() -> Self {
+        Self {
+            seconds: 4.0,
+            cooldown_seconds: 12.0,
+        }
+    }
+}
+
+/// A stationary copy of the player that outlaws shoot at instead, until its timer runs out.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Decoy(Timer);
+
+impl Decoy {
+    pub fn new(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+/// Players with this component can't drop another decoy until it runs out.
+#[derive(Component, Debug)]
+pub struct DecoyCooldown(Timer);
+
+/// Finds the decoys outlaws should shoot at.
+#[derive(SystemParam)]
+pub struct Decoys<'w, 's> {
+    decoys: Query<'w, 's, (Entity, &'static GlobalTransform), With<Decoy>>,
+}
+
+impl Decoys<'_, '_> {
+    /// The decoy closest to `origin`, if there is one within `range`.
+    pub fn closest(&self, origin: Vec3, range: f32) -> Option<(Entity, Vec3)> {
+        self.decoys
+            .iter()
+            .map(|(entity, transform)| (entity, transform.translation()))
+            .filter(|(_, position)| position.distance(origin) <= range)
+            .min_by(|(_, a), (_, b)| a.distance(origin).total_cmp(&b.distance(origin)))
+    }
+}
+
+fn is_unlocked(save_data: &SaveData) -> bool {
+    save_data.furthest_level >= DECOY_UNLOCK_LEVEL
+}
+
+/// Tells the player about decoys the first time they get to use them.
+fn announce_decoy(
+    _: Trigger<OnAdd, Player>,
+    input: Res<LastInputKind>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    // only touch the save data if something changes, every change gets written to disk
+    if !is_unlocked(&save_data) || save_data.achievements.contains(DECOY_ANNOUNCED) {
+        return;
+    }
+    save_data.unlock_achievement(DECOY_ANNOUNCED);
+    commands.trigger(Toast::new(input.glyph(
+        "New trick: press Q to leave a decoy behind for the outlaws to shoot at",
+        "New trick: press B to leave a decoy behind for the outlaws to shoot at",
+    )));
+}
+
+fn drop_decoy(
+    trigger: Trigger<Started<DecoyAction>>,
+    players: Query<(&Transform, &Mesh3d, Has<DecoyCooldown>), With<Player>>,
+    save_data: Res<SaveData>,
+    settings: Res<DecoySettings>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let Ok((transform, mesh, cooling_down)) = players.get(trigger.target()) else {
+        return;
+    };
+    if cooling_down || !is_unlocked(&save_data) {
+        return;
+    }
+
+    commands
+        .entity(trigger.target())
+        .insert(DecoyCooldown(Timer::from_seconds(
+            settings.cooldown_seconds,
+            TimerMode::Once,
+        )));
+    commands.trigger(SpawnVfxEvent::new(Vfx::Puff, transform.translation));
+    commands
+        .spawn((
+            Name::new("Decoy"),
+            Decoy::new(settings.seconds),
+            *transform,
+            mesh.clone(),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: DECOY_COLOR.with_alpha(SHIMMER_ALPHA.1),
+                emissive: DECOY_COLOR.to_linear() * 2.0,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            })),
+            StateScoped(Screen::Gameplay),
+        ))
+        .insert((
+            // only bullets and explosions can hit it, everyone else walks right through
+            Collider::capsule(0.5, 1.),
+            RigidBody::Kinematic,
+            CollisionLayers::new(GameLayer::Player, GameLayer::Bullet),
+            // the first hit is all it takes, without counting as a kill
+            Health::default(),
+            AbsorbNextHit,
+            NoHealthHat,
+        ))
+        .observe(dispel_decoy_on_hit);
+}
+
+fn cool_down_decoys(
+    time: GameTime,
+    mut players: Query<(Entity, &mut DecoyCooldown)>,
+    mut commands: Commands,
+) {
+    for (entity, mut cooldown) in &mut players {
+        if cooldown.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<DecoyCooldown>();
+        }
+    }
+}
+
+fn dispel_decoy_on_hit(
+    trigger: Trigger<HitAbsorbedEvent>,
+    decoys: Query<&Transform, With<Decoy>>,
+    mut commands: Commands,
+) {
+    if let Ok(transform) = decoys.get(trigger.target()) {
+        dispel(trigger.target(), transform.translation, &mut commands);
+    }
+}
+
+fn dispel_expired_decoys(
+    time: GameTime,
+    mut decoys: Query<(Entity, &Transform, &mut Decoy)>,
+    mut commands: Commands,
+) {
+    for (entity, transform, mut decoy) in &mut decoys {
+        if decoy.0.tick(time.delta()).finished() {
+            dispel(entity, transform.translation, &mut commands);
+        }
+    }
+}
+
+fn dispel(decoy: Entity, position: Vec3, commands: &mut Commands) {
+    commands.trigger(SpawnVfxEvent::new(Vfx::Puff, position));
+    commands.entity(decoy).try_despawn();
+}
+
+fn shimmer_decoys(
+    time: GameTime,
+    decoys: Query<(&Decoy, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let wave = (time.elapsed_secs() * SHIMMER_FREQUENCY * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+    for (decoy, material) in &decoys {
+        let Some(material) = materials.get_mut(material) else {
+            continue;
+        };
+        let fade = (decoy.0.remaining_secs() / FADE_SECONDS).min(1.0);
+        let alpha = SHIMMER_ALPHA.0.lerp(SHIMMER_ALPHA.1, wave) * fade;
+        material.base_color.set_alpha(alpha);
+    }
+}
@@ -0,0 +1,127 @@
+//! The activity bubble: outlaws far away from the player don't need to think every frame. Past
+//! [`ActivitySettings::throttle_distance`] their AI and aiming only run every few frames, past
+//! [`ActivitySettings::suspend_distance`] they stop moving, aiming and shooting altogether until
+//! the player comes closer again. Alerted squads are never suspended, they're coming for the player
+//! from anywhere.
+//!
+//! Outlaws have to get [`ActivitySettings::hysteresis`] further out than a distance before they're
+//! slowed down, but wake up as soon as they're back within it. That way, ones right at the edge
+//! don't flicker between the two.
+
+use super::Enemy;
+use super::squad::Alerted;
+use crate::HotSystems;
+use crate::gameplay::Gameplay;
+use crate::gameplay::player::Player;
+use avian3d::prelude::LinearVelocity;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ActivitySettings>()
+        .register_type::<Activity>()
+        .init_resource::<ActivitySettings>()
+        .init_resource::<ActivityFrame>();
+    app.add_systems(
+        Update,
+        update_activity
+            .before(HotSystems::EnemyAi)
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ActivitySettings {
+    /// Outlaws further away from the player than this only think every `throttled_interval` frames.
+    pub throttle_distance: f32,
+    /// Outlaws further away from the player than this don't do anything at all.
+    pub suspend_distance: f32,
+    /// How much further out than a distance outlaws have to get before it applies to them.
+    pub hysteresis: f32,
+    pub throttled_interval: u32,
+}
+
+impl Default for ActivitySettings {
+    fn default() -> Self {
+        Self {
+            // further than any gun reaches, so throttling never gets in the way of a fight
+            throttle_distance: 25.0,
+            suspend_distance: 50.0,
+            hysteresis: 5.0,
+            throttled_interval: 4,
+        }
+    }
+}
+
+/// How much an outlaw is doing right now, depending on how far away the player is.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum Activity {
+    #[default]
+    Active,
+    /// Only thinks every [`ActivitySettings::throttled_interval`] frames.
+    Throttled,
+    /// Stands still and neither aims nor shoots.
+    Suspended,
+}
+
+/// Counts frames, to spread throttled outlaws out over them.
+#[derive(Resource, Debug, Default)]
+struct ActivityFrame(u32);
+
+/// Tells the AI and attack systems which outlaws to skip this frame.
+#[derive(SystemParam)]
+pub struct ActivityBubble<'w> {
+    settings: Res<'w, ActivitySettings>,
+    frame: Res<'w, ActivityFrame>,
+}
+
+impl ActivityBubble<'_> {
+    /// Whether `entity` gets to think this frame. Throttled outlaws take turns, so they don't all
+    /// think on the same frame.
+    pub fn is_due(&self, entity: Entity, activity: Activity) -> bool {
+        match activity {
+            Activity::Active => true,
+            Activity::Throttled => {
+                let interval = self.settings.throttled_interval.max(1);
+                self.frame.0.wrapping_add(entity.index()) % interval == 0
+            }
+            Activity::Suspended => false,
+        }
+    }
+}
+
+fn update_activity(
+    settings: Res<ActivitySettings>,
+    mut frame: ResMut<ActivityFrame>,
+    player: Single<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemies: Query<(&Transform, &mut Activity, &mut LinearVelocity, Has<Alerted>), With<Enemy>>,
+) {
+    frame.0 = frame.0.wrapping_add(1);
+    let player = player.translation;
+    for (transform, mut activity, mut velocity, alerted) in &mut enemies {
+        let distance = transform.translation.xz().distance(player.xz());
+        let beyond = |limit: f32, was_beyond: bool| {
+            if was_beyond {
+                distance > limit
+            } else {
+                distance > limit + settings.hysteresis
+            }
+        };
+        let next =
+            if !alerted && beyond(settings.suspend_distance, *activity == Activity::Suspended) {
+                Activity::Suspended
+            } else if beyond(settings.throttle_distance, *activity != Activity::Active) {
+                Activity::Throttled
+            } else {
+                Activity::Active
+            };
+        if next == Activity::Suspended && *activity != Activity::Suspended {
+            // nobody is going to tell them to stop otherwise
+            velocity.x = 0.0;
+            velocity.z = 0.0;
+        }
+        activity.set_if_neq(next);
+    }
+}
@@ -0,0 +1,149 @@
+//! Enemy archetypes: every kind of outlaw comes with its own spawn point component, which levels
+//! place where it should show up. An archetype implements [`EnemyArchetype`] on that component and
+//! gets registered with [`RegisterEnemyArchetype::register_enemy_archetype`], from then on its
+//! outlaws spawn like everyone else's, with [`enemy_bundle`] and the spawn-in animation.
+//!
+//! Everything that needs to know about all archetypes, like the level editor's palette or the
+//! double outlaws mutator, goes through the [`EnemyArchetypeRegistry`] instead of naming them.
+
+use super::spawning::Spawning;
+use super::{EnemySpawnPoint, enemy_bundle, on_death};
+use bevy::prelude::*;
+use bevy::reflect::GetTypeRegistration;
+use std::any::TypeId;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EnemyArchetypeRegistry>();
+}
+
+/// A kind of outlaw, implemented on the component that marks where it spawns.
+pub trait EnemyArchetype: Component + Clone + Default + GetTypeRegistration {
+    /// What the spawned outlaws are called, also shown in the level editor.
+    const NAME: &'static str;
+    const COLOR: Color;
+
+    /// What this outlaw has in common with every other one, like how fast they move.
+    fn stats(&self) -> EnemySpawnPoint;
+
+    /// Adds what sets this archetype apart to a freshly spawned outlaw.
+    fn equip(
+        &self,
+        enemy: &mut EntityCommands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    );
+}
+
+/// Triggered on a spawn point right after it sent out its outlaw.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EnemySpawnedEvent {
+    pub enemy: Entity,
+    /// The spawn point's component, see [`EnemyArchetypeRegistry::get`].
+    pub archetype: TypeId,
+}
+
+/// A registered [`EnemyArchetype`], without its type.
+#[derive(Debug, Clone, Copy)]
+pub struct EnemyArchetypeInfo {
+    pub name: &'static str,
+    pub type_id: TypeId,
+    insert_default: fn(&mut EntityCommands),
+    copy: fn(&mut World, Entity, Entity),
+}
+
+impl EnemyArchetypeInfo {
+    fn of<A: EnemyArchetype>() -> Self {
+        Self {
+            name: A::NAME,
+            type_id: TypeId::of::<A>(),
+            insert_default: |entity| {
+                entity.insert(A::default());
+            },
+            copy: |world, from, to| {
+                if let Some(spawn_point) = world.get::<A>(from).cloned() {
+                    world.entity_mut(to).insert(spawn_point);
+                }
+            },
+        }
+    }
+
+    /// Turns `entity` into a spawn point of this archetype, with the default tuning.
+    pub fn insert_default(&self, entity: &mut EntityCommands) {
+        (self.insert_default)(entity);
+    }
+
+    /// Puts a copy of the spawn point on `from` onto `to`, which makes `to` send out an outlaw
+    /// too.
+    pub fn copy(&self, world: &mut World, from: Entity, to: Entity) {
+        (self.copy)(world, from, to);
+    }
+}
+
+/// Every [`EnemyArchetype`] the game knows about, in the order they were registered.
+#[derive(Resource, Debug, Default)]
+pub struct EnemyArchetypeRegistry {
+    archetypes: Vec<EnemyArchetypeInfo>,
+}
+
+impl EnemyArchetypeRegistry {
+    pub fn iter(&self) -> impl Iterator<Item = &EnemyArchetypeInfo> {
+        self.archetypes.iter()
+    }
+
+    pub fn get(&self, type_id: TypeId) -> Option<&EnemyArchetypeInfo> {
+        self.archetypes
+            .iter()
+            .find(|archetype| archetype.type_id == type_id)
+    }
+}
+
+pub trait RegisterEnemyArchetype {
+    /// Makes outlaws of archetype `A` spawn wherever its spawn point shows up.
+    fn register_enemy_archetype<A: EnemyArchetype>(&mut self) -> &mut Self;
+}
+
+impl RegisterEnemyArchetype for App {
+    fn register_enemy_archetype<A: EnemyArchetype>(&mut self) -> &mut Self {
+        self.register_type::<A>()
+            .init_resource::<EnemyArchetypeRegistry>()
+            .add_observer(spawn_enemy_on_spawn_point::<A>);
+        let mut registry = self.world_mut().resource_mut::<EnemyArchetypeRegistry>();
+        if registry.get(TypeId::of::<A>()).is_none() {
+            registry.archetypes.push(EnemyArchetypeInfo::of::<A>());
+        }
+        self
+    }
+}
+
+fn spawn_enemy_on_spawn_point<A: EnemyArchetype>(
+    trigger: Trigger<OnAdd, A>,
+    spawn_points: Query<(&Transform, &A)>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) -> Result {
+    let (position, spawn_point) = spawn_points.get(trigger.target())?;
+
+    let mut enemy = commands.spawn((
+        enemy_bundle(
+            A::NAME,
+            *position,
+            &spawn_point.stats(),
+            A::COLOR,
+            &mut meshes,
+            &mut materials,
+        ),
+        Spawning::default(),
+    ));
+    enemy.observe(on_death);
+    spawn_point.equip(&mut enemy, &mut meshes, &mut materials);
+    let enemy = enemy.id();
+    commands
+        .entity(trigger.target())
+        .trigger(EnemySpawnedEvent {
+            enemy,
+            archetype: TypeId::of::<A>(),
+        });
+
+    Ok(())
+}
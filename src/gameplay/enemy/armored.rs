@@ -0,0 +1,154 @@
+//! Armored enemies carry a big shield and slowly turn it towards the player, or whatever decoy
+//! they're shooting at.
+//! Throwing straight at them does nothing, you need to hit them from behind or ricochet off a wall.
+
+use super::archetype::{EnemyArchetype, RegisterEnemyArchetype};
+use super::flinch::Flinch;
+use super::{CanUseRangedAttack, EnemySpawnPoint};
+use crate::ai::enemy_ai::FollowPlayerBehavior;
+use crate::asset_tracking::LoadResource;
+use crate::audio::TimeDilatedPitch;
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::WeaponTarget;
+use crate::gameplay::health_and_damage::{FrontalShield, ShieldBlockEvent};
+use crate::gameplay::player::Player;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_enemy_archetype::<ArmoredSpawnPoint>()
+        .register_type::<ArmoredAssets>()
+        .load_resource::<ArmoredAssets>();
+    app.add_observer(on_shield_block);
+    app.add_systems(
+        Update,
+        turn_shields_towards_player.run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How fast (in radians per second) shields turn to face the player.
+const SHIELD_TURN_SPEED: f32 = 1.5;
+
+#[derive(Component, Reflect, Copy, Clone, Debug, PartialEq)]
+#[reflect(Default, Component)]
+pub struct ArmoredSpawnPoint {
+    detection_range: f32,
+    movement_speed: f32,
+    attacks_per_second: f32,
+    /// See [`super::squad`].
+    #[reflect(default)]
+    squad: u32,
+}
+
+impl Default for ArmoredSpawnPoint {
+    fn default() -> Self {
+        let default_ai = FollowPlayerBehavior::default();
+        Self {
+            detection_range: default_ai.detection_range,
+            movement_speed: default_ai.movement_speed * 0.7,
+            attacks_per_second: 0.7,
+            squad: 0,
+        }
+    }
+}
+
+impl EnemyArchetype for ArmoredSpawnPoint {
+    const NAME: &'static str = "Armored Enemy";
+    const COLOR: Color = Color::srgb_u8(70, 70, 90);
+
+    fn stats(&self) -> EnemySpawnPoint {
+        EnemySpawnPoint {
+            detection_range: self.detection_range,
+            movement_speed: self.movement_speed,
+            attacks_per_second: self.attacks_per_second,
+            squad: self.squad,
+            ..default()
+        }
+    }
+
+    fn equip(
+        &self,
+        enemy: &mut EntityCommands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+    ) {
+        let shield_mesh = meshes.add(Cuboid::new(1.4, 1.6, 0.15));
+        let shield_material = materials.add(StandardMaterial {
+            base_color: Color::srgb_u8(120, 120, 130),
+            metallic: 0.8,
+            perceptual_roughness: 0.4,
+            ..default()
+        });
+        enemy
+            .insert((
+                CanUseRangedAttack::single_shot(1, 12., 2., 12., 0.5),
+                FrontalShield::default(),
+                Flinch::STURDY,
+            ))
+            .with_child((
+                Name::new("Shield"),
+                Mesh3d(shield_mesh),
+                MeshMaterial3d(shield_material),
+                Transform::from_xyz(0.0, 0.0, -0.7),
+            ));
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct ArmoredAssets {
+    #[dependency]
+    block_sfx: Handle<AudioSource>,
+}
+
+impl FromWorld for ArmoredAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            block_sfx: asset_server.load("audio/sound_effects/boomerang_sfx/ding.ogg"),
+        }
+    }
+}
+
+fn turn_shields_towards_player(
+    time: GameTime,
+    player: Single<&Transform, With<Player>>,
+    mut shield_bearers: Query<
+        (&mut Transform, Option<&WeaponTarget>),
+        (With<FrontalShield>, Without<Player>),
+    >,
+    targets: Query<&GlobalTransform>,
+) {
+    let max_turn = SHIELD_TURN_SPEED * time.delta_secs();
+    for (mut transform, weapon_target) in &mut shield_bearers {
+        // whoever they're shooting at, e.g. a decoy, otherwise the player
+        let facing = weapon_target
+            .and_then(|weapon_target| weapon_target.target_entity)
+            .and_then(|target| targets.get(target).ok())
+            .map_or(player.translation, GlobalTransform::translation);
+        let to_player = (facing - transform.translation).with_y(0.0);
+        if to_player.length_squared() < f32::EPSILON {
+            continue;
+        }
+        let target = Transform::IDENTITY.looking_to(to_player, Vec3::Y).rotation;
+        let angle = transform.rotation.angle_between(target);
+        if angle <= max_turn {
+            transform.rotation = target;
+        } else {
+            transform.rotation = transform.rotation.slerp(target, max_turn / angle);
+        }
+    }
+}
+
+fn on_shield_block(
+    _trigger: Trigger<ShieldBlockEvent>,
+    assets: Res<ArmoredAssets>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Name::new("Shield Block SFX"),
+        AudioPlayer::new(assets.block_sfx.clone()),
+        PlaybackSettings::DESPAWN,
+        TimeDilatedPitch(0.4),
+    ));
+}
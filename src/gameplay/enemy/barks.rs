@@ -0,0 +1,296 @@
+//! Barks: outlaws shout short lines at each other now and then, shown in a speech bubble above
+//! their head along with a yell. They bark when they spot the player, when their gun runs dry and
+//! when one of their pals goes down next to them.
+//!
+//! Not every one of those moments gets a bark, see [`BarkKind::chance`]. Every outlaw has to catch
+//! their breath for [`BARK_COOLDOWN_SECONDS`] after barking, and only [`MAX_BARKS`] bubbles are up
+//! at once, so big fights don't turn into a wall of text.
+
+use super::spawning::Spawning;
+use super::{BurstFire, Enemy};
+use crate::asset_tracking::LoadResource;
+use crate::audio::{DistanceAttenuation, TimeDilatedPitch};
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::WeaponTarget;
+use crate::gameplay::health_and_damage::{DeathEvent, Health};
+use crate::localization::Locale;
+use crate::screens::Screen;
+use crate::ui_assets::FontAssets;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, thread_rng};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<BarkAssets>()
+        .load_resource::<BarkAssets>();
+    app.add_observer(add_barker)
+        .add_observer(bark_on_ally_death)
+        .add_observer(bark);
+    app.add_systems(
+        Update,
+        (tick_barkers, bark_on_state_change, update_bubbles)
+            .chain()
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How long an outlaw stays quiet after barking.
+const BARK_COOLDOWN_SECONDS: f32 = 8.0;
+/// How long a speech bubble stays up.
+const BUBBLE_SECONDS: f32 = 2.0;
+/// No more bubbles than this at once, further barks are swallowed.
+const MAX_BARKS: usize = 2;
+/// Outlaws this close to one that dies may bark about it.
+const ALLY_DOWN_RANGE: f32 = 15.0;
+/// How far above the outlaw's middle the bubble floats.
+const BUBBLE_HEIGHT: f32 = 1.8;
+const SFX_NEAR_DISTANCE: f32 = 3.0;
+const SFX_FAR_DISTANCE: f32 = 30.0;
+
+const BUBBLE_COLOR: Color = Color::srgb(0.96, 0.91, 0.8);
+const BUBBLE_TEXT_COLOR: Color = Color::srgb(0.2, 0.12, 0.06);
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct BarkAssets {
+    #[dependency]
+    yells: Vec<Handle<AudioSource>>,
+}
+
+impl FromWorld for BarkAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            yells: (1..=4)
+                .map(|index| {
+                    asset_server.load(format!(
+                        "audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death{index}.ogg"
+                    ))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// What an outlaw is barking about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BarkKind {
+    Spotted,
+    Reloading,
+    AllyDown,
+}
+
+impl BarkKind {
+    /// How likely it is that the outlaw actually says something, from 0.0 to 1.0.
+    fn chance(self) -> f64 {
+        match self {
+            BarkKind::Spotted => 0.6,
+            // happens after every burst, so it would get old quickly
+            BarkKind::Reloading => 0.2,
+            BarkKind::AllyDown => 0.7,
+        }
+    }
+
+    fn lines(self) -> &'static [&'static str] {
+        match self {
+            BarkKind::Spotted => &["Over there!", "Get 'em, boys!", "Found ya!"],
+            BarkKind::Reloading => &["Reloading!", "Cover me!", "Hold still, dang it!"],
+            BarkKind::AllyDown => &["Man down!", "They got Jeb!", "You'll pay for that!"],
+        }
+    }
+}
+
+/// Makes an outlaw bark about something, if they're not on cooldown and there's room for another
+/// bubble.
+#[derive(Event, Debug, Clone, Copy)]
+struct BarkEvent(BarkKind);
+
+/// Keeps track of what an outlaw was up to, to notice when that changes.
+#[derive(Component, Debug)]
+struct Barker {
+    cooldown: Timer,
+    saw_target: bool,
+    shots_left: u32,
+}
+
+impl Default for Barker {
+    fn default() -> Self {
+        let mut cooldown = Timer::from_seconds(BARK_COOLDOWN_SECONDS, TimerMode::Once);
+        // ready to bark right away
+        cooldown.tick(cooldown.duration());
+        Self {
+            cooldown,
+            saw_target: false,
+            shots_left: 0,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct BarkBubble {
+    speaker: Entity,
+    timer: Timer,
+}
+
+fn add_barker(trigger: Trigger<OnAdd, Enemy>, mut commands: Commands) {
+    commands.entity(trigger.target()).insert(Barker::default());
+}
+
+fn tick_barkers(time: GameTime, mut barkers: Query<&mut Barker>) {
+    for mut barker in &mut barkers {
+        barker.cooldown.tick(time.delta());
+    }
+}
+
+fn bark_on_state_change(
+    mut barkers: Query<
+        (Entity, &WeaponTarget, Option<&BurstFire>, &mut Barker),
+        (With<Health>, Without<Spawning>),
+    >,
+    mut commands: Commands,
+) {
+    for (entity, target, burst, mut barker) in &mut barkers {
+        let sees_target = target.target_entity.is_some();
+        if sees_target && !barker.saw_target {
+            commands
+                .entity(entity)
+                .trigger(BarkEvent(BarkKind::Spotted));
+        }
+        barker.saw_target = sees_target;
+
+        // a burst running out while the target is still in sight, rather than losing sight of it
+        let shots_left = burst.map_or(0, |burst| burst.shots_left);
+        if sees_target && shots_left == 0 && barker.shots_left > 0 {
+            commands
+                .entity(entity)
+                .trigger(BarkEvent(BarkKind::Reloading));
+        }
+        barker.shots_left = shots_left;
+    }
+}
+
+/// The closest living outlaw nearby has something to say about it.
+fn bark_on_ally_death(
+    trigger: Trigger<DeathEvent>,
+    dead: Query<&GlobalTransform, With<Enemy>>,
+    // the dead outlaw's health is already gone
+    living: Query<(Entity, &GlobalTransform), (With<Barker>, With<Health>)>,
+    mut commands: Commands,
+) {
+    let Ok(dead) = dead.get(trigger.target()) else {
+        return;
+    };
+    let dead = dead.translation();
+    let closest = living
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation().distance(dead)))
+        .filter(|(_, distance)| *distance < ALLY_DOWN_RANGE)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((entity, _)) = closest {
+        commands
+            .entity(entity)
+            .trigger(BarkEvent(BarkKind::AllyDown));
+    }
+}
+
+fn bark(
+    trigger: Trigger<BarkEvent>,
+    mut barkers: Query<(&mut Barker, &GlobalTransform)>,
+    bubbles: Query<(), With<BarkBubble>>,
+    assets: Res<BarkAssets>,
+    font_assets: Res<FontAssets>,
+    locale: Locale,
+    mut commands: Commands,
+) {
+    let speaker = trigger.target();
+    let Ok((mut barker, transform)) = barkers.get_mut(speaker) else {
+        return;
+    };
+    if !barker.cooldown.finished() || bubbles.iter().count() >= MAX_BARKS {
+        return;
+    }
+    let kind = trigger.event().0;
+    let mut rng = thread_rng();
+    if !rng.gen_bool(kind.chance()) {
+        return;
+    }
+    barker.cooldown.reset();
+
+    let line = kind.lines().choose(&mut rng).copied().unwrap_or_default();
+    commands.spawn((
+        Name::new("Bark Bubble"),
+        BarkBubble {
+            speaker,
+            timer: Timer::from_seconds(BUBBLE_SECONDS, TimerMode::Once),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            ..default()
+        },
+        BackgroundColor(BUBBLE_COLOR),
+        BorderRadius::all(Val::Px(8.0)),
+        // hidden until it's been placed above the speaker
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(
+            Text::new(locale.tr(line)),
+            TextFont::from_font_size(16.0).with_font(font_assets.content.clone()),
+            TextColor(BUBBLE_TEXT_COLOR),
+        )],
+    ));
+    if let Some(yell) = assets.yells.choose(&mut rng) {
+        commands.spawn((
+            Name::new("Bark SFX"),
+            Transform::from_translation(transform.translation()),
+            AudioPlayer::new(yell.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Decibels(-6.0)),
+            // higher and quicker than a dying outlaw, so it sounds like a shout
+            TimeDilatedPitch(rng.gen_range(1.3..1.6)),
+            DistanceAttenuation::new(SFX_NEAR_DISTANCE, SFX_FAR_DISTANCE),
+            StateScoped(Gameplay::Normal),
+        ));
+    }
+}
+
+fn update_bubbles(
+    time: GameTime,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    speakers: Query<&GlobalTransform, With<Health>>,
+    mut bubbles: Query<(
+        Entity,
+        &mut BarkBubble,
+        &ComputedNode,
+        &mut Node,
+        &mut Visibility,
+    )>,
+    mut commands: Commands,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    for (entity, mut bubble, computed, mut node, mut visibility) in &mut bubbles {
+        // dead outlaws don't finish their sentence
+        let Ok(speaker) = speakers.get(bubble.speaker) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if bubble.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let Ok(screen_position) = camera.world_to_viewport(
+            camera_transform,
+            speaker.translation() + Vec3::Y * BUBBLE_HEIGHT,
+        ) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        // centered above the speaker's head
+        let size = computed.size() * computed.inverse_scale_factor();
+        node.left = Val::Px(screen_position.x - size.x / 2.0);
+        node.top = Val::Px(screen_position.y - size.y);
+        *visibility = Visibility::Inherited;
+    }
+}
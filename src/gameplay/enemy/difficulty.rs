@@ -0,0 +1,263 @@
+//! Adaptive difficulty: keeps an eye on how the last couple of levels went and nudges enemies to
+//! be a bit easier or harder. The current [`DifficultyLevel`] goes from -1.0 (as easy as it gets)
+//! to 1.0 (as hard as it gets), what that means for enemies is set in [`AdaptiveDifficulty`].
+//! Players who'd rather have every level play the same can turn it off in the settings menu.
+//!
+//! What counts as doing well:
+//! - not dying
+//! - not taking a lot of hits
+//! - boomerangs that hit an outlaw, rather than just flying around
+
+use super::elite::roll_elites;
+use super::spawning::Spawning;
+use super::{CanDelayBetweenAttacks, CanUseRangedAttack, Enemy};
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::BoomerangFlightHistory;
+use crate::gameplay::health_and_damage::HurtEvent;
+use crate::gameplay::outcome::Winner;
+use crate::gameplay::player::Player;
+use crate::gameplay::wanted::Bounty;
+use crate::settings::{Settings, SettingsApplied};
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+use std::collections::VecDeque;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AdaptiveDifficulty>()
+        .init_resource::<DifficultyLevel>()
+        .init_resource::<LevelPerformance>()
+        .init_resource::<RecentPerformance>()
+        .register_type::<AdaptiveDifficulty>()
+        .register_type::<DifficultyLevel>()
+        .register_type::<LevelPerformance>();
+    app.add_observer(apply_adaptive_difficulty_setting)
+        .add_observer(count_throws)
+        .add_observer(count_hits_taken);
+    app.add_systems(OnEnter(Gameplay::Normal), reset_level_performance);
+    app.add_systems(OnEnter(Gameplay::GameOver), adjust_difficulty);
+    app.add_systems(
+        Update,
+        // after elites are rolled, so skipped enemies aren't turned into elites at the same time
+        adjust_spawning_enemies
+            .after(roll_elites)
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How many levels are taken into account.
+const RECENT_LEVELS: usize = 3;
+/// The share of boomerangs that should hit an outlaw for a level to count as neither too easy nor
+/// too hard.
+const EXPECTED_ACCURACY: f32 = 0.5;
+/// Hits taken in a single level that make it count as a struggle.
+const HITS_TAKEN_TOLERANCE: f32 = 3.0;
+
+/// The designer-set bounds. Every value is picked between the `easiest` and `hardest` end according
+/// to the [`DifficultyLevel`], with 0.0 being exactly what the level was made with.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AdaptiveDifficulty {
+    /// Turned off in the settings menu.
+    pub enabled: bool,
+    /// How far the difficulty level may move after a single level.
+    pub max_step: f32,
+    /// Multiplier on the time between attacks.
+    pub attack_delay_easiest: f32,
+    pub attack_delay_hardest: f32,
+    /// Multiplier on bullet speed.
+    pub bullet_speed_easiest: f32,
+    pub bullet_speed_hardest: f32,
+    /// Chance for a regular enemy to not show up at all. Only ever makes levels easier, wanted
+    /// outlaws always show up, and so does at least one enemy.
+    pub skip_chance_easiest: f32,
+}
+
+impl Default for AdaptiveDifficulty {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_step: 0.25,
+            attack_delay_easiest: 1.4,
+            attack_delay_hardest: 0.8,
+            bullet_speed_easiest: 0.8,
+            bullet_speed_hardest: 1.2,
+            skip_chance_easiest: 0.25,
+        }
+    }
+}
+
+impl AdaptiveDifficulty {
+    /// Blends from 1.0 at the default difficulty towards `easiest` or `hardest`.
+    fn scale(level: f32, easiest: f32, hardest: f32) -> f32 {
+        if level < 0.0 {
+            1.0 + (easiest - 1.0) * -level
+        } else {
+            1.0 + (hardest - 1.0) * level
+        }
+    }
+
+    pub fn attack_delay(&self, level: f32) -> f32 {
+        Self::scale(level, self.attack_delay_easiest, self.attack_delay_hardest)
+    }
+
+    pub fn bullet_speed(&self, level: f32) -> f32 {
+        Self::scale(level, self.bullet_speed_easiest, self.bullet_speed_hardest)
+    }
+
+    pub fn skip_chance(&self, level: f32) -> f32 {
+        self.skip_chance_easiest * (-level).max(0.0)
+    }
+}
+
+/// How hard enemies are right now, from -1.0 (easiest) to 1.0 (hardest).
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct DifficultyLevel(pub f32);
+
+/// How the level that's being played is going so far.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct LevelPerformance {
+    pub throws: u32,
+    /// Throws that hit at least one outlaw.
+    pub hits: u32,
+    pub hits_taken: u32,
+    pub died: bool,
+}
+
+impl LevelPerformance {
+    /// How well the level went, from -1.0 (badly) to 1.0 (very well).
+    pub fn rating(&self) -> f32 {
+        if self.died {
+            return -1.0;
+        }
+        let accuracy = if self.throws == 0 {
+            EXPECTED_ACCURACY
+        } else {
+            self.hits as f32 / self.throws as f32
+        };
+        let accuracy_rating = (accuracy - EXPECTED_ACCURACY) / EXPECTED_ACCURACY;
+        let damage_rating = 1.0 - 2.0 * (self.hits_taken as f32 / HITS_TAKEN_TOLERANCE);
+        ((accuracy_rating + damage_rating) / 2.0).clamp(-1.0, 1.0)
+    }
+}
+
+/// The last couple of finished levels, most recent last.
+#[derive(Resource, Debug, Default)]
+pub struct RecentPerformance(pub VecDeque<LevelPerformance>);
+
+fn apply_adaptive_difficulty_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut difficulty: ResMut<AdaptiveDifficulty>,
+    mut level: ResMut<DifficultyLevel>,
+) {
+    difficulty.enabled = settings.adaptive_difficulty;
+    if !difficulty.enabled {
+        *level = DifficultyLevel::default();
+    }
+}
+
+fn reset_level_performance(mut performance: ResMut<LevelPerformance>) {
+    *performance = LevelPerformance::default();
+}
+
+fn count_throws(
+    trigger: Trigger<OnRemove, BoomerangFlightHistory>,
+    histories: Query<&BoomerangFlightHistory>,
+    enemies: Query<(), With<Enemy>>,
+    state: Option<Res<State<Gameplay>>>,
+    mut performance: ResMut<LevelPerformance>,
+) {
+    // boomerangs also get cleaned up when leaving the level, those don't count
+    if state.is_none_or(|state| *state.get() != Gameplay::Normal) {
+        return;
+    }
+    let Ok(history) = histories.get(trigger.target()) else {
+        return;
+    };
+    performance.throws += 1;
+    if history.hits.iter().any(|hit| enemies.contains(*hit)) {
+        performance.hits += 1;
+    }
+}
+
+fn count_hits_taken(
+    trigger: Trigger<HurtEvent>,
+    players: Query<(), With<Player>>,
+    mut performance: ResMut<LevelPerformance>,
+) {
+    if players.contains(trigger.target()) {
+        performance.hits_taken += 1;
+    }
+}
+
+/// Moves the difficulty towards how well the recent levels went, a step at a time.
+fn adjust_difficulty(
+    winner: Res<Winner>,
+    difficulty: Res<AdaptiveDifficulty>,
+    mut performance: ResMut<LevelPerformance>,
+    mut recent: ResMut<RecentPerformance>,
+    mut level: ResMut<DifficultyLevel>,
+) {
+    performance.died = *winner == Winner::Enemy;
+    recent.0.push_back(performance.clone());
+    while recent.0.len() > RECENT_LEVELS {
+        recent.0.pop_front();
+    }
+    if !difficulty.enabled {
+        return;
+    }
+
+    let rating = recent.0.iter().map(LevelPerformance::rating).sum::<f32>() / recent.0.len() as f32;
+    let step = (rating - level.0).clamp(-difficulty.max_step, difficulty.max_step);
+    level.0 = (level.0 + step).clamp(-1.0, 1.0);
+    debug!(
+        "Difficulty level {:.2} after rating the recent levels {rating:.2}",
+        level.0
+    );
+}
+
+/// Runs once enemies come out of a spawn point, like rolling elites does.
+fn adjust_spawning_enemies(
+    difficulty: Res<AdaptiveDifficulty>,
+    level: Res<DifficultyLevel>,
+    mut spawning: Query<
+        (
+            Entity,
+            &Spawning,
+            &mut CanDelayBetweenAttacks,
+            Option<&mut CanUseRangedAttack>,
+            Has<Bounty>,
+        ),
+        (With<Enemy>, Added<Spawning>),
+    >,
+    enemies: Query<(), With<Enemy>>,
+    mut commands: Commands,
+) {
+    if !difficulty.enabled || level.0 == 0.0 {
+        return;
+    }
+    let attack_delay = difficulty.attack_delay(level.0);
+    let bullet_speed = difficulty.bullet_speed(level.0);
+    let skip_chance = difficulty.skip_chance(level.0).clamp(0.0, 1.0) as f64;
+
+    let mut remaining = enemies.iter().count();
+    let mut rng = thread_rng();
+    for (entity, spawning, mut delay, ranged, wanted) in &mut spawning {
+        if !wanted && remaining > 1 && rng.gen_bool(skip_chance) {
+            remaining -= 1;
+            if let Some(dust_devil) = spawning.dust_devil {
+                commands.entity(dust_devil).despawn();
+            }
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let duration = delay.timer.duration().mul_f32(attack_delay);
+        delay.timer.set_duration(duration);
+        if let Some(mut ranged) = ranged {
+            ranged.speed *= bullet_speed;
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Dynamite throwers lob sticks of dynamite over walls to where the player is headed.
+//! Keep moving and get out of the marked area before the fuse runs out.
+
+use super::archetype::{EnemyArchetype, RegisterEnemyArchetype};
+use super::flinch::Flinching;
+use super::spawning::Spawning;
+use super::{CanDelayBetweenAttacks, Enemy, EnemySpawnPoint};
+use crate::ai::enemy_ai::FollowPlayerBehavior;
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::bullets::LobDynamiteEvent;
+use crate::gameplay::decoy::Decoys;
+use crate::gameplay::player::Player;
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_enemy_archetype::<DynamiteThrowerSpawnPoint>()
+        .register_type::<CanLobDynamite>();
+    app.add_systems(
+        Update,
+        lob_dynamite_at_player.run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct CanLobDynamite {
+    max_range: f32,
+    flight_time: f32,
+    fuse: f32,
+    radius: f32,
+    damage: u32,
+}
+
+impl Default for CanLobDynamite {
+    fn default() -> Self {
+        Self {
+            max_range: 18.,
+            flight_time: 1.2,
+            fuse: 2.0,
+            radius: 3.,
+            damage: 1,
+        }
+    }
+}
+
+#[derive(Component, Reflect, Copy, Clone, Debug, PartialEq)]
+#[reflect(Default, Component)]
+pub struct DynamiteThrowerSpawnPoint {
+    detection_range: f32,
+    movement_speed: f32,
+    attacks_per_second: f32,
+    /// See [`super::squad`].
+    #[reflect(default)]
+    squad: u32,
+}
+
+impl Default for DynamiteThrowerSpawnPoint {
+    fn default() -> Self {
+        let default_ai = FollowPlayerBehavior::default();
+        Self {
+            detection_range: default_ai.detection_range,
+            movement_speed: default_ai.movement_speed * 0.6,
+            attacks_per_second: 0.3,
+            squad: 0,
+        }
+    }
+}
+
+impl EnemyArchetype for DynamiteThrowerSpawnPoint {
+    const NAME: &'static str = "Dynamite Thrower";
+    const COLOR: Color = Color::srgb_u8(160, 90, 20);
+
+    fn stats(&self) -> EnemySpawnPoint {
+        EnemySpawnPoint {
+            detection_range: self.detection_range,
+            movement_speed: self.movement_speed,
+            attacks_per_second: self.attacks_per_second,
+            squad: self.squad,
+            ..default()
+        }
+    }
+
+    fn equip(
+        &self,
+        enemy: &mut EntityCommands,
+        _meshes: &mut Assets<Mesh>,
+        _materials: &mut Assets<StandardMaterial>,
+    ) {
+        enemy.insert(CanLobDynamite::default());
+    }
+}
+
+fn lob_dynamite_at_player(
+    time: GameTime,
+    mut throwers: Query<
+        (&Transform, &CanLobDynamite, &mut CanDelayBetweenAttacks),
+        (With<Enemy>, Without<Spawning>, Without<Flinching>),
+    >,
+    player: Single<(&Transform, Option<&LinearVelocity>), With<Player>>,
+    decoys: Decoys,
+    mut commands: Commands,
+) {
+    let (player_transform, player_velocity) = player.into_inner();
+    for (transform, lob, mut can_delay) in &mut throwers {
+        can_delay.timer.tick(time.delta());
+        if !can_delay.timer.just_finished() {
+            continue;
+        }
+
+        // decoys don't move, no need to lead them
+        let predicted =
+            if let Some((_, decoy)) = decoys.closest(transform.translation, lob.max_range) {
+                decoy
+            } else {
+                let distance = transform.translation.distance(player_transform.translation);
+                if distance > lob.max_range {
+                    continue;
+                }
+                // lead the target, so standing still isn't the only way to get hit
+                let velocity = player_velocity.map(|v| v.0).unwrap_or_default();
+                player_transform.translation + velocity.with_y(0.0) * lob.flight_time
+            };
+
+        commands.trigger(LobDynamiteEvent {
+            origin: transform.translation + Vec3::Y,
+            target: predicted,
+            flight_time: lob.flight_time,
+            fuse: lob.fuse,
+            radius: lob.radius,
+            damage: lob.damage,
+        });
+    }
+}
@@ -0,0 +1,195 @@
+//! Every now and then an enemy coming out of a spawn point is an elite, with one or more
+//! [`Affix`]es that make it tougher. Elites are a bit bigger, tinted in the color of their first
+//! affix, and worth more bounty.
+//! The adaptive difficulty (see [`super::difficulty`]) leaves elites alone, later levels count as
+//! harder and roll elites more often, see [`EliteSettings`].
+
+use super::spawning::Spawning;
+use super::{CanUseRangedAttack, Enemy};
+use crate::ai::enemy_ai::FollowPlayerBehavior;
+use crate::ai::strafe::StrafeBehavior;
+use crate::gameplay::Gameplay;
+use crate::gameplay::bullets::LobDynamiteEvent;
+use crate::gameplay::health_and_damage::{DeathEvent, Health};
+use crate::gameplay::health_pips::MaxHealth;
+use crate::gameplay::level::LevelAssets;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, thread_rng};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EliteSettings>()
+        .register_type::<EliteSettings>()
+        .register_type::<Elite>();
+    app.add_systems(Update, roll_elites.run_if(in_state(Gameplay::Normal)));
+}
+
+const ELITE_SCALE: f32 = 1.2;
+/// How much of the affix color goes into the enemy's own color.
+const TINT_STRENGTH: f32 = 0.6;
+
+/// How likely enemies are to be elites.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct EliteSettings {
+    /// Chance for an enemy to be an elite, by level. Levels past the end use the last entry.
+    pub chance_by_level: Vec<f32>,
+    /// Elites roll between one and this many affixes.
+    pub max_affixes: usize,
+    /// Every affix adds this much to the bounty multiplier, on top of the regular 1.0.
+    pub bounty_per_affix: f32,
+}
+
+impl Default for EliteSettings {
+    fn default() -> Self {
+        Self {
+            chance_by_level: vec![0.0, 0.05, 0.1, 0.15, 0.2],
+            max_affixes: 2,
+            bounty_per_affix: 0.5,
+        }
+    }
+}
+
+impl EliteSettings {
+    fn chance(&self, level: usize) -> f32 {
+        self.chance_by_level
+            .get(level)
+            .or(self.chance_by_level.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Affix {
+    /// Moves a lot faster.
+    Fast,
+    /// Takes a couple more hits.
+    Tanky,
+    /// Perfectly accurate, faster bullets. Only for gunmen.
+    Deadeye,
+    /// Leaves a lit stick of dynamite behind when it dies.
+    Explosive,
+}
+
+impl Affix {
+    const ALL: [Affix; 4] = [Affix::Fast, Affix::Tanky, Affix::Deadeye, Affix::Explosive];
+
+    fn color(self) -> Color {
+        match self {
+            Affix::Fast => Color::srgb(0.2, 0.8, 1.0),
+            Affix::Tanky => Color::srgb(0.5, 0.5, 0.55),
+            Affix::Deadeye => Color::srgb(1.0, 0.85, 0.1),
+            Affix::Explosive => Color::srgb(1.0, 0.4, 0.0),
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Elite {
+    pub affixes: Vec<Affix>,
+}
+
+impl Elite {
+    /// What the bounty for killing this elite gets multiplied with.
+    pub fn bounty_multiplier(&self, settings: &EliteSettings) -> f32 {
+        1.0 + self.affixes.len() as f32 * settings.bounty_per_affix
+    }
+}
+
+/// Only enemies coming out of spawn points can be elites. Runs once they've been spawned, so the
+/// [`MaxHealth`] of tanky ones can be bumped along with their [`Health`].
+pub(super) fn roll_elites(
+    settings: Res<EliteSettings>,
+    level_assets: Option<Res<LevelAssets>>,
+    mut enemies: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Health,
+            &mut FollowPlayerBehavior,
+            Option<&mut CanUseRangedAttack>,
+            Option<&mut StrafeBehavior>,
+            &MeshMaterial3d<StandardMaterial>,
+        ),
+        (With<Enemy>, Added<Spawning>),
+    >,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let level = level_assets.map_or(0, |assets| assets.current_level);
+    let chance = settings.chance(level);
+    let mut rng = thread_rng();
+    for (entity, mut transform, mut health, mut follow, ranged, strafe, material) in &mut enemies {
+        if !rng.gen_bool(chance.clamp(0.0, 1.0) as f64) {
+            continue;
+        }
+
+        let applicable: Vec<_> = Affix::ALL
+            .into_iter()
+            .filter(|affix| *affix != Affix::Deadeye || ranged.is_some())
+            .collect();
+        let count = rng.gen_range(1..=settings.max_affixes.clamp(1, applicable.len()));
+        let affixes: Vec<_> = applicable
+            .choose_multiple(&mut rng, count)
+            .copied()
+            .collect();
+
+        let mut ranged = ranged;
+        let mut strafe = strafe;
+        for affix in &affixes {
+            match affix {
+                Affix::Fast => {
+                    follow.movement_speed *= 1.5;
+                    if let Some(strafe) = strafe.as_mut() {
+                        strafe.movement_speed *= 1.5;
+                    }
+                }
+                Affix::Tanky => {
+                    health.0 += 2;
+                    commands.entity(entity).insert(MaxHealth(health.0));
+                }
+                Affix::Deadeye => {
+                    if let Some(ranged) = ranged.as_mut() {
+                        ranged.spread = 0.0;
+                        ranged.aim_lead = 1.0;
+                        ranged.speed *= 1.3;
+                    }
+                }
+                Affix::Explosive => {
+                    commands.entity(entity).observe(explode_on_death);
+                }
+            }
+        }
+
+        transform.scale = Vec3::splat(ELITE_SCALE);
+        if let Some(base) = materials.get(material).map(|material| material.base_color) {
+            let tint = base.mix(&affixes[0].color(), TINT_STRENGTH);
+            commands
+                .entity(entity)
+                .insert(MeshMaterial3d(materials.add(tint)));
+        }
+        debug!("Elite enemy with {affixes:?}");
+        commands.entity(entity).insert(Elite { affixes });
+    }
+}
+
+fn explode_on_death(
+    trigger: Trigger<DeathEvent>,
+    transforms: Query<&GlobalTransform>,
+    mut commands: Commands,
+) {
+    let Ok(transform) = transforms.get(trigger.target()) else {
+        return;
+    };
+    let position = transform.translation();
+    commands.trigger(LobDynamiteEvent {
+        origin: position,
+        target: position,
+        flight_time: 0.1,
+        fuse: 1.0,
+        radius: 3.0,
+        damage: 1,
+    });
+}
@@ -0,0 +1,138 @@
+//! Outlaws that survive a hit flinch: the hit shoves them back a little, they swell up for a moment
+//! and lose the burst they were firing, and their attack delay starts over once they've recovered.
+//! So landing a hit that doesn't kill still buys players some breathing room.
+//!
+//! How hard an outlaw flinches is up to its archetype, see [`Flinch`].
+
+use super::spawning::Spawning;
+use super::{BurstFire, CanDelayBetweenAttacks, Enemy};
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::health_and_damage::{DeathEvent, HurtEvent};
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Flinch>().register_type::<Flinching>();
+    app.add_observer(flinch_when_hurt)
+        .add_observer(stop_flinching_on_death);
+    app.add_systems(
+        Update,
+        recover_from_flinch.run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How an outlaw reacts to hits it survives.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Flinch {
+    /// How long the outlaw can't move or shoot.
+    pub seconds: f32,
+    /// How fast (in units per second) the hit shoves it away at first.
+    pub knockback: f32,
+    /// How much bigger it briefly gets, 0.25 is a quarter.
+    pub punch: f32,
+}
+
+impl Default for Flinch {
+    fn default() -> Self {
+        Self {
+            seconds: 0.4,
+            knockback: 6.0,
+            punch: 0.25,
+        }
+    }
+}
+
+impl Flinch {
+    /// For outlaws that are harder to rattle, like the ones carrying a shield.
+    pub const STURDY: Self = Self {
+        seconds: 0.2,
+        knockback: 2.0,
+        punch: 0.1,
+    };
+}
+
+/// An outlaw reeling from a hit. It doesn't move or shoot until this is gone.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Flinching {
+    timer: Timer,
+    knockback: Vec3,
+    punch: f32,
+    /// The outlaw's scale before the hit, to go back to afterwards.
+    scale: Vec3,
+}
+
+fn flinch_when_hurt(
+    trigger: Trigger<HurtEvent>,
+    mut enemies: Query<
+        (
+            &Transform,
+            &Flinch,
+            Option<&Flinching>,
+            &mut CanDelayBetweenAttacks,
+            &mut BurstFire,
+        ),
+        (With<Enemy>, Without<Spawning>),
+    >,
+    mut commands: Commands,
+) {
+    let Ok((transform, flinch, flinching, mut can_delay, mut burst)) =
+        enemies.get_mut(trigger.target())
+    else {
+        return;
+    };
+    can_delay.timer.reset();
+    burst.shots_left = 0;
+
+    // hits that didn't come from anywhere in particular still rattle them, they just stay put
+    let away = trigger
+        .event()
+        .from
+        .and_then(|from| (transform.translation - from).with_y(0.0).try_normalize())
+        .unwrap_or_default();
+    commands.entity(trigger.target()).insert(Flinching {
+        timer: Timer::from_seconds(flinch.seconds, TimerMode::Once),
+        knockback: away * flinch.knockback,
+        punch: flinch.punch,
+        // getting hit again mid-flinch shouldn't make the punch stick
+        scale: flinching.map_or(transform.scale, |flinching| flinching.scale),
+    });
+}
+
+fn recover_from_flinch(
+    time: GameTime,
+    mut flinching: Query<(Entity, &mut Flinching, &mut Transform, &mut LinearVelocity)>,
+    mut commands: Commands,
+) {
+    for (entity, mut flinch, mut transform, mut velocity) in &mut flinching {
+        flinch.timer.tick(time.delta());
+        if flinch.timer.finished() {
+            transform.scale = flinch.scale;
+            velocity.x = 0.0;
+            velocity.z = 0.0;
+            commands.entity(entity).remove::<Flinching>();
+            continue;
+        }
+        let progress = flinch.timer.fraction();
+        // shoved hard at first, then skidding to a halt
+        let knockback = flinch.knockback * (1.0 - progress);
+        velocity.x = knockback.x;
+        velocity.z = knockback.z;
+        // swells up and settles back down
+        transform.scale = flinch.scale * (1.0 + flinch.punch * (progress * PI).sin());
+    }
+}
+
+fn stop_flinching_on_death(
+    trigger: Trigger<DeathEvent>,
+    mut flinching: Query<(&Flinching, &mut Transform)>,
+    mut commands: Commands,
+) {
+    if let Ok((flinch, mut transform)) = flinching.get_mut(trigger.target()) {
+        transform.scale = flinch.scale;
+        commands.entity(trigger.target()).remove::<Flinching>();
+    }
+}
@@ -0,0 +1,665 @@
+use crate::ai::enemy_ai::{AiMovementState, FollowPlayerBehavior};
+use crate::ai::strafe::StrafeBehavior;
+use crate::asset_tracking::LoadResource;
+use crate::audio::{DuckMusic, DuckPriority, TimeDilatedPitch};
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::WeaponTarget;
+use crate::gameplay::bullets::FireBulletsEvent;
+use crate::gameplay::coop::CoopPartner;
+use crate::gameplay::decoy::Decoys;
+use crate::gameplay::health_and_damage::{DeathEvent, FrontalShield};
+use crate::gameplay::player::Player;
+use crate::gameplay::score::ScoreEvent;
+use crate::gameplay::throwing_plane::ThrowingPlane;
+use crate::gameplay::{boomerang::BoomerangHittable, health_and_damage::Health};
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use activity::{Activity, ActivityBubble};
+use archetype::{EnemyArchetype, RegisterEnemyArchetype};
+use avian3d::prelude::{
+    AngularDamping, Collider, CollisionLayers, Friction, LinearDamping, LinearVelocity, LockedAxes,
+    PhysicsLayer, Restitution, RigidBody, SpatialQuery, SpatialQueryFilter,
+};
+use bevy::color;
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use elite::{Elite, EliteSettings};
+use flinch::{Flinch, Flinching};
+use rand::{Rng, thread_rng};
+use shotgunner::ShotgunSpread;
+use spawning::Spawning;
+use squad::SquadId;
+
+pub mod activity;
+pub mod archetype;
+pub mod armored;
+mod barks;
+pub mod difficulty;
+pub mod dynamite_thrower;
+mod elite;
+pub mod flinch;
+pub mod shotgunner;
+pub mod spawning;
+pub mod squad;
+mod weak_point;
+
+pub fn plugin(app: &mut App) {
+    app.add_plugins((
+        archetype::plugin,
+        shotgunner::plugin,
+        dynamite_thrower::plugin,
+        armored::plugin,
+        spawning::plugin,
+        elite::plugin,
+        difficulty::plugin,
+        squad::plugin,
+        weak_point::plugin,
+        barks::plugin,
+        activity::plugin,
+        flinch::plugin,
+    ));
+    app.register_enemy_archetype::<EnemySpawnPoint>()
+        .register_type::<BurstFire>();
+    app.init_resource::<EnemySpawningConfig>();
+    app.load_resource::<PistoleroAssets>();
+    app.add_observer(strafe_between_shots);
+    app.init_gizmo_group::<EnemyAimGizmo>();
+    app.add_systems(
+        Update,
+        (
+            update_aim_preview_position,
+            attack_target_after_delay,
+            toggle_strafing,
+        )
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct CanUseRangedAttack {
+    damage: u32,
+    max_range: f32,
+    min_range: f32,
+    speed: f32,
+    /// How far ahead of a moving player this enemy aims, from 0.0 (straight at the player) to
+    /// 1.0 (exactly where the bullet and the player would meet).
+    aim_lead: f32,
+    /// The angle (in radians) of the cone each shot randomly lands in.
+    spread: f32,
+    /// How many shots are fired every time the attack comes off cooldown.
+    burst_count: u32,
+    /// Seconds between two shots of the same burst.
+    burst_interval: f32,
+}
+
+impl CanUseRangedAttack {
+    /// A weapon that fires one perfectly accurate shot per attack.
+    pub fn single_shot(
+        damage: u32,
+        max_range: f32,
+        min_range: f32,
+        speed: f32,
+        aim_lead: f32,
+    ) -> Self {
+        Self {
+            damage,
+            max_range,
+            min_range,
+            speed,
+            aim_lead,
+            spread: 0.0,
+            burst_count: 1,
+            burst_interval: 0.0,
+        }
+    }
+
+    /// Turns `direction` by a random angle within the spread cone.
+    fn scatter(&self, direction: Vec3, rng: &mut impl Rng) -> Vec3 {
+        if self.spread <= 0.0 {
+            return direction;
+        }
+        let half_spread = self.spread / 2.0;
+        Quat::from_rotation_y(rng.gen_range(-half_spread..=half_spread)) * direction
+    }
+
+    /// Where to aim from `origin` to hit a target at `target` moving with `target_velocity`.
+    fn aim_point(&self, origin: Vec3, target: Vec3, target_velocity: Vec3) -> Vec3 {
+        let target_velocity = target_velocity.with_y(0.0);
+        let Some(time) = intercept_time(origin, target, target_velocity, self.speed) else {
+            return target;
+        };
+        target + target_velocity * time * self.aim_lead.clamp(0.0, 1.0)
+    }
+}
+
+/// How long something flying at `speed` takes to meet a target moving at a constant velocity,
+/// if it can catch up at all. Outlaws use it to lead their shots.
+pub fn intercept_time(
+    origin: Vec3,
+    target: Vec3,
+    target_velocity: Vec3,
+    speed: f32,
+) -> Option<f32> {
+    let offset = target - origin;
+    // |offset + velocity * t| = speed * t, solved for t
+    let a = target_velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(target_velocity);
+    let c = offset.length_squared();
+
+    if a.abs() < f32::EPSILON {
+        let time = -c / b;
+        return (time > 0.0).then_some(time);
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+    match (t1 > 0.0, t2 > 0.0) {
+        (true, true) => Some(t1.min(t2)),
+        (true, false) => Some(t1),
+        (false, true) => Some(t2),
+        (false, false) => None,
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+struct CanDelayBetweenAttacks {
+    timer: Timer,
+}
+
+/// The burst an enemy is currently firing, if any.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+struct BurstFire {
+    shots_left: u32,
+    next_shot: Timer,
+}
+
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct EnemyAimGizmo;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PlayerSeeking;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(Activity, Flinch)]
+pub struct Enemy;
+
+/// Where a gunslinger spawns. Also the stats every other [`archetype`] hands to [`enemy_bundle`].
+#[derive(Component, Reflect, Copy, Clone, Debug, PartialEq)]
+#[reflect(Default, Component)]
+pub struct EnemySpawnPoint {
+    pub detection_range: f32,
+    pub movement_speed: f32,
+    pub attacks_per_second: f32,
+    /// The angle (in radians) of the cone each shot randomly lands in.
+    #[reflect(default)]
+    pub spread: f32,
+    /// Shots per attack. 0 counts as 1, so levels made before bursts existed keep working.
+    #[reflect(default)]
+    pub burst_count: u32,
+    /// Seconds between two shots of the same burst.
+    #[reflect(default)]
+    pub burst_interval: f32,
+    /// Outlaws with the same squad number fight together, 0 for none. See [`squad`].
+    #[reflect(default)]
+    pub squad: u32,
+}
+
+impl Default for EnemySpawnPoint {
+    fn default() -> Self {
+        let default_ai = FollowPlayerBehavior::default();
+        Self {
+            detection_range: default_ai.detection_range,
+            movement_speed: default_ai.movement_speed,
+            attacks_per_second: 1.0,
+            spread: 0.0,
+            burst_count: 1,
+            burst_interval: 0.15,
+            squad: 0,
+        }
+    }
+}
+
+/// The plain gunslinger, the outlaw every other archetype builds on.
+impl EnemyArchetype for EnemySpawnPoint {
+    const NAME: &'static str = "Gunslinger";
+    const COLOR: Color = Color::srgb_u8(124, 32, 32);
+
+    fn stats(&self) -> EnemySpawnPoint {
+        *self
+    }
+
+    fn equip(
+        &self,
+        enemy: &mut EntityCommands,
+        _meshes: &mut Assets<Mesh>,
+        _materials: &mut Assets<StandardMaterial>,
+    ) {
+        enemy.insert(CanUseRangedAttack {
+            damage: 1,
+            max_range: 15.,
+            min_range: 2.,
+            speed: 15.,
+            aim_lead: 0.75,
+            spread: self.spread,
+            burst_count: self.burst_count.max(1),
+            burst_interval: self.burst_interval,
+        });
+    }
+}
+
+/// Everything enemies have in common, no matter their archetype.
+pub fn enemy_bundle(
+    name: &'static str,
+    position: Transform,
+    spawn_point: &EnemySpawnPoint,
+    color: Color,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+) -> impl Bundle {
+    (
+        (
+            Enemy,
+            Name::new(name),
+            FollowPlayerBehavior {
+                detection_range: spawn_point.detection_range,
+                movement_speed: spawn_point.movement_speed,
+                ..default()
+            },
+            position,
+            Mesh3d(meshes.add(Capsule3d::default())),
+            MeshMaterial3d(materials.add(color)),
+            StateScoped(Screen::Gameplay),
+            BoomerangHittable,
+            Collider::capsule(0.5, 1.),
+            CollisionLayers::new(
+                GameLayer::Enemy,
+                [
+                    GameLayer::Player,
+                    GameLayer::Boomerang,
+                    // GameLayer::Terrain,
+                    // GameLayer::Default,
+                ],
+            ),
+            LinearVelocity::ZERO,
+            LockedAxes::ROTATION_LOCKED.lock_translation_y(),
+            RigidBody::Kinematic,
+            Health(1),
+        ),
+        CanDelayBetweenAttacks {
+            timer: Timer::from_seconds(1.0 / spawn_point.attacks_per_second, TimerMode::Repeating), // todo revert cooldown when done testing navmesh stuff
+        },
+        BurstFire::default(),
+        WeaponTarget {
+            target_entity: None,
+        },
+        SquadId(spawn_point.squad),
+    )
+}
+
+/// Gunmen sidestep while reloading, so they're harder to pin down.
+fn strafe_between_shots(
+    trigger: Trigger<OnAdd, CanUseRangedAttack>,
+    attackers: Query<(&CanUseRangedAttack, &FollowPlayerBehavior)>,
+    mut commands: Commands,
+) {
+    let Ok((ranged_attack, follow)) = attackers.get(trigger.target()) else {
+        return;
+    };
+    commands
+        .entity(trigger.target())
+        .insert(StrafeBehavior::new(
+            follow.movement_speed,
+            ranged_attack.min_range,
+        ));
+}
+
+/// Strafe only while the player is in sight and the gun is on cooldown.
+fn toggle_strafing(mut attackers: Query<(&WeaponTarget, &BurstFire, &mut StrafeBehavior)>) {
+    for (target, burst, mut strafe) in &mut attackers {
+        strafe.active = target.target_entity.is_some() && burst.shots_left == 0;
+    }
+}
+
+fn update_aim_preview_position(
+    mut attacker_query: Query<
+        (
+            Entity,
+            &Transform,
+            &CanUseRangedAttack,
+            &BurstFire,
+            &Activity,
+            &mut WeaponTarget,
+        ),
+        Without<Spawning>,
+    >,
+    player_query: Single<(Entity, &Transform, Option<&LinearVelocity>), With<Player>>,
+    partners: Query<(Entity, &Transform, Option<&LinearVelocity>), With<CoopPartner>>,
+    decoys: Decoys,
+    spatial_query: SpatialQuery,
+    throwing_plane: Res<ThrowingPlane>,
+    activity_bubble: ActivityBubble,
+    mut gizmos: Gizmos<EnemyAimGizmo>,
+) {
+    let (player_entity, player_transform, player_velocity) = player_query.into_inner();
+    let player_translation = player_transform.translation;
+    let player_velocity = player_velocity.map(|v| v.0).unwrap_or_default();
+
+    for (
+        origin_entity,
+        origin_transform,
+        can_use_ranged_attack,
+        burst,
+        activity,
+        mut weapon_target,
+    ) in attacker_query.iter_mut()
+    {
+        if *activity == Activity::Suspended {
+            weapon_target.target_entity = None;
+            continue;
+        }
+        // throttled ones keep whatever they saw last time
+        if !activity_bubble.is_due(origin_entity, *activity) {
+            continue;
+        }
+        let origin = throwing_plane.project(origin_transform.translation);
+        let max_distance = can_use_ranged_attack.max_range;
+        let solid = true;
+        let filter = SpatialQueryFilter {
+            excluded_entities: EntityHashSet::from([origin_entity]),
+            ..Default::default()
+        };
+
+        // in co-op, whichever player is closer gets shot at first
+        let mut players = vec![(player_entity, player_translation, player_velocity)];
+        players.extend(partners.iter().map(|(partner, transform, velocity)| {
+            let velocity = velocity.map(|v| v.0).unwrap_or_default();
+            (partner, transform.translation, velocity)
+        }));
+        players.sort_by(|(_, a, _), (_, b, _)| {
+            a.distance_squared(origin)
+                .total_cmp(&b.distance_squared(origin))
+        });
+
+        // decoys draw the fire while they're in sight, they don't move
+        let candidates = decoys
+            .closest(origin, max_distance)
+            .map(|(decoy, position)| (decoy, position, Vec3::ZERO))
+            .into_iter()
+            .chain(players);
+        let in_sight = candidates.find_map(|(target, translation, velocity)| {
+            let (ray, _) = throwing_plane.ray_between(origin, translation)?;
+            let direction = *ray.direction;
+            let first_hit =
+                spatial_query.cast_ray(origin, ray.direction, max_distance, solid, &filter)?;
+            (first_hit.entity == target).then_some((
+                target,
+                translation,
+                velocity,
+                direction,
+                first_hit.distance,
+            ))
+        });
+        let Some((target, translation, velocity, direction, distance)) = in_sight else {
+            weapon_target.target_entity = None;
+            continue;
+        };
+
+        // the telegraph shows where the shot will actually go, not where the target is
+        let aim_point =
+            throwing_plane.project(can_use_ranged_attack.aim_point(origin, translation, velocity));
+        let aim_direction = (aim_point - origin).normalize_or(direction);
+        let target_location = origin + aim_direction * distance;
+
+        // turns yellow while a burst is coming out, so players know when it's safe to peek
+        let color = if burst.shots_left > 0 {
+            color::palettes::css::YELLOW
+        } else {
+            color::palettes::css::RED
+        };
+        gizmos.line(origin, target_location, color);
+        if can_use_ranged_attack.spread > 0.0 {
+            let half_spread = can_use_ranged_attack.spread / 2.0;
+            for angle in [-half_spread, half_spread] {
+                let edge = Quat::from_rotation_y(angle) * aim_direction;
+                gizmos.line(origin, origin + edge * distance, color.with_alpha(0.3));
+            }
+        }
+        weapon_target.target_entity = Some(target);
+    }
+}
+
+fn attack_target_after_delay(
+    mut commands: Commands,
+    mut attacker_query: Query<
+        (
+            Entity,
+            &CanUseRangedAttack,
+            &Transform,
+            &WeaponTarget,
+            &mut CanDelayBetweenAttacks,
+            &mut BurstFire,
+            Option<&ShotgunSpread>,
+            &Activity,
+        ),
+        (With<Enemy>, Without<Spawning>, Without<Flinching>),
+    >,
+    time: GameTime,
+    targets: Query<(&Transform, Option<&LinearVelocity>), Without<Enemy>>,
+    pistolero_assets: Res<PistoleroAssets>,
+) {
+    let mut rand = thread_rng();
+    for (
+        attacker_entity,
+        ranged_attack,
+        origin_transform,
+        attacker_target,
+        mut can_delay,
+        mut burst,
+        spread,
+        activity,
+    ) in attacker_query.iter_mut()
+    {
+        // cheap enough to keep going for throttled ones, so their shots stay on time
+        if *activity == Activity::Suspended {
+            continue;
+        }
+        can_delay.timer.tick(time.delta());
+        let Some((target_transform, target_velocity)) = attacker_target
+            .target_entity
+            .and_then(|target| targets.get(target).ok())
+        else {
+            // lost sight of the target, no point in emptying the rest of the burst into a wall
+            burst.shots_left = 0;
+            continue;
+        };
+        if can_delay.timer.just_finished() {
+            burst.shots_left = ranged_attack.burst_count;
+            // the first shot goes out right away
+            burst.next_shot = Timer::default();
+        }
+        burst.next_shot.tick(time.delta());
+        if burst.shots_left > 0 && burst.next_shot.finished() {
+            burst.shots_left -= 1;
+            burst.next_shot = Timer::from_seconds(ranged_attack.burst_interval, TimerMode::Once);
+
+            let aim_point = ranged_attack.aim_point(
+                origin_transform.translation,
+                target_transform.translation,
+                target_velocity.map(|v| v.0).unwrap_or_default(),
+            );
+            let bullet_velocity = ranged_attack.scatter(
+                (aim_point - origin_transform.translation).normalize_or_zero(),
+                &mut rand,
+            );
+
+            // particles
+            commands.entity(attacker_entity).trigger(
+                SpawnVfxEvent::new(Vfx::GunSmoke, origin_transform.translation)
+                    .toward(bullet_velocity),
+            );
+
+            // bullets
+            let mut bullets = FireBulletsEvent::single(
+                origin_transform.translation,
+                bullet_velocity,
+                ranged_attack.speed,
+                ranged_attack.damage,
+            );
+            if let Some(spread) = spread {
+                bullets.pellets = spread.pellets;
+                bullets.spread = spread.spread;
+                bullets.falloff = Some(spread.falloff);
+            }
+            commands.trigger(bullets);
+
+            // shotguns sound deeper than pistols
+            let base_pitch = if spread.is_some() { 0.5 } else { 0.8 };
+            let pitch = rand.r#gen::<f32>() * 0.4;
+            commands.spawn((
+                Name::from("Gunshot SFX"),
+                AudioPlayer::new(pistolero_assets.gunshot.clone()),
+                PlaybackSettings::DESPAWN,
+                TimeDilatedPitch(base_pitch + pitch),
+            ));
+            commands.spawn((
+                Name::new("ShellCasing"),
+                Transform::from_translation(origin_transform.translation),
+                SceneRoot(pistolero_assets.shell.clone()),
+                Collider::cylinder(0.05, 0.2),
+                CollisionLayers::new(GameLayer::DeadEnemy, GameLayer::all_bits()),
+                RigidBody::Dynamic,
+                LinearVelocity(-bullet_velocity * 3.),
+                Friction::default(),
+                Restitution::default(),
+                LinearDamping(0.5),
+                AngularDamping(0.5),
+                StateScoped(Screen::Gameplay),
+            ));
+        }
+    }
+}
+
+/// Turns a dead enemy into a ragdoll and pays out its bounty. Observe it on every enemy spawned
+/// with [`enemy_bundle`].
+pub(super) fn on_death(
+    trigger: Trigger<DeathEvent>,
+    query: Query<(&Transform, Option<&Elite>)>,
+    elite_settings: Res<EliteSettings>,
+    pistolero_assets: Res<PistoleroAssets>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands
+        .entity(trigger.target())
+        .remove::<CanUseRangedAttack>()
+        .remove::<CanDelayBetweenAttacks>()
+        .remove::<BurstFire>()
+        .remove::<StrafeBehavior>()
+        .remove::<FrontalShield>()
+        .remove::<FollowPlayerBehavior>()
+        .remove::<AiMovementState>()
+        .remove::<LockedAxes>()
+        .insert(RigidBody::Dynamic)
+        .insert(MeshMaterial3d(materials.add(Color::srgb_u8(240, 200, 200))))
+        // how hard they get sent flying depends on the violence level, see death_fx
+        .insert(LinearDamping(0.5))
+        .insert(AngularDamping(0.5))
+        .insert(CollisionLayers::new(
+            GameLayer::DeadEnemy,
+            GameLayer::all_bits(),
+        ));
+    let (transform, elite) = query.get(trigger.target()).unwrap();
+    let multiplicator = trigger.event().0 as f32
+        * elite.map_or(1.0, |elite| elite.bounty_multiplier(&elite_settings));
+    commands.trigger(ScoreEvent::AddScore(
+        100. * multiplicator,
+        transform.translation,
+    ));
+    commands.trigger(ScoreEvent::EnemyDeath);
+    let rand = thread_rng().gen_range(0..pistolero_assets.death_screams.len());
+    commands.spawn((
+        Name::from("DeathScream"),
+        AudioPlayer::new(pistolero_assets.death_screams[rand].clone()),
+        PlaybackSettings::DESPAWN,
+        TimeDilatedPitch(1.0),
+    ));
+    commands.trigger(DuckMusic(DuckPriority::DeathScream));
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct EnemySpawningConfig {
+    num_enemies: usize,
+    min_radius: f64,
+    max_radius: f64,
+}
+
+impl Default for EnemySpawningConfig {
+    fn default() -> Self {
+        Self {
+            num_enemies: 0,
+            min_radius: 5.,
+            max_radius: 30.,
+        }
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct PistoleroAssets {
+    gunshot: Handle<AudioSource>,
+    shell: Handle<Scene>,
+    death_screams: Vec<Handle<AudioSource>>,
+}
+
+impl FromWorld for PistoleroAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let death_scream = vec![
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 1.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 2.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 3.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 4.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 5.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 6.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 1.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 2.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 3.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 4.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 5.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/Wilhelm 6.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death1.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death2.ogg"),
+            // asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death3.ogg"),
+            // asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death4.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death5.ogg"),
+            // asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death6.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death7.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death8.ogg"),
+            asset_server.load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death9.ogg"),
+            asset_server
+                .load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death10.ogg"),
+            asset_server
+                .load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death11.ogg"),
+            // asset_server
+            //     .load("audio/sound_effects/Wilhelm-ScreamSFX/enemy_death/enemy_death12.ogg"),
+        ];
+        PistoleroAssets {
+            gunshot: asset_server.load("audio/sound_effects/213925__diboz__pistol_riccochet.ogg"),
+            shell: asset_server
+                .load(GltfAssetLabel::Scene(0).from_asset("models/bullet_casing.glb")),
+            death_screams: death_scream,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Shotgunners fire a wide spread of pellets that lose their punch over distance, but take their
+//! time reloading. Best dodged by keeping your distance or slipping between the pellets.
+
+use super::archetype::{EnemyArchetype, RegisterEnemyArchetype};
+use super::{CanUseRangedAttack, EnemySpawnPoint};
+use crate::ai::enemy_ai::FollowPlayerBehavior;
+use crate::gameplay::bullets::DamageFalloff;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_enemy_archetype::<ShotgunnerSpawnPoint>()
+        .register_type::<ShotgunSpread>();
+}
+
+/// Enemies with this component fire several pellets at once instead of a single bullet.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ShotgunSpread {
+    pub pellets: usize,
+    /// The angle (in radians) between the outermost pellets.
+    pub spread: f32,
+    pub falloff: DamageFalloff,
+}
+
+impl Default for ShotgunSpread {
+    fn default() -> Self {
+        Self {
+            pellets: 5,
+            spread: 40f32.to_radians(),
+            falloff: DamageFalloff {
+                full_damage_range: 5.,
+                reduced_damage: 1,
+                max_range: 12.,
+            },
+        }
+    }
+}
+
+#[derive(Component, Reflect, Copy, Clone, Debug, PartialEq)]
+#[reflect(Default, Component)]
+pub struct ShotgunnerSpawnPoint {
+    detection_range: f32,
+    movement_speed: f32,
+    attacks_per_second: f32,
+    /// See [`super::squad`].
+    #[reflect(default)]
+    squad: u32,
+}
+
+impl Default for ShotgunnerSpawnPoint {
+    fn default() -> Self {
+        let default_ai = FollowPlayerBehavior::default();
+        Self {
+            detection_range: default_ai.detection_range,
+            movement_speed: default_ai.movement_speed * 0.8,
+            attacks_per_second: 0.4,
+            squad: 0,
+        }
+    }
+}
+
+impl EnemyArchetype for ShotgunnerSpawnPoint {
+    const NAME: &'static str = "Shotgunner";
+    const COLOR: Color = Color::srgb_u8(92, 64, 24);
+
+    fn stats(&self) -> EnemySpawnPoint {
+        EnemySpawnPoint {
+            detection_range: self.detection_range,
+            movement_speed: self.movement_speed,
+            attacks_per_second: self.attacks_per_second,
+            squad: self.squad,
+            ..default()
+        }
+    }
+
+    fn equip(
+        &self,
+        enemy: &mut EntityCommands,
+        _meshes: &mut Assets<Mesh>,
+        _materials: &mut Assets<StandardMaterial>,
+    ) {
+        enemy.insert((
+            CanUseRangedAttack::single_shot(2, 10., 2., 12., 0.25),
+            ShotgunSpread::default(),
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+//! Enemies coming out of spawn points don't just pop into existence. A dust devil whirls over a
+//! shadow on the ground for a moment first, then the enemy rises out of the ground.
+//! Until it's all the way up, an enemy can't be hurt and doesn't move or shoot, so players don't
+//! get cheap kills on enemies that aren't ready yet (or the other way around).
+//!
+//! Spawn points that ended up inside a wall or off the navmesh get moved to the closest spot nearby
+//! that isn't, before the dust devil shows up, so enemies never spawn stuck or out of reach.
+
+use crate::ai::strafe::closest_on_nav_mesh;
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::health_and_damage::Invulnerable;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use avian3d::prelude::{Collider, LinearVelocity, SpatialQuery, SpatialQueryFilter};
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+use oxidized_navigation::{NavMesh, NavMeshSettings};
+use rand::{Rng, thread_rng};
+use std::f32::consts::TAU;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(start_telegraph);
+    app.add_systems(
+        Update,
+        (
+            (find_clear_spawn_position, rise_from_ground).chain(),
+            spin_dust_devils,
+        )
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How long the dust devil spins before the enemy starts rising.
+const TELEGRAPH_SECONDS: f32 = 1.0;
+const RISE_SECONDS: f32 = 0.5;
+/// How deep below its spawn point the enemy waits, enough to be out of reach of boomerangs.
+const SINK_DEPTH: f32 = 3.0;
+/// Enemies stand this far above the ground, which is where the shadow goes.
+const HALF_HEIGHT: f32 = 1.0;
+
+const DUST_MOTES: usize = 12;
+const DUST_DEVIL_RADIUS: f32 = 0.8;
+const DUST_DEVIL_HEIGHT: f32 = 2.5;
+const DUST_COLOR: Color = Color::srgba(0.76, 0.63, 0.45, 0.7);
+const SHADOW_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+
+/// How long into the telegraph the spawn position gets checked, so the level's colliders are in
+/// the physics world by then.
+const SETTLE_SECONDS: f32 = 0.2;
+/// Spawn points get moved at most this far.
+const MAX_SPAWN_CORRECTION: f32 = 4.0;
+/// Distance between the rings of positions tried around a bad spawn point.
+const SPAWN_CORRECTION_STEP: f32 = 0.5;
+const SPAWN_CORRECTION_DIRECTIONS: usize = 8;
+/// A bit slimmer and shorter than an actual enemy, so standing on the ground or brushing against
+/// a wall doesn't count as being stuck in it.
+const CLEARANCE_RADIUS: f32 = 0.45;
+const CLEARANCE_LENGTH: f32 = 0.5;
+
+/// An enemy on its way out of the ground. Add this along with the enemy, spawn points do.
+#[derive(Component, Debug)]
+pub struct Spawning {
+    timer: Timer,
+    /// Where the enemy ends up once it's fully risen.
+    standing_y: f32,
+    /// Whether the spawn position was checked for walls and the navmesh yet.
+    checked: bool,
+    pub(super) dust_devil: Option<Entity>,
+}
+
+impl Default for Spawning {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(TELEGRAPH_SECONDS + RISE_SECONDS, TimerMode::Once),
+            standing_y: 0.0,
+            checked: false,
+            dust_devil: None,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct DustDevil {
+    timer: Timer,
+}
+
+#[derive(Component, Debug)]
+struct DustMote {
+    angle: f32,
+    /// 0.0 at the bottom of the dust devil, 1.0 at the top.
+    height: f32,
+}
+
+fn start_telegraph(
+    trigger: Trigger<OnAdd, Spawning>,
+    mut spawning: Query<(&mut Spawning, &mut Transform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let Ok((mut spawning, mut transform)) = spawning.get_mut(trigger.target()) else {
+        return;
+    };
+    spawning.standing_y = transform.translation.y;
+    transform.translation.y -= SINK_DEPTH;
+    commands.entity(trigger.target()).insert(Invulnerable);
+
+    let ground = transform
+        .translation
+        .with_y(spawning.standing_y - HALF_HEIGHT);
+    let mote_mesh = meshes.add(Plane3d::default().mesh().size(0.25, 0.25));
+    let mote_material = materials.add(StandardMaterial {
+        base_color: DUST_COLOR,
+        alpha_mode: AlphaMode::Blend,
+        double_sided: true,
+        unlit: true,
+        ..default()
+    });
+    let mut rng = thread_rng();
+    let motes: Vec<_> = (0..DUST_MOTES)
+        .map(|_| DustMote {
+            angle: rng.gen_range(0.0..TAU),
+            height: rng.r#gen(),
+        })
+        .collect();
+
+    let dust_devil = commands
+        .spawn((
+            Name::new("Dust Devil"),
+            DustDevil {
+                timer: Timer::from_seconds(TELEGRAPH_SECONDS + RISE_SECONDS, TimerMode::Once),
+            },
+            Transform::from_translation(ground),
+            // shows up once it's clear where the enemy comes out
+            Visibility::Hidden,
+            StateScoped(Screen::Gameplay),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("Spawn Shadow"),
+                Mesh3d(meshes.add(Circle::new(DUST_DEVIL_RADIUS))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: SHADOW_COLOR,
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                })),
+                // lying flat, just above the ground so it doesn't z-fight
+                Transform::from_xyz(0.0, 0.01, 0.0)
+                    .with_rotation(Quat::from_rotation_x(-TAU / 4.0)),
+                NotShadowCaster,
+                NotShadowReceiver,
+            ));
+            for mote in motes {
+                parent.spawn((
+                    Name::new("Dust Mote"),
+                    Mesh3d(mote_mesh.clone()),
+                    MeshMaterial3d(mote_material.clone()),
+                    Transform::default(),
+                    mote,
+                    NotShadowCaster,
+                    NotShadowReceiver,
+                ));
+            }
+        })
+        .id();
+    spawning.dust_devil = Some(dust_devil);
+}
+
+/// Looks for the closest spot around the spawn point that's out of walls, on the navmesh and can
+/// be walked to from the spawn point in a straight line. The navmesh may still be generating when
+/// the level starts, so this keeps trying until the enemy starts rising, and settles for a spot
+/// that's just out of walls then.
+fn find_clear_spawn_position(
+    nav_mesh_settings: Option<Res<NavMeshSettings>>,
+    nav_mesh: Option<Res<NavMesh>>,
+    spatial_query: SpatialQuery,
+    mut spawning: Query<(&mut Spawning, &mut Transform), Without<DustDevil>>,
+    mut dust_devils: Query<(&mut Transform, &mut Visibility), With<DustDevil>>,
+) {
+    let tiles = nav_mesh.map(|nav_mesh| nav_mesh.get());
+    let tiles = tiles.as_ref().and_then(|tiles| tiles.read().ok());
+    let filter = SpatialQueryFilter::from_mask([GameLayer::Terrain, GameLayer::Default]);
+    let clearance = Collider::capsule(CLEARANCE_RADIUS, CLEARANCE_LENGTH);
+
+    for (mut spawning, mut transform) in &mut spawning {
+        if spawning.checked || spawning.timer.elapsed_secs() < SETTLE_SECONDS {
+            continue;
+        }
+        let origin = transform.translation.with_y(spawning.standing_y);
+        let out_of_walls = |point: Vec3| {
+            let reachable = point == origin
+                || Dir3::new(point - origin).is_ok_and(|direction| {
+                    spatial_query
+                        .cast_ray(origin, direction, origin.distance(point), true, &filter)
+                        .is_none()
+                });
+            reachable
+                && spatial_query
+                    .shape_intersections(&clearance, point, Quat::IDENTITY, &filter)
+                    .is_empty()
+        };
+        let on_nav_mesh = |point: Vec3| match (&tiles, &nav_mesh_settings) {
+            (Some(tiles), Some(settings)) => closest_on_nav_mesh(tiles, settings, point).is_some(),
+            _ => true,
+        };
+
+        let out_of_time = spawning.timer.elapsed_secs() >= TELEGRAPH_SECONDS;
+        let clear = spawn_candidates(origin)
+            .find(|point| out_of_walls(*point) && on_nav_mesh(*point))
+            .or_else(|| {
+                out_of_time
+                    .then(|| spawn_candidates(origin).find(|point| out_of_walls(*point)))
+                    .flatten()
+            });
+        let position = match clear {
+            Some(position) => position,
+            None if out_of_time => {
+                warn!("No clear spot around the enemy spawn point at {origin}, spawning it anyway");
+                origin
+            }
+            // try again next frame, the navmesh might be done by then
+            None => continue,
+        };
+
+        spawning.checked = true;
+        transform.translation.x = position.x;
+        transform.translation.z = position.z;
+        if let Some((mut dust_devil, mut visibility)) = spawning
+            .dust_devil
+            .and_then(|dust_devil| dust_devils.get_mut(dust_devil).ok())
+        {
+            dust_devil.translation.x = position.x;
+            dust_devil.translation.z = position.z;
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+/// The spawn point itself, then rings of points around it, closest first.
+fn spawn_candidates(origin: Vec3) -> impl Iterator<Item = Vec3> {
+    let rings = (MAX_SPAWN_CORRECTION / SPAWN_CORRECTION_STEP) as usize;
+    let around = (1..=rings).flat_map(move |ring| {
+        (0..SPAWN_CORRECTION_DIRECTIONS).map(move |index| {
+            let angle = TAU * index as f32 / SPAWN_CORRECTION_DIRECTIONS as f32;
+            origin + Vec3::new(angle.cos(), 0.0, angle.sin()) * SPAWN_CORRECTION_STEP * ring as f32
+        })
+    });
+    std::iter::once(origin).chain(around)
+}
+
+fn rise_from_ground(
+    time: GameTime,
+    mut spawning: Query<(Entity, &mut Spawning, &mut Transform, &mut LinearVelocity)>,
+    mut commands: Commands,
+) {
+    for (entity, mut spawning, mut transform, mut velocity) in &mut spawning {
+        velocity.0 = Vec3::ZERO;
+        spawning.timer.tick(time.delta());
+        let rising = (spawning.timer.elapsed_secs() - TELEGRAPH_SECONDS).max(0.0) / RISE_SECONDS;
+        let rising = EaseFunction::BackOut.sample_clamped(rising);
+        transform.translation.y = spawning.standing_y - SINK_DEPTH * (1.0 - rising);
+
+        if spawning.timer.finished() {
+            transform.translation.y = spawning.standing_y;
+            commands
+                .entity(entity)
+                .remove::<Spawning>()
+                .remove::<Invulnerable>();
+        }
+    }
+}
+
+fn spin_dust_devils(
+    time: GameTime,
+    mut devils: Query<(Entity, &mut DustDevil, &mut Transform), Without<DustMote>>,
+    mut motes: Query<(&mut DustMote, &mut Transform), Without<DustDevil>>,
+    mut commands: Commands,
+) {
+    for (entity, mut devil, mut transform) in &mut devils {
+        if devil.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // blows away as the enemy comes up
+        let fade = (devil.timer.remaining_secs() / RISE_SECONDS).min(1.0);
+        transform.scale = Vec3::splat(fade);
+    }
+
+    let dt = time.delta_secs();
+    for (mut mote, mut transform) in &mut motes {
+        mote.angle = (mote.angle + dt * 8.0) % TAU;
+        mote.height = (mote.height + dt * 0.8) % 1.0;
+        // wider at the top, like a proper twister
+        let radius = DUST_DEVIL_RADIUS * (0.3 + 0.7 * mote.height);
+        transform.translation = Vec3::new(
+            mote.angle.cos() * radius,
+            mote.height * DUST_DEVIL_HEIGHT,
+            mote.angle.sin() * radius,
+        );
+        transform.rotation = Quat::from_rotation_y(-mote.angle);
+    }
+}
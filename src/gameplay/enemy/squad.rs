@@ -0,0 +1,192 @@
+//! Squads: outlaws whose spawn points share a `squad` number fight together. As soon as one of
+//! them spots the player, gets hurt or dies, the whole squad is [`Alerted`] and comes for the
+//! player, no matter how far away the others are.
+//!
+//! Once alerted, the squad spreads out its attack timers, so the player doesn't get shot by every
+//! member at the same moment. Members also keep some space between each other while they close in,
+//! instead of walking up in a single file.
+//!
+//! Outlaws with squad number 0 are on their own, like before squads existed.
+
+use super::CanDelayBetweenAttacks;
+use super::spawning::Spawning;
+use crate::HotSystems;
+use crate::ai::enemy_ai::{AiMovementState, FollowPlayerBehavior};
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::WeaponTarget;
+use crate::gameplay::health_and_damage::{DeathEvent, Health, HurtEvent};
+use crate::screens::Screen;
+use avian3d::prelude::LinearVelocity;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SquadId>().init_resource::<Squads>();
+    app.add_observer(alert_squad_on_hurt)
+        .add_observer(alert_squad_on_death);
+    app.add_systems(OnEnter(Screen::Gameplay), forget_squads);
+    app.add_systems(
+        Update,
+        (
+            (spot_player, alert_members).chain(),
+            keep_spacing.after(HotSystems::EnemyAi),
+        )
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// Moving squad members try to stay at least this far apart.
+const SPACING: f32 = 2.5;
+/// How hard members push away from each other, relative to their movement speed.
+const SPACING_WEIGHT: f32 = 0.6;
+
+/// Which squad an outlaw belongs to, 0 for none.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct SquadId(pub u32);
+
+impl SquadId {
+    pub fn is_squad(self) -> bool {
+        self.0 != 0
+    }
+}
+
+/// Put on every member of a squad that's on to the player. They chase the player regardless of
+/// their detection range.
+#[derive(Component, Debug, Default)]
+pub struct Alerted;
+
+/// The squads of the current level that are on to the player.
+#[derive(Resource, Debug, Default)]
+pub struct Squads {
+    alerted: HashSet<u32>,
+    /// Alerted since their attacks were last spread out.
+    newly_alerted: HashSet<u32>,
+}
+
+impl Squads {
+    /// Puts the whole squad on alert. Does nothing for outlaws that aren't in one.
+    pub fn alert(&mut self, squad: SquadId) {
+        if squad.is_squad() && self.alerted.insert(squad.0) {
+            self.newly_alerted.insert(squad.0);
+        }
+    }
+
+    pub fn is_alerted(&self, squad: SquadId) -> bool {
+        self.alerted.contains(&squad.0)
+    }
+}
+
+fn forget_squads(mut squads: ResMut<Squads>) {
+    *squads = Squads::default();
+}
+
+fn alert_squad_on_hurt(
+    trigger: Trigger<HurtEvent>,
+    members: Query<&SquadId>,
+    mut squads: ResMut<Squads>,
+) {
+    if let Ok(squad) = members.get(trigger.target()) {
+        squads.alert(*squad);
+    }
+}
+
+fn alert_squad_on_death(
+    trigger: Trigger<DeathEvent>,
+    members: Query<&SquadId>,
+    mut squads: ResMut<Squads>,
+) {
+    if let Ok(squad) = members.get(trigger.target()) {
+        squads.alert(*squad);
+    }
+}
+
+fn spot_player(
+    members: Query<(&SquadId, &WeaponTarget), (Without<Alerted>, Without<Spawning>)>,
+    mut squads: ResMut<Squads>,
+) {
+    for (squad, weapon_target) in &members {
+        if weapon_target.target_entity.is_some() {
+            squads.alert(*squad);
+        }
+    }
+}
+
+fn alert_members(
+    mut squads: ResMut<Squads>,
+    mut members: Query<
+        (
+            Entity,
+            &SquadId,
+            Option<&mut CanDelayBetweenAttacks>,
+            Has<Alerted>,
+        ),
+        With<Health>,
+    >,
+    mut commands: Commands,
+) {
+    // also catches members that only spawned after their squad was alerted
+    for (entity, squad, _, alerted) in &members {
+        if !alerted && squads.is_alerted(*squad) {
+            commands.entity(entity).insert(Alerted);
+        }
+    }
+
+    // an even spread over one attack interval, so the shots come one after the other
+    for squad in std::mem::take(&mut squads.newly_alerted) {
+        let mut delays: Vec<_> = members
+            .iter_mut()
+            .filter(|(_, id, ..)| id.0 == squad)
+            .filter_map(|(_, _, delay, _)| delay)
+            .collect();
+        let count = delays.len() as f32;
+        for (index, delay) in delays.iter_mut().enumerate() {
+            let interval = delay.timer.duration();
+            delay
+                .timer
+                .set_elapsed(interval.mul_f32(index as f32 / count));
+        }
+    }
+}
+
+fn keep_spacing(
+    mut members: Query<
+        (
+            Entity,
+            &SquadId,
+            &Transform,
+            &FollowPlayerBehavior,
+            &AiMovementState,
+            &mut LinearVelocity,
+        ),
+        Without<Spawning>,
+    >,
+) {
+    let positions: Vec<(Entity, SquadId, Vec3)> = members
+        .iter()
+        .map(|(entity, squad, transform, ..)| (entity, *squad, transform.translation))
+        .collect();
+    for (entity, squad, transform, behavior, state, mut velocity) in &mut members {
+        if !squad.is_squad() || !matches!(state, AiMovementState::Moving { .. }) {
+            continue;
+        }
+        let me = transform.translation;
+        // the closer a squad mate, the harder the push
+        let push: Vec3 = positions
+            .iter()
+            .filter(|(other, other_squad, _)| *other != entity && other_squad == squad)
+            .filter_map(|(_, _, position)| {
+                let away = (me - *position).with_y(0.0);
+                let distance = away.length();
+                (distance < SPACING).then(|| away.normalize_or_zero() * (1.0 - distance / SPACING))
+            })
+            .sum();
+        if push == Vec3::ZERO {
+            continue;
+        }
+        let steered = (velocity.0.with_y(0.0) + push * behavior.movement_speed * SPACING_WEIGHT)
+            .clamp_length_max(behavior.movement_speed);
+        velocity.x = steered.x;
+        velocity.z = steered.z;
+    }
+}
@@ -0,0 +1,91 @@
+//! Every outlaw has a [`WeakPoint`] at the back of their head. Boomerangs coming in from the front
+//! run into the body first, so it takes flanking them or a ricochet around them to land a critical
+//! hit. Those deal double damage, pay an extra bounty, ding and throw sparks.
+
+use super::Enemy;
+use crate::asset_tracking::LoadResource;
+use crate::audio::TimeDilatedPitch;
+use crate::gameplay::health_and_damage::{CriticalHitEvent, DeathEvent, WeakPoint};
+use crate::gameplay::score::ScoreEvent;
+use crate::physics_layers::GameLayer;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use avian3d::prelude::{Collider, CollisionLayers};
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<WeakPointAssets>()
+        .load_resource::<WeakPointAssets>();
+    app.add_observer(add_weak_point)
+        .add_observer(remove_weak_point_on_death)
+        .add_observer(on_critical_hit);
+}
+
+/// Extra bounty for every critical hit, on top of the one for the kill.
+const CRITICAL_HIT_BOUNTY: f32 = 50.0;
+const WEAK_POINT_RADIUS: f32 = 0.25;
+/// Behind the head, sticking out of the back of the body a little. The front is -Z.
+const WEAK_POINT_OFFSET: Vec3 = Vec3::new(0.0, 0.6, 0.4);
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct WeakPointAssets {
+    #[dependency]
+    ding_sfx: Handle<AudioSource>,
+}
+
+impl FromWorld for WeakPointAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            ding_sfx: asset_server.load("audio/sound_effects/boomerang_sfx/ding.ogg"),
+        }
+    }
+}
+
+fn add_weak_point(trigger: Trigger<OnAdd, Enemy>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Weak Point"),
+        WeakPoint,
+        Transform::from_translation(WEAK_POINT_OFFSET),
+        Collider::sphere(WEAK_POINT_RADIUS),
+        CollisionLayers::new(GameLayer::Enemy, GameLayer::Boomerang),
+        ChildOf(trigger.target()),
+    ));
+}
+
+/// Corpses don't have any weak points left.
+fn remove_weak_point_on_death(
+    trigger: Trigger<DeathEvent>,
+    children: Query<&Children>,
+    weak_points: Query<(), With<WeakPoint>>,
+    mut commands: Commands,
+) {
+    let Ok(children) = children.get(trigger.target()) else {
+        return;
+    };
+    for child in children.iter() {
+        if weak_points.contains(child) {
+            commands.entity(child).despawn();
+        }
+    }
+}
+
+fn on_critical_hit(
+    trigger: Trigger<CriticalHitEvent>,
+    enemies: Query<(), With<Enemy>>,
+    assets: Res<WeakPointAssets>,
+    mut commands: Commands,
+) {
+    if !enemies.contains(trigger.target()) {
+        return;
+    }
+    let position = trigger.event().position;
+    commands.trigger(ScoreEvent::AddScore(CRITICAL_HIT_BOUNTY, position));
+    commands.trigger(SpawnVfxEvent::new(Vfx::Sparks, position));
+    commands.spawn((
+        Name::new("Critical Hit SFX"),
+        AudioPlayer::new(assets.ding_sfx.clone()),
+        PlaybackSettings::DESPAWN,
+        TimeDilatedPitch(1.8),
+    ));
+}
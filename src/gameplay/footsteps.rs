@@ -0,0 +1,156 @@
+//! Footsteps for everything that walks, runs or gallops around. Players, outlaws, civilians and
+//! horses get [`Footsteps`] on their own, each with their own stride. A step is heard every time
+//! a character has covered one stride, so the faster they go, the quicker the steps come.
+//!
+//! Dead outlaws and civilians don't take steps anymore. While their ragdolls slide along the
+//! ground, they kick up a bit of dust every stride instead.
+
+use crate::audio::{DistanceAttenuation, TimeDilatedPitch};
+use crate::gameplay::Gameplay;
+use crate::gameplay::civilian::Civilian;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::horse::Horse;
+use crate::gameplay::player::{Player, Riding};
+use crate::physics_layers::GameLayer;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use avian3d::prelude::{CollisionLayers, LinearVelocity};
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Footsteps>()
+        .init_resource::<FootstepAssets>();
+    app.add_observer(add_player_footsteps)
+        .add_observer(add_enemy_footsteps)
+        .add_observer(add_civilian_footsteps)
+        .add_observer(add_horse_footsteps);
+    app.add_systems(Update, take_steps.run_if(in_state(Gameplay::Normal)));
+}
+
+/// Steps are heard at full volume up to this distance...
+const SFX_NEAR_DISTANCE: f32 = 3.0;
+/// ...and not at all from this one on.
+const SFX_FAR_DISTANCE: f32 = 25.0;
+/// Ragdolls falling or flying faster than this aren't sliding, they don't raise any dust.
+const MAX_SLIDING_FALL_SPEED: f32 = 1.0;
+
+/// Plays a step sound every `stride` world units the entity moves along the ground.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Footsteps {
+    /// Distance covered between two steps.
+    pub stride: f32,
+    pub pitch: f32,
+    pub decibels: f32,
+    /// How far the entity has come since its last step.
+    travelled: f32,
+    previous_position: Option<Vec2>,
+}
+
+impl Footsteps {
+    pub fn new(stride: f32, pitch: f32, decibels: f32) -> Self {
+        Self {
+            stride,
+            pitch,
+            decibels,
+            travelled: 0.0,
+            previous_position: None,
+        }
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct FootstepAssets {
+    #[dependency]
+    steps: Vec<Handle<AudioSource>>,
+}
+
+impl FromWorld for FootstepAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            steps: (1..=4)
+                .map(|i| assets.load(format!("audio/sound_effects/step{i}.ogg")))
+                .collect(),
+        }
+    }
+}
+
+fn add_player_footsteps(trigger: Trigger<OnAdd, Player>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .insert(Footsteps::new(1.6, 1.0, -8.0));
+}
+
+fn add_enemy_footsteps(trigger: Trigger<OnAdd, Enemy>, mut commands: Commands) {
+    // heavier boots than the player's
+    commands
+        .entity(trigger.target())
+        .insert(Footsteps::new(1.4, 0.85, -6.0));
+}
+
+fn add_civilian_footsteps(trigger: Trigger<OnAdd, Civilian>, mut commands: Commands) {
+    commands
+        .entity(trigger.target())
+        .insert(Footsteps::new(1.0, 1.15, -10.0));
+}
+
+fn add_horse_footsteps(trigger: Trigger<OnAdd, Horse>, mut commands: Commands) {
+    // long strides, deep hooves
+    commands
+        .entity(trigger.target())
+        .insert(Footsteps::new(2.5, 0.6, -3.0));
+}
+
+fn take_steps(
+    assets: Res<FootstepAssets>,
+    mut walkers: Query<(
+        &GlobalTransform,
+        &mut Footsteps,
+        Option<&CollisionLayers>,
+        Option<&LinearVelocity>,
+        Has<Riding>,
+    )>,
+    mut commands: Commands,
+) {
+    let mut rng = thread_rng();
+    for (transform, mut footsteps, layers, velocity, riding) in &mut walkers {
+        let position = transform.translation();
+        let Some(previous) = footsteps.previous_position.replace(position.xz()) else {
+            continue;
+        };
+        // the horse does the walking
+        if riding {
+            footsteps.travelled = 0.0;
+            continue;
+        }
+        footsteps.travelled += previous.distance(position.xz());
+        if footsteps.travelled < footsteps.stride {
+            continue;
+        }
+        // big jumps like respawning or riding in shouldn't set off a drum roll
+        footsteps.travelled = footsteps.travelled.min(footsteps.stride * 2.0) - footsteps.stride;
+
+        let dead = layers.is_some_and(|layers| layers.memberships.has_all(GameLayer::DeadEnemy));
+        if dead {
+            let sliding = velocity.is_none_or(|velocity| velocity.y.abs() < MAX_SLIDING_FALL_SPEED);
+            if sliding {
+                commands.trigger(SpawnVfxEvent::new(Vfx::Puff, position));
+            }
+            continue;
+        }
+
+        let step = assets.steps[rng.gen_range(0..assets.steps.len())].clone();
+        commands.spawn((
+            Name::new("Footstep SFX"),
+            Transform::from_translation(position),
+            AudioPlayer::new(step),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Decibels(footsteps.decibels)),
+            TimeDilatedPitch(footsteps.pitch * rng.gen_range(0.9..1.1)),
+            DistanceAttenuation::new(SFX_NEAR_DISTANCE, SFX_FAR_DISTANCE),
+            StateScoped(Gameplay::Normal),
+        ));
+    }
+}
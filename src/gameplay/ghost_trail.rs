@@ -0,0 +1,110 @@
+//! A fading line along the path the last boomerang actually flew, drawn once it's back or has
+//! landed. Makes it easier to see why a ricochet missed, for players and playtesters alike.
+//! Can be turned off in the settings menu.
+
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::BoomerangFlightHistory;
+use crate::settings::{Settings, SettingsApplied};
+use bevy::color::palettes;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<GhostTrailSettings>()
+        .init_resource::<GhostTrail>()
+        .register_type::<GhostTrailSettings>();
+    app.add_observer(apply_ghost_trail_setting)
+        .add_observer(remember_flight);
+    app.add_systems(OnExit(Gameplay::Normal), clear_ghost_trail);
+    app.add_systems(
+        Update,
+        draw_ghost_trail.run_if(|trail: Res<GhostTrail>| trail.path.is_some()),
+    );
+}
+
+/// How long the trail takes to fade out, in real seconds.
+const FADE_SECONDS: f32 = 2.5;
+/// Opacity of the trail right after the boomerang is done.
+const START_ALPHA: f32 = 0.6;
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct GhostTrailSettings {
+    pub enabled: bool,
+}
+
+impl Default for GhostTrailSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// The path of the last boomerang that finished flying, while it's still fading out.
+#[derive(Resource, Debug, Default)]
+struct GhostTrail {
+    path: Option<Vec<Vec3>>,
+    fade: Timer,
+}
+
+fn apply_ghost_trail_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut ghost_trail: ResMut<GhostTrailSettings>,
+    mut trail: ResMut<GhostTrail>,
+) {
+    ghost_trail.enabled = settings.ghost_trail;
+    if !ghost_trail.enabled {
+        trail.path = None;
+    }
+}
+
+fn remember_flight(
+    trigger: Trigger<OnRemove, BoomerangFlightHistory>,
+    histories: Query<(&BoomerangFlightHistory, &GlobalTransform)>,
+    state: Option<Res<State<Gameplay>>>,
+    settings: Res<GhostTrailSettings>,
+    mut trail: ResMut<GhostTrail>,
+) {
+    // boomerangs also get cleaned up when leaving the level, nobody is looking at those
+    if !settings.enabled || state.is_none_or(|state| *state.get() != Gameplay::Normal) {
+        return;
+    }
+    let Ok((history, transform)) = histories.get(trigger.target()) else {
+        return;
+    };
+    // the waypoints end at the last bounce, the rest of the way is where the boomerang ended up
+    let path = history
+        .waypoints
+        .iter()
+        .copied()
+        .chain([transform.translation()])
+        .collect();
+    *trail = GhostTrail {
+        path: Some(path),
+        fade: Timer::from_seconds(FADE_SECONDS, TimerMode::Once),
+    };
+}
+
+fn clear_ghost_trail(mut trail: ResMut<GhostTrail>) {
+    trail.path = None;
+}
+
+fn draw_ghost_trail(
+    // real time, so the trail doesn't linger while aiming the next throw in slow motion
+    time: UiTime,
+    mut trail: ResMut<GhostTrail>,
+    mut gizmos: Gizmos,
+) {
+    if trail.fade.tick(time.delta()).finished() {
+        trail.path = None;
+        return;
+    }
+    let alpha = START_ALPHA * trail.fade.fraction_remaining();
+    let color = Color::from(palettes::css::WHITE_SMOKE).with_alpha(alpha);
+    if let Some(path) = &trail.path {
+        gizmos.linestrip(path.iter().copied(), color);
+        for bounce in path.iter().skip(1).take(path.len().saturating_sub(2)) {
+            gizmos.sphere(Isometry3d::from_translation(*bounce), 0.2, color);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Hats: outlaws lose theirs when they go down, and walking over a hat lying around collects it.
+//! How many hats were collected is kept in the save, and collecting enough of them unlocks other
+//! hats for the player to wear instead of the stetson, see [`PlayerHat`] and
+//! [`crate::gameplay::cosmetics`].
+
+use crate::gameplay::cosmetics::PlayerHat;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::HatFellOffEvent;
+use crate::gameplay::pickups::{PickedUpEvent, Pickup, RegisterPickup};
+use crate::save::SaveData;
+use crate::theme::toast::Toast;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_pickup::<DroppedHat>();
+    app.add_observer(drop_outlaw_hats).add_observer(collect_hat);
+}
+
+/// A hat an outlaw lost, waiting to be collected.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct DroppedHat;
+
+impl Pickup for DroppedHat {
+    const NAME: &'static str = "Dropped Hat";
+}
+
+fn drop_outlaw_hats(
+    trigger: Trigger<HatFellOffEvent>,
+    enemies: Query<(), With<Enemy>>,
+    mut commands: Commands,
+) {
+    if enemies.contains(trigger.event().wearer) {
+        commands.entity(trigger.target()).insert(DroppedHat);
+    }
+}
+
+fn collect_hat(
+    trigger: Trigger<PickedUpEvent>,
+    hats: Query<(), With<DroppedHat>>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    if !hats.contains(trigger.target()) {
+        return;
+    }
+    save_data.hats_collected += 1;
+    let collected = save_data.hats_collected;
+    if let Some(hat) = PlayerHat::ALL
+        .into_iter()
+        .find(|hat| hat.hats_needed() == collected)
+    {
+        commands.trigger(Toast::new(format!(
+            "{collected} hats collected, the {} is yours to wear",
+            hat.label()
+        )));
+    }
+}
@@ -1,29 +1,43 @@
 //! This module is in charge of managing health.
 //! Give an enemy, player or obj health by attaching the [`Health`] component to it, e.g. `Health(3)`, to give it 3 health points.
-//! Damage an enemy, player or obj by triggering the [`HealthEvent`] on an entity, e.g. `HealthEvent::Damage(1)` to reduce health by one.
+//! Damage an enemy, player or obj by triggering the [`HealthEvent`] on an entity, e.g. `HealthEvent::Damage(1, 0, None)` to reduce health by one.
 //! Listen to the [`DeathEvent`] on the entity to handle special cases, like Game Over screen, ragdolling or exploding.
+//! Listen to the [`HurtEvent`] for hits that weren't deadly.
+//! Give an entity a [`FrontalShield`] to make it immune to boomerangs coming straight at its front.
+//! Give an entity [`Invulnerable`] to ignore all damage for a while.
+//! Give an entity [`OneHitKill`] to have any hit take all of its health.
+//! Give an entity a [`WeakPoint`] child to let boomerangs land critical hits on it.
 
 use avian3d::prelude::{
-    AngularVelocity, Collider, CollisionLayers, CollisionStarted, LinearVelocity, PhysicsLayer,
-    RigidBody,
+    AngularVelocity, Collider, CollidingEntities, CollisionLayers, CollisionStarted,
+    LinearVelocity, PhysicsLayer, RigidBody,
 };
 use bevy::prelude::*;
 use rand::{Rng, thread_rng};
 
 use crate::{asset_tracking::LoadResource, physics_layers::GameLayer, screens::Screen};
 
-use crate::gameplay::boomerang::Boomerang;
+use crate::gameplay::boomerang::{Boomerang, BoomerangFlightHistory, BoomerangTargetKind};
+use crate::gameplay::bullets::BulletOrigin;
 
 #[derive(Event)]
 pub enum HealthEvent {
-    // Damage, NumBounces (for multiplier)
-    Damage(u32, usize),
+    // Damage, NumBounces (for multiplier), where the hit came from (if anywhere in particular)
+    Damage(u32, usize, Option<Vec3>),
 }
 
 // NumBounces
 #[derive(Event)]
 pub struct DeathEvent(pub usize);
 
+/// Triggered on an entity whenever it took damage but survived. Dying triggers a [`DeathEvent`]
+/// instead.
+#[derive(Event, Default)]
+pub struct HurtEvent {
+    /// Where the hit came from, if anywhere in particular.
+    pub from: Option<Vec3>,
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct Health(pub i32);
@@ -37,17 +51,98 @@ impl Default for Health {
 #[reflect(Component)]
 pub struct CanDamage(pub u32);
 
+/// The hat floating above something with [`Health`], its wearer.
 #[derive(Component)]
-pub struct HealthUi(Entity);
+pub struct HealthUi(pub Entity);
+
+/// Triggered on a hat when it falls off its wearer, because they lost all of their [`Health`].
+#[derive(Event, Debug)]
+pub struct HatFellOffEvent {
+    pub wearer: Entity,
+}
+
+/// Boomerangs hitting this entity from the front bounce off harmlessly. Hits from behind, or right
+/// after the boomerang ricocheted off a wall, still deal damage.
+/// The front is the entity's forward direction (-Z).
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct FrontalShield {
+    /// Half of the angle (in radians) covered by the shield.
+    pub half_angle: f32,
+}
+
+impl Default for FrontalShield {
+    fn default() -> Self {
+        Self {
+            half_angle: 60f32.to_radians(),
+        }
+    }
+}
+
+impl FrontalShield {
+    /// Whether something flying from `from` towards the shield bearer at `transform` is blocked.
+    pub fn blocks(&self, transform: &GlobalTransform, from: Vec3) -> bool {
+        let Some(towards_attacker) = (from - transform.translation()).with_y(0.0).try_normalize()
+        else {
+            return false;
+        };
+        let facing = transform.forward().with_y(0.0).normalize_or_zero();
+        facing.dot(towards_attacker) > self.half_angle.cos()
+    }
+}
+
+/// Triggered on an entity whenever its [`FrontalShield`] blocks a hit.
+#[derive(Event)]
+pub struct ShieldBlockEvent;
+
+/// The next hit on this entity deals no damage and triggers a [`HitAbsorbedEvent`] instead.
+/// Removed once it has absorbed a hit.
+#[derive(Component, Debug, Default)]
+pub struct AbsorbNextHit;
+
+/// Triggered on an entity whenever its [`AbsorbNextHit`] soaked up a hit.
+#[derive(Event)]
+pub struct HitAbsorbedEvent;
+
+/// Entities with this component ignore every [`HealthEvent`] until it's removed.
+#[derive(Component, Debug, Default)]
+pub struct Invulnerable;
+
+/// Any hit this entity takes is deadly, no matter how much [`Health`] it has left. Absorbed hits
+/// still don't hurt, see [`AbsorbNextHit`].
+#[derive(Component, Debug, Default)]
+pub struct OneHitKill;
+
+/// A small extra hitbox on something with [`Health`], like the back of an outlaw's head. Needs its
+/// own collider, as a child of the entity with the health. Hits on it deal
+/// [`WEAK_POINT_DAMAGE_MULTIPLIER`] times the damage and trigger a [`CriticalHitEvent`] first.
+#[derive(Component, Debug, Default)]
+#[require(CollidingEntities)]
+pub struct WeakPoint;
+
+pub const WEAK_POINT_DAMAGE_MULTIPLIER: u32 = 2;
+
+/// Triggered on an entity right before a hit on one of its [`WeakPoint`]s deals damage.
+#[derive(Event, Debug)]
+pub struct CriticalHitEvent {
+    /// Where the weak point got hit.
+    pub position: Vec3,
+}
+
+/// Entities with this component won't get a hat spawned for their [`Health`].
+#[derive(Component, Default)]
+pub struct NoHealthHat;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Health>()
+        .register_type::<FrontalShield>()
         .add_event::<HealthEvent>()
         .add_event::<DeathEvent>()
         .load_resource::<HealthAsset>()
         .add_systems(Update, on_damage_event)
         .add_systems(PostUpdate, move_ui)
         .add_observer(add_health_ui)
+        .add_observer(track_weak_point_owner)
         .add_observer(remove_health_ui)
         .add_observer(on_health_event);
 }
@@ -58,7 +153,7 @@ const HAT_HEIGHT: f32 = 2.01;
 fn add_health_ui(
     trigger: Trigger<OnAdd, Health>,
     health_asset: Res<HealthAsset>,
-    health_carriers: Query<&Transform, With<Health>>,
+    health_carriers: Query<&Transform, (With<Health>, Without<NoHealthHat>)>,
     mut commands: Commands,
 ) {
     let Ok(transform) = health_carriers.get(trigger.target()) else {
@@ -90,7 +185,10 @@ fn remove_health_ui(
                 Collider::cuboid(1.6, 0.4, 1.6),
                 CollisionLayers::new(GameLayer::DeadEnemy, GameLayer::all_bits()),
             ))
-            .remove::<HealthUi>();
+            .remove::<HealthUi>()
+            .trigger(HatFellOffEvent {
+                wearer: trigger.target(),
+            });
     }
 }
 
@@ -109,16 +207,26 @@ fn move_ui(
 
 fn on_health_event(
     trigger: Trigger<HealthEvent>,
-    mut health: Query<&mut Health>,
+    mut health: Query<(&mut Health, Has<AbsorbNextHit>, Has<OneHitKill>), Without<Invulnerable>>,
     mut commands: Commands,
 ) {
-    let Ok(mut health) = health.get_mut(trigger.target()) else {
+    let Ok((mut health, absorbs_hit, one_hit_kill)) = health.get_mut(trigger.target()) else {
         return;
     };
-    let bounces = match trigger.event() {
-        HealthEvent::Damage(dmg, bounces) => {
+    if absorbs_hit {
+        commands
+            .entity(trigger.target())
+            .remove::<AbsorbNextHit>()
+            .trigger(HitAbsorbedEvent);
+        return;
+    }
+    let (bounces, from) = match trigger.event() {
+        HealthEvent::Damage(dmg, bounces, from) => {
             health.0 -= *dmg as i32;
-            bounces
+            if one_hit_kill && *dmg > 0 {
+                health.0 = health.0.min(0);
+            }
+            (bounces, *from)
         }
     };
     if health.0 <= 0 {
@@ -126,32 +234,150 @@ fn on_health_event(
             .entity(trigger.target())
             .remove::<Health>()
             .trigger(DeathEvent(*bounces));
+    } else {
+        commands
+            .entity(trigger.target())
+            .trigger(HurtEvent { from });
+    }
+}
+
+/// The entity a weak point belongs to has to keep track of what's touching it too, see
+/// [`on_damage_event`].
+fn track_weak_point_owner(
+    trigger: Trigger<OnAdd, WeakPoint>,
+    parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    if let Ok(child_of) = parents.get(trigger.target()) {
+        commands
+            .entity(child_of.parent())
+            .insert_if_new(CollidingEntities::default());
     }
 }
 
+/// A hit that started this frame, on the body and/or a weak point of `target`.
+struct Hit {
+    target: Entity,
+    damager: Entity,
+    body: bool,
+    weak_point: bool,
+}
+
 fn on_damage_event(
     mut collision_event: EventReader<CollisionStarted>,
-    health_query: Query<Entity, With<Health>>,
-    damager_query: Query<(Entity, &CanDamage, Option<&Boomerang>)>,
+    health_query: Query<Has<Invulnerable>, With<Health>>,
+    weak_points: Query<(&ChildOf, &CollidingEntities), With<WeakPoint>>,
+    colliding: Query<&CollidingEntities>,
+    damager_query: Query<(&CanDamage, Option<&Boomerang>, Option<&BulletOrigin>)>,
+    mut flight_histories: Query<&mut BoomerangFlightHistory>,
+    shields: Query<(&FrontalShield, &GlobalTransform)>,
+    transforms: Query<&GlobalTransform>,
     mut commands: Commands,
 ) {
+    // hitting the body and a weak point in the same frame counts as one hit
+    let mut hits: Vec<Hit> = Vec::new();
     for CollisionStarted(entity1, entity2) in collision_event.read() {
-        for health_entity in health_query.iter() {
-            for (damager_entity, damager, boomerang) in damager_query.iter() {
-                if (*entity1 == health_entity || *entity2 == health_entity)
-                    && (*entity1 == damager_entity || *entity2 == damager_entity)
-                {
-                    let bounces = match boomerang {
-                        Some(boomerang) => boomerang.path_index + 1,
-                        None => 0,
-                    };
-                    commands
-                        .entity(health_entity)
-                        .trigger(HealthEvent::Damage(damager.0, bounces));
+        for (hit_entity, damager) in [(*entity1, *entity2), (*entity2, *entity1)] {
+            if !damager_query.contains(damager) {
+                continue;
+            }
+            let (target, weak_point) = match weak_points.get(hit_entity) {
+                Ok((child_of, _)) => (child_of.parent(), true),
+                Err(_) => (hit_entity, false),
+            };
+            if !health_query.contains(target) {
+                continue;
+            }
+            match hits
+                .iter_mut()
+                .find(|hit| hit.target == target && hit.damager == damager)
+            {
+                Some(hit) => {
+                    hit.body |= !weak_point;
+                    hit.weak_point |= weak_point;
                 }
+                None => hits.push(Hit {
+                    target,
+                    damager,
+                    body: !weak_point,
+                    weak_point,
+                }),
             }
         }
     }
+
+    for hit in hits {
+        // whatever is still inside the other part already hit it on the way in
+        let already_hit = match (hit.body, hit.weak_point) {
+            (true, true) => false,
+            (true, false) => weak_points.iter().any(|(child_of, colliding)| {
+                child_of.parent() == hit.target && colliding.contains(&hit.damager)
+            }),
+            (false, _) => colliding
+                .get(hit.target)
+                .is_ok_and(|colliding| colliding.contains(&hit.damager)),
+        };
+        if already_hit {
+            continue;
+        }
+        let Ok((damager, boomerang, bullet_origin)) = damager_query.get(hit.damager) else {
+            continue;
+        };
+        if let Ok(mut history) = flight_histories.get_mut(hit.damager) {
+            history.record_hit(hit.target);
+        }
+        if let Some(boomerang) = boomerang {
+            if is_blocked_by_shield(hit.target, boomerang, &shields, &transforms) {
+                commands.entity(hit.target).trigger(ShieldBlockEvent);
+                continue;
+            }
+        }
+        let bounces = match boomerang {
+            Some(boomerang) => boomerang.path_index + 1,
+            None => 0,
+        };
+        // bullets came from whoever fired them, anything else from where it hit
+        let position = transforms
+            .get(hit.damager)
+            .ok()
+            .map(GlobalTransform::translation);
+        let source = bullet_origin.map(|origin| origin.0).or(position);
+        let invulnerable = health_query.get(hit.target).unwrap_or_default();
+        let mut damage = damager.0;
+        if hit.weak_point && !invulnerable {
+            damage *= WEAK_POINT_DAMAGE_MULTIPLIER;
+            if let Some(position) = position {
+                commands
+                    .entity(hit.target)
+                    .trigger(CriticalHitEvent { position });
+            }
+        }
+        commands
+            .entity(hit.target)
+            .trigger(HealthEvent::Damage(damage, bounces, source));
+    }
+}
+
+/// Checks the angle the boomerang came in at. Boomerangs that just ricocheted off a wall always
+/// get through, that's the trick to beating shields.
+fn is_blocked_by_shield(
+    target: Entity,
+    boomerang: &Boomerang,
+    shields: &Query<(&FrontalShield, &GlobalTransform)>,
+    transforms: &Query<&GlobalTransform>,
+) -> bool {
+    let Ok((shield, shield_transform)) = shields.get(target) else {
+        return false;
+    };
+    let from = match boomerang.current_origin() {
+        Some(BoomerangTargetKind::Entity(origin)) => match transforms.get(origin) {
+            Ok(origin_transform) => origin_transform.translation(),
+            Err(_) => return false,
+        },
+        // ricochet off a wall
+        Some(BoomerangTargetKind::Position(_)) | None => return false,
+    };
+    shield.blocks(shield_transform, from)
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -0,0 +1,150 @@
+//! A row of pips floating above enemies that shows how much [`Health`] they have left.
+//! Enemies only get one once they've been hit, and it fades away again if they aren't hit for a
+//! few seconds. The hat still does the job for enemies that go down in one hit.
+
+use crate::clock::UiTime;
+use bevy::ecs::spawn::SpawnIter;
+use bevy::prelude::*;
+
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::{Health, HealthEvent};
+use crate::screens::Screen;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<MaxHealth>();
+    app.add_observer(remember_max_health)
+        .add_observer(show_health_pips);
+    app.add_systems(
+        Update,
+        update_health_pips.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Pips show up this far above the enemy's origin, just over the hat.
+const PIPS_HEIGHT: f32 = 2.6;
+const PIP_SIZE: f32 = 8.0;
+const PIP_GAP: f32 = 2.0;
+/// How long the pips stick around after the last hit, including the fade.
+const VISIBLE_SECONDS: f32 = 3.0;
+const FADE_SECONDS: f32 = 0.75;
+
+const FULL_COLOR: Color = Color::srgb(0.9, 0.15, 0.1);
+const EMPTY_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.6);
+
+/// The [`Health`] an enemy spawned with, so the pips know how many to show.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct MaxHealth(pub i32);
+
+#[derive(Component, Debug)]
+struct HealthPips {
+    target: Entity,
+    count: i32,
+    shown: Timer,
+}
+
+#[derive(Component, Debug)]
+struct Pip(i32);
+
+fn remember_max_health(
+    trigger: Trigger<OnAdd, Health>,
+    enemies: Query<&Health, With<Enemy>>,
+    mut commands: Commands,
+) {
+    if let Ok(health) = enemies.get(trigger.target()) {
+        commands
+            .entity(trigger.target())
+            .insert(MaxHealth(health.0));
+    }
+}
+
+fn show_health_pips(
+    trigger: Trigger<HealthEvent>,
+    enemies: Query<&MaxHealth, (With<Enemy>, With<Health>)>,
+    mut pips: Query<&mut HealthPips>,
+    mut commands: Commands,
+) {
+    let target = trigger.target();
+    let Ok(max_health) = enemies.get(target) else {
+        return;
+    };
+    if let Some(mut pips) = pips.iter_mut().find(|pips| pips.target == target) {
+        pips.shown.reset();
+        return;
+    }
+    commands.spawn((
+        Name::new("Health Pips"),
+        HealthPips {
+            target,
+            count: max_health.0,
+            shown: Timer::from_seconds(VISIBLE_SECONDS, TimerMode::Once),
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            column_gap: Val::Px(PIP_GAP),
+            ..default()
+        },
+        // hidden until it's been put above the enemy
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        Children::spawn(SpawnIter((0..max_health.0).map(|index| {
+            (
+                Pip(index),
+                Node {
+                    width: Val::Px(PIP_SIZE),
+                    height: Val::Px(PIP_SIZE),
+                    ..default()
+                },
+                BackgroundColor(FULL_COLOR),
+                Pickable::IGNORE,
+            )
+        }))),
+    ));
+}
+
+fn update_health_pips(
+    time: UiTime,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    enemies: Query<(&Health, &GlobalTransform)>,
+    mut displays: Query<(Entity, &mut HealthPips, &mut Node, &mut Visibility)>,
+    mut pips: Query<(&Pip, &ChildOf, &mut BackgroundColor)>,
+    mut commands: Commands,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    for (entity, mut display, mut node, mut visibility) in &mut displays {
+        // gone once the enemy is dead, the hat flying off says enough
+        let Ok((health, transform)) = enemies.get(display.target) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if display.shown.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let Ok(screen_position) = camera.world_to_viewport(
+            camera_transform,
+            transform.translation() + Vec3::Y * PIPS_HEIGHT,
+        ) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let width = display.count as f32 * (PIP_SIZE + PIP_GAP) - PIP_GAP;
+        node.left = Val::Px(screen_position.x - width / 2.0);
+        node.top = Val::Px(screen_position.y);
+        *visibility = Visibility::Inherited;
+
+        let alpha = (display.shown.remaining_secs() / FADE_SECONDS).min(1.0);
+        for (pip, child_of, mut color) in &mut pips {
+            if child_of.parent() != entity {
+                continue;
+            }
+            let base = if pip.0 < health.0 {
+                FULL_COLOR
+            } else {
+                EMPTY_COLOR
+            };
+            color.0 = base.with_alpha(base.alpha() * alpha);
+        }
+    }
+}
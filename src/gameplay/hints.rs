@@ -0,0 +1,219 @@
+//! Tutorial hints that show up as toasts when the player seems to be missing a mechanic.
+//! Each one is shown at most once, ever: which ones were shown is kept in the [`SaveData`].
+//!
+//! What counts as a mistake:
+//! - leaving aim mode a couple of times without painting a single target
+//! - not using aim mode at all for a while into a level
+//! - boomerangs that bounced around but didn't hit a single outlaw, a couple of times
+//!
+//! It also remembers which [`Mechanic`]s the player has used at all, for the loading screen tips.
+
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::aim_mode::{AimModeState, AimModeTargets, cleanup_target_list};
+use crate::gameplay::boomerang::BoomerangFlightHistory;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::input::{CycleTargetAction, LastInputKind};
+use crate::gameplay::player::Riding;
+use crate::save::SaveData;
+use crate::theme::toast::Toast;
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Started;
+use serde::Deserialize;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<Mistakes>();
+    app.add_systems(OnEnter(Gameplay::Normal), reset_mistakes);
+    app.add_systems(OnEnter(AimModeState::Aiming), remember_aiming);
+    app.add_systems(
+        OnExit(AimModeState::Aiming),
+        count_empty_aims.before(cleanup_target_list),
+    );
+    app.add_systems(Update, suggest_aim_mode.run_if(in_state(Gameplay::Normal)));
+    app.add_observer(count_missed_ricochets)
+        .add_observer(remember_cycling_targets)
+        .add_observer(remember_riding);
+}
+
+/// How often a mistake has to happen before the hint shows up.
+const MISTAKES_BEFORE_HINT: u32 = 3;
+/// How long into a level the player may go without aiming.
+const SECONDS_WITHOUT_AIMING: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hint {
+    PaintTargets,
+    UseAimMode,
+    Ricochet,
+}
+
+impl Hint {
+    /// Stored in the save data, don't change these.
+    fn id(self) -> &'static str {
+        match self {
+            Hint::PaintTargets => "paint_targets",
+            Hint::UseAimMode => "use_aim_mode",
+            Hint::Ricochet => "ricochet",
+        }
+    }
+
+    /// Mentions the buttons for the kind of input the player is using.
+    fn text(self, input: LastInputKind) -> &'static str {
+        match self {
+            Hint::PaintTargets => input.glyph(
+                "Sweep the cursor over outlaws while aiming to paint them, then press space to throw",
+                "Sweep the crosshair over outlaws while aiming to paint them, then press Y to throw",
+            ),
+            Hint::UseAimMode => input.glyph(
+                "Hold the left mouse button to aim, time slows down while you do",
+                "Hold the right bumper to aim, time slows down while you do",
+            ),
+            Hint::Ricochet => {
+                "Boomerangs ricochet off walls, paint outlaws behind cover to reach them"
+            }
+        }
+    }
+}
+
+/// Something the player can do that's easy to miss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Mechanic {
+    Aim,
+    Paint,
+    Ricochet,
+    CycleTarget,
+    Horse,
+}
+
+impl Mechanic {
+    /// Stored in the save data, don't change these.
+    pub fn id(self) -> &'static str {
+        match self {
+            Mechanic::Aim => "aim",
+            Mechanic::Paint => "paint",
+            Mechanic::Ricochet => "ricochet",
+            Mechanic::CycleTarget => "cycle_target",
+            Mechanic::Horse => "horse",
+        }
+    }
+}
+
+/// Mistakes made in the current level.
+#[derive(Resource, Debug, Default)]
+struct Mistakes {
+    empty_aims: u32,
+    missed_ricochets: u32,
+    seconds_without_aiming: f32,
+    has_aimed: bool,
+}
+
+fn show_hint(
+    hint: Hint,
+    input: LastInputKind,
+    save_data: &mut ResMut<SaveData>,
+    commands: &mut Commands,
+) {
+    // only touch the save data if something changes, every change gets written to disk
+    if save_data.hints_shown.contains(hint.id()) {
+        return;
+    }
+    save_data.hints_shown.insert(hint.id().to_string());
+    commands.trigger(Toast::new(hint.text(input)));
+}
+
+fn remember_mechanic(mechanic: Mechanic, save_data: &mut ResMut<SaveData>) {
+    // only touch the save data if something changes, every change gets written to disk
+    if !save_data.mechanics_used.contains(mechanic.id()) {
+        save_data.mechanics_used.insert(mechanic.id().to_string());
+    }
+}
+
+fn reset_mistakes(mut mistakes: ResMut<Mistakes>) {
+    *mistakes = Mistakes::default();
+}
+
+fn remember_aiming(mut mistakes: ResMut<Mistakes>, mut save_data: ResMut<SaveData>) {
+    mistakes.has_aimed = true;
+    remember_mechanic(Mechanic::Aim, &mut save_data);
+}
+
+fn remember_cycling_targets(
+    _: Trigger<Started<CycleTargetAction>>,
+    mut save_data: ResMut<SaveData>,
+) {
+    remember_mechanic(Mechanic::CycleTarget, &mut save_data);
+}
+
+fn remember_riding(_: Trigger<OnAdd, Riding>, mut save_data: ResMut<SaveData>) {
+    remember_mechanic(Mechanic::Horse, &mut save_data);
+}
+
+fn count_empty_aims(
+    targets: Single<&AimModeTargets>,
+    last_input: Res<LastInputKind>,
+    mut mistakes: ResMut<Mistakes>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    if !targets.is_empty() {
+        remember_mechanic(Mechanic::Paint, &mut save_data);
+        return;
+    }
+    mistakes.empty_aims += 1;
+    if mistakes.empty_aims >= MISTAKES_BEFORE_HINT {
+        show_hint(
+            Hint::PaintTargets,
+            *last_input,
+            &mut save_data,
+            &mut commands,
+        );
+    }
+}
+
+fn suggest_aim_mode(
+    time: UiTime,
+    last_input: Res<LastInputKind>,
+    mut mistakes: ResMut<Mistakes>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    if mistakes.has_aimed || mistakes.seconds_without_aiming >= SECONDS_WITHOUT_AIMING {
+        return;
+    }
+    mistakes.seconds_without_aiming += time.delta_secs();
+    if mistakes.seconds_without_aiming >= SECONDS_WITHOUT_AIMING {
+        show_hint(Hint::UseAimMode, *last_input, &mut save_data, &mut commands);
+    }
+}
+
+/// Looks at every boomerang once it's done flying.
+fn count_missed_ricochets(
+    trigger: Trigger<OnRemove, BoomerangFlightHistory>,
+    histories: Query<&BoomerangFlightHistory>,
+    enemies: Query<(), With<Enemy>>,
+    state: Option<Res<State<Gameplay>>>,
+    last_input: Res<LastInputKind>,
+    mut mistakes: ResMut<Mistakes>,
+    mut save_data: ResMut<SaveData>,
+    mut commands: Commands,
+) {
+    // boomerangs also get cleaned up when leaving the level, those don't count
+    if state.is_none_or(|state| *state.get() != Gameplay::Normal) {
+        return;
+    }
+    let Ok(history) = histories.get(trigger.target()) else {
+        return;
+    };
+    let hit_an_outlaw = history.hits.iter().any(|hit| enemies.contains(*hit));
+    if history.bounce_normals.is_empty() {
+        return;
+    }
+    if hit_an_outlaw {
+        remember_mechanic(Mechanic::Ricochet, &mut save_data);
+        return;
+    }
+    mistakes.missed_ricochets += 1;
+    if mistakes.missed_ricochets >= MISTAKES_BEFORE_HINT {
+        show_hint(Hint::Ricochet, *last_input, &mut save_data, &mut commands);
+    }
+}
@@ -0,0 +1,92 @@
+//! Makes things light up for a moment when they get hurt.
+//! Every mesh of an entity (including its children, e.g. scenes) gets a copy of its material with a
+//! bright emissive glow, which is swapped back for the original after [`HIT_FLASH_SECS`].
+//! Flashes white by default, put a [`HitFlashColor`] on an entity to change that.
+
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::health_and_damage::HurtEvent;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<HitFlashColor>();
+    app.add_observer(flash_on_hurt);
+    app.add_systems(
+        Update,
+        restore_flashed_materials.run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How long a flash lasts, in physics time so it slows down with everything else.
+const HIT_FLASH_SECS: f32 = 0.1;
+
+/// How bright the flash glows.
+const HIT_FLASH_INTENSITY: f32 = 4.0;
+
+/// The color this entity flashes in when it gets hurt.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct HitFlashColor(pub Color);
+
+/// Sits on a mesh entity while it is flashing.
+#[derive(Component, Debug)]
+struct HitFlash {
+    timer: Timer,
+    original: Handle<StandardMaterial>,
+    flash: Handle<StandardMaterial>,
+}
+
+fn flash_on_hurt(
+    trigger: Trigger<HurtEvent>,
+    colors: Query<&HitFlashColor>,
+    children: Query<&Children>,
+    mut meshes: Query<(&mut MeshMaterial3d<StandardMaterial>, Option<&mut HitFlash>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let hurt = trigger.target();
+    let color = colors.get(hurt).map_or(Color::WHITE, |c| c.0);
+
+    for entity in std::iter::once(hurt).chain(children.iter_descendants(hurt)) {
+        let Ok((mut material, flash)) = meshes.get_mut(entity) else {
+            continue;
+        };
+
+        // Already flashing, just keep it going for longer
+        if let Some(mut flash) = flash {
+            flash.timer.reset();
+            continue;
+        }
+
+        let Some(original) = materials.get(&material.0) else {
+            continue;
+        };
+        let flash = materials.add(StandardMaterial {
+            emissive: (color.to_linear() * HIT_FLASH_INTENSITY).with_alpha(1.0),
+            ..original.clone()
+        });
+        commands.entity(entity).insert(HitFlash {
+            timer: Timer::from_seconds(HIT_FLASH_SECS, TimerMode::Once),
+            original: material.0.clone(),
+            flash: flash.clone(),
+        });
+        material.0 = flash;
+    }
+}
+
+fn restore_flashed_materials(
+    time: GameTime,
+    mut flashing: Query<(Entity, &mut HitFlash, &mut MeshMaterial3d<StandardMaterial>)>,
+    mut commands: Commands,
+) {
+    for (entity, mut flash, mut material) in &mut flashing {
+        if !flash.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        // Something else might have swapped the material in the meantime, leave theirs alone
+        if material.0 == flash.flash {
+            material.0 = flash.original.clone();
+        }
+        commands.entity(entity).remove::<HitFlash>();
+    }
+}
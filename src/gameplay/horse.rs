@@ -0,0 +1,129 @@
+//! Horses to get around big levels faster.
+//! Place a [`HorseSpawnPoint`] in the level scene. Walk up to the horse and interact to mount or
+//! dismount it. Getting hit while riding knocks the player out of the saddle.
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::interaction::{InteractEvent, Interactable};
+use crate::gameplay::player::{Player, Rideable, Riding};
+use crate::screens::Screen;
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<HorseSpawnPoint>()
+        .register_type::<Horse>();
+    app.add_observer(spawn_horse_on_spawn_point)
+        .add_observer(update_prompt_on_mount)
+        .add_observer(update_prompt_on_dismount);
+    app.add_systems(Update, carry_riders.run_if(in_state(Gameplay::Normal)));
+}
+
+const MOUNT_PROMPT: &str = "Mount horse";
+const DISMOUNT_PROMPT: &str = "Dismount";
+
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct Horse;
+
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[reflect(Default, Component)]
+pub struct HorseSpawnPoint {
+    speed: f32,
+    turn_rate: f32,
+}
+
+impl Default for HorseSpawnPoint {
+    fn default() -> Self {
+        Self {
+            speed: 12.0,
+            turn_rate: 4.0,
+        }
+    }
+}
+
+fn spawn_horse_on_spawn_point(
+    trigger: Trigger<OnAdd, HorseSpawnPoint>,
+    spawn_points: Query<(&Transform, &HorseSpawnPoint)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) -> Result {
+    let (transform, spawn_point) = spawn_points.get(trigger.target())?;
+
+    commands
+        .spawn((
+            Name::new("Horse"),
+            Horse,
+            Transform::from_translation(transform.translation.with_y(0.6))
+                .with_rotation(transform.rotation),
+            Mesh3d(meshes.add(Cuboid::new(0.8, 1.2, 2.2))),
+            MeshMaterial3d(materials.add(Color::srgb_u8(110, 70, 40))),
+            Rideable {
+                speed: spawn_point.speed,
+                turn_rate: spawn_point.turn_rate,
+                throw_height: 2.2,
+                camera_zoom_out: 0.3,
+            },
+            Interactable::new(2.5, MOUNT_PROMPT),
+            StateScoped(Screen::Gameplay),
+        ))
+        .observe(toggle_mount);
+
+    Ok(())
+}
+
+fn toggle_mount(trigger: Trigger<InteractEvent>, riders: Query<&Riding>, mut commands: Commands) {
+    let rider = trigger.event().interactor;
+    match riders.get(rider) {
+        Ok(riding) if riding.0 == trigger.target() => {
+            commands.entity(rider).remove::<Riding>();
+        }
+        Ok(_) => {}
+        Err(_) => {
+            commands.entity(rider).insert(Riding(trigger.target()));
+        }
+    }
+}
+
+fn update_prompt_on_mount(
+    trigger: Trigger<OnAdd, Riding>,
+    riders: Query<&Riding>,
+    mut horses: Query<&mut Interactable, With<Horse>>,
+) {
+    let Ok(riding) = riders.get(trigger.target()) else {
+        return;
+    };
+    if let Ok(mut interactable) = horses.get_mut(riding.0) {
+        interactable.prompt = DISMOUNT_PROMPT.to_string();
+    }
+}
+
+fn update_prompt_on_dismount(
+    trigger: Trigger<OnRemove, Riding>,
+    riders: Query<&Riding>,
+    mut horses: Query<&mut Interactable, With<Horse>>,
+) {
+    let Ok(riding) = riders.get(trigger.target()) else {
+        return;
+    };
+    if let Ok(mut interactable) = horses.get_mut(riding.0) {
+        interactable.prompt = MOUNT_PROMPT.to_string();
+    }
+}
+
+/// Keeps ridden horses underneath their rider, facing the way they're going.
+fn carry_riders(
+    riders: Query<(&Transform, &Riding, Option<&LinearVelocity>), With<Player>>,
+    mut horses: Query<&mut Transform, (With<Horse>, Without<Player>)>,
+) {
+    for (rider_transform, riding, velocity) in &riders {
+        let Ok(mut horse_transform) = horses.get_mut(riding.0) else {
+            continue;
+        };
+        horse_transform.translation = rider_transform.translation.with_y(0.6);
+        let heading = velocity.map(|v| v.0.with_y(0.0)).unwrap_or_default();
+        if heading.length_squared() > 0.01 {
+            horse_transform.look_to(heading, Vec3::Y);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! What a boomerang sounds like when it hits something. The sound depends on the [`Surface`] it
+//! hit, and it gets louder the faster the boomerang was flying.
+//!
+//! Level geometry can be given a [`Surface`] in Blender (via Skein), on the object itself or any of
+//! its parents. Without one, the surface is guessed from the name of the object's material, e.g.
+//! "Wood Planks" sounds like wood. Anything else sounds like stone. Characters are flesh.
+//!
+//! Bounces off walls and painted targets are heard where the boomerang turns around. Hits on
+//! anything with [`Health`] are heard as soon as it touches them, since boomerangs fly right
+//! through.
+
+use crate::audio::{DistanceAttenuation, TimeDilatedPitch};
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::{
+    Boomerang, BoomerangFlightHistory, BoomerangSettings, BoomerangTargetKind, BounceBoomerangEvent,
+};
+use crate::gameplay::civilian::Civilian;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::Health;
+use crate::gameplay::player::Player;
+use crate::physics_layers::GameLayer;
+use avian3d::prelude::{Collider, CollisionStarted, SpatialQuery, SpatialQueryFilter};
+use bevy::audio::Volume;
+use bevy::gltf::GltfMaterialName;
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Surface>()
+        .init_resource::<ImpactAssets>();
+    app.register_required_components_with::<Enemy, Surface>(|| Surface::Flesh)
+        .register_required_components_with::<Civilian, Surface>(|| Surface::Flesh)
+        .register_required_components_with::<Player, Surface>(|| Surface::Flesh);
+    app.add_systems(
+        Update,
+        (play_bounce_impacts, play_hit_impacts).run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// Impacts are never quieter than this, or louder than that, relative to a boomerang flying at
+/// [`BoomerangSettings::max_movement_speed`].
+const GAIN_RANGE: (f32, f32) = (0.3, 1.5);
+/// How far from a bounce point to look for what the boomerang bounced off of.
+const BOUNCE_PROBE_RADIUS: f32 = 0.3;
+const SFX_NEAR_DISTANCE: f32 = 4.0;
+const SFX_FAR_DISTANCE: f32 = 40.0;
+
+/// What something is made of, as far as the sounds of hitting it are concerned.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component, Default)]
+pub enum Surface {
+    Flesh,
+    Wood,
+    #[default]
+    Stone,
+    Metal,
+}
+
+impl Surface {
+    /// Guesses the surface from a material name, e.g. "Rusty Metal".
+    fn from_material_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        [
+            ("wood", Surface::Wood),
+            ("plank", Surface::Wood),
+            ("barrel", Surface::Wood),
+            ("metal", Surface::Metal),
+            ("iron", Surface::Metal),
+            ("steel", Surface::Metal),
+            ("stone", Surface::Stone),
+            ("rock", Surface::Stone),
+        ]
+        .into_iter()
+        .find(|(keyword, _)| name.contains(keyword))
+        .map(|(_, surface)| surface)
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct ImpactAssets {
+    #[dependency]
+    flesh: Handle<AudioSource>,
+    #[dependency]
+    wood: Handle<AudioSource>,
+    #[dependency]
+    stone: Handle<AudioSource>,
+    #[dependency]
+    metal: Handle<AudioSource>,
+}
+
+impl ImpactAssets {
+    /// The sound and how far to pitch it.
+    fn sound(&self, surface: Surface) -> (Handle<AudioSource>, f32) {
+        match surface {
+            Surface::Flesh => (self.flesh.clone(), 0.6),
+            Surface::Wood => (self.wood.clone(), 1.3),
+            Surface::Stone => (self.stone.clone(), 1.0),
+            Surface::Metal => (self.metal.clone(), 1.4),
+        }
+    }
+}
+
+impl FromWorld for ImpactAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            flesh: assets.load("audio/sound_effects/step2.ogg"),
+            wood: assets.load("audio/sound_effects/step3.ogg"),
+            stone: assets.load("audio/sound_effects/boomerang_sfx/ding.ogg"),
+            metal: assets.load("audio/sound_effects/213925__diboz__pistol_riccochet.ogg"),
+        }
+    }
+}
+
+/// Bounces go up in pitch, so long chains of ricochets build up.
+fn bounce_pitch(bounces: usize) -> f32 {
+    1.0.lerp(4.0, bounces as f32 / 10.0)
+}
+
+fn play_bounce_impacts(
+    mut bounces: EventReader<BounceBoomerangEvent>,
+    boomerangs: Query<(&Boomerang, &BoomerangFlightHistory, &GlobalTransform)>,
+    healthy: Query<(), With<Health>>,
+    spatial_query: SpatialQuery,
+    surfaces: SurfaceQuery,
+    assets: Res<ImpactAssets>,
+    settings: Res<BoomerangSettings>,
+    mut commands: Commands,
+) {
+    for event in bounces.read() {
+        let Ok((boomerang, history, transform)) = boomerangs.get(event.boomerang_entity) else {
+            continue;
+        };
+        let position = transform.translation();
+        let surface = match event._bounce_on {
+            // heard when the boomerang touched them, see play_hit_impacts
+            BoomerangTargetKind::Entity(entity) if healthy.contains(entity) => continue,
+            BoomerangTargetKind::Entity(entity) => surfaces.of(entity),
+            BoomerangTargetKind::Position(_) => spatial_query
+                .shape_intersections(
+                    &Collider::sphere(BOUNCE_PROBE_RADIUS),
+                    position,
+                    Quat::IDENTITY,
+                    &SpatialQueryFilter::from_mask([GameLayer::Terrain, GameLayer::Default]),
+                )
+                .first()
+                .map_or(Surface::default(), |entity| surfaces.of(*entity)),
+        };
+        play_impact(
+            &assets,
+            surface,
+            position,
+            boomerang.speed(&settings) / settings.max_movement_speed,
+            bounce_pitch(history.bounces()),
+            &mut commands,
+        );
+    }
+}
+
+fn play_hit_impacts(
+    mut collisions: EventReader<CollisionStarted>,
+    boomerangs: Query<(&Boomerang, &GlobalTransform)>,
+    healthy: Query<(), With<Health>>,
+    surfaces: SurfaceQuery,
+    assets: Res<ImpactAssets>,
+    settings: Res<BoomerangSettings>,
+    mut commands: Commands,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        let (boomerang, hit) = if boomerangs.contains(*a) {
+            (*a, *b)
+        } else {
+            (*b, *a)
+        };
+        let Ok((boomerang, transform)) = boomerangs.get(boomerang) else {
+            continue;
+        };
+        if !healthy.contains(hit) {
+            continue;
+        }
+        play_impact(
+            &assets,
+            surfaces.of(hit),
+            transform.translation(),
+            boomerang.speed(&settings) / settings.max_movement_speed,
+            1.0,
+            &mut commands,
+        );
+    }
+}
+
+fn play_impact(
+    assets: &ImpactAssets,
+    surface: Surface,
+    position: Vec3,
+    relative_speed: f32,
+    pitch: f32,
+    commands: &mut Commands,
+) {
+    let (sound, surface_pitch) = assets.sound(surface);
+    let gain = relative_speed.clamp(GAIN_RANGE.0, GAIN_RANGE.1);
+    let variation = thread_rng().gen_range(0.95..1.05);
+    commands.spawn((
+        Name::new(format!("{surface:?} Impact SFX")),
+        Transform::from_translation(position),
+        AudioPlayer::new(sound),
+        PlaybackSettings::DESPAWN.with_volume(Volume::Linear(gain)),
+        TimeDilatedPitch(pitch * surface_pitch * variation),
+        DistanceAttenuation::new(SFX_NEAR_DISTANCE, SFX_FAR_DISTANCE),
+        StateScoped(Gameplay::Normal),
+    ));
+}
+
+/// Finds out what an entity is made of, see the module docs.
+#[derive(bevy::ecs::system::SystemParam)]
+struct SurfaceQuery<'w, 's> {
+    surfaces: Query<'w, 's, &'static Surface>,
+    materials: Query<'w, 's, &'static GltfMaterialName>,
+    parents: Query<'w, 's, &'static ChildOf>,
+}
+
+impl SurfaceQuery<'_, '_> {
+    fn of(&self, entity: Entity) -> Surface {
+        let authored = std::iter::once(entity)
+            .chain(self.parents.iter_ancestors(entity))
+            .find_map(|entity| self.surfaces.get(entity).ok().copied());
+        authored
+            .or_else(|| {
+                self.materials
+                    .get(entity)
+                    .ok()
+                    .and_then(|name| Surface::from_material_name(&name.0))
+            })
+            .unwrap_or_default()
+    }
+}
@@ -1,13 +1,36 @@
+use crate::AppSystems;
+use crate::localization::Locale;
+use crate::settings::{Settings, SettingsApplied};
+use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
 #[derive(InputContext)]
 pub struct PlayerActions;
 
+/// Controls of the second player in local co-op, always on a gamepad.
+#[derive(InputContext)]
+pub struct PartnerActions;
+
 pub fn plugin(app: &mut App) {
     app.add_plugins(EnhancedInputPlugin);
     app.add_input_context::<PlayerActions>();
+    app.add_input_context::<PartnerActions>();
+    app.init_resource::<ControlSettings>()
+        .register_type::<ControlSettings>()
+        .add_observer(apply_control_settings);
     app.add_observer(regular_binding);
+    app.add_observer(partner_binding);
+
+    app.init_resource::<LastInputKind>()
+        .register_type::<LastInputKind>();
+    app.add_systems(
+        Update,
+        (
+            record_last_input_kind.in_set(AppSystems::RecordInput),
+            update_button_prompts.in_set(AppSystems::Update),
+        ),
+    );
 }
 
 #[derive(Debug, InputAction)]
@@ -22,15 +45,116 @@ pub struct FireBoomerangAction;
 #[input_action(output = bool)]
 pub struct AimModeAction;
 
-struct ControlSettings;
+/// Throws at the painted targets while aiming. Letting go of [`AimModeAction`] without it cancels.
+/// Shares its buttons with [`FireBoomerangAction`], so it must not swallow them.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, consume_input = false)]
+pub struct ConfirmAimAction;
+
+/// Steps through the enemies under the cursor while aiming, positive for the next one and
+/// negative for the previous one.
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+pub struct CycleTargetAction;
+
+/// Held to bend boomerangs that are in the air towards the cursor.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct SteerBoomerangAction;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct InteractAction;
+
+impl InteractAction {
+    /// The label of the button bound to this action, for on-screen prompts.
+    pub fn prompt_glyph(kind: LastInputKind) -> &'static str {
+        kind.glyph("E", "A")
+    }
+}
+
+/// Leaves a decoy behind for the outlaws to shoot at, see [`crate::gameplay::decoy`].
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct DecoyAction;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2)]
+pub struct PartnerMoveAction;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct PartnerThrowAction;
+
+/// Held by the partner to agree to slowing down time while player one is aiming.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub struct PartnerAimAction;
+
+/// How the gamepad sticks feel, picked in the settings menu.
+/// Bindings are built when a player spawns, so changes apply from the next level on.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ControlSettings {
+    /// Exponent of the stick response curve. 1.0 is linear, higher values give finer control
+    /// around the center at the cost of needing to push the stick further for full speed.
+    pub stick_curve: f32,
+    /// How far the stick has to be pushed before it registers at all, from 0.0 to 1.0.
+    pub stick_deadzone: f32,
+    /// Flips the horizontal axis of the sticks.
+    pub invert_stick_x: bool,
+}
 
 impl ControlSettings {
     const AIM_MODE_DELAY: f32 = 0.001;
+
+    pub const MIN_STICK_CURVE: f32 = 0.5;
+    pub const MAX_STICK_CURVE: f32 = 3.0;
+    pub const MAX_STICK_DEADZONE: f32 = 0.5;
+
+    /// Applied to every stick, in addition to the modifiers the whole action gets.
+    fn stick_modifiers(&self) -> Scale {
+        let x = if self.invert_stick_x { -1.0 } else { 1.0 };
+        Scale::new(Vec3::new(x, 1.0, 1.0))
+    }
+
+    /// Applied to movement actions. Keys and buttons only ever report 0 or 1, which both the
+    /// deadzone and the curve leave alone, so this only changes how the sticks feel.
+    fn movement_modifiers(&self) -> (DeadZone, ExponentialCurve) {
+        (
+            DeadZone {
+                lower_threshold: self.stick_deadzone,
+                ..default()
+            },
+            ExponentialCurve::splat(self.stick_curve),
+        )
+    }
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self {
+            stick_curve: 1.0,
+            stick_deadzone: DeadZone::default().lower_threshold,
+            invert_stick_x: false,
+        }
+    }
+}
+
+fn apply_control_settings(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut controls: ResMut<ControlSettings>,
+) {
+    controls.stick_curve = settings.stick_curve;
+    controls.stick_deadzone = settings.stick_deadzone;
+    controls.invert_stick_x = settings.invert_stick_x;
 }
 
 fn regular_binding(
     trigger: Trigger<Binding<PlayerActions>>,
     mut player: Query<&mut Actions<PlayerActions>>,
+    settings: Res<ControlSettings>,
 ) {
     // We have to bind the input mapping to the player at runtime
     let mut actions = player.get_mut(trigger.target()).unwrap();
@@ -38,21 +162,149 @@ fn regular_binding(
         .bind::<PlayerMoveAction>()
         .to((
             Cardinal::wasd_keys(),
-            Axial::left_stick(),
+            Axial::left_stick().with_modifiers_each(settings.stick_modifiers()),
             Cardinal::arrow_keys(),
             Cardinal::dpad_buttons(),
         ))
-        .with_modifiers(DeadZone::default());
+        .with_modifiers(settings.movement_modifiers());
 
-    // 'Tap' means you need to release within the specified time for it to fire
-    // actions
-    //     .bind::<FireBoomerangAction>()
-    //     .to(MouseButton::Left)
-    //     .with_conditions(Tap::new(ControlSettings::AIM_MODE_DELAY));
+    // bound before the fire action so it gets to see the buttons first
+    actions
+        .bind::<ConfirmAimAction>()
+        .to((KeyCode::Space, GamepadButton::North));
+
+    // 'Release' starts when the button goes down and fires once it's let go, the time in between
+    // charges up the throw
+    actions
+        .bind::<FireBoomerangAction>()
+        .to((KeyCode::Space, GamepadButton::North))
+        .with_conditions(Release::default());
 
     // 'Hold' fires only after the specified time has passed while the input remains pressed
     actions
         .bind::<AimModeAction>()
         .to((MouseButton::Left, GamepadButton::RightTrigger))
         .with_conditions(Hold::new(ControlSettings::AIM_MODE_DELAY)); // trigger after this many seconds
+
+    // the right bumper is busy holding aim mode, so the other shoulder buttons do the cycling
+    actions.bind::<CycleTargetAction>().to((
+        Input::mouse_wheel().with_modifiers(SwizzleAxis::YXZ),
+        GamepadButton::RightTrigger2,
+        GamepadButton::LeftTrigger.with_modifiers(Negate::all()),
+    ));
+
+    actions.bind::<SteerBoomerangAction>().to((
+        KeyCode::ShiftLeft,
+        MouseButton::Right,
+        GamepadButton::West,
+    ));
+
+    actions
+        .bind::<InteractAction>()
+        .to((KeyCode::KeyE, GamepadButton::South));
+
+    actions
+        .bind::<DecoyAction>()
+        .to((KeyCode::KeyQ, GamepadButton::East));
+}
+
+fn partner_binding(
+    trigger: Trigger<Binding<PartnerActions>>,
+    mut partner: Query<&mut Actions<PartnerActions>>,
+    settings: Res<ControlSettings>,
+) {
+    let mut actions = partner.get_mut(trigger.target()).unwrap();
+    actions
+        .bind::<PartnerMoveAction>()
+        .to((
+            Axial::left_stick().with_modifiers_each(settings.stick_modifiers()),
+            Cardinal::dpad_buttons(),
+        ))
+        .with_modifiers(settings.movement_modifiers());
+
+    actions
+        .bind::<PartnerThrowAction>()
+        .to(GamepadButton::RightTrigger);
+
+    actions
+        .bind::<PartnerAimAction>()
+        .to(GamepadButton::LeftTrigger);
+}
+
+// ===== PROMPTS =====
+
+/// Mouse movement below this many pixels per frame doesn't count as using the mouse, so a bumped
+/// desk doesn't flip the prompts back while playing on a gamepad.
+const MOUSE_MOTION_THRESHOLD: f32 = 4.0;
+/// Same for stick movement, so a drifting stick doesn't flip them while playing on keyboard.
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// What the player touched last, so on-screen prompts can show the matching buttons.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub enum LastInputKind {
+    #[default]
+    KeyboardMouse,
+    Gamepad,
+}
+
+impl LastInputKind {
+    /// Picks the glyph for this kind of input.
+    pub fn glyph(self, keyboard: &'static str, gamepad: &'static str) -> &'static str {
+        match self {
+            LastInputKind::KeyboardMouse => keyboard,
+            LastInputKind::Gamepad => gamepad,
+        }
+    }
+}
+
+fn record_last_input_kind(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    gamepads: Query<&Gamepad>,
+    mut last: ResMut<LastInputKind>,
+) {
+    let used_gamepad = gamepads.iter().any(|gamepad| {
+        gamepad.get_just_pressed().next().is_some()
+            || gamepad.left_stick().length() > STICK_THRESHOLD
+            || gamepad.right_stick().length() > STICK_THRESHOLD
+    });
+    let used_keyboard_mouse = keys.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || mouse_motion.delta.length() > MOUSE_MOTION_THRESHOLD;
+
+    // the prompts only need to change when the kind does
+    if used_gamepad {
+        last.set_if_neq(LastInputKind::Gamepad);
+    } else if used_keyboard_mouse {
+        last.set_if_neq(LastInputKind::KeyboardMouse);
+    }
+}
+
+/// A static label like "[R] Retry", showing the button for whatever the player used last.
+#[derive(Component, Debug, Clone)]
+pub struct ButtonPrompt {
+    pub keyboard: &'static str,
+    pub gamepad: &'static str,
+    /// English text, gets translated.
+    pub text: &'static str,
+}
+
+fn update_button_prompts(
+    last: Res<LastInputKind>,
+    locale: Locale,
+    mut prompts: Query<(Ref<ButtonPrompt>, &mut Text)>,
+) {
+    let refresh_all = last.is_changed() || locale.is_changed();
+    for (prompt, mut text) in &mut prompts {
+        if !(refresh_all || prompt.is_added()) {
+            continue;
+        }
+        text.0 = format!(
+            "[{}] {}",
+            last.glyph(prompt.keyboard, prompt.gamepad),
+            locale.tr(prompt.text)
+        );
+    }
 }
@@ -0,0 +1,145 @@
+//! Props with a little story to tell: gravestones, wanted posters, notes nailed to a door...
+//! Add an [`Inspectable`] in the level scene (e.g. via Skein custom properties in Blender) and the
+//! player can walk up to the prop and read its text in a popup. The popup closes again when they
+//! walk away or press interact a second time. Fights don't stop while it's open, so keep it short.
+//!
+//! Both the title and the text are translated like any other string, add them to the
+//! `assets/i18n` files using the English text as the key.
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::interaction::{FocusedInteractable, InteractEvent, Interactable};
+use crate::localization::Localized;
+use crate::theme::palette::{HEADER_TEXT, LABEL_TEXT};
+use crate::ui_assets::FontAssets;
+use bevy::color::palettes::css::BLACK;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Inspectable>();
+
+    app.add_systems(
+        Update,
+        close_popup_out_of_range.run_if(in_state(Gameplay::Normal)),
+    );
+
+    app.add_observer(setup_inspectable)
+        .add_observer(toggle_popup);
+}
+
+const INSPECT_PROMPT: &str = "Read";
+
+/// A prop the player can read up on, authored in the level scene.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct Inspectable {
+    /// Shown at the top of the popup, e.g. "Here lies Slim Jim". Can be left empty.
+    pub title: String,
+    pub text: String,
+    /// How close the player needs to be to read it.
+    pub radius: f32,
+}
+
+impl Default for Inspectable {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            text: String::new(),
+            radius: 2.0,
+        }
+    }
+}
+
+/// The open popup, showing the text of the given inspectable.
+#[derive(Component, Debug)]
+struct InspectPopup(Entity);
+
+fn setup_inspectable(
+    trigger: Trigger<OnAdd, Inspectable>,
+    inspectables: Query<&Inspectable>,
+    mut commands: Commands,
+) {
+    let Ok(inspectable) = inspectables.get(trigger.target()) else {
+        return;
+    };
+    commands
+        .entity(trigger.target())
+        .insert(Interactable::new(inspectable.radius, INSPECT_PROMPT));
+}
+
+fn toggle_popup(
+    trigger: Trigger<InteractEvent>,
+    inspectables: Query<&Inspectable>,
+    popups: Query<(Entity, &InspectPopup)>,
+    font_assets: Res<FontAssets>,
+    mut commands: Commands,
+) {
+    let Ok(inspectable) = inspectables.get(trigger.target()) else {
+        return;
+    };
+    let mut was_open = false;
+    for (popup, InspectPopup(source)) in &popups {
+        was_open |= *source == trigger.target();
+        commands.entity(popup).despawn();
+    }
+    if was_open {
+        return;
+    }
+
+    let mut popup = commands.spawn((
+        Name::new("Inspect Popup"),
+        InspectPopup(trigger.target()),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(120.0),
+            left: Val::Percent(25.0),
+            width: Val::Percent(50.0),
+            padding: UiRect::all(Val::Px(16.0)),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        BackgroundColor(BLACK.with_alpha(0.7).into()),
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    ));
+    popup.with_children(|parent| {
+        if !inspectable.title.is_empty() {
+            parent.spawn((
+                Text(inspectable.title.clone()),
+                Localized(inspectable.title.clone()),
+                TextFont {
+                    font: font_assets.header.clone(),
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(HEADER_TEXT),
+                Pickable::IGNORE,
+            ));
+        }
+        parent.spawn((
+            Text(inspectable.text.clone()),
+            Localized(inspectable.text.clone()),
+            TextFont {
+                font: font_assets.content.clone(),
+                font_size: 24.0,
+                ..default()
+            },
+            TextColor(LABEL_TEXT),
+            TextLayout::new_with_justify(JustifyText::Center),
+            Pickable::IGNORE,
+        ));
+    });
+}
+
+fn close_popup_out_of_range(
+    focused: Res<FocusedInteractable>,
+    popups: Query<(Entity, &InspectPopup)>,
+    mut commands: Commands,
+) {
+    for (popup, InspectPopup(source)) in &popups {
+        if focused.0 != Some(*source) {
+            commands.entity(popup).despawn();
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Generic "walk up to it and press a button" interactions.
+//! Add an [`Interactable`] to any entity (pickups, doors, NPCs, level exits...) to show a contextual
+//! prompt while the player is within its radius. Pressing the interact button triggers an
+//! [`InteractEvent`] on that entity, so every kind of interactable can just `.observe()` it.
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::input::{InteractAction, LastInputKind};
+use crate::gameplay::player::Player;
+use crate::localization::Locale;
+use crate::ui_assets::FontAssets;
+use bevy::color::palettes::css::{BLACK, WHITE};
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Started;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Interactable>();
+    app.init_resource::<FocusedInteractable>();
+
+    app.add_systems(OnEnter(Gameplay::Normal), spawn_interaction_prompt);
+    app.add_systems(
+        Update,
+        (focus_nearest_interactable, update_interaction_prompt)
+            .chain()
+            .run_if(in_state(Gameplay::Normal)),
+    );
+    app.add_systems(OnExit(Gameplay::Normal), clear_focused_interactable);
+
+    app.add_observer(on_interact_action);
+}
+
+/// Marks an entity the player can interact with.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct Interactable {
+    /// How close the player needs to be for the prompt to show up.
+    pub radius: f32,
+    /// Short description of what will happen, e.g. "Open door".
+    pub prompt: String,
+}
+
+impl Default for Interactable {
+    fn default() -> Self {
+        Self {
+            radius: 2.5,
+            prompt: "Interact".to_string(),
+        }
+    }
+}
+
+impl Interactable {
+    pub fn new(radius: f32, prompt: impl Into<String>) -> Self {
+        Self {
+            radius,
+            prompt: prompt.into(),
+        }
+    }
+}
+
+/// Triggered on an [`Interactable`] entity when the player activates it.
+#[derive(Event, Debug)]
+pub struct InteractEvent {
+    /// The entity which interacted with the target, usually the player.
+    pub interactor: Entity,
+}
+
+/// The interactable closest to the player, if any are in range.
+#[derive(Resource, Default, Debug)]
+pub struct FocusedInteractable(pub Option<Entity>);
+
+#[derive(Component)]
+struct InteractionPrompt;
+
+fn spawn_interaction_prompt(font_assets: Res<FontAssets>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Interaction Prompt"),
+        InteractionPrompt,
+        Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        Text::new(""),
+        TextFont {
+            font: font_assets.content.clone(),
+            font_size: 24.0,
+            ..default()
+        },
+        TextColor(WHITE.into()),
+        TextShadow {
+            color: BLACK.into(),
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    ));
+}
+
+fn focus_nearest_interactable(
+    player: Single<&GlobalTransform, With<Player>>,
+    interactables: Query<(Entity, &GlobalTransform, &Interactable)>,
+    mut focused: ResMut<FocusedInteractable>,
+) {
+    let player_position = player.translation().with_y(0.0);
+    let nearest = interactables
+        .iter()
+        .map(|(entity, transform, interactable)| {
+            let distance = transform
+                .translation()
+                .with_y(0.0)
+                .distance(player_position);
+            (entity, distance, interactable.radius)
+        })
+        .filter(|(_, distance, radius)| distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _, _)| entity);
+
+    if focused.0 != nearest {
+        focused.0 = nearest;
+    }
+}
+
+fn update_interaction_prompt(
+    focused: Res<FocusedInteractable>,
+    interactables: Query<(&GlobalTransform, &Interactable)>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    prompt: Single<(&mut Node, &mut Text, &mut Visibility), With<InteractionPrompt>>,
+    last_input: Res<LastInputKind>,
+    locale: Locale,
+) {
+    let (mut node, mut text, mut visibility) = prompt.into_inner();
+    let Some((transform, interactable)) = focused.0.and_then(|e| interactables.get(e).ok()) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let (camera, camera_transform) = camera.into_inner();
+    let Ok(screen_position) =
+        camera.world_to_viewport(camera_transform, transform.translation() + Vec3::Y * 2.5)
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    text.0 = format!(
+        "[{}] {}",
+        InteractAction::prompt_glyph(*last_input),
+        locale.tr(&interactable.prompt)
+    );
+    node.left = Val::Px(screen_position.x);
+    node.top = Val::Px(screen_position.y);
+    *visibility = Visibility::Inherited;
+}
+
+fn on_interact_action(
+    _trigger: Trigger<Started<InteractAction>>,
+    focused: Res<FocusedInteractable>,
+    interactables: Query<(), With<Interactable>>,
+    player: Single<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    // Only ever set while in `Gameplay::Normal`, so this doubles as our state check.
+    let Some(target) = focused.0.filter(|e| interactables.contains(*e)) else {
+        return;
+    };
+
+    commands.entity(target).trigger(InteractEvent {
+        interactor: player.into_inner(),
+    });
+}
+
+fn clear_focused_interactable(mut focused: ResMut<FocusedInteractable>) {
+    focused.0 = None;
+}
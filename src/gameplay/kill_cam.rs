@@ -0,0 +1,85 @@
+//! The kill cam: when the last outlaw painted for a throw goes down to the boomerang, time nearly
+//! stops and the camera punches in on them for a moment, then everything eases back to normal.
+//! It's a beat for the end of a good throw, separate from the slow motion of aim mode.
+//!
+//! This only decides when it happens and how strong it is right now, see [`KillCam`]. Slowing down
+//! time is up to [`crate::gameplay::aim_mode`], punching in up to [`crate::gameplay::camera`].
+
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::{Boomerang, BoomerangFlightHistory};
+use crate::gameplay::health_and_damage::DeathEvent;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<KillCam>().init_resource::<KillCam>();
+    app.add_observer(start_kill_cam);
+    app.add_systems(Update, tick_kill_cam.run_if(in_state(Gameplay::Normal)));
+    app.add_systems(OnExit(Gameplay::Normal), stop_kill_cam);
+}
+
+/// How long the kill cam lasts, in real time.
+const KILL_CAM_SECONDS: f32 = 0.4;
+/// How fast time goes at the start of the kill cam.
+pub const KILL_CAM_SPEED: f32 = 0.15;
+
+/// The kill cam that's currently playing, if any.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct KillCam {
+    timer: Option<Timer>,
+    /// Where the final kill happened.
+    pub focus: Vec3,
+}
+
+impl KillCam {
+    pub fn is_active(&self) -> bool {
+        self.timer.is_some()
+    }
+
+    /// How strong the kill cam is right now, from 1.0 right after the kill down to 0.0 once it's
+    /// over. Holds on for most of it, then lets go quickly.
+    pub fn strength(&self) -> f32 {
+        self.timer
+            .as_ref()
+            .map_or(0.0, |timer| 1.0 - timer.fraction().powi(3))
+    }
+
+    /// How fast time should go because of the kill cam, 1.0 when there's none.
+    pub fn speed(&self) -> f32 {
+        1.0_f32.lerp(KILL_CAM_SPEED, self.strength())
+    }
+}
+
+fn start_kill_cam(
+    trigger: Trigger<DeathEvent>,
+    boomerangs: Query<(&Boomerang, &BoomerangFlightHistory)>,
+    transforms: Query<&GlobalTransform>,
+    mut kill_cam: ResMut<KillCam>,
+) {
+    let victim = trigger.target();
+    // the boomerang that hit it last has to have been headed for it last
+    let final_kill = boomerangs.iter().any(|(boomerang, history)| {
+        history.hits.last() == Some(&victim) && boomerang.last_painted_entity() == Some(victim)
+    });
+    if !final_kill {
+        return;
+    }
+    kill_cam.timer = Some(Timer::from_seconds(KILL_CAM_SECONDS, TimerMode::Once));
+    kill_cam.focus = transforms
+        .get(victim)
+        .map_or(kill_cam.focus, GlobalTransform::translation);
+}
+
+fn tick_kill_cam(time: UiTime, mut kill_cam: ResMut<KillCam>) {
+    let Some(timer) = kill_cam.timer.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).finished() {
+        kill_cam.timer = None;
+    }
+}
+
+fn stop_kill_cam(mut kill_cam: ResMut<KillCam>) {
+    kill_cam.timer = None;
+}
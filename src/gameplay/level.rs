@@ -1,15 +1,34 @@
 //! Spawn the main level.
+//! Level scenes aren't loaded up front. [LevelStreaming] loads the level that's about to be played,
+//! preloads the next one while the player heads for the exit and unloads everything else. The game
+//! waits on [`Screen::LevelLoading`] until the level is there, see [`current_level_loaded`].
+//! Every level can have additions on top of its glTF scene, e.g. extra spawn points, pickups or
+//! exits placed with the level editor dev tool. They're kept in a [`DynamicScene`] at
+//! `assets/levels/<level>.scn.ron`, streamed along with the level and spawned next to it. That
+//! way levels can be patched without exporting them from Blender again. The file is optional, a
+//! level without one is played as it is.
 
 use crate::audio::music;
+use crate::gameplay::objective::ObjectiveCompletedEvent;
 use crate::physics_layers::GameLayer;
 use crate::{asset_tracking::LoadResource, screens::Screen};
 use avian3d::prelude::CollisionLayers;
+use bevy::asset::{AssetLoadFailedEvent, AssetPath, LoadState};
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
+use std::path::Path;
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<LevelAssets>();
+    app.register_type::<LevelAssets>()
+        .register_type::<LevelAddition>();
     app.load_resource::<LevelAssets>();
+    app.init_resource::<LevelStreaming>();
+    app.add_observer(preload_next_level);
+    app.add_systems(OnEnter(Screen::Title), unload_levels);
+    app.add_systems(
+        Update,
+        skip_missing_additions.run_if(in_state(Screen::Gameplay)),
+    );
 }
 
 /// Todo: maybe add a pub enum LevelSelection
@@ -18,9 +37,9 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Resource)]
 pub struct LevelAssets {
     #[dependency]
-    music: Handle<AudioSource>,
-    #[dependency]
-    pub levels: Vec<Handle<Scene>>,
+    pub music: Handle<AudioSource>,
+    /// The glTF file of every level, in order. Loaded on demand by [LevelStreaming].
+    pub levels: Vec<&'static str>,
     pub current_level: usize,
     pub all_bounties: HashMap<usize, f32>,
 
@@ -33,11 +52,11 @@ impl FromWorld for LevelAssets {
         let asset_server = world.resource::<AssetServer>();
         // add new levels here
         let levels = vec![
-            asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/Level1.glb")),
-            asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/Level2.glb")),
-            asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/Level3.glb")),
-            asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/Level4.glb")),
-            asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/Level5.glb")),
+            "models/Level1.glb",
+            "models/Level2.glb",
+            "models/Level3.glb",
+            "models/Level4.glb",
+            "models/Level5.glb",
         ];
         Self {
             music: asset_server.load("audio/music/BoomerangTheme.ogg"),
@@ -50,8 +69,154 @@ impl FromWorld for LevelAssets {
     }
 }
 
+impl LevelAssets {
+    fn scene_path(&self, level: usize) -> AssetPath<'static> {
+        GltfAssetLabel::Scene(0).from_asset(self.levels[level])
+    }
+
+    /// The scene with the additions to the given level, relative to the assets folder.
+    pub fn additions_path(&self, level: usize) -> String {
+        let name = Path::new(self.levels[level])
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        format!("levels/{name}.scn.ron")
+    }
+}
+
+/// Marks entities that were added on top of the level's glTF scene, see
+/// [`LevelAssets::additions_path`].
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct LevelAddition;
+
+/// Keeps the scene of the level that's being played loaded, plus the next one once it's been
+/// preloaded. Any other level is released, which unloads its scene along with the meshes,
+/// materials and textures only it was using.
+#[derive(Resource, Debug, Default)]
+pub struct LevelStreaming {
+    current: Option<LoadedLevel>,
+    next: Option<LoadedLevel>,
+}
+
+#[derive(Debug)]
+struct LoadedLevel {
+    index: usize,
+    scene: Handle<Scene>,
+    additions: Handle<DynamicScene>,
+}
+
+impl LoadedLevel {
+    fn load(index: usize, level_assets: &LevelAssets, asset_server: &AssetServer) -> Self {
+        Self {
+            index,
+            scene: asset_server.load(level_assets.scene_path(index)),
+            additions: asset_server.load(level_assets.additions_path(index)),
+        }
+    }
+}
+
+impl LevelStreaming {
+    /// The scene of the given level and its additions, reusing them if they're already loaded or
+    /// being preloaded. Every other level gets released.
+    fn enter_level(
+        &mut self,
+        index: usize,
+        level_assets: &LevelAssets,
+        asset_server: &AssetServer,
+    ) -> (Handle<Scene>, Handle<DynamicScene>) {
+        let mut entered = None;
+        for level in [self.current.take(), self.next.take()]
+            .into_iter()
+            .flatten()
+        {
+            if level.index == index && entered.is_none() {
+                entered = Some(level);
+            } else {
+                info!("Unloading level {}", level.index);
+            }
+        }
+        let entered =
+            entered.unwrap_or_else(|| LoadedLevel::load(index, level_assets, asset_server));
+        let handles = (entered.scene.clone(), entered.additions.clone());
+        self.current = Some(entered);
+        handles
+    }
+
+    /// Starts loading the given level in the background, so it's ready once the player gets there.
+    fn preload(&mut self, index: usize, level_assets: &LevelAssets, asset_server: &AssetServer) {
+        let already_loaded = [&self.current, &self.next]
+            .into_iter()
+            .flatten()
+            .any(|level| level.index == index);
+        if already_loaded {
+            return;
+        }
+        info!("Preloading level {}", index);
+        self.next = Some(LoadedLevel::load(index, level_assets, asset_server));
+    }
+}
+
+/// Once the objective is done, the player still has to walk to the exit. Plenty of time to get the
+/// next level loaded.
+fn preload_next_level(
+    _trigger: Trigger<ObjectiveCompletedEvent>,
+    level_assets: Res<LevelAssets>,
+    asset_server: Res<AssetServer>,
+    mut streaming: ResMut<LevelStreaming>,
+) {
+    let next = level_assets.current_level + 1;
+    if next < level_assets.levels.len() {
+        streaming.preload(next, &level_assets, &asset_server);
+    }
+}
+
+/// Starts loading the level that's about to be played, unless it's already there.
+pub fn load_current_level(
+    level_assets: Res<LevelAssets>,
+    asset_server: Res<AssetServer>,
+    mut streaming: ResMut<LevelStreaming>,
+) {
+    info!("Loading level {}", level_assets.current_level);
+    streaming.enter_level(level_assets.current_level, &level_assets, &asset_server);
+}
+
+/// Whether the level that's about to be played is loaded, along with its meshes, materials and
+/// textures. Its additions only have to be done trying, they're optional.
+pub fn current_level_loaded(
+    streaming: Res<LevelStreaming>,
+    asset_server: Res<AssetServer>,
+) -> bool {
+    let Some(level) = &streaming.current else {
+        return false;
+    };
+    let additions_done = asset_server.is_loaded_with_dependencies(&level.additions)
+        || matches!(
+            asset_server.load_state(&level.additions),
+            LoadState::Failed(_)
+        );
+    asset_server.is_loaded_with_dependencies(&level.scene) && additions_done
+}
+
+/// Back on the title screen nobody knows which level comes next, so nothing needs to stay loaded.
+fn unload_levels(mut streaming: ResMut<LevelStreaming>) {
+    for level in [streaming.current.take(), streaming.next.take()]
+        .into_iter()
+        .flatten()
+    {
+        info!("Unloading level {}", level.index);
+    }
+}
+
 /// A system that spawns the main level.
-pub fn spawn_level(mut commands: Commands, level_assets: ResMut<LevelAssets>) {
+pub fn spawn_level(
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    asset_server: Res<AssetServer>,
+    mut streaming: ResMut<LevelStreaming>,
+) {
+    let (scene, additions) =
+        streaming.enter_level(level_assets.current_level, &level_assets, &asset_server);
     commands.spawn((
         Name::new("Level"),
         Transform::default(),
@@ -64,7 +229,7 @@ pub fn spawn_level(mut commands: Commands, level_assets: ResMut<LevelAssets>) {
             ),
             (
                 Name::new("Environment"),
-                SceneRoot(level_assets.levels[level_assets.current_level].clone(),),
+                SceneRoot(scene),
                 CollisionLayers::new(
                     GameLayer::Terrain,
                     [
@@ -77,6 +242,23 @@ pub fn spawn_level(mut commands: Commands, level_assets: ResMut<LevelAssets>) {
                     ]
                 ),
             ),
+            (Name::new("Level Additions"), DynamicSceneRoot(additions),),
         ],
     ));
 }
+
+/// Levels without additions are fine, the scene spawner just shouldn't keep waiting for them.
+fn skip_missing_additions(
+    mut failed: EventReader<AssetLoadFailedEvent<DynamicScene>>,
+    roots: Query<(Entity, &DynamicSceneRoot)>,
+    mut commands: Commands,
+) {
+    for event in failed.read() {
+        for (entity, root) in &roots {
+            if root.0.id() == event.id {
+                debug!("No level additions at {}", event.path);
+                commands.entity(entity).remove::<DynamicSceneRoot>();
+            }
+        }
+    }
+}
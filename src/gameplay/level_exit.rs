@@ -0,0 +1,191 @@
+//! The level exit. It stays locked until the level objective is completed, after which the player
+//! has to walk into it to actually win the level. This gives them some time to look around.
+//! Place an [`ExitZone`] in the level scene to choose where it is. If a level doesn't have one,
+//! an exit is opened up at the player spawn point.
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::objective::ObjectiveCompletedEvent;
+use crate::gameplay::outcome::{OutcomeEvent, OutcomeReason};
+use crate::gameplay::player::{Player, PlayerSpawnPoint};
+use crate::localization::Localized;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use crate::ui_assets::FontAssets;
+use avian3d::prelude::{Collider, CollidingEntities, CollisionLayers, Sensor};
+use bevy::color::palettes::css::{BLACK, GOLD};
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<ExitZone>();
+
+    app.add_systems(OnEnter(Gameplay::Normal), spawn_exit_marker);
+    app.add_systems(
+        Update,
+        (leave_level_through_exit, update_exit_marker).run_if(in_state(Gameplay::Normal)),
+    );
+
+    app.add_observer(setup_exit_zone)
+        .add_observer(unlock_exit_zones);
+}
+
+/// A sensor area which wins the level once the player walks into it, after it has been unlocked.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct ExitZone {
+    pub radius: f32,
+}
+
+impl Default for ExitZone {
+    fn default() -> Self {
+        Self { radius: 2.0 }
+    }
+}
+
+/// Marks [`ExitZone`]s which the player can use to leave the level.
+#[derive(Component, Debug)]
+pub struct ExitUnlocked;
+
+fn setup_exit_zone(
+    trigger: Trigger<OnAdd, ExitZone>,
+    zones: Query<&ExitZone>,
+    mut commands: Commands,
+) {
+    let Ok(zone) = zones.get(trigger.target()) else {
+        return;
+    };
+    commands.entity(trigger.target()).insert((
+        Sensor,
+        Collider::cylinder(zone.radius, 2.0),
+        CollisionLayers::new(GameLayer::Default, GameLayer::Player),
+        CollidingEntities::default(),
+    ));
+}
+
+fn unlock_exit_zones(
+    _trigger: Trigger<ObjectiveCompletedEvent>,
+    zones: Query<(Entity, &ExitZone)>,
+    spawn_points: Query<&GlobalTransform, With<PlayerSpawnPoint>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let mut zones: Vec<(Entity, f32)> = zones.iter().map(|(e, z)| (e, z.radius)).collect();
+
+    if zones.is_empty() {
+        let Some(spawn_point) = spawn_points.iter().next() else {
+            warn!("No exit zone and no player spawn point, can't place an exit!");
+            return;
+        };
+        let zone = ExitZone::default();
+        let radius = zone.radius;
+        let entity = commands
+            .spawn((
+                Name::new("Exit Zone"),
+                zone,
+                Transform::from_translation(spawn_point.translation().with_y(0.0)),
+                Visibility::default(),
+                StateScoped(Screen::Gameplay),
+            ))
+            .id();
+        zones.push((entity, radius));
+    }
+
+    let material = materials.add(StandardMaterial {
+        base_color: GOLD.with_alpha(0.3).into(),
+        emissive: LinearRgba::from(GOLD) * 2.0,
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+    for (entity, radius) in zones {
+        let glow = commands
+            .spawn((
+                Name::new("Exit Glow"),
+                Mesh3d(meshes.add(Cylinder::new(radius, 0.1))),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(0.0, 0.05, 0.0),
+                NotShadowCaster,
+                NotShadowReceiver,
+            ))
+            .id();
+        commands.entity(entity).insert(ExitUnlocked).add_child(glow);
+    }
+}
+
+fn leave_level_through_exit(
+    zones: Query<&CollidingEntities, (With<ExitZone>, With<ExitUnlocked>)>,
+    player: Single<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    let player = player.into_inner();
+    if zones.iter().any(|colliding| colliding.contains(&player)) {
+        commands.trigger(OutcomeEvent::Win(OutcomeReason::ReachedExit));
+    }
+}
+
+// ===================
+// HUD
+// ===================
+
+#[derive(Component)]
+struct ExitMarker;
+
+fn spawn_exit_marker(font_assets: Res<FontAssets>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Exit Marker"),
+        ExitMarker,
+        Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        Text::new("EXIT"),
+        Localized("EXIT".to_string()),
+        TextFont {
+            font: font_assets.header.clone(),
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(GOLD.into()),
+        TextShadow {
+            color: BLACK.into(),
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    ));
+}
+
+/// Keeps the marker above the closest unlocked exit, clamped to the screen edges if it's off-screen.
+fn update_exit_marker(
+    zones: Query<&GlobalTransform, (With<ExitZone>, With<ExitUnlocked>)>,
+    player: Single<&GlobalTransform, With<Player>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    marker: Single<(&mut Node, &mut Visibility), With<ExitMarker>>,
+) {
+    let (mut node, mut visibility) = marker.into_inner();
+    let player_position = player.translation();
+    let Some(exit) = zones.iter().map(|t| t.translation()).min_by(|a, b| {
+        a.distance_squared(player_position)
+            .total_cmp(&b.distance_squared(player_position))
+    }) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let (camera, camera_transform) = camera.into_inner();
+    let (Ok(screen_position), Some(viewport_size)) = (
+        camera.world_to_viewport(camera_transform, exit + Vec3::Y * 2.0),
+        camera.logical_viewport_size(),
+    ) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    const EDGE_MARGIN: f32 = 40.0;
+    let clamped = screen_position.clamp(Vec2::splat(EDGE_MARGIN), viewport_size - EDGE_MARGIN);
+    node.left = Val::Px(clamped.x);
+    node.top = Val::Px(clamped.y);
+    *visibility = Visibility::Inherited;
+}
@@ -8,30 +8,116 @@ use bevy::prelude::*;
 use crate::screens::Screen;
 
 pub mod aim_mode;
-mod ammo;
+pub mod ammo;
+pub mod assists;
+pub mod blob_shadows;
 pub(crate) mod boomerang;
+pub mod bullets;
 pub mod camera;
+pub mod charge_throw;
+pub mod civilian;
+pub mod collectibles;
+pub mod coop;
+pub mod corpses;
+pub mod cosmetics;
+pub mod crosshair;
+pub mod damage_indicator;
+pub mod deadeye;
+pub mod death_fx;
+pub mod decoy;
 pub mod enemy;
+pub mod footsteps;
+pub mod ghost_trail;
+pub mod hats;
 pub mod health_and_damage;
+pub mod health_pips;
+pub mod hints;
+pub mod hit_flash;
+pub mod horse;
+pub mod impact_audio;
 pub mod input;
+pub mod inspectable;
+pub mod interaction;
+pub mod kill_cam;
 pub mod level;
+pub mod level_exit;
 pub mod mouse_position;
+pub mod mutators;
+pub mod objective;
+pub mod outcome;
+pub mod pickups;
 pub mod player;
-mod score;
+pub mod practice_range;
+pub mod quick_restart;
+pub mod recap;
+pub mod score;
+pub mod sequence;
+pub mod style;
+#[cfg(test)]
+mod tests;
+pub mod throwing_plane;
+pub mod wanted;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_sub_state::<Gameplay>().add_plugins((
         camera::plugin,
         level::plugin,
         input::plugin,
-        player::plugin,
-        mouse_position::plugin,
-        boomerang::plugin,
+        (player::plugin, decoy::plugin, cosmetics::plugin),
+        (mouse_position::plugin, throwing_plane::plugin),
+        (boomerang::plugin, charge_throw::plugin, kill_cam::plugin),
         aim_mode::plugin,
         enemy::plugin,
         health_and_damage::plugin,
-        score::plugin,
+        (
+            score::plugin,
+            style::plugin,
+            mutators::plugin,
+            assists::plugin,
+        ),
         ammo::plugin,
+        interaction::plugin,
+        (objective::plugin, outcome::plugin, level_exit::plugin),
+        (collectibles::plugin, hats::plugin, pickups::plugin),
+        (sequence::plugin, practice_range::plugin),
+    ));
+    app.add_plugins((
+        (bullets::plugin, deadeye::plugin),
+        civilian::plugin,
+        (horse::plugin, footsteps::plugin, impact_audio::plugin),
+        coop::plugin,
+        hit_flash::plugin,
+        crosshair::plugin,
+        (health_pips::plugin, damage_indicator::plugin),
+        quick_restart::plugin,
+        hints::plugin,
+        recap::plugin,
+        wanted::plugin,
+        blob_shadows::plugin,
+        (death_fx::plugin, corpses::plugin),
+        ghost_trail::plugin,
+        inspectable::plugin,
+    ));
+}
+
+/// The part of the gameplay that runs without a window, renderer, audio or input devices, see
+/// [`crate::headless`].
+pub(super) fn headless_plugin(app: &mut App) {
+    app.add_sub_state::<Gameplay>().add_plugins((
+        health_and_damage::plugin,
+        ammo::plugin,
+        (boomerang::plugin, kill_cam::plugin),
+        bullets::plugin,
+        deadeye::plugin,
+        enemy::plugin,
+        objective::plugin,
+        outcome::plugin,
+        wanted::plugin,
+        style::plugin,
+        corpses::plugin,
+        throwing_plane::plugin,
+        mutators::plugin,
+        assists::plugin,
     ));
 }
 
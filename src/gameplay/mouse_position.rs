@@ -1,4 +1,4 @@
-use crate::gameplay::boomerang::BOOMERANG_FLYING_HEIGHT;
+use crate::gameplay::throwing_plane::ThrowingPlane;
 use bevy::app::{App, PreUpdate};
 use bevy::math::Vec3;
 use bevy::prelude::*;
@@ -9,9 +9,9 @@ use bevy::window::PrimaryWindow;
 pub struct MousePosition {
     /// The position in screen space coordinates.
     pub screen: Option<Vec2>,
-    /// The position in global space on the Y=0 plane.
+    /// The position in global space on the level's floor.
     pub global: Option<Vec3>,
-    /// The position in global space on the boomerang-throwing plane.
+    /// The position in global space on the [`ThrowingPlane`].
     pub boomerang_throwing_plane: Option<Vec3>,
 }
 
@@ -32,6 +32,7 @@ fn update_mouse_position(
     mut mouse_position: ResMut<MousePosition>,
     window_query: Single<&Window, With<PrimaryWindow>>,
     camera_query: Single<(&Camera, &GlobalTransform)>,
+    throwing_plane: Res<ThrowingPlane>,
 ) -> Result {
     mouse_position.reset();
 
@@ -48,46 +49,19 @@ fn update_mouse_position(
         return Ok(());
     };
 
-    mouse_position.boomerang_throwing_plane = plane_cast(
-        camera,
-        camera_transform,
-        cursor_position,
-        BOOMERANG_FLYING_HEIGHT,
-    );
-    mouse_position.global = plane_cast(camera, camera_transform, cursor_position, 0.0);
-
-    Ok(())
-}
-
-fn plane_cast(
-    camera: &Camera,
-    camera_transform: &GlobalTransform,
-    cursor_position: Vec2,
-    plane_height: f32,
-) -> Option<Vec3> {
-    // Mathematically, we can represent the ground as an infinite flat plane.
-    // To do that, we need a point (to position the plane) and a normal vector
-    // (the "up" direction, perpendicular to the ground plane).
-
-    // We can get the correct values from the ground entity's GlobalTransform
-    // I'm assuming our ground plane is at Y=0 and pointing upwards.
-    let plane_origin = Vec3::Y * plane_height;
-    let plane = InfinitePlane3d::default();
-
     // Ask Bevy to give us a ray pointing from the viewport (screen) into the world
     let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
         // if it was impossible to compute for whatever reason; we can't do anything
-        return None;
+        return Ok(());
     };
 
-    // do a ray-plane intersection test, giving us the distance to the ground
-    let Some(distance) = ray.intersect_plane(plane_origin, plane) else {
-        // If the ray does not intersect the ground
-        // (the camera is not looking towards the ground), we can't do anything
-        return None;
+    // either is None if the camera isn't looking towards that plane
+    mouse_position.boomerang_throwing_plane = throwing_plane.intersect(ray);
+    let floor = ThrowingPlane {
+        height: 0.0,
+        ..*throwing_plane
     };
+    mouse_position.global = floor.intersect(ray);
 
-    // use the distance to compute the actual point on the ground in world-space
-    let global_cursor = ray.get_point(distance);
-    Some(global_cursor)
+    Ok(())
 }
@@ -0,0 +1,160 @@
+//! Mutators: optional twists on the rules for players who've already finished the campaign. They're
+//! picked on their own screen, reachable from the title screen once the last level was beaten, and
+//! stay on for every level played after that until they're switched off again.
+//!
+//! [`Mutators`] holds what's switched on. The tuning it overrides is applied when a level starts,
+//! and bounties are scaled by [`Mutators::score_multiplier`]: the ones that make the game easier
+//! pay less, the ones that make it harder pay more.
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::BoomerangSettings;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::enemy::archetype::{EnemyArchetypeRegistry, EnemySpawnedEvent};
+use crate::gameplay::health_and_damage::OneHitKill;
+use crate::gameplay::player::Player;
+use crate::screens::Screen;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Mutators>().init_resource::<Mutators>();
+    app.add_observer(make_fragile::<Player>)
+        .add_observer(make_fragile::<Enemy>)
+        .add_observer(double_spawn_point);
+    app.add_systems(OnEnter(Gameplay::Normal), apply_mutators);
+}
+
+/// How far to the side of its spawn point the second outlaw of a doubled one comes out.
+const TWIN_OFFSET: f32 = 1.5;
+
+/// One of the twists that can be switched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Mutator {
+    /// Every hit kills, no matter if it's the player or an outlaw taking it.
+    OneHitKills,
+    /// Boomerangs always make it back: through walls if they have to, and never fumbled.
+    BoomerangNeverFalls,
+    /// Every spawn point sends out two outlaws.
+    DoubleEnemies,
+    /// Aiming doesn't slow down time.
+    NoSlowMo,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 4] = [
+        Mutator::OneHitKills,
+        Mutator::BoomerangNeverFalls,
+        Mutator::DoubleEnemies,
+        Mutator::NoSlowMo,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Mutator::OneHitKills => "One-Hit Kills",
+            Mutator::BoomerangNeverFalls => "Boomerang Never Falls",
+            Mutator::DoubleEnemies => "Double Outlaws",
+            Mutator::NoSlowMo => "No Slow-Mo",
+        }
+    }
+
+    /// What bounties are multiplied with while this one is on.
+    pub fn score_multiplier(self) -> f32 {
+        match self {
+            // the player goes down in one hit anyway, it's the tanky outlaws that get easier
+            Mutator::OneHitKills => 0.75,
+            Mutator::BoomerangNeverFalls => 0.75,
+            // twice the outlaws already means twice the bounties
+            Mutator::DoubleEnemies => 1.25,
+            Mutator::NoSlowMo => 1.5,
+        }
+    }
+}
+
+/// Which [`Mutator`]s are switched on.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct Mutators {
+    pub one_hit_kills: bool,
+    pub boomerang_never_falls: bool,
+    pub double_enemies: bool,
+    pub no_slow_mo: bool,
+}
+
+impl Mutators {
+    pub fn is_on(&self, mutator: Mutator) -> bool {
+        match mutator {
+            Mutator::OneHitKills => self.one_hit_kills,
+            Mutator::BoomerangNeverFalls => self.boomerang_never_falls,
+            Mutator::DoubleEnemies => self.double_enemies,
+            Mutator::NoSlowMo => self.no_slow_mo,
+        }
+    }
+
+    pub fn toggle(&mut self, mutator: Mutator) {
+        let on = match mutator {
+            Mutator::OneHitKills => &mut self.one_hit_kills,
+            Mutator::BoomerangNeverFalls => &mut self.boomerang_never_falls,
+            Mutator::DoubleEnemies => &mut self.double_enemies,
+            Mutator::NoSlowMo => &mut self.no_slow_mo,
+        };
+        *on = !*on;
+    }
+
+    /// What bounties are multiplied with, all the multipliers of the mutators that are on combined.
+    pub fn score_multiplier(&self) -> f32 {
+        Mutator::ALL
+            .into_iter()
+            .filter(|mutator| self.is_on(*mutator))
+            .map(Mutator::score_multiplier)
+            .product()
+    }
+}
+
+/// Tuning that lives in other modules' settings.
+fn apply_mutators(mutators: Res<Mutators>, mut boomerang_settings: ResMut<BoomerangSettings>) {
+    boomerang_settings.never_fall = mutators.boomerang_never_falls;
+}
+
+fn make_fragile<T: Component>(
+    trigger: Trigger<OnAdd, T>,
+    mutators: Res<Mutators>,
+    mut commands: Commands,
+) {
+    if mutators.one_hit_kills {
+        commands.entity(trigger.target()).insert(OneHitKill);
+    }
+}
+
+/// Put on the copy of a doubled spawn point, so it isn't doubled again.
+#[derive(Component, Debug)]
+struct Twin;
+
+/// Puts a copy of the spawn point right next to it, which sends out an outlaw of its own.
+fn double_spawn_point(
+    trigger: Trigger<EnemySpawnedEvent>,
+    spawn_points: Query<&Transform, Without<Twin>>,
+    archetypes: Res<EnemyArchetypeRegistry>,
+    mutators: Res<Mutators>,
+    mut commands: Commands,
+) {
+    if !mutators.double_enemies {
+        return;
+    }
+    let source = trigger.target();
+    let (Ok(transform), Some(archetype)) = (
+        spawn_points.get(source),
+        archetypes.get(trigger.event().archetype).copied(),
+    ) else {
+        return;
+    };
+    let mut twin_transform = *transform;
+    twin_transform.translation += transform.right() * TWIN_OFFSET;
+    let twin = commands
+        .spawn((
+            Name::new("Twin Spawn Point"),
+            Twin,
+            twin_transform,
+            StateScoped(Screen::Gameplay),
+        ))
+        .id();
+    commands.queue(move |world: &mut World| archetype.copy(world, source, twin));
+}
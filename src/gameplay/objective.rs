@@ -0,0 +1,246 @@
+//! Decides when a level is won or lost, and tells [`crate::gameplay::outcome`] about it.
+//! Every level has exactly one [`LevelObjective`], authored in the level scene (e.g. via Skein on
+//! any entity). Levels without one default to [`LevelObjective::EliminateAll`].
+//! Player death always loses the level, no matter the objective. So does killing a civilian, if the
+//! objective is about protecting people.
+//! Completing the objective unlocks the level exit, walking into it wins the level.
+
+use crate::audio::{DuckMusic, DuckPriority};
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::civilian::CivilianKilledEvent;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::{DeathEvent, Health, NoHealthHat};
+use crate::gameplay::outcome::{OutcomeEvent, OutcomeReason};
+use crate::gameplay::score::ScoreEvent;
+use crate::localization::Locale;
+use crate::ui_assets::FontAssets;
+use bevy::color::palettes::css::{BLACK, WHITE};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<LevelObjective>()
+        .register_type::<ProtectTarget>()
+        .init_resource::<ActiveObjective>();
+
+    app.add_systems(
+        OnEnter(Gameplay::Normal),
+        (reset_active_objective, spawn_objective_hud),
+    );
+    app.add_systems(
+        Update,
+        (tick_survive_timer, update_objective_hud)
+            .chain()
+            .run_if(in_state(Gameplay::Normal)),
+    );
+
+    app.add_observer(on_level_objective_added)
+        .add_observer(on_protect_target_added)
+        .add_observer(evaluate_objective_on_score_event)
+        .add_observer(on_civilian_killed);
+}
+
+/// The win condition of a level. Put this on any entity in the level scene.
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub enum LevelObjective {
+    /// Kill every enemy in the level.
+    #[default]
+    EliminateAll,
+    /// Stay alive for the given amount of (physics) seconds.
+    Survive { seconds: f32 },
+    /// Kill every enemy while keeping all [`ProtectTarget`]s alive.
+    Protect,
+    /// Nothing to win or lose, used by the [`crate::gameplay::practice_range`].
+    Practice,
+}
+
+impl LevelObjective {
+    /// Whether killing a civilian loses the level.
+    pub fn forbids_civilian_casualties(&self) -> bool {
+        matches!(self, LevelObjective::Protect)
+    }
+}
+
+/// Something the player needs to keep alive, like a wagon. Used by [`LevelObjective::Protect`].
+/// Gets [`Health`] on spawn, so it can be shot by bullets (it needs a collider for that).
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct ProtectTarget {
+    pub health: i32,
+}
+
+impl Default for ProtectTarget {
+    fn default() -> Self {
+        Self { health: 5 }
+    }
+}
+
+/// The objective of the level that's currently being played.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveObjective {
+    pub objective: LevelObjective,
+    /// Seconds survived so far, only relevant for [`LevelObjective::Survive`].
+    pub elapsed: f32,
+    /// Set once the objective has been fulfilled, so we only ever complete it once.
+    pub completed: bool,
+}
+
+/// Fired once the [`ActiveObjective`] has been fulfilled.
+#[derive(Event, Debug)]
+pub struct ObjectiveCompletedEvent;
+
+fn reset_active_objective(mut commands: Commands) {
+    commands.insert_resource(ActiveObjective::default());
+}
+
+fn on_level_objective_added(
+    trigger: Trigger<OnAdd, LevelObjective>,
+    objectives: Query<&LevelObjective>,
+    mut active_objective: ResMut<ActiveObjective>,
+) {
+    let Ok(objective) = objectives.get(trigger.target()) else {
+        return;
+    };
+    info!("Level objective: {:?}", objective);
+    active_objective.objective = objective.clone();
+}
+
+fn on_protect_target_added(
+    trigger: Trigger<OnAdd, ProtectTarget>,
+    targets: Query<&ProtectTarget>,
+    mut commands: Commands,
+) {
+    let Ok(target) = targets.get(trigger.target()) else {
+        return;
+    };
+    commands
+        .entity(trigger.target())
+        .insert((NoHealthHat, Health(target.health)))
+        .observe(on_protect_target_death);
+}
+
+fn on_protect_target_death(
+    _trigger: Trigger<DeathEvent>,
+    active_objective: Res<ActiveObjective>,
+    mut commands: Commands,
+) {
+    if active_objective.objective == LevelObjective::Protect && !active_objective.completed {
+        commands.trigger(OutcomeEvent::Lose(OutcomeReason::ProtectTargetLost));
+    }
+}
+
+fn on_civilian_killed(
+    _trigger: Trigger<CivilianKilledEvent>,
+    active_objective: Res<ActiveObjective>,
+    mut commands: Commands,
+) {
+    if active_objective.objective.forbids_civilian_casualties() {
+        commands.trigger(OutcomeEvent::Lose(OutcomeReason::CivilianKilled));
+    }
+}
+
+fn tick_survive_timer(
+    time: GameTime,
+    mut active_objective: ResMut<ActiveObjective>,
+    mut commands: Commands,
+) {
+    let LevelObjective::Survive { seconds } = active_objective.objective else {
+        return;
+    };
+    active_objective.elapsed += time.delta_secs();
+    if active_objective.elapsed >= seconds {
+        complete_objective(&mut active_objective, &mut commands);
+    }
+}
+
+fn evaluate_objective_on_score_event(
+    trigger: Trigger<ScoreEvent>,
+    enemies: Query<&Health, With<Enemy>>,
+    mut active_objective: ResMut<ActiveObjective>,
+    mut commands: Commands,
+) {
+    if !matches!(trigger.event(), ScoreEvent::EnemyDeath) {
+        return;
+    }
+    let kill_objective = matches!(
+        active_objective.objective,
+        LevelObjective::EliminateAll | LevelObjective::Protect
+    );
+    if kill_objective && enemies.is_empty() {
+        // let the last kill sink in
+        commands.trigger(DuckMusic(DuckPriority::FinalKill));
+        complete_objective(&mut active_objective, &mut commands);
+    }
+}
+
+/// Marks the objective as done. Doesn't end the level by itself, the player still has to reach the
+/// exit (see [`crate::gameplay::level_exit`]).
+fn complete_objective(active_objective: &mut ActiveObjective, commands: &mut Commands) {
+    if active_objective.completed {
+        return;
+    }
+    info!("Objective completed: {:?}", active_objective.objective);
+    active_objective.completed = true;
+    commands.trigger(ObjectiveCompletedEvent);
+}
+
+// ===================
+// HUD
+// ===================
+
+#[derive(Component)]
+struct ObjectiveLabel;
+
+fn spawn_objective_hud(font_assets: Res<FontAssets>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Objective"),
+        ObjectiveLabel,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            top: Val::Px(20.0),
+            ..default()
+        },
+        Text::new(""),
+        TextFont {
+            font: font_assets.content.clone(),
+            font_size: 24.0,
+            ..default()
+        },
+        TextColor(WHITE.into()),
+        TextShadow {
+            color: BLACK.into(),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    ));
+}
+
+fn update_objective_hud(
+    active_objective: Res<ActiveObjective>,
+    enemies: Query<(), (With<Enemy>, With<Health>)>,
+    mut label: Single<&mut Text, With<ObjectiveLabel>>,
+    locale: Locale,
+) {
+    if active_objective.completed {
+        label.0 = locale.tr("Head for the exit!").to_string();
+        return;
+    }
+
+    let enemies_left = enemies.iter().count();
+    let (text, count) = match active_objective.objective {
+        LevelObjective::EliminateAll => ("Outlaws left: {}", enemies_left.to_string()),
+        LevelObjective::Survive { seconds } => {
+            let remaining = (seconds - active_objective.elapsed).max(0.0);
+            ("Survive: {}s", format!("{remaining:.0}"))
+        }
+        LevelObjective::Protect => (
+            "Protect the wagon! Outlaws left: {}",
+            enemies_left.to_string(),
+        ),
+        LevelObjective::Practice => ("Practice range", String::new()),
+    };
+    label.0 = locale.tr(text).replace("{}", &count);
+}
@@ -0,0 +1,94 @@
+//! Ends levels. Whatever decides that a level is won or lost, like the player dying, the objective
+//! failing or the player walking through the exit, triggers an [`OutcomeEvent`] saying so and why.
+//! Only the first one counts: it's recorded in [`Winner`] and [`OutcomeReason`], then the level
+//! moves on to [`Gameplay::GameOver`], where the score screen, the recap and everything else that
+//! cares about how the level went reads them.
+
+use crate::gameplay::Gameplay;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Winner>()
+        .register_type::<OutcomeReason>()
+        .init_resource::<Winner>()
+        .init_resource::<OutcomeReason>();
+    app.add_observer(end_level);
+}
+
+/// Triggered to end the level that's currently being played.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutcomeEvent {
+    Win(OutcomeReason),
+    Lose(OutcomeReason),
+}
+
+impl OutcomeEvent {
+    pub fn winner(&self) -> Winner {
+        match self {
+            OutcomeEvent::Win(_) => Winner::Player,
+            OutcomeEvent::Lose(_) => Winner::Enemy,
+        }
+    }
+
+    pub fn reason(&self) -> OutcomeReason {
+        match self {
+            OutcomeEvent::Win(reason) | OutcomeEvent::Lose(reason) => *reason,
+        }
+    }
+}
+
+/// Who won the last level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub enum Winner {
+    Player,
+    #[default]
+    Enemy,
+}
+
+/// Why the last level ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub enum OutcomeReason {
+    /// The player walked through the exit after completing the objective.
+    ReachedExit,
+    PlayerDied,
+    /// Something the objective was about protecting got destroyed.
+    ProtectTargetLost,
+    /// A civilian was killed while the objective forbade it.
+    CivilianKilled,
+    /// The player left the level before it was decided.
+    #[default]
+    GaveUp,
+}
+
+impl OutcomeReason {
+    /// What the game over screen says about losing this way.
+    pub fn defeat_message(self) -> &'static str {
+        match self {
+            OutcomeReason::ProtectTargetLost => "Ye let 'em wreck what ye were guardin', pardner",
+            OutcomeReason::CivilianKilled => "Ye gunned down an innocent soul, pardner",
+            OutcomeReason::GaveUp => "Ye rode off 'fore the job was done, pardner",
+            OutcomeReason::ReachedExit | OutcomeReason::PlayerDied => {
+                "You been took t' an early grave, pardner"
+            }
+        }
+    }
+}
+
+fn end_level(
+    trigger: Trigger<OutcomeEvent>,
+    state: Res<State<Gameplay>>,
+    mut next_state: ResMut<NextState<Gameplay>>,
+    mut commands: Commands,
+) {
+    // whatever ended the level first wins, e.g. dying while stepping into the exit
+    if *state.get() != Gameplay::Normal || matches!(*next_state, NextState::Pending(_)) {
+        return;
+    }
+    let outcome = trigger.event();
+    info!("Level over: {:?}", outcome);
+    commands.insert_resource(outcome.winner());
+    commands.insert_resource(outcome.reason());
+    next_state.set(Gameplay::GameOver);
+}
@@ -0,0 +1,102 @@
+//! Pickups: things lying around in levels that players collect by walking up to them. A pickup
+//! implements [`Pickup`] on its component and gets registered with
+//! [`RegisterPickup::register_pickup`], after that it's collected like any other one:
+//! [`PickedUpEvent`] gets triggered on it and it's despawned in a sparkle. What it does for the
+//! player is up to whoever observes that.
+//!
+//! The [`PickupRegistry`] keeps track of every registered pickup, e.g. for the level editor.
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::player::Player;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use bevy::prelude::*;
+use bevy::reflect::GetTypeRegistration;
+use std::any::TypeId;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<PickupRegistry>();
+}
+
+/// Something players collect by walking up to it.
+pub trait Pickup: Component + GetTypeRegistration {
+    const NAME: &'static str;
+    /// How close to it a player has to get.
+    const RADIUS: f32 = 1.5;
+}
+
+/// Triggered on a pickup when a player collects it, right before it's despawned.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PickedUpEvent {
+    pub by: Entity,
+}
+
+/// A registered [`Pickup`], without its type.
+#[derive(Debug, Clone, Copy)]
+pub struct PickupInfo {
+    pub name: &'static str,
+    pub type_id: TypeId,
+}
+
+/// Every [`Pickup`] the game knows about, in the order they were registered.
+#[derive(Resource, Debug, Default)]
+pub struct PickupRegistry {
+    pickups: Vec<PickupInfo>,
+}
+
+impl PickupRegistry {
+    pub fn iter(&self) -> impl Iterator<Item = &PickupInfo> {
+        self.pickups.iter()
+    }
+}
+
+pub trait RegisterPickup {
+    /// Makes players collect pickups of type `P`.
+    fn register_pickup<P: Pickup>(&mut self) -> &mut Self;
+}
+
+impl RegisterPickup for App {
+    fn register_pickup<P: Pickup>(&mut self) -> &mut Self {
+        self.register_type::<P>()
+            .init_resource::<PickupRegistry>()
+            .add_systems(
+                Update,
+                collect_pickups::<P>.run_if(in_state(Gameplay::Normal)),
+            );
+        let mut registry = self.world_mut().resource_mut::<PickupRegistry>();
+        if !registry
+            .pickups
+            .iter()
+            .any(|pickup| pickup.type_id == TypeId::of::<P>())
+        {
+            registry.pickups.push(PickupInfo {
+                name: P::NAME,
+                type_id: TypeId::of::<P>(),
+            });
+        }
+        self
+    }
+}
+
+fn collect_pickups<P: Pickup>(
+    players: Query<(Entity, &GlobalTransform), With<Player>>,
+    pickups: Query<(Entity, &GlobalTransform), With<P>>,
+    mut commands: Commands,
+) {
+    for (pickup, transform) in &pickups {
+        let position = transform.translation().with_y(0.0);
+        let Some((player, _)) = players
+            .iter()
+            .find(|(_, player)| player.translation().with_y(0.0).distance(position) <= P::RADIUS)
+        else {
+            continue;
+        };
+        commands
+            .entity(pickup)
+            .trigger(PickedUpEvent { by: player })
+            .despawn();
+        commands.trigger(SpawnVfxEvent::new(
+            Vfx::PickupSparkle,
+            transform.translation(),
+        ));
+    }
+}
@@ -1,12 +1,18 @@
 //! Player-specific behavior.
 
+use crate::audio::{DuckMusic, DuckPriority, SfxListener};
+use crate::clock::{GameTime, UiTime};
 use crate::gameplay::Gameplay;
-use crate::gameplay::ammo::HasLimitedAmmo;
+use crate::gameplay::aim_mode::AimModeState;
+use crate::gameplay::ammo::BoomerangInventory;
 use crate::gameplay::boomerang::CurrentBoomerangThrowOrigin;
-use crate::gameplay::camera::CameraFollowTarget;
-use crate::gameplay::health_and_damage::{DeathEvent, Health};
+use crate::gameplay::camera::{CameraFollowTarget, CameraZoomOut};
+use crate::gameplay::health_and_damage::{AbsorbNextHit, DeathEvent, Health, HitAbsorbedEvent};
+use crate::gameplay::hit_flash::HitFlashColor;
 use crate::gameplay::input::{PlayerActions, PlayerMoveAction};
-use crate::gameplay::score::ScoreEvent;
+use crate::gameplay::mouse_position::MousePosition;
+use crate::gameplay::outcome::{OutcomeEvent, OutcomeReason};
+use crate::gameplay::throwing_plane::{BOOMERANG_FLYING_HEIGHT, ThrowingPlane};
 use crate::physics_layers::GameLayer;
 use crate::screens::Screen;
 use avian3d::prelude::{
@@ -19,17 +25,26 @@ use bevy_enhanced_input::prelude::{Actions, Fired};
 
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-struct PlayerSpawnPoint;
+pub struct PlayerSpawnPoint;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Player>()
-        .register_type::<PlayerSpawnPoint>();
+        .register_type::<PlayerSpawnPoint>()
+        .register_type::<Rideable>()
+        .register_type::<WalkInput>();
+    app.register_required_components::<Player, SfxListener>();
 
     app.add_observer(spawn_player_to_point);
     // we attach movement-related observers to the player entity so that they
     // get despawned when the player does. That way, movement happens only while
     // playing, not while e.g. in a menu or splash screen.
     app.add_observer(add_player_movement_on_spawn);
+    app.add_observer(on_start_riding)
+        .add_observer(on_stop_riding);
+    app.add_systems(
+        Update,
+        (walk, turn_player, recover_from_stagger).run_if(in_state(Gameplay::Normal)),
+    );
     app.add_systems(OnExit(Gameplay::Normal), teardown);
 }
 
@@ -67,6 +82,7 @@ fn spawn_player_to_point(
                 GameLayer::Player,
                 [
                     GameLayer::Enemy,
+                    GameLayer::Civilian,
                     GameLayer::Bullet,
                     GameLayer::Terrain,
                     GameLayer::Default,
@@ -76,8 +92,13 @@ fn spawn_player_to_point(
             // also solves problem with weird wall slides
             Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
         ))
-        .insert((Health::default(), HasLimitedAmmo(1)))
-        .observe(on_player_death);
+        .insert((
+            Health::default(),
+            BoomerangInventory::new(1),
+            HitFlashColor(Color::srgb(1.0, 0.1, 0.1)),
+        ))
+        .observe(on_player_death)
+        .observe(dismount_on_hit);
 }
 
 fn teardown(player: Single<Entity, With<Player>>, mut commands: Commands) {
@@ -88,25 +109,144 @@ fn teardown(player: Single<Entity, With<Player>>, mut commands: Commands) {
         .remove::<AngularVelocity>();
 }
 
-fn on_player_death(_trigger: Trigger<DeathEvent>, mut commands: Commands) {
-    commands.trigger(ScoreEvent::PlayerDeath);
+pub(super) fn on_player_death(_trigger: Trigger<DeathEvent>, mut commands: Commands) {
+    commands.trigger(OutcomeEvent::Lose(OutcomeReason::PlayerDied));
+    commands.trigger(DuckMusic(DuckPriority::PlayerDeath));
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
 pub struct Player;
 
+// ===== RIDING =====
+
+/// Something the player can ride, like a horse.
+/// While riding, these settings replace the player's own [MovementSettings].
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Rideable {
+    pub speed: f32,
+    /// How fast (in radians per second) the mount can change direction.
+    pub turn_rate: f32,
+    /// Boomerangs thrown while riding fly this high above the floor.
+    pub throw_height: f32,
+    /// See [CameraZoomOut].
+    pub camera_zoom_out: f32,
+}
+
+/// Added to the player while riding a [Rideable]. Getting hit knocks the player off their mount
+/// instead of hurting them. Remove it to dismount.
+#[derive(Component, Debug)]
+pub struct Riding(pub Entity);
+
+fn on_start_riding(
+    trigger: Trigger<OnAdd, Riding>,
+    riders: Query<&Riding>,
+    rideables: Query<&Rideable>,
+    mut throwing_plane: ResMut<ThrowingPlane>,
+    mut commands: Commands,
+) {
+    let Ok(riding) = riders.get(trigger.target()) else {
+        return;
+    };
+    let Ok(rideable) = rideables.get(riding.0) else {
+        warn!("Tried to ride {:?}, which isn't rideable", riding.0);
+        commands.entity(trigger.target()).remove::<Riding>();
+        return;
+    };
+    throwing_plane.height = rideable.throw_height;
+    commands
+        .entity(trigger.target())
+        .insert((AbsorbNextHit, CameraZoomOut(rideable.camera_zoom_out)));
+}
+
+fn on_stop_riding(
+    trigger: Trigger<OnRemove, Riding>,
+    mut throwing_plane: ResMut<ThrowingPlane>,
+    mut commands: Commands,
+) {
+    throwing_plane.height = BOOMERANG_FLYING_HEIGHT;
+    commands
+        .entity(trigger.target())
+        .try_remove::<(AbsorbNextHit, CameraZoomOut)>();
+}
+
+fn dismount_on_hit(trigger: Trigger<HitAbsorbedEvent>, mut commands: Commands) {
+    commands.entity(trigger.target()).remove::<Riding>();
+}
+
+// ===== STAGGER =====
+
+/// Keeps the player from moving until the timer runs out, e.g. after fumbling a catch.
+#[derive(Component, Debug)]
+pub struct Staggered(Timer);
+
+impl Staggered {
+    pub fn new(seconds: f32) -> Self {
+        Self(Timer::from_seconds(seconds, TimerMode::Once))
+    }
+}
+
+fn recover_from_stagger(
+    time: GameTime,
+    mut staggered: Query<(Entity, &mut Staggered)>,
+    mut commands: Commands,
+) {
+    for (entity, mut stagger) in &mut staggered {
+        if stagger.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Staggered>();
+        }
+    }
+}
+
+// ===== MOVEMENT =====
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
+#[require(WalkInput)]
 pub struct MovementSettings {
     pub walk_speed: f32,
+    /// How quickly (in units per second squared) walking picks up speed.
+    pub acceleration: f32,
+    /// How quickly (in units per second squared) letting go of the stick comes to a stop.
+    pub deceleration: f32,
+    /// How much of the acceleration is lost while changing direction, from 0.0 (none) to 1.0 (all
+    /// of it when turning around). Makes strafing back and forth feel like it has some weight.
+    pub turn_penalty: f32,
+    /// How fast (in radians per second) the player turns to face where they're aiming or walking.
+    pub turn_rate: f32,
 }
 impl Default for MovementSettings {
     fn default() -> Self {
-        Self { walk_speed: 6. }
+        Self {
+            walk_speed: 6.,
+            acceleration: 60.,
+            deceleration: 45.,
+            turn_penalty: 0.35,
+            turn_rate: 15.,
+        }
+    }
+}
+
+impl MovementSettings {
+    /// How quickly to go from `current` to `desired`, which is slower the more it's a turn.
+    fn acceleration_towards(&self, current: Vec3, desired: Vec3) -> f32 {
+        let (Some(current), Some(desired)) = (current.try_normalize(), desired.try_normalize())
+        else {
+            return self.acceleration;
+        };
+        // 0.0 going straight ahead, 1.0 turning around
+        let turn = (1.0 - current.dot(desired)) / 2.0;
+        self.acceleration * (1.0 - self.turn_penalty.clamp(0.0, 1.0) * turn)
     }
 }
 
+/// Where a walker wants to go: camera-aligned, on the ground and zero while there's no input.
+/// [`walk`] gets their velocity there over time.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct WalkInput(pub Vec3);
+
 fn add_player_movement_on_spawn(
     trigger: Trigger<OnAdd, Player>,
     query: Query<Entity, With<Player>>,
@@ -123,30 +263,102 @@ fn add_player_movement_on_spawn(
 
 fn record_player_directional_input(
     trigger: Trigger<Fired<PlayerMoveAction>>,
-    player_query: Single<
-        (&mut LinearVelocity, &MovementSettings),
-        (With<Player>, Without<Camera3d>),
-    >,
+    mut player: Single<&mut WalkInput, (With<Player>, Without<Camera3d>)>,
     camera_query: Single<&Transform, With<Camera3d>>,
 ) {
     // Rotate input to be on the ground and aligned with camera
     let camera_rotation = camera_query.into_inner().rotation;
     let input_mapped_to_3d = Vec3::new(trigger.value.x, 0.0, -trigger.value.y);
-    let velocity = (camera_rotation * input_mapped_to_3d)
+    player.0 = (camera_rotation * input_mapped_to_3d)
         .with_y(0.)
         .normalize_or_zero();
-
-    let (mut linear_velocity, settings) = player_query.into_inner();
-    let final_velocity = velocity * settings.walk_speed;
-    linear_velocity.0 = final_velocity;
 }
 
 fn stop_player_directional_input(
     _trigger: Trigger<Completed<PlayerMoveAction>>,
-    player: Single<&mut LinearVelocity, With<Player>>,
+    mut player: Single<&mut WalkInput, With<Player>>,
 ) {
-    let mut player = player.into_inner();
-    player.x = 0.;
-    player.y = 0.;
-    player.z = 0.;
+    player.0 = Vec3::ZERO;
+}
+
+/// Speeds walkers up towards their [`WalkInput`] and slows them down once they let go.
+fn walk(
+    time: GameTime,
+    mut walkers: Query<(
+        &WalkInput,
+        &MovementSettings,
+        &mut LinearVelocity,
+        Option<&Riding>,
+        Has<Staggered>,
+    )>,
+    rideables: Query<&Rideable>,
+) {
+    for (input, settings, mut linear_velocity, riding, staggered) in &mut walkers {
+        if staggered {
+            linear_velocity.0 = Vec3::ZERO;
+            continue;
+        }
+        // Picks up from whatever the physics left of last frame's velocity, so walking into a wall
+        // doesn't build up speed that's released all at once when sliding off it.
+        let current = linear_velocity.0.with_y(0.0);
+        let rideable = riding.and_then(|riding| rideables.get(riding.0).ok());
+        if let Some(rideable) = rideable.filter(|_| input.0 != Vec3::ZERO) {
+            // Mounts can't turn on the spot, steer the current heading towards the input instead
+            let heading = current.xz().normalize_or_zero();
+            let desired = input.0.xz();
+            let steered = if heading == Vec2::ZERO {
+                desired
+            } else {
+                heading.rotate_towards(desired, rideable.turn_rate * time.delta_secs())
+            };
+            linear_velocity.0 = Vec3::new(steered.x, 0.0, steered.y) * rideable.speed;
+            continue;
+        }
+
+        let desired = input.0 * settings.walk_speed;
+        let acceleration = if desired == Vec3::ZERO {
+            settings.deceleration
+        } else {
+            settings.acceleration_towards(current, desired)
+        };
+        let step = acceleration * time.delta_secs();
+        let difference = desired - current;
+        linear_velocity.0 = if difference.length() <= step {
+            desired
+        } else {
+            current + difference.normalize() * step
+        };
+    }
+}
+
+// ===== FACING =====
+
+/// Turns the player towards the cursor while aiming, and towards where they're walking otherwise.
+/// Standing still keeps the last facing.
+fn turn_player(
+    // real time, so the player still snaps around quickly while aiming in slow motion
+    time: UiTime,
+    aim_mode: Res<State<AimModeState>>,
+    mouse_position: Res<MousePosition>,
+    player: Single<(&mut Transform, &LinearVelocity, &MovementSettings), With<Player>>,
+) {
+    let (mut transform, velocity, settings) = player.into_inner();
+    let direction = match aim_mode.get() {
+        AimModeState::Aiming => mouse_position
+            .boomerang_throwing_plane
+            .map(|aim| aim - transform.translation),
+        _ => Some(velocity.0),
+    };
+    let Some(direction) = direction.and_then(|direction| direction.with_y(0.0).try_normalize())
+    else {
+        return;
+    };
+
+    let target = Transform::default().looking_to(direction, Vec3::Y).rotation;
+    let angle = transform.rotation.angle_between(target);
+    if angle < f32::EPSILON {
+        return;
+    }
+    let step = (settings.turn_rate * time.delta_secs() / angle).min(1.0);
+    transform.rotation = transform.rotation.slerp(target, step);
 }
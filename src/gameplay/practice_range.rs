@@ -0,0 +1,362 @@
+//! The practice range, always open from the title screen. No outlaws and no bounty, just target
+//! dummies to try out throws, ricochets and new boomerangs on.
+//!
+//! The range isn't a level scene but built right here, while [`PracticeRange`] exists. Dummies
+//! can't be killed, they count how often they were hit and the most bounces a hit took. Some of
+//! them stand still, some walk back and forth. Posts along the lane mark the distance from where
+//! the player starts. Boomerangs come right back into their slot as soon as they're thrown, and the
+//! lever next to the start resets every dummy.
+
+use crate::audio::music;
+use crate::gameplay::Gameplay;
+use crate::gameplay::ammo::InfiniteAmmo;
+use crate::gameplay::health_and_damage::{Health, HealthEvent, Invulnerable, NoHealthHat};
+use crate::gameplay::interaction::{InteractEvent, Interactable};
+use crate::gameplay::level::LevelAssets;
+use crate::gameplay::objective::LevelObjective;
+use crate::gameplay::player::{Player, PlayerSpawnPoint};
+use crate::localization::Locale;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use crate::theme::particles::{SpawnVfxEvent, Vfx};
+use crate::ui_assets::FontAssets;
+use avian3d::prelude::{Collider, CollisionLayers, LinearVelocity, RigidBody};
+use bevy::color::palettes::css::{BLACK, WHITE};
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<TargetDummy>();
+    app.add_observer(give_infinite_ammo);
+    app.add_systems(OnEnter(Screen::Title), leave_practice_range);
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_practice_range.run_if(resource_exists::<PracticeRange>),
+    );
+    app.add_systems(
+        Update,
+        (walk_dummies, update_dummy_labels, place_labels)
+            .chain()
+            .run_if(in_state(Gameplay::Normal).and(resource_exists::<PracticeRange>)),
+    );
+}
+
+/// Exists while the player is on the practice range instead of a level.
+#[derive(Resource, Debug, Default)]
+pub struct PracticeRange;
+
+/// How far apart the distance markers are, in meters.
+const MARKER_SPACING: f32 = 5.0;
+const MARKER_COUNT: usize = 6;
+/// Half the size of the ground.
+const RANGE_EXTENT: f32 = 40.0;
+/// How far the walls along the lane are from its middle.
+const WALL_OFFSET: f32 = 9.0;
+const LABEL_HEIGHT: f32 = 2.2;
+/// How far the reset lever leans to either side.
+const LEVER_TILT: f32 = std::f32::consts::FRAC_PI_4;
+
+const GROUND_COLOR: Color = Color::srgb(0.76, 0.6, 0.42);
+const WALL_COLOR: Color = Color::srgb(0.55, 0.38, 0.24);
+const DUMMY_COLOR: Color = Color::srgb(0.9, 0.8, 0.45);
+const MARKER_COLOR: Color = Color::srgb(0.35, 0.22, 0.12);
+const LEVER_COLOR: Color = Color::srgb(0.7, 0.1, 0.05);
+
+/// A practice target. Can't be killed, keeps count of the hits instead.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct TargetDummy {
+    pub hits: u32,
+    /// The most bounces any of the hits took, 1 for a direct hit.
+    pub best_bounces: usize,
+}
+
+/// Walks the dummy back and forth across the lane, up to `extent` away from `origin`.
+#[derive(Component, Debug)]
+struct WalkingDummy {
+    origin: Vec3,
+    extent: f32,
+    speed: f32,
+}
+
+#[derive(Component, Debug)]
+struct ResetLever;
+
+/// UI text following an entity in the world around.
+#[derive(Component, Debug)]
+struct WorldLabel(Entity);
+
+fn leave_practice_range(mut commands: Commands) {
+    commands.remove_resource::<PracticeRange>();
+}
+
+fn give_infinite_ammo(
+    trigger: Trigger<OnAdd, Player>,
+    practice: Option<Res<PracticeRange>>,
+    mut commands: Commands,
+) {
+    if practice.is_some() {
+        commands.entity(trigger.target()).insert(InfiniteAmmo);
+    }
+}
+
+fn spawn_practice_range(
+    level_assets: Res<LevelAssets>,
+    font_assets: Res<FontAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let terrain = CollisionLayers::new(
+        GameLayer::Terrain,
+        [
+            GameLayer::Terrain,
+            GameLayer::Player,
+            GameLayer::Default,
+            GameLayer::Bullet,
+            GameLayer::Enemy,
+            GameLayer::DeadEnemy,
+        ],
+    );
+    let range = commands
+        .spawn((
+            Name::new("Practice Range"),
+            Transform::default(),
+            Visibility::default(),
+            StateScoped(Screen::Gameplay),
+            children![
+                (
+                    Name::new("Gameplay Music"),
+                    music(level_assets.music.clone()),
+                ),
+                (Name::new("Practice Objective"), LevelObjective::Practice),
+                (
+                    Name::new("Sun"),
+                    DirectionalLight {
+                        shadows_enabled: true,
+                        ..default()
+                    },
+                    Transform::from_xyz(10.0, 20.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+                ),
+                (
+                    Name::new("Player Spawn Point"),
+                    PlayerSpawnPoint,
+                    Transform::default(),
+                ),
+            ],
+        ))
+        .id();
+
+    let mut spawn_block = |name: &'static str, position: Vec3, size: Vec3, color: Color| {
+        commands.spawn((
+            Name::new(name),
+            Transform::from_translation(position),
+            Mesh3d(meshes.add(Cuboid::from_size(size))),
+            MeshMaterial3d(materials.add(color)),
+            Collider::cuboid(size.x, size.y, size.z),
+            RigidBody::Static,
+            terrain,
+            ChildOf(range),
+        ));
+    };
+    spawn_block(
+        "Ground",
+        Vec3::NEG_Y * 0.5,
+        Vec3::new(RANGE_EXTENT * 2.0, 1.0, RANGE_EXTENT * 2.0),
+        GROUND_COLOR,
+    );
+    let lane_length = MARKER_SPACING * (MARKER_COUNT + 1) as f32;
+    for side in [-1.0, 1.0] {
+        spawn_block(
+            "Wall",
+            Vec3::new(lane_length / 2.0, 1.5, side * WALL_OFFSET),
+            Vec3::new(lane_length, 3.0, 1.0),
+            WALL_COLOR,
+        );
+    }
+    spawn_block(
+        "Back Wall",
+        Vec3::new(lane_length + 0.5, 1.5, 0.0),
+        Vec3::new(1.0, 3.0, WALL_OFFSET * 2.0 + 1.0),
+        WALL_COLOR,
+    );
+
+    let post = meshes.add(Cuboid::new(0.3, 1.2, 0.3));
+    let post_material = materials.add(MARKER_COLOR);
+    for marker in 1..=MARKER_COUNT {
+        let distance = marker as f32 * MARKER_SPACING;
+        for side in [-1.0, 1.0] {
+            let post = commands
+                .spawn((
+                    Name::new("Distance Marker"),
+                    Transform::from_xyz(distance, 0.6, side * (WALL_OFFSET - 1.5)),
+                    Mesh3d(post.clone()),
+                    MeshMaterial3d(post_material.clone()),
+                    ChildOf(range),
+                ))
+                .id();
+            spawn_label(post, format!("{distance} m"), &font_assets, &mut commands);
+        }
+    }
+
+    let dummy_mesh = meshes.add(Capsule3d::default());
+    let dummy_material = materials.add(DUMMY_COLOR);
+    let dummies = [
+        (Vec3::new(8.0, 1.0, 0.0), None),
+        (Vec3::new(14.0, 1.0, -4.0), None),
+        (Vec3::new(22.0, 1.0, 3.0), None),
+        (Vec3::new(18.0, 1.0, 0.0), Some(3.0)),
+        (Vec3::new(28.0, 1.0, 0.0), Some(5.0)),
+    ];
+    for (position, walking_speed) in dummies {
+        let mut dummy = commands.spawn((
+            Name::new("Target Dummy"),
+            TargetDummy::default(),
+            Transform::from_translation(position),
+            Mesh3d(dummy_mesh.clone()),
+            MeshMaterial3d(dummy_material.clone()),
+            Collider::capsule(0.5, 1.),
+            RigidBody::Kinematic,
+            CollisionLayers::new(GameLayer::Enemy, [GameLayer::Boomerang, GameLayer::Player]),
+            // hits still get counted, they just don't hurt
+            (Health::default(), Invulnerable, NoHealthHat),
+            ChildOf(range),
+        ));
+        dummy.observe(count_hit);
+        if let Some(speed) = walking_speed {
+            dummy.insert((
+                Name::new("Walking Target Dummy"),
+                WalkingDummy {
+                    origin: position,
+                    extent: WALL_OFFSET - 3.0,
+                    speed,
+                },
+                LinearVelocity(Vec3::Z * speed),
+            ));
+        }
+        let dummy = dummy.id();
+        spawn_label(dummy, String::new(), &font_assets, &mut commands);
+    }
+
+    commands
+        .spawn((
+            Name::new("Reset Lever"),
+            ResetLever,
+            Transform::from_xyz(-2.0, 0.6, 3.0).with_rotation(Quat::from_rotation_x(LEVER_TILT)),
+            Mesh3d(meshes.add(Cylinder::new(0.1, 1.2))),
+            MeshMaterial3d(materials.add(LEVER_COLOR)),
+            Interactable::new(2.5, "Reset targets"),
+            ChildOf(range),
+        ))
+        .observe(reset_dummies);
+}
+
+fn spawn_label(target: Entity, text: String, font_assets: &FontAssets, commands: &mut Commands) {
+    commands.spawn((
+        Name::new("Practice Label"),
+        WorldLabel(target),
+        Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        Text::new(text),
+        TextFont::from_font_size(18.0).with_font(font_assets.content.clone()),
+        TextColor(WHITE.into()),
+        TextShadow {
+            color: BLACK.into(),
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn count_hit(trigger: Trigger<HealthEvent>, mut dummies: Query<&mut TargetDummy>) {
+    let Ok(mut dummy) = dummies.get_mut(trigger.target()) else {
+        return;
+    };
+    let HealthEvent::Damage(_, bounces, _) = trigger.event();
+    dummy.hits += 1;
+    dummy.best_bounces = dummy.best_bounces.max(*bounces);
+}
+
+fn reset_dummies(
+    trigger: Trigger<InteractEvent>,
+    mut dummies: Query<(
+        &mut TargetDummy,
+        &mut Transform,
+        Option<(&WalkingDummy, &mut LinearVelocity)>,
+    )>,
+    mut levers: Query<&mut Transform, (With<ResetLever>, Without<TargetDummy>)>,
+    mut commands: Commands,
+) {
+    for (mut dummy, mut transform, walking) in &mut dummies {
+        *dummy = TargetDummy::default();
+        if let Some((walking, mut velocity)) = walking {
+            transform.translation = walking.origin;
+            velocity.0 = Vec3::Z * walking.speed;
+        }
+        commands.trigger(SpawnVfxEvent::new(Vfx::Puff, transform.translation));
+    }
+    // flip it over to the other side, so it's clear something happened
+    if let Ok(mut lever) = levers.get_mut(trigger.target()) {
+        lever.rotation = lever.rotation.inverse();
+    }
+}
+
+fn walk_dummies(mut dummies: Query<(&WalkingDummy, &Transform, &mut LinearVelocity)>) {
+    for (walking, transform, mut velocity) in &mut dummies {
+        let offset = transform.translation.z - walking.origin.z;
+        if (offset > walking.extent && velocity.z > 0.0)
+            || (offset < -walking.extent && velocity.z < 0.0)
+        {
+            velocity.z = -velocity.z;
+        }
+    }
+}
+
+fn update_dummy_labels(
+    dummies: Query<&TargetDummy, Changed<TargetDummy>>,
+    mut labels: Query<(&WorldLabel, &mut Text)>,
+    locale: Locale,
+) {
+    for (label, mut text) in &mut labels {
+        let Ok(dummy) = dummies.get(label.0) else {
+            continue;
+        };
+        let hits = locale.tr("Hits: {}").replace("{}", &dummy.hits.to_string());
+        text.0 = if dummy.best_bounces > 1 {
+            let bounces = locale
+                .tr("Best: {} bounces")
+                .replace("{}", &dummy.best_bounces.to_string());
+            format!("{hits}\n{bounces}")
+        } else {
+            hits
+        };
+    }
+}
+
+fn place_labels(
+    camera: Single<(&Camera, &GlobalTransform)>,
+    targets: Query<&GlobalTransform>,
+    mut labels: Query<(&WorldLabel, &mut Node, &mut Visibility)>,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    for (label, mut node, mut visibility) in &mut labels {
+        let screen_position = targets.get(label.0).ok().and_then(|transform| {
+            camera
+                .world_to_viewport(
+                    camera_transform,
+                    transform.translation() + Vec3::Y * LABEL_HEIGHT,
+                )
+                .ok()
+        });
+        let Some(screen_position) = screen_position else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        node.left = Val::Px(screen_position.x);
+        node.top = Val::Px(screen_position.y);
+        *visibility = Visibility::Inherited;
+    }
+}
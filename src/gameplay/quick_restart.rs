@@ -0,0 +1,120 @@
+//! Restart the level without reaching for the mouse: hold R (or Select on a gamepad) during a run,
+//! or just press it on the game over panel, where a prompt shows which button that is.
+//! Mid-run it has to be held for a moment, with a bar filling up to show it's about to happen, so
+//! brushing against the key doesn't throw away a good run.
+
+use crate::clock::UiTime;
+use bevy::prelude::*;
+
+use crate::gameplay::Gameplay;
+use crate::gameplay::input::ButtonPrompt;
+use crate::screens::Screen;
+use crate::theme::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<RestartHold>();
+    app.add_systems(OnEnter(Gameplay::Normal), spawn_restart_bar);
+    app.add_systems(
+        Update,
+        (
+            (hold_to_restart, update_restart_bar)
+                .chain()
+                .run_if(in_state(Gameplay::Normal)),
+            press_to_restart.run_if(in_state(Gameplay::GameOver)),
+        ),
+    );
+}
+
+const HOLD_SECONDS: f32 = 0.75;
+const KEY: KeyCode = KeyCode::KeyR;
+const BUTTON: GamepadButton = GamepadButton::Select;
+const KEY_GLYPH: &str = "R";
+const BUTTON_GLYPH: &str = "Select";
+
+#[derive(Resource, Debug, Default)]
+struct RestartHold {
+    /// How long the restart input has been held so far.
+    held: f32,
+    /// Only once the input was let go, so keeping it held after a restart doesn't restart again.
+    armed: bool,
+}
+
+#[derive(Component)]
+struct RestartBar;
+
+fn restart_pressed(keyboard: &ButtonInput<KeyCode>, gamepads: &Query<&Gamepad>) -> bool {
+    keyboard.pressed(KEY) || gamepads.iter().any(|gamepad| gamepad.pressed(BUTTON))
+}
+
+fn hold_to_restart(
+    // real time, so aiming in slow motion doesn't drag it out
+    time: UiTime,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut hold: ResMut<RestartHold>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if !restart_pressed(&keyboard, &gamepads) {
+        *hold = RestartHold {
+            held: 0.0,
+            armed: true,
+        };
+        return;
+    }
+    if !hold.armed {
+        return;
+    }
+    hold.held += time.delta_secs();
+    if hold.held >= HOLD_SECONDS {
+        next_screen.set(Screen::Retry);
+    }
+}
+
+fn press_to_restart(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if keyboard.just_pressed(KEY) || gamepads.iter().any(|gamepad| gamepad.just_pressed(BUTTON)) {
+        next_screen.set(Screen::Retry);
+    }
+}
+
+/// Reminds players on the game over panel that they don't need to click "Retry".
+pub fn restart_prompt() -> impl Bundle {
+    (
+        Name::new("Restart Prompt"),
+        ButtonPrompt {
+            keyboard: KEY_GLYPH,
+            gamepad: BUTTON_GLYPH,
+            text: "Retry",
+        },
+        Text::default(),
+        TextFont::from_font_size(20.0),
+        TextColor(ui_palette::LABEL_TEXT),
+    )
+}
+
+fn spawn_restart_bar(mut hold: ResMut<RestartHold>, mut commands: Commands) {
+    *hold = RestartHold::default();
+    commands.spawn((
+        Name::new("Restart Bar"),
+        RestartBar,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.0),
+            left: Val::Percent(35.0),
+            width: Val::Percent(0.0),
+            height: Val::Px(6.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.9, 0.8, 0.5)),
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    ));
+}
+
+fn update_restart_bar(hold: Res<RestartHold>, mut bar: Single<&mut Node, With<RestartBar>>) {
+    let progress = (hold.held / HOLD_SECONDS).min(1.0);
+    bar.width = Val::Percent(30.0 * progress);
+}
@@ -0,0 +1,222 @@
+//! A small top-down recap of how the run ended, shown next to the game over panel.
+//! While playing, the positions of everyone and everything flying around are recorded for the
+//! last couple of seconds. On game over those get played back in slow motion, over and over, as
+//! dots on a little map centered on the player.
+
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::boomerang::Boomerang;
+use crate::gameplay::bullets::Bullet;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::Health;
+use crate::gameplay::player::Player;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<RecapRecording>();
+    app.add_systems(OnEnter(Gameplay::Normal), clear_recording);
+    app.add_systems(Update, record.run_if(in_state(Gameplay::Normal)));
+    app.add_systems(OnEnter(Gameplay::GameOver), spawn_recap);
+    app.add_systems(Update, play_recap.run_if(in_state(Gameplay::GameOver)));
+}
+
+/// How much of the end of the run gets recorded, in game seconds.
+const RECORDED_SECONDS: f32 = 2.0;
+/// Playback speed, relative to how fast things happened.
+const PLAYBACK_SPEED: f32 = 0.25;
+/// A short pause on the last frame before the recap starts over.
+const HOLD_LAST_FRAME_SECONDS: f32 = 0.75;
+
+const MAP_SIZE: f32 = 220.0;
+/// How many world units fit across the map.
+const MAP_WORLD_SIZE: f32 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Blip {
+    Player,
+    Enemy,
+    DeadEnemy,
+    Boomerang,
+    Bullet,
+}
+
+impl Blip {
+    fn size(self) -> f32 {
+        match self {
+            Blip::Player | Blip::Enemy | Blip::DeadEnemy => 10.0,
+            Blip::Boomerang | Blip::Bullet => 5.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Blip::Player => Color::srgb(0.9, 0.9, 0.2),
+            Blip::Enemy => Color::srgb(0.85, 0.15, 0.15),
+            Blip::DeadEnemy => Color::srgba(0.6, 0.5, 0.5, 0.6),
+            Blip::Boomerang => Color::srgb(0.6, 0.85, 1.0),
+            Blip::Bullet => Color::WHITE,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RecapFrame {
+    time: f32,
+    blips: Vec<(Vec2, Blip)>,
+}
+
+#[derive(Resource, Debug, Default)]
+struct RecapRecording {
+    frames: VecDeque<RecapFrame>,
+}
+
+impl RecapRecording {
+    fn duration(&self) -> f32 {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(first), Some(last)) => last.time - first.time,
+            _ => 0.0,
+        }
+    }
+
+    /// The recorded frame closest to `seconds` after the recording starts.
+    fn frame_at(&self, seconds: f32) -> Option<&RecapFrame> {
+        let start = self.frames.front()?.time;
+        self.frames
+            .iter()
+            .find(|frame| frame.time - start >= seconds)
+            .or(self.frames.back())
+    }
+
+    /// Where the player was at the very end, the recap is centered on that.
+    fn final_player_position(&self) -> Vec2 {
+        self.frames
+            .back()
+            .and_then(|frame| frame.blips.iter().find(|(_, blip)| *blip == Blip::Player))
+            .map_or(Vec2::ZERO, |(position, _)| *position)
+    }
+}
+
+fn clear_recording(mut recording: ResMut<RecapRecording>) {
+    recording.frames.clear();
+}
+
+fn record(
+    time: UiTime,
+    players: Query<&GlobalTransform, With<Player>>,
+    enemies: Query<(&GlobalTransform, Has<Health>), With<Enemy>>,
+    boomerangs: Query<&GlobalTransform, With<Boomerang>>,
+    bullets: Query<&GlobalTransform, With<Bullet>>,
+    mut recording: ResMut<RecapRecording>,
+) {
+    let flat = |transform: &GlobalTransform| transform.translation().xz();
+    let blips = players
+        .iter()
+        .map(|transform| (flat(transform), Blip::Player))
+        .chain(enemies.iter().map(|(transform, alive)| {
+            let blip = if alive { Blip::Enemy } else { Blip::DeadEnemy };
+            (flat(transform), blip)
+        }))
+        .chain(boomerangs.iter().map(|t| (flat(t), Blip::Boomerang)))
+        .chain(bullets.iter().map(|t| (flat(t), Blip::Bullet)))
+        .collect();
+
+    let now = time.elapsed_secs();
+    recording.frames.push_back(RecapFrame { time: now, blips });
+    while recording
+        .frames
+        .front()
+        .is_some_and(|frame| now - frame.time > RECORDED_SECONDS)
+    {
+        recording.frames.pop_front();
+    }
+}
+
+#[derive(Component, Debug, Default)]
+struct RecapMap {
+    playback: f32,
+}
+
+/// One dot on the map. There's a fixed pool of them, they get moved around every frame.
+#[derive(Component, Debug)]
+struct RecapDot(usize);
+
+fn spawn_recap(recording: Res<RecapRecording>, mut commands: Commands) {
+    let dots = recording
+        .frames
+        .iter()
+        .map(|frame| frame.blips.len())
+        .max()
+        .unwrap_or_default();
+    if dots == 0 {
+        return;
+    }
+
+    commands
+        .spawn((
+            Name::new("Recap"),
+            RecapMap::default(),
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(40.0),
+                top: Val::Px(40.0),
+                width: Val::Px(MAP_SIZE),
+                height: Val::Px(MAP_SIZE),
+                border: UiRect::all(Val::Px(2.0)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.04, 0.03, 0.85)),
+            BorderColor(Color::srgb(0.5, 0.4, 0.3)),
+            Pickable::IGNORE,
+            StateScoped(Gameplay::GameOver),
+        ))
+        .with_children(|parent| {
+            for index in 0..dots {
+                parent.spawn((
+                    Name::new("Recap Dot"),
+                    RecapDot(index),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        ..default()
+                    },
+                    BackgroundColor::default(),
+                    BorderRadius::MAX,
+                    Visibility::Hidden,
+                    Pickable::IGNORE,
+                ));
+            }
+        });
+}
+
+fn play_recap(
+    // real time, so the playback speed doesn't depend on whatever the game clock is doing
+    time: UiTime,
+    recording: Res<RecapRecording>,
+    map: Single<&mut RecapMap>,
+    mut dots: Query<(&RecapDot, &mut Node, &mut BackgroundColor, &mut Visibility)>,
+) {
+    let mut map = map.into_inner();
+    let loop_length = recording.duration() / PLAYBACK_SPEED + HOLD_LAST_FRAME_SECONDS;
+    map.playback = (map.playback + time.delta_secs()) % loop_length.max(f32::EPSILON);
+    let Some(frame) = recording.frame_at(map.playback * PLAYBACK_SPEED) else {
+        return;
+    };
+
+    let center = recording.final_player_position();
+    let scale = MAP_SIZE / MAP_WORLD_SIZE;
+    for (dot, mut node, mut color, mut visibility) in &mut dots {
+        let Some((position, blip)) = frame.blips.get(dot.0) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let on_map = (*position - center) * scale + Vec2::splat(MAP_SIZE / 2.0);
+        let size = blip.size();
+        node.left = Val::Px(on_map.x - size / 2.0);
+        node.top = Val::Px(on_map.y - size / 2.0);
+        node.width = Val::Px(size);
+        node.height = Val::Px(size);
+        color.0 = blip.color();
+        *visibility = Visibility::Inherited;
+    }
+}
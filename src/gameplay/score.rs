@@ -1,4 +1,4 @@
-use avian3d::prelude::Physics;
+use crate::clock::GameTime;
 use bevy::{
     color::palettes::css::{BLACK, WHITE},
     prelude::*,
@@ -6,11 +6,18 @@ use bevy::{
 
 use crate::audio::sound_effect_non_dilated;
 use crate::gameplay::level::LevelAssets;
+use crate::gameplay::mutators::Mutators;
+use crate::gameplay::outcome::{OutcomeReason, Winner};
+use crate::gameplay::quick_restart::restart_prompt;
+use crate::gameplay::style::StyleMeter;
+use crate::localization::{Locale, Localized};
+use crate::save::SaveData;
+use crate::screens::tips::tip_label;
 use crate::theme::film_grain::FilmGrainSettingsTween;
 use crate::{
-    gameplay::{Gameplay, enemy::Enemy, health_and_damage::Health},
+    gameplay::Gameplay,
     screens::Screen,
-    theme::widget,
+    theme::{navigation::Activate, widget},
     ui_assets::{FontAssets, PanelAssets},
 };
 
@@ -38,8 +45,7 @@ impl Default for ScoreSettings {
 }
 
 pub fn plugin(app: &mut App) {
-    app.init_resource::<Winner>()
-        .init_resource::<ScoreSettings>();
+    app.init_resource::<ScoreSettings>();
     app.register_type::<Score>()
         .add_systems(
             OnEnter(Gameplay::GameOver),
@@ -69,8 +75,11 @@ fn setup(
     panel: Res<PanelAssets>,
     score: Res<Score>,
     winner: Res<Winner>,
+    reason: Res<OutcomeReason>,
     level_assets: ResMut<LevelAssets>,
     font_assets: Res<FontAssets>,
+    locale: Locale,
+    mut save_data: ResMut<SaveData>,
     mut commands: Commands,
 ) {
     let text = match *winner {
@@ -86,9 +95,16 @@ fn setup(
                 })
                 .or_insert(score.actual_score);
             info!("{:?}", level_data.all_bounties);
-            format!("You claimed $ {} as bounty", score.actual_score)
+            let best = save_data
+                .bounties
+                .entry(level_data.current_level)
+                .or_default();
+            *best = best.max(score.actual_score);
+            locale
+                .tr("You claimed $ {} as bounty")
+                .replace("{}", &score.actual_score.to_string())
         }
-        Winner::Enemy => "You been took t' an early grave, pardner".to_string(),
+        Winner::Enemy => locale.tr(reason.defeat_message()).to_string(),
     };
     commands
         .spawn((
@@ -100,6 +116,7 @@ fn setup(
             parent.spawn((
                 Name::new("Label"),
                 Text::new("CONGRATS, COWBOY"),
+                Localized("CONGRATS, COWBOY".to_string()),
                 TextFont::from_font_size(40.0).with_font(font_assets.header.clone()),
             ));
             parent.spawn((
@@ -127,27 +144,20 @@ fn setup(
                 &panel,
                 &font_assets.header,
             ));
+            parent.spawn(restart_prompt());
+            parent.spawn(tip_label());
         });
 }
 
-fn on_click_retry_level(
-    _trigger: Trigger<Pointer<Click>>,
-    mut next_state: ResMut<NextState<Screen>>,
-) {
+fn on_click_retry_level(_trigger: Trigger<Activate>, mut next_state: ResMut<NextState<Screen>>) {
     next_state.set(Screen::Retry);
 }
 
-fn on_click_next_level(
-    _trigger: Trigger<Pointer<Click>>,
-    mut next_state: ResMut<NextState<Screen>>,
-) {
+fn on_click_next_level(_trigger: Trigger<Activate>, mut next_state: ResMut<NextState<Screen>>) {
     next_state.set(Screen::NextLevel);
 }
 
-fn on_click_main_menu(
-    _trigger: Trigger<Pointer<Click>>,
-    mut next_state: ResMut<NextState<Screen>>,
-) {
+fn on_click_main_menu(_trigger: Trigger<Activate>, mut next_state: ResMut<NextState<Screen>>) {
     next_state.set(Screen::Title);
 }
 
@@ -157,19 +167,21 @@ fn reload_current_level(
 ) {
     let level_data = level_assets.into_inner();
     info!("Restarting level {}", level_data.current_level);
-    next_state.set(Screen::Gameplay);
+    next_state.set(Screen::LevelLoading);
 }
 
 fn load_next_level(
     mut next_state: ResMut<NextState<Screen>>,
     level_assets: ResMut<LevelAssets>,
+    mut save_data: ResMut<SaveData>,
     mut commands: Commands,
 ) {
     let level_data = level_assets.into_inner();
     if level_data.current_level < level_data.levels.len() - 1 {
         level_data.current_level += 1;
+        save_data.furthest_level = save_data.furthest_level.max(level_data.current_level);
         info!("Loading next level: {}", level_data.current_level);
-        next_state.set(Screen::Gameplay);
+        next_state.set(Screen::LevelLoading);
 
         // bird cry on start of last level
         if level_data.current_level == level_data.levels.len() - 1 {
@@ -177,6 +189,8 @@ fn load_next_level(
         }
     } else {
         level_data.current_level = 0;
+        // unlocks the mutators on the title screen
+        save_data.campaign_finished = true;
         next_state.set(Screen::Credits);
     }
 }
@@ -220,7 +234,7 @@ fn setup_scoreboard(font_assets: Res<FontAssets>, mut commands: Commands) {
 }
 
 fn update_score(
-    time: Res<Time<Physics>>,
+    time: GameTime,
     mut score: ResMut<Score>,
     scoreboard: Single<&mut Text, With<ScoreBoard>>,
 ) {
@@ -239,7 +253,7 @@ struct FloatingScore(Vec3, f32);
 
 fn float_score(
     score_settings: Res<ScoreSettings>,
-    time: Res<Time<Physics>>,
+    time: GameTime,
     camera: Single<(&Camera, &GlobalTransform)>,
     mut floatys: Query<(Entity, &mut Node, &mut FloatingScore, &mut TextColor)>,
     mut commands: Commands,
@@ -265,23 +279,31 @@ fn float_score(
 fn on_score_event(
     trigger: Trigger<ScoreEvent>,
     score_settings: Res<ScoreSettings>,
+    style: Res<StyleMeter>,
+    mutators: Res<Mutators>,
     mut score: ResMut<Score>,
     font_assets: Res<FontAssets>,
-    mut next_state: ResMut<NextState<Gameplay>>,
-    enemies: Query<&Health, With<Enemy>>,
     mut commands: Commands,
 ) {
     match trigger.event() {
         ScoreEvent::AddScore(dollars, position) => {
+            // the style meter and mutators only multiply gains, penalties hurt the same at any rank
+            let dollars = if *dollars > 0.0 {
+                (dollars * style.multiplier() * mutators.score_multiplier()).round()
+            } else {
+                *dollars
+            };
             score.current_t = 0.0;
             score.actual_score += dollars;
             score.old_score = score.current_displayed_score;
 
-            let font_size = score_settings.min_font_size.lerp(
-                score_settings.max_font_size,
-                *dollars / score_settings.max_font_size_score,
-            );
-            let color = Color::hsv(0.0, *dollars / score_settings.max_font_size_score, 1.0);
+            // Penalties show up at the smallest size, in full red
+            let intensity = (dollars / score_settings.max_font_size_score).max(0.0);
+            let font_size = score_settings
+                .min_font_size
+                .lerp(score_settings.max_font_size, intensity);
+            let saturation = if dollars < 0.0 { 1.0 } else { intensity };
+            let color = Color::hsv(0.0, saturation, 1.0);
             commands.spawn((
                 Node {
                     position_type: PositionType::Absolute,
@@ -301,16 +323,9 @@ fn on_score_event(
                 FloatingScore(*position, 0.0),
             ));
         }
-        ScoreEvent::EnemyDeath => {
-            if enemies.is_empty() {
-                commands.insert_resource(Winner::Player);
-                next_state.set(Gameplay::GameOver);
-            }
-        }
-        ScoreEvent::PlayerDeath => {
-            commands.insert_resource(Winner::Enemy);
-            next_state.set(Gameplay::GameOver);
-        }
+        // Whether that was the last one is up to the objective module, see
+        // crate::gameplay::outcome for how levels end
+        ScoreEvent::EnemyDeath => {}
     };
 }
 
@@ -318,15 +333,6 @@ fn on_score_event(
 pub enum ScoreEvent {
     AddScore(f32, Vec3),
     EnemyDeath,
-    PlayerDeath,
-}
-
-#[derive(PartialEq, Reflect, Resource, Default)]
-#[reflect(Resource)]
-pub enum Winner {
-    Player,
-    #[default]
-    Enemy,
 }
 
 #[derive(Default, Reflect, Resource)]
@@ -0,0 +1,126 @@
+//! The short intro when a level starts: the level's name, a quick look at the outlaws waiting in
+//! it, then the camera comes back to the player riding in. Only plays the first time around, not
+//! when retrying the same level.
+
+use super::{Cue, PlaySequence, Sequence};
+use crate::gameplay::Gameplay;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::level::LevelAssets;
+use crate::gameplay::player::Player;
+use crate::gameplay::practice_range::PracticeRange;
+use crate::gameplay::wanted::Bounty;
+use crate::localization::Locale;
+use crate::screens::Screen;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelIntro>();
+    app.add_systems(OnEnter(Screen::Title), forget_played_intro);
+    app.add_systems(
+        Update,
+        play_level_intro
+            .run_if(in_state(Gameplay::Normal).and(not(resource_exists::<PracticeRange>))),
+    );
+}
+
+/// How long the level card stays up before the camera starts moving.
+const TITLE_SECONDS: f32 = 1.8;
+/// Time spent on every outlaw, moving over and having a look.
+const PAN_SECONDS: f32 = 1.3;
+const MOVE_SECONDS: f32 = 0.8;
+/// At most this many outlaws are shown, it's supposed to be short.
+const MAX_OUTLAWS: usize = 4;
+/// How far away the player starts riding in from.
+const RIDE_IN_DISTANCE: f32 = 3.0;
+
+/// The level the intro was last played for.
+#[derive(Resource, Debug, Default)]
+struct LevelIntro {
+    played: Option<usize>,
+}
+
+fn forget_played_intro(mut intro: ResMut<LevelIntro>) {
+    intro.played = None;
+}
+
+fn play_level_intro(
+    mut intro: ResMut<LevelIntro>,
+    level_assets: Res<LevelAssets>,
+    player: Query<(Entity, &Transform), With<Player>>,
+    enemies: Query<(&Transform, Option<&Bounty>), With<Enemy>>,
+    locale: Locale,
+    mut commands: Commands,
+) {
+    let level = level_assets.current_level;
+    if intro.played == Some(level) {
+        return;
+    }
+    // waits for the level scene to spawn
+    let Ok((player, player_transform)) = player.single() else {
+        return;
+    };
+    intro.played = Some(level);
+
+    let title = locale
+        .tr("Level {}")
+        .replace("{}", &(level + 1).to_string());
+    let mut sequence = Sequence::default()
+        .at(0.0, Cue::Sound(level_assets.eagle_sfx.clone()))
+        .at(
+            0.0,
+            Cue::TextCard {
+                text: title,
+                seconds: TITLE_SECONDS,
+            },
+        );
+
+    let mut outlaws: Vec<_> = enemies.iter().collect();
+    // wanted outlaws first, then left to right so the camera doesn't jump around
+    outlaws.sort_by(|(a, a_bounty), (b, b_bounty)| {
+        b_bounty
+            .is_some()
+            .cmp(&a_bounty.is_some())
+            .then(a.translation.x.total_cmp(&b.translation.x))
+    });
+    let mut start = TITLE_SECONDS;
+    for (transform, bounty) in outlaws.into_iter().take(MAX_OUTLAWS) {
+        sequence = sequence.at(
+            start,
+            Cue::CameraTo {
+                target: transform.translation,
+                seconds: MOVE_SECONDS,
+            },
+        );
+        if let Some(bounty) = bounty {
+            sequence = sequence.at(
+                start + MOVE_SECONDS * 0.5,
+                Cue::TextCard {
+                    text: format!("{}: {}", locale.tr("WANTED"), bounty.name),
+                    seconds: PAN_SECONDS - MOVE_SECONDS * 0.5,
+                },
+            );
+        }
+        start += PAN_SECONDS;
+    }
+
+    let ride_in_from = player_transform
+        .with_translation(player_transform.translation - Vec3::X * RIDE_IN_DISTANCE);
+    commands.entity(player).insert(ride_in_from);
+    sequence = sequence
+        .at(
+            start,
+            Cue::CameraTo {
+                target: player_transform.translation,
+                seconds: MOVE_SECONDS,
+            },
+        )
+        .at(
+            start,
+            Cue::Animate {
+                entity: player,
+                to: *player_transform,
+                seconds: MOVE_SECONDS,
+            },
+        );
+    commands.trigger(PlaySequence(sequence));
+}
@@ -0,0 +1,320 @@
+//! A tiny cutscene player. A [`Sequence`] is a timeline of [`Cue`]s: camera moves, text cards,
+//! entities sliding into place and sound effects, each starting at a given time. Trigger
+//! [`PlaySequence`] to play one, e.g. the [`level_intro`].
+//!
+//! While a sequence plays, the camera stops following the player, physics is paused and the
+//! player can't move or throw. Pressing enter, space or A skips the rest of it. Either way
+//! [`SequenceFinished`] is triggered at the end, and the camera makes its way back to the player.
+
+pub mod level_intro;
+
+use crate::audio::sound_effect_non_dilated;
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::camera::{CameraDetached, framing_position};
+use crate::gameplay::input::PlayerActions;
+use crate::gameplay::player::Player;
+use crate::theme::palette::HEADER_TEXT;
+use crate::ui_assets::FontAssets;
+use avian3d::prelude::{Physics, PhysicsTime};
+use bevy::color::palettes::css::BLACK;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::Actions;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ActiveSequence>();
+    app.add_plugins(level_intro::plugin);
+
+    app.add_systems(
+        Update,
+        (skip_sequence, play_sequence).chain().run_if(
+            in_state(Gameplay::Normal).and(|active: Res<ActiveSequence>| active.0.is_some()),
+        ),
+    );
+    app.add_systems(OnExit(Gameplay::Normal), stop_sequence);
+
+    app.add_observer(start_sequence);
+}
+
+/// How long text cards take to fade in and out.
+const CARD_FADE_SECONDS: f32 = 0.3;
+
+/// Something that happens during a [`Sequence`].
+#[derive(Debug, Clone)]
+pub enum Cue {
+    /// Moves the camera over to `target`, framed the way the player usually is.
+    CameraTo {
+        target: Vec3,
+        seconds: f32,
+    },
+    /// Shows `text` in the middle of the screen. Should already be translated.
+    TextCard {
+        text: String,
+        seconds: f32,
+    },
+    /// Moves `entity` from wherever it is to `to`.
+    Animate {
+        entity: Entity,
+        to: Transform,
+        seconds: f32,
+    },
+    Sound(Handle<AudioSource>),
+}
+
+impl Cue {
+    fn seconds(&self) -> f32 {
+        match self {
+            Cue::CameraTo { seconds, .. }
+            | Cue::TextCard { seconds, .. }
+            | Cue::Animate { seconds, .. } => *seconds,
+            Cue::Sound(_) => 0.0,
+        }
+    }
+}
+
+/// A timeline of [`Cue`]s, in real seconds from the start of the sequence.
+#[derive(Debug, Clone, Default)]
+pub struct Sequence {
+    cues: Vec<(f32, Cue)>,
+}
+
+impl Sequence {
+    pub fn at(mut self, seconds: f32, cue: Cue) -> Self {
+        self.cues.push((seconds, cue));
+        self
+    }
+}
+
+/// Plays the sequence, cutting short any other one that's still playing.
+#[derive(Event, Debug)]
+pub struct PlaySequence(pub Sequence);
+
+/// Triggered once a sequence is over, whether it played out or got skipped.
+#[derive(Event, Debug)]
+pub struct SequenceFinished;
+
+/// The sequence that's playing right now, if any.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveSequence(Option<Playback>);
+
+#[derive(Debug)]
+struct Playback {
+    sequence: Sequence,
+    elapsed: f32,
+    cues: Vec<CueState>,
+}
+
+#[derive(Debug)]
+enum CueState {
+    Pending,
+    /// Moving something from this transform, or showing this text card.
+    Running(Option<Transform>, Option<Entity>),
+    Done,
+}
+
+fn start_sequence(
+    trigger: Trigger<PlaySequence>,
+    mut active: ResMut<ActiveSequence>,
+    mut physics_time: ResMut<Time<Physics>>,
+    camera: Single<Entity, With<Camera3d>>,
+    player: Query<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    if let Some(playback) = active.0.take() {
+        finish_cues(playback, &mut commands);
+    }
+    let sequence = trigger.event().0.clone();
+    active.0 = Some(Playback {
+        cues: sequence.cues.iter().map(|_| CueState::Pending).collect(),
+        sequence,
+        elapsed: 0.0,
+    });
+
+    physics_time.pause();
+    commands.entity(camera.into_inner()).insert(CameraDetached);
+    for player in &player {
+        commands.entity(player).remove::<Actions<PlayerActions>>();
+    }
+}
+
+fn skip_sequence(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut active: ResMut<ActiveSequence>,
+    mut physics_time: ResMut<Time<Physics>>,
+    camera: Single<Entity, With<Camera3d>>,
+    player: Query<Entity, With<Player>>,
+    mut commands: Commands,
+) {
+    let skipped = keys.any_just_pressed([KeyCode::Enter, KeyCode::Space])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.any_just_pressed([GamepadButton::South, GamepadButton::Start]));
+    if !skipped {
+        return;
+    }
+    if let Some(playback) = active.0.take() {
+        finish_cues(playback, &mut commands);
+    }
+    hand_back_control(
+        &mut physics_time,
+        camera.into_inner(),
+        &player,
+        &mut commands,
+    );
+}
+
+fn play_sequence(
+    // real time, physics is paused while this plays
+    time: UiTime,
+    mut active: ResMut<ActiveSequence>,
+    mut physics_time: ResMut<Time<Physics>>,
+    mut camera: Single<(Entity, &mut Transform), With<Camera3d>>,
+    mut transforms: Query<&mut Transform, Without<Camera3d>>,
+    mut cards: Query<&mut TextColor>,
+    player: Query<Entity, With<Player>>,
+    font_assets: Res<FontAssets>,
+    mut commands: Commands,
+) {
+    let Some(playback) = active.0.as_mut() else {
+        return;
+    };
+    playback.elapsed += time.delta_secs();
+
+    for ((start, cue), state) in playback.sequence.cues.iter().zip(&mut playback.cues) {
+        if playback.elapsed < *start {
+            continue;
+        }
+        if matches!(state, CueState::Pending) {
+            *state = match cue {
+                Cue::CameraTo { .. } => CueState::Running(Some(*camera.1), None),
+                Cue::TextCard { text, .. } => {
+                    let card = commands.spawn(text_card(text, &font_assets)).id();
+                    CueState::Running(None, Some(card))
+                }
+                Cue::Animate { entity, .. } => {
+                    CueState::Running(transforms.get(*entity).ok().copied(), None)
+                }
+                Cue::Sound(sound) => {
+                    commands.spawn(sound_effect_non_dilated(sound.clone(), 0.0));
+                    CueState::Done
+                }
+            };
+        }
+        let CueState::Running(from, card) = state else {
+            continue;
+        };
+
+        let t = ((playback.elapsed - start) / cue.seconds().max(f32::EPSILON)).min(1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        match cue {
+            Cue::CameraTo { target, .. } => {
+                if let Some(from) = from {
+                    camera.1.translation = from.translation.lerp(framing_position(*target), eased);
+                }
+            }
+            Cue::TextCard { seconds, .. } => {
+                let shown = playback.elapsed - start;
+                let alpha = (shown.min(*seconds - shown) / CARD_FADE_SECONDS).clamp(0.0, 1.0);
+                if let Some(mut color) = card.and_then(|card| cards.get_mut(card).ok()) {
+                    color.0.set_alpha(alpha);
+                }
+            }
+            Cue::Animate { entity, to, .. } => {
+                if let (Some(from), Ok(mut transform)) = (from, transforms.get_mut(*entity)) {
+                    transform.translation = from.translation.lerp(to.translation, eased);
+                    transform.rotation = from.rotation.slerp(to.rotation, eased);
+                    transform.scale = from.scale.lerp(to.scale, eased);
+                }
+            }
+            Cue::Sound(_) => {}
+        }
+        if t >= 1.0 {
+            if let Some(card) = card {
+                commands.entity(*card).despawn();
+            }
+            *state = CueState::Done;
+        }
+    }
+
+    if playback
+        .cues
+        .iter()
+        .all(|state| matches!(state, CueState::Done))
+    {
+        active.0 = None;
+        hand_back_control(&mut physics_time, camera.0, &player, &mut commands);
+    }
+}
+
+/// Cleans up after a sequence that got cut short, everything that was supposed to move ends up
+/// where it would have.
+fn finish_cues(playback: Playback, commands: &mut Commands) {
+    for ((_, cue), state) in playback.sequence.cues.into_iter().zip(playback.cues) {
+        if let CueState::Running(_, Some(card)) = state {
+            commands.entity(card).despawn();
+        }
+        if matches!(state, CueState::Done) {
+            continue;
+        }
+        if let Cue::Animate { entity, to, .. } = cue {
+            commands.entity(entity).insert(to);
+        }
+    }
+}
+
+fn hand_back_control(
+    physics_time: &mut Time<Physics>,
+    camera: Entity,
+    player: &Query<Entity, With<Player>>,
+    commands: &mut Commands,
+) {
+    physics_time.unpause();
+    commands.entity(camera).remove::<CameraDetached>();
+    for player in player {
+        commands
+            .entity(player)
+            .insert(Actions::<PlayerActions>::default());
+    }
+    commands.trigger(SequenceFinished);
+}
+
+/// Leaving the level in the middle of a sequence, e.g. by giving up. The player doesn't get their
+/// controls back, they're torn down with the level anyway.
+fn stop_sequence(
+    mut active: ResMut<ActiveSequence>,
+    mut physics_time: ResMut<Time<Physics>>,
+    camera: Single<Entity, With<Camera3d>>,
+    mut commands: Commands,
+) {
+    let Some(playback) = active.0.take() else {
+        return;
+    };
+    finish_cues(playback, &mut commands);
+    physics_time.unpause();
+    commands
+        .entity(camera.into_inner())
+        .remove::<CameraDetached>();
+}
+
+fn text_card(text: &str, font_assets: &FontAssets) -> impl Bundle {
+    (
+        Name::new("Text Card"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Percent(30.0),
+            ..default()
+        },
+        Text::new(text),
+        TextFont::from_font_size(64.0).with_font(font_assets.header.clone()),
+        TextColor(HEADER_TEXT.with_alpha(0.0)),
+        TextLayout::new_with_justify(JustifyText::Center),
+        TextShadow {
+            color: BLACK.into(),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+    )
+}
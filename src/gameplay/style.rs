@@ -0,0 +1,274 @@
+//! The style meter: killing outlaws one after the other, and killing them with ricochets, builds up
+//! style, which raises the style rank shown on the HUD. The higher the rank, the more every bounty
+//! is worth, see [`StyleMeter::multiplier`]. Penalties aren't multiplied.
+//!
+//! Style drains away once the player stops killing for a moment, and is gone entirely as soon as
+//! they get hit. How fast it builds up and drains is tuned with [`StyleSettings`].
+
+use crate::clock::GameTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::{DeathEvent, HitAbsorbedEvent, HurtEvent};
+use crate::gameplay::player::Player;
+use crate::screens::Screen;
+use crate::ui_assets::FontAssets;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<StyleSettings>()
+        .init_resource::<StyleSettings>()
+        .init_resource::<StyleMeter>();
+    app.add_observer(gain_style_on_kill)
+        .add_observer(lose_style_on_hurt)
+        .add_observer(lose_style_on_hit_absorbed);
+    app.add_systems(OnEnter(Screen::Gameplay), reset_style);
+    app.add_systems(OnEnter(Gameplay::Normal), spawn_style_meter);
+    app.add_systems(
+        Update,
+        (drain_style, update_style_meter)
+            .chain()
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// A step on the style meter, the player reaches it at `threshold` style.
+pub struct StyleRank {
+    pub letter: &'static str,
+    pub threshold: f32,
+    /// Bounties are multiplied by this while at this rank.
+    pub multiplier: f32,
+    color: Color,
+}
+
+pub const STYLE_RANKS: [StyleRank; 5] = [
+    StyleRank {
+        letter: "D",
+        threshold: 10.0,
+        multiplier: 1.2,
+        color: Color::srgb(0.6, 0.6, 0.6),
+    },
+    StyleRank {
+        letter: "C",
+        threshold: 25.0,
+        multiplier: 1.5,
+        color: Color::srgb(0.4, 0.7, 1.0),
+    },
+    StyleRank {
+        letter: "B",
+        threshold: 45.0,
+        multiplier: 2.0,
+        color: Color::srgb(0.4, 1.0, 0.4),
+    },
+    StyleRank {
+        letter: "A",
+        threshold: 70.0,
+        multiplier: 2.5,
+        color: Color::srgb(1.0, 0.65, 0.0),
+    },
+    StyleRank {
+        letter: "S",
+        threshold: 100.0,
+        multiplier: 3.0,
+        color: Color::srgb(1.0, 0.2, 0.1),
+    },
+];
+
+const BAR_WIDTH: f32 = 160.0;
+const BAR_HEIGHT: f32 = 10.0;
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct StyleSettings {
+    /// Style for every kill.
+    pub per_kill: f32,
+    /// Extra style for every ricochet the killing boomerang took.
+    pub per_ricochet: f32,
+    /// The meter can't fill up past this, so there's some buffer above the top rank.
+    pub max: f32,
+    /// How much style drains per second.
+    pub drain_per_second: f32,
+    /// How long after a kill the style starts draining.
+    pub drain_delay_seconds: f32,
+}
+
+impl Default for StyleSettings {
+    fn default() -> Self {
+        Self {
+            per_kill: 15.0,
+            per_ricochet: 10.0,
+            max: 120.0,
+            drain_per_second: 6.0,
+            drain_delay_seconds: 1.5,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct StyleMeter {
+    pub style: f32,
+    /// How long ago the last style was gained.
+    since_gain: f32,
+}
+
+impl StyleMeter {
+    /// The highest rank reached with the current style, if any.
+    pub fn rank(&self) -> Option<&'static StyleRank> {
+        STYLE_RANKS
+            .iter()
+            .rev()
+            .find(|rank| self.style >= rank.threshold)
+    }
+
+    /// What bounties are multiplied by right now.
+    pub fn multiplier(&self) -> f32 {
+        self.rank().map_or(1.0, |rank| rank.multiplier)
+    }
+
+    /// How far the meter is on the way to the next rank, from 0.0 to 1.0. Full at the top rank.
+    pub fn progress(&self) -> f32 {
+        let (from, to) = match self.rank() {
+            Some(rank) => {
+                let next = STYLE_RANKS
+                    .iter()
+                    .find(|next| next.threshold > rank.threshold);
+                match next {
+                    Some(next) => (rank.threshold, next.threshold),
+                    None => return 1.0,
+                }
+            }
+            None => (0.0, STYLE_RANKS[0].threshold),
+        };
+        ((self.style - from) / (to - from)).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Component, Debug)]
+struct StyleMeterHud;
+
+#[derive(Component, Debug)]
+struct StyleLetter;
+
+#[derive(Component, Debug)]
+struct StyleBar;
+
+fn reset_style(mut meter: ResMut<StyleMeter>) {
+    *meter = StyleMeter::default();
+}
+
+fn gain_style_on_kill(
+    trigger: Trigger<DeathEvent>,
+    enemies: Query<(), With<Enemy>>,
+    settings: Res<StyleSettings>,
+    mut meter: ResMut<StyleMeter>,
+) {
+    if !enemies.contains(trigger.target()) {
+        return;
+    }
+    // a direct hit counts as one bounce
+    let ricochets = trigger.event().0.saturating_sub(1) as f32;
+    meter.style =
+        (meter.style + settings.per_kill + settings.per_ricochet * ricochets).min(settings.max);
+    meter.since_gain = 0.0;
+}
+
+fn lose_style_on_hurt(
+    trigger: Trigger<HurtEvent>,
+    players: Query<(), With<Player>>,
+    mut meter: ResMut<StyleMeter>,
+) {
+    if players.contains(trigger.target()) {
+        *meter = StyleMeter::default();
+    }
+}
+
+/// Getting knocked off a horse counts as getting hit too.
+fn lose_style_on_hit_absorbed(
+    trigger: Trigger<HitAbsorbedEvent>,
+    players: Query<(), With<Player>>,
+    mut meter: ResMut<StyleMeter>,
+) {
+    if players.contains(trigger.target()) {
+        *meter = StyleMeter::default();
+    }
+}
+
+fn drain_style(time: GameTime, settings: Res<StyleSettings>, mut meter: ResMut<StyleMeter>) {
+    if meter.style <= 0.0 {
+        return;
+    }
+    meter.since_gain += time.delta_secs();
+    if meter.since_gain > settings.drain_delay_seconds {
+        meter.style = (meter.style - settings.drain_per_second * time.delta_secs()).max(0.0);
+    }
+}
+
+fn spawn_style_meter(font_assets: Res<FontAssets>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Style Meter"),
+        StyleMeterHud,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(40.0),
+            top: Val::Px(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+        children![
+            (
+                Name::new("Style Rank"),
+                StyleLetter,
+                Text::new(""),
+                TextFont::from_font_size(48.0).with_font(font_assets.header.clone()),
+                TextShadow::default(),
+            ),
+            (
+                Name::new("Style Bar Background"),
+                Node {
+                    width: Val::Px(BAR_WIDTH),
+                    height: Val::Px(BAR_HEIGHT),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::BLACK.with_alpha(0.5)),
+                BorderColor(Color::BLACK),
+                children![(
+                    Name::new("Style Bar"),
+                    StyleBar,
+                    Node {
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                )],
+            ),
+        ],
+    ));
+}
+
+fn update_style_meter(
+    meter: Res<StyleMeter>,
+    hud: Single<&mut Visibility, With<StyleMeterHud>>,
+    letter: Single<(&mut Text, &mut TextColor), With<StyleLetter>>,
+    bar: Single<(&mut Node, &mut BackgroundColor), With<StyleBar>>,
+) {
+    if !meter.is_changed() {
+        return;
+    }
+    let Some(rank) = meter.rank() else {
+        *hud.into_inner() = Visibility::Hidden;
+        return;
+    };
+    *hud.into_inner() = Visibility::Inherited;
+    let (mut text, mut text_color) = letter.into_inner();
+    if text.0 != rank.letter {
+        text.0 = rank.letter.to_string();
+    }
+    text_color.0 = rank.color;
+    let (mut node, mut background) = bar.into_inner();
+    node.width = Val::Percent(meter.progress() * 100.0);
+    background.0 = rank.color;
+}
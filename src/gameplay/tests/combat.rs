@@ -0,0 +1,855 @@
+use super::{Simulation, boomerangs_in_play};
+use crate::gameplay::Gameplay;
+use crate::gameplay::aim_mode::SLOW_MO_SCALING_FACTOR;
+use crate::gameplay::ammo::{
+    BoomerangInventory, ConsumeBoomerangSlot, InfiniteAmmo, ThrowCooldown, ThrowRejected,
+};
+use crate::gameplay::assists::Assists;
+use crate::gameplay::boomerang::{
+    Boomerang, BoomerangFlightHistory, BoomerangTargetKind, STRANDED_SECONDS, SteeringBoomerangs,
+    WeaponTarget,
+};
+use crate::gameplay::bullets::{Bullet, FireBulletsEvent};
+use crate::gameplay::coop::CoopPartner;
+use crate::gameplay::corpses::{CorpseBudget, Frozen};
+use crate::gameplay::deadeye::Deflected;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::enemy::activity::Activity;
+use crate::gameplay::enemy::flinch::Flinching;
+use crate::gameplay::enemy::shotgunner::ShotgunnerSpawnPoint;
+use crate::gameplay::enemy::spawning::Spawning;
+use crate::gameplay::enemy::squad::{Alerted, SquadId};
+use crate::gameplay::health_and_damage::{FrontalShield, Health, HealthEvent, HurtEvent};
+use crate::gameplay::kill_cam::KillCam;
+use crate::gameplay::mouse_position::MousePosition;
+use crate::gameplay::mutators::Mutators;
+use crate::gameplay::outcome::{OutcomeEvent, OutcomeReason, Winner};
+use crate::gameplay::player::Staggered;
+use crate::gameplay::style::StyleMeter;
+use crate::gameplay::throwing_plane::{BOOMERANG_FLYING_HEIGHT, ThrowingPlane};
+use crate::gameplay::wanted::Bounty;
+use crate::physics_layers::GameLayer;
+use avian3d::prelude::{Collider, CollisionLayers, LinearVelocity, RigidBody};
+use bevy::prelude::*;
+
+const PLAYER_POSITION: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+const ENEMY_POSITION: Vec3 = Vec3::new(8.0, 1.0, 0.0);
+
+#[test]
+fn boomerang_kills_enemy_and_pays_bounty() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(enemy).is_none()));
+    assert_eq!(sim.bounty(), 100.0);
+}
+
+#[test]
+fn player_dying_next_to_outlaws_pays_no_bounty() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let first = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    let second = sim.spawn_enemy(-ENEMY_POSITION, PLAYER_POSITION);
+
+    sim.app
+        .world_mut()
+        .entity_mut(player)
+        .trigger(HealthEvent::Damage(100, 0, None));
+    sim.app.update();
+
+    assert!(!sim.is_alive(player));
+    assert!(sim.is_alive(first));
+    assert!(sim.is_alive(second));
+    assert_eq!(sim.bounty(), 0.0);
+}
+
+#[test]
+fn wanted_outlaw_pays_out_reward() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    sim.app.world_mut().entity_mut(enemy).insert(Bounty {
+        name: "Test Outlaw".to_string(),
+        reward: 250.0,
+    });
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(enemy).is_none()));
+    assert_eq!(sim.bounty(), 350.0);
+}
+
+#[test]
+fn ricochets_multiply_bounty() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+
+    sim.throw_boomerang(
+        player,
+        vec![
+            BoomerangTargetKind::Position(Vec3::new(4.0, 1.5, 6.0)),
+            BoomerangTargetKind::Entity(enemy),
+        ],
+    );
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(enemy).is_none()));
+    assert_eq!(sim.bounty(), 200.0);
+}
+
+#[test]
+fn boomerang_returns_ammo_to_thrower() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(6.0, 1.5, 0.0))],
+    );
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 0);
+
+    assert!(sim.run_until(10.0, |world| !boomerangs_in_play(world)));
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 1);
+}
+
+#[test]
+fn infinite_ammo_keeps_the_slot_ready() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.app
+        .world_mut()
+        .entity_mut(player)
+        .insert((BoomerangInventory::new(1), InfiniteAmmo))
+        .trigger(ConsumeBoomerangSlot(0));
+    sim.app.update();
+
+    assert_eq!(sim.ammo(player), 1);
+    assert!(sim.app.world().get::<ThrowCooldown>(player).is_some());
+}
+
+#[test]
+fn boomerang_coming_back_from_behind_staggers_the_thrower() {
+    let mut sim = Simulation::new();
+    // faces -Z
+    let player = sim.spawn_player(PLAYER_POSITION);
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(0.0, 1.5, -6.0))],
+    );
+    assert!(sim.run_until(10.0, |world| !boomerangs_in_play(world)));
+    assert!(sim.app.world().get::<Staggered>(player).is_none());
+
+    sim.app
+        .world_mut()
+        .get_mut::<BoomerangInventory>(player)
+        .unwrap()
+        .consume(0);
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(0.0, 1.5, 6.0))],
+    );
+    assert!(sim.run_until(10.0, |world| world.get::<Staggered>(player).is_some()));
+    assert!(sim.is_alive(player));
+}
+
+#[test]
+fn auto_catch_assist_catches_boomerangs_coming_from_behind() {
+    let mut sim = Simulation::new();
+    sim.app.insert_resource(Assists {
+        auto_catch: true,
+        ..default()
+    });
+    // faces -Z
+    let player = sim.spawn_player(PLAYER_POSITION);
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(0.0, 1.5, 6.0))],
+    );
+    assert!(sim.run_until(10.0, |world| !boomerangs_in_play(world)));
+    assert!(sim.app.world().get::<Staggered>(player).is_none());
+}
+
+#[test]
+fn boomerang_detours_around_a_short_wall() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.spawn_wall(Vec3::new(3.0, 1.0, 0.0), Vec3::new(0.5, 4.0, 2.0));
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(6.0, 1.5, 0.0))],
+    );
+    assert!(sim.run_until(10.0, |world| !boomerangs_in_play(world)));
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 1);
+}
+
+#[test]
+fn bouncing_off_a_wall_at_an_angle_records_its_surface_normal() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    // faces the player along -X
+    sim.spawn_wall(Vec3::new(6.25, 1.0, 0.0), Vec3::new(0.5, 4.0, 20.0));
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(6.0, 1.5, 4.0))],
+    );
+
+    let normal = |world: &mut World| {
+        world
+            .query::<&BoomerangFlightHistory>()
+            .iter(world)
+            .find_map(|history| history.bounce_normals.first().copied())
+    };
+    assert!(sim.run_until(5.0, |world| normal(world).is_some()));
+    let normal = normal(sim.app.world_mut()).unwrap();
+    // not halfway between coming in and going back, which would point back at the player
+    assert!(normal.abs_diff_eq(Vec3::NEG_X, 0.01), "{normal}");
+}
+
+#[test]
+fn boomerang_blocked_by_a_wall_gets_stuck_in_it() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.spawn_wall(Vec3::new(3.0, 1.0, 0.0), Vec3::new(0.5, 4.0, 60.0));
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(6.0, 1.5, 0.0))],
+    );
+    // stays in the wall for a while instead of coming straight back
+    assert!(!sim.run_until(3.0, |world| !boomerangs_in_play(world)));
+    let stuck_at = boomerang_position(&mut sim);
+    assert!(stuck_at.x > 2.75 && stuck_at.x < 3.25, "{stuck_at}");
+    assert!((stuck_at.y - BOOMERANG_FLYING_HEIGHT).abs() < 0.01);
+    assert_eq!(sim.ammo(player), 0);
+
+    assert!(sim.run_until(STRANDED_SECONDS + 1.0, |world| !boomerangs_in_play(world)));
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 1);
+}
+
+#[test]
+fn walking_up_to_a_stuck_boomerang_pulls_it_out() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.spawn_wall(Vec3::new(3.0, 1.0, 0.0), Vec3::new(0.5, 4.0, 60.0));
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(6.0, 1.5, 0.0))],
+    );
+    assert!(!sim.run_until(3.0, |world| !boomerangs_in_play(world)));
+
+    // around the wall, right on the other side
+    let stuck_at = boomerang_position(&mut sim);
+    sim.app
+        .world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation = Vec3::new(stuck_at.x + 1.0, PLAYER_POSITION.y, stuck_at.z);
+    assert!(sim.run_until(0.5, |world| !boomerangs_in_play(world)));
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 1);
+}
+
+fn boomerang_position(sim: &mut Simulation) -> Vec3 {
+    sim.app
+        .world_mut()
+        .query_filtered::<&Transform, With<Boomerang>>()
+        .single(sim.app.world())
+        .unwrap()
+        .translation
+}
+
+#[test]
+fn steering_bends_a_boomerang_towards_the_cursor() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.app
+        .world_mut()
+        .entity_mut(player)
+        .insert(SteeringBoomerangs);
+    sim.app
+        .world_mut()
+        .resource_mut::<MousePosition>()
+        .boomerang_throwing_plane = Some(Vec3::new(4.0, 1.5, 8.0));
+
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(10.0, 1.5, 0.0))],
+    );
+    assert!(sim.run_until(2.0, |world| {
+        world
+            .query_filtered::<&Transform, With<Boomerang>>()
+            .iter(world)
+            .any(|transform| transform.translation.z > 1.0)
+    }));
+}
+
+#[test]
+fn charged_throw_flies_faster() {
+    let distance_flown = |charge: f32| {
+        let mut sim = Simulation::new();
+        let player = sim.spawn_player(PLAYER_POSITION);
+        sim.throw_charged_boomerang(
+            player,
+            vec![BoomerangTargetKind::Position(Vec3::new(30.0, 1.5, 0.0))],
+            charge,
+        );
+        for _ in 0..20 {
+            sim.app.update();
+        }
+        let world = sim.app.world_mut();
+        world
+            .query_filtered::<&Transform, With<Boomerang>>()
+            .single(world)
+            .unwrap()
+            .translation
+            .x
+    };
+    assert!(distance_flown(1.0) > distance_flown(0.0));
+}
+
+#[test]
+fn slow_motion_slows_flying_boomerangs_by_its_factor() {
+    let distance_flown = |speed: f32, frames: usize| {
+        let mut sim = Simulation::new();
+        sim.set_time_speed(speed);
+        let player = sim.spawn_player(PLAYER_POSITION);
+        sim.throw_boomerang(
+            player,
+            vec![BoomerangTargetKind::Position(Vec3::new(30.0, 1.5, 0.0))],
+        );
+        for _ in 0..frames {
+            sim.app.update();
+        }
+        boomerang_position(&mut sim).x
+    };
+    let normal = distance_flown(1.0, 12);
+    // ten times as many frames at a tenth of the speed
+    let slowed = distance_flown(SLOW_MO_SCALING_FACTOR, 120);
+    assert!(normal > 1.0);
+    assert!(
+        (slowed - normal).abs() < normal * 0.15,
+        "{slowed} vs {normal}"
+    );
+}
+
+#[test]
+fn boomerangs_fly_on_a_raised_arena_floor() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.app.world_mut().resource_mut::<ThrowingPlane>().floor = 3.0;
+
+    // painted targets get moved onto the plane too
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(Vec3::new(10.0, 1.5, 0.0))],
+    );
+    for _ in 0..10 {
+        sim.app.update();
+    }
+
+    let world = sim.app.world_mut();
+    let height = world
+        .query_filtered::<&Transform, With<Boomerang>>()
+        .single(world)
+        .unwrap()
+        .translation
+        .y;
+    assert!((height - (3.0 + BOOMERANG_FLYING_HEIGHT)).abs() < 0.01);
+}
+
+#[test]
+fn boomerang_survives_target_despawning_mid_flight() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+    for _ in 0..5 {
+        sim.app.update();
+    }
+    sim.app.world_mut().despawn(enemy);
+
+    assert!(sim.run_until(10.0, |world| !boomerangs_in_play(world)));
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 1);
+}
+
+#[test]
+fn hitting_an_outlaw_in_the_back_of_the_head_is_a_critical_hit() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    // turned away from the player
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, ENEMY_POSITION + Vec3::X);
+    sim.app.world_mut().entity_mut(enemy).insert(Health(3));
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+
+    assert!(sim.run_until(5.0, |world| {
+        world
+            .get::<Health>(enemy)
+            .is_some_and(|health| health.0 < 3)
+    }));
+    assert_eq!(sim.app.world().get::<Health>(enemy).unwrap().0, 1);
+    assert_eq!(sim.bounty(), 50.0);
+}
+
+#[test]
+fn outlaws_surviving_a_hit_flinch_away_from_it() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    sim.app.world_mut().entity_mut(enemy).insert(Health(3));
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+
+    assert!(sim.run_until(5.0, |world| world.get::<Flinching>(enemy).is_some()));
+    assert!(sim.run_until(2.0, |world| world.get::<Flinching>(enemy).is_none()));
+    let position = sim.app.world().get::<Transform>(enemy).unwrap();
+    assert!(
+        position.translation.distance(PLAYER_POSITION) > ENEMY_POSITION.distance(PLAYER_POSITION)
+    );
+    assert_eq!(position.scale, Vec3::ONE);
+}
+
+#[test]
+fn one_hit_kills_mutator_takes_down_tough_outlaws_in_one_hit() {
+    let mut sim = Simulation::new();
+    sim.app.insert_resource(Mutators {
+        one_hit_kills: true,
+        ..default()
+    });
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    sim.app.world_mut().entity_mut(enemy).insert(Health(3));
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(enemy).is_none()));
+}
+
+#[test]
+fn double_outlaws_mutator_doubles_every_archetype() {
+    let mut sim = Simulation::new();
+    sim.app.insert_resource(Mutators {
+        double_enemies: true,
+        ..default()
+    });
+    sim.spawn_player(PLAYER_POSITION);
+    sim.spawn_gunslinger(ENEMY_POSITION);
+    sim.app.world_mut().spawn((
+        ShotgunnerSpawnPoint::default(),
+        Transform::from_translation(-ENEMY_POSITION),
+    ));
+    sim.app.update();
+
+    let enemies = sim
+        .app
+        .world_mut()
+        .query_filtered::<(), With<Enemy>>()
+        .iter(sim.app.world())
+        .count();
+    assert_eq!(enemies, 4);
+}
+
+#[test]
+fn boomerangs_dive_down_to_hit_low_targets() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    // well below the flying height, a boomerang passing over at that height would miss it
+    let critter = sim
+        .app
+        .world_mut()
+        .spawn((
+            Name::new("Critter"),
+            Transform::from_xyz(6.0, 0.2, 0.0),
+            Collider::sphere(0.2),
+            RigidBody::Kinematic,
+            CollisionLayers::new(GameLayer::Enemy, GameLayer::Boomerang),
+            Health(1),
+        ))
+        .id();
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(critter)]);
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(critter).is_none()));
+}
+
+#[test]
+fn frontal_shield_blocks_direct_throws() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    sim.app
+        .world_mut()
+        .entity_mut(enemy)
+        .insert(FrontalShield::default());
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+    sim.app.update();
+
+    assert!(sim.run_until(10.0, |world| !boomerangs_in_play(world)));
+    assert!(sim.is_alive(enemy));
+    assert_eq!(sim.bounty(), 0.0);
+}
+
+#[test]
+fn frontal_shield_lets_ricochets_through() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    sim.app
+        .world_mut()
+        .entity_mut(enemy)
+        .insert(FrontalShield::default());
+
+    sim.throw_boomerang(
+        player,
+        vec![
+            BoomerangTargetKind::Position(Vec3::new(12.0, 1.5, 0.0)),
+            BoomerangTargetKind::Entity(enemy),
+        ],
+    );
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(enemy).is_none()));
+}
+
+#[test]
+fn player_death_ends_the_level() {
+    let mut sim = Simulation::new();
+    sim.spawn_player(PLAYER_POSITION);
+    sim.spawn_gunslinger(Vec3::new(6.0, 1.0, 0.0));
+
+    assert_eq!(sim.gameplay_state(), Gameplay::Normal);
+    assert!(sim.run_until(10.0, |world| {
+        world.resource::<State<Gameplay>>().get() == &Gameplay::GameOver
+    }));
+    assert_eq!(
+        *sim.app.world().resource::<OutcomeReason>(),
+        OutcomeReason::PlayerDied
+    );
+}
+
+#[test]
+fn outlaws_shoot_the_coop_partner_too() {
+    let mut sim = Simulation::new();
+    // out of range, only the partner is close enough to shoot at
+    let player = sim.spawn_player(Vec3::new(-24.0, 1.0, 0.0));
+    let partner = sim
+        .app
+        .world_mut()
+        .spawn((
+            Name::new("Player 2"),
+            CoopPartner::default(),
+            Transform::from_translation(PLAYER_POSITION),
+            Collider::capsule(0.5, 1.),
+            RigidBody::Kinematic,
+            CollisionLayers::new(
+                GameLayer::Player,
+                [GameLayer::Enemy, GameLayer::Bullet, GameLayer::Default],
+            ),
+            Health::default(),
+        ))
+        .id();
+    sim.spawn_gunslinger(Vec3::new(6.0, 1.0, 0.0));
+
+    assert!(sim.run_until(10.0, |world| world.get::<Health>(partner).is_none()));
+    assert!(sim.is_alive(player));
+}
+
+#[test]
+fn only_the_first_outcome_of_a_level_counts() {
+    let mut sim = Simulation::new();
+    sim.spawn_player(PLAYER_POSITION);
+
+    let world = sim.app.world_mut();
+    world.trigger(OutcomeEvent::Lose(OutcomeReason::PlayerDied));
+    world.trigger(OutcomeEvent::Win(OutcomeReason::ReachedExit));
+    sim.app.update();
+
+    assert_eq!(sim.gameplay_state(), Gameplay::GameOver);
+    assert_eq!(*sim.app.world().resource::<Winner>(), Winner::Enemy);
+}
+
+#[test]
+fn enemy_spawning_inside_a_wall_comes_out_next_to_it() {
+    let mut sim = Simulation::new();
+    let wall = Vec3::new(6.0, 1.0, 0.0);
+    sim.spawn_wall(wall, Vec3::new(2.0, 4.0, 2.0));
+    sim.spawn_gunslinger(wall);
+
+    let risen = |world: &mut World| {
+        world
+            .query_filtered::<(), (With<Enemy>, Without<Spawning>)>()
+            .iter(world)
+            .next()
+            .is_some()
+    };
+    assert!(sim.run_until(3.0, risen));
+    let position = sim
+        .app
+        .world_mut()
+        .query_filtered::<&Transform, With<Enemy>>()
+        .single(sim.app.world())
+        .unwrap()
+        .translation;
+    assert!((position.x - wall.x).abs() > 1.0 || position.z.abs() > 1.0);
+    assert!(position.xz().distance(wall.xz()) < 4.0);
+}
+
+#[test]
+fn clashing_boomerangs_ricochet_and_still_come_back() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let other_thrower = sim
+        .app
+        .world_mut()
+        .spawn(Transform::from_translation(ENEMY_POSITION))
+        .id();
+
+    // head-on, so they meet halfway
+    sim.throw_boomerang(
+        player,
+        vec![BoomerangTargetKind::Position(ENEMY_POSITION.with_y(1.5))],
+    );
+    sim.throw_boomerang(
+        other_thrower,
+        vec![BoomerangTargetKind::Position(PLAYER_POSITION.with_y(1.5))],
+    );
+
+    let clashed_midway = |world: &mut World| {
+        world
+            .query::<&BoomerangFlightHistory>()
+            .iter(world)
+            .any(|history| {
+                history.waypoints[1..]
+                    .iter()
+                    .any(|waypoint| waypoint.x > 2.0 && waypoint.x < 6.0)
+            })
+    };
+    assert!(sim.run_until(5.0, clashed_midway));
+
+    assert!(sim.run_until(20.0, |world| !boomerangs_in_play(world)));
+    sim.app.update();
+    assert_eq!(sim.ammo(player), 1);
+}
+
+#[test]
+fn rejected_throw_blocks_throwing_for_a_moment() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+
+    sim.app
+        .world_mut()
+        .entity_mut(player)
+        .trigger(ThrowRejected);
+    sim.app.update();
+    assert!(sim.app.world().get::<ThrowCooldown>(player).is_some());
+
+    assert!(sim.run_until(1.0, |world| world.get::<ThrowCooldown>(player).is_none()));
+}
+
+#[test]
+fn bullet_hits_remember_where_they_were_fired_from() {
+    #[derive(Component)]
+    struct HitFrom(Vec3);
+
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.app.world_mut().entity_mut(player).observe(
+        |trigger: Trigger<HealthEvent>, mut commands: Commands| {
+            if let HealthEvent::Damage(_, _, Some(source)) = trigger.event() {
+                commands.entity(trigger.target()).insert(HitFrom(*source));
+            }
+        },
+    );
+    sim.app.world_mut().trigger(FireBulletsEvent::single(
+        ENEMY_POSITION,
+        Vec3::NEG_X,
+        20.0,
+        1,
+    ));
+
+    assert!(sim.run_until(2.0, |world| world.get::<HitFrom>(player).is_some()));
+    let source = sim.app.world().get::<HitFrom>(player).unwrap().0;
+    assert!(source.distance(ENEMY_POSITION) < 0.01);
+}
+
+#[test]
+fn boomerangs_knock_painted_bullets_back_where_they_came_from() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    sim.app.world_mut().trigger(FireBulletsEvent::single(
+        ENEMY_POSITION,
+        Vec3::NEG_X,
+        6.0,
+        1,
+    ));
+    sim.app.update();
+    let bullet = sim
+        .app
+        .world_mut()
+        .query_filtered::<Entity, With<Bullet>>()
+        .single(sim.app.world())
+        .unwrap();
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(bullet)]);
+
+    assert!(sim.run_until(2.0, |world| world.get::<Deflected>(bullet).is_some()));
+    let velocity = sim.app.world().get::<LinearVelocity>(bullet).unwrap();
+    assert!(velocity.x > 0.0);
+    assert_eq!(sim.bounty(), 75.0);
+}
+
+#[test]
+fn only_the_last_painted_outlaw_going_down_starts_the_kill_cam() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let first = sim.spawn_enemy(Vec3::new(5.0, 1.0, 4.0), PLAYER_POSITION);
+    let last = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    let kill_cam_active = |world: &mut World| world.resource::<KillCam>().is_active();
+
+    sim.throw_boomerang(
+        player,
+        vec![
+            BoomerangTargetKind::Entity(first),
+            BoomerangTargetKind::Entity(last),
+        ],
+    );
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(first).is_none()));
+    assert!(!kill_cam_active(sim.app.world_mut()));
+    assert!(sim.run_until(5.0, kill_cam_active));
+    assert!(!sim.is_alive(last));
+    assert!(sim.run_until(1.0, |world| !kill_cam_active(world)));
+}
+
+#[test]
+fn outlaws_aim_at_decoys_instead_of_the_player() {
+    let mut sim = Simulation::new();
+    sim.spawn_player(PLAYER_POSITION);
+    sim.spawn_gunslinger(ENEMY_POSITION);
+    let decoy = sim.spawn_decoy(Vec3::new(4.0, 1.0, 4.0), 10.0);
+
+    let aiming_at_decoy = |world: &mut World| {
+        world
+            .query::<&WeaponTarget>()
+            .iter(world)
+            .any(|target| target.target_entity == Some(decoy))
+    };
+    assert!(sim.run_until(5.0, aiming_at_decoy));
+}
+
+#[test]
+fn hurting_one_squad_member_alerts_the_whole_squad() {
+    let mut sim = Simulation::new();
+    sim.spawn_player(PLAYER_POSITION);
+    let hurt = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    let squad_mate = sim.spawn_enemy(Vec3::new(40.0, 1.0, 40.0), PLAYER_POSITION);
+    let loner = sim.spawn_enemy(Vec3::new(-40.0, 1.0, 40.0), PLAYER_POSITION);
+    for member in [hurt, squad_mate] {
+        sim.app.world_mut().entity_mut(member).insert(SquadId(1));
+    }
+
+    sim.app
+        .world_mut()
+        .entity_mut(hurt)
+        .trigger(HurtEvent::default());
+    sim.app.update();
+
+    assert!(sim.app.world().get::<Alerted>(squad_mate).is_some());
+    assert!(sim.app.world().get::<Alerted>(loner).is_none());
+}
+
+#[test]
+fn ricochet_kills_build_style_until_the_player_gets_hurt() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+
+    sim.throw_boomerang(
+        player,
+        vec![
+            BoomerangTargetKind::Position(Vec3::new(4.0, 1.5, 6.0)),
+            BoomerangTargetKind::Entity(enemy),
+        ],
+    );
+
+    assert!(sim.run_until(5.0, |world| world.get::<Health>(enemy).is_none()));
+    assert!(sim.app.world().resource::<StyleMeter>().multiplier() > 1.0);
+
+    sim.app
+        .world_mut()
+        .entity_mut(player)
+        .trigger(HurtEvent::default());
+    sim.app.update();
+    assert_eq!(sim.app.world().resource::<StyleMeter>().style, 0.0);
+}
+
+#[test]
+fn corpses_past_the_ragdoll_budget_freeze() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(ENEMY_POSITION, PLAYER_POSITION);
+    sim.app.insert_resource(CorpseBudget {
+        min_ragdolls: 0,
+        max_ragdolls: 0,
+        ..default()
+    });
+
+    sim.throw_boomerang(player, vec![BoomerangTargetKind::Entity(enemy)]);
+
+    assert!(sim.run_until(5.0, |world| world.get::<Frozen>(enemy).is_some()));
+    assert_eq!(
+        sim.app.world().get::<RigidBody>(enemy),
+        Some(&RigidBody::Static)
+    );
+}
+
+#[test]
+fn far_away_outlaws_are_suspended_until_the_player_comes_closer() {
+    let mut sim = Simulation::new();
+    let player = sim.spawn_player(PLAYER_POSITION);
+    let enemy = sim.spawn_enemy(Vec3::new(100.0, 1.0, 0.0), PLAYER_POSITION);
+    sim.app.update();
+    assert_eq!(
+        sim.app.world().get::<Activity>(enemy),
+        Some(&Activity::Suspended)
+    );
+
+    // back within the suspend distance, but still past the throttle distance
+    sim.app
+        .world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation
+        .x = 55.0;
+    sim.app.update();
+    assert_eq!(
+        sim.app.world().get::<Activity>(enemy),
+        Some(&Activity::Throttled)
+    );
+
+    sim.app
+        .world_mut()
+        .get_mut::<Transform>(player)
+        .unwrap()
+        .translation
+        .x = 90.0;
+    sim.app.update();
+    assert_eq!(
+        sim.app.world().get::<Activity>(enemy),
+        Some(&Activity::Active)
+    );
+}
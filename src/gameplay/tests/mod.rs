@@ -0,0 +1,207 @@
+//! Headless simulation of the combat code, so regressions in e.g. boomerang flight show up in
+//! `cargo test` instead of mid-playtest.
+//! [`Simulation`] builds the headless app (see [`crate::headless`]), with physics and the combat
+//! related gameplay plugins, but no window, renderer, audio or input. Spawn the actors you need,
+//! script boomerang throws and then step time forward until something happens.
+
+mod combat;
+
+use crate::AppPlugin;
+use crate::asset_tracking;
+use crate::gameplay::Gameplay;
+use crate::gameplay::ammo::BoomerangInventory;
+use crate::gameplay::boomerang::{self, BoomerangTargetKind, ThrowBoomerangEvent};
+use crate::gameplay::decoy::Decoy;
+use crate::gameplay::enemy::{EnemySpawnPoint, enemy_bundle, on_death};
+use crate::gameplay::health_and_damage::Health;
+use crate::gameplay::player::{Player, on_player_death};
+use crate::gameplay::score::ScoreEvent;
+use crate::physics_layers::GameLayer;
+use crate::screens::Screen;
+use avian3d::prelude::{Collider, CollisionLayers, Physics, PhysicsTime, RigidBody};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+const FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// A headless game, already in [`Gameplay::Normal`].
+pub struct Simulation {
+    pub app: App,
+}
+
+/// Sum of all bounty handed out through [`ScoreEvent::AddScore`].
+#[derive(Resource, Debug, Default)]
+pub struct Bounty(pub f32);
+
+impl Simulation {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(AppPlugin { headless: true });
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME));
+        app.init_resource::<Bounty>();
+        app.add_observer(record_bounty);
+
+        app.finish();
+        app.cleanup();
+
+        // Let the asset server hand over the resource assets, then skip waiting for their files
+        app.update();
+        asset_tracking::insert_resources_without_loading(app.world_mut());
+
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Gameplay);
+        app.update();
+
+        Self { app }
+    }
+
+    /// Spawns a player that stands still with a single boomerang slot, empty as if they just
+    /// threw it.
+    pub fn spawn_player(&mut self, position: Vec3) -> Entity {
+        let mut inventory = BoomerangInventory::new(1);
+        inventory.consume(0);
+        self.app
+            .world_mut()
+            .spawn((
+                Name::new("Player"),
+                Player,
+                Transform::from_translation(position),
+                Collider::capsule(0.5, 1.),
+                RigidBody::Kinematic,
+                CollisionLayers::new(
+                    GameLayer::Player,
+                    [GameLayer::Enemy, GameLayer::Bullet, GameLayer::Default],
+                ),
+                Health::default(),
+                inventory,
+            ))
+            .observe(on_player_death)
+            .id()
+    }
+
+    /// Spawns an enemy that doesn't attack, facing `look_at`.
+    pub fn spawn_enemy(&mut self, position: Vec3, look_at: Vec3) -> Entity {
+        let world = self.app.world_mut();
+        let bundle = world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+            let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+            enemy_bundle(
+                "Test Enemy",
+                Transform::from_translation(position)
+                    .looking_at(look_at.with_y(position.y), Vec3::Y),
+                &EnemySpawnPoint::default(),
+                Color::WHITE,
+                &mut meshes,
+                &mut materials,
+            )
+        });
+        world.spawn(bundle).observe(on_death).id()
+    }
+
+    /// Spawns a regular gunslinger that shoots at the player.
+    pub fn spawn_gunslinger(&mut self, position: Vec3) {
+        self.app.world_mut().spawn((
+            EnemySpawnPoint::default(),
+            Transform::from_translation(position),
+        ));
+    }
+
+    /// Spawns a decoy that outlaws shoot at for `seconds`.
+    pub fn spawn_decoy(&mut self, position: Vec3, seconds: f32) -> Entity {
+        self.app
+            .world_mut()
+            .spawn((
+                Name::new("Decoy"),
+                Decoy::new(seconds),
+                Transform::from_translation(position),
+                Collider::capsule(0.5, 1.),
+                RigidBody::Kinematic,
+                CollisionLayers::new(GameLayer::Player, GameLayer::Bullet),
+            ))
+            .id()
+    }
+
+    /// Spawns a box of level geometry, `size` being its full extents.
+    pub fn spawn_wall(&mut self, position: Vec3, size: Vec3) {
+        self.app.world_mut().spawn((
+            Name::new("Wall"),
+            Transform::from_translation(position),
+            Collider::cuboid(size.x, size.y, size.z),
+            RigidBody::Static,
+        ));
+    }
+
+    pub fn throw_boomerang(&mut self, thrower: Entity, targets: Vec<BoomerangTargetKind>) {
+        self.throw_charged_boomerang(thrower, targets, 0.0);
+    }
+
+    pub fn throw_charged_boomerang(
+        &mut self,
+        thrower: Entity,
+        targets: Vec<BoomerangTargetKind>,
+        charge: f32,
+    ) {
+        self.app.world_mut().send_event(ThrowBoomerangEvent {
+            thrower_entity: thrower,
+            target: targets,
+            slot: Some(0),
+            charge,
+        });
+    }
+
+    /// Slows down (or speeds up) everything in the level, like slow motion does.
+    pub fn set_time_speed(&mut self, speed: f32) {
+        self.app
+            .world_mut()
+            .resource_mut::<Time<Physics>>()
+            .set_relative_speed(speed);
+    }
+
+    /// Steps the simulation until `condition` holds. Returns false if it didn't within `seconds`.
+    pub fn run_until(&mut self, seconds: f32, condition: impl Fn(&mut World) -> bool) -> bool {
+        let frames = (seconds / FRAME_TIME.as_secs_f32()).ceil() as usize;
+        for _ in 0..frames {
+            self.app.update();
+            if condition(self.app.world_mut()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn bounty(&self) -> f32 {
+        self.app.world().resource::<Bounty>().0
+    }
+
+    pub fn gameplay_state(&self) -> Gameplay {
+        self.app.world().resource::<State<Gameplay>>().get().clone()
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.app.world().get::<Health>(entity).is_some()
+    }
+
+    /// How many boomerangs `entity` could throw right now.
+    pub fn ammo(&self, entity: Entity) -> usize {
+        self.app
+            .world()
+            .get::<BoomerangInventory>(entity)
+            .map_or(0, BoomerangInventory::ready_count)
+    }
+}
+
+fn record_bounty(trigger: Trigger<ScoreEvent>, mut bounty: ResMut<Bounty>) {
+    if let ScoreEvent::AddScore(dollars, _) = trigger.event() {
+        bounty.0 += dollars;
+    }
+}
+
+/// Whether any boomerang is still in the air or falling.
+pub fn boomerangs_in_play(world: &mut World) -> bool {
+    world
+        .query_filtered::<(), With<boomerang::Boomerang>>()
+        .iter(world)
+        .next()
+        .is_some()
+}
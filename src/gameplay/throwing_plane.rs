@@ -0,0 +1,99 @@
+//! The throwing plane: boomerangs fly on a plane [`BOOMERANG_FLYING_HEIGHT`] above the level's
+//! floor, and everything that aims them, whether it's the cursor, the target preview or an outlaw
+//! checking whether a shot is clear, works on that same plane. [`ThrowingPlane`] keeps track of
+//! where it is and has the helpers for getting points and rays onto it.
+//!
+//! Levels with an elevated arena raise the floor with an [`ArenaFloor`], riding a horse lifts the
+//! plane for as long as the player is in the saddle.
+
+use crate::gameplay::Gameplay;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<ThrowingPlane>()
+        .register_type::<ArenaFloor>()
+        .init_resource::<ThrowingPlane>();
+    app.add_observer(move_floor);
+    app.add_systems(OnEnter(Gameplay::Normal), reset_throwing_plane);
+}
+
+/// How high above the floor boomerangs fly while the player is on foot.
+pub const BOOMERANG_FLYING_HEIGHT: f32 = 1.5;
+
+/// The plane boomerangs are thrown and aimed on. Newly thrown boomerangs keep the plane they were
+/// thrown on until they're back.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct ThrowingPlane {
+    /// The height of the level's floor, 0 unless there's an [`ArenaFloor`].
+    pub floor: f32,
+    /// How far above the floor the plane is. [`BOOMERANG_FLYING_HEIGHT`], or more e.g. while
+    /// riding.
+    pub height: f32,
+    /// Straight up, unless the arena is tilted.
+    pub normal: Dir3,
+}
+
+impl Default for ThrowingPlane {
+    fn default() -> Self {
+        Self {
+            floor: 0.0,
+            height: BOOMERANG_FLYING_HEIGHT,
+            normal: Dir3::Y,
+        }
+    }
+}
+
+impl ThrowingPlane {
+    /// The point on the plane right above (or below) the world's origin.
+    pub fn origin(&self) -> Vec3 {
+        Vec3::Y * self.floor + self.normal * self.height
+    }
+
+    /// Moves `point` onto the plane along its normal. For the usual flat plane, that's just
+    /// changing its height.
+    pub fn project(&self, point: Vec3) -> Vec3 {
+        point - self.normal * (point - self.origin()).dot(*self.normal)
+    }
+
+    /// Where `ray` hits the plane, if it does, e.g. for finding the point under the cursor.
+    pub fn intersect(&self, ray: Ray3d) -> Option<Vec3> {
+        let distance = ray.intersect_plane(self.origin(), InfinitePlane3d::new(self.normal))?;
+        Some(ray.get_point(distance))
+    }
+
+    /// Drops the part of `direction` that points out of the plane, e.g. to turn the normal of a
+    /// slanted wall into one boomerangs can ricochet along. [`None`] if nothing is left of it.
+    pub fn flatten(&self, direction: Vec3) -> Option<Dir3> {
+        Dir3::new(direction - self.normal * direction.dot(*self.normal)).ok()
+    }
+
+    /// A ray from `from` to `to`, both moved onto the plane first, and how long it is. [`None`] if
+    /// they end up on the same spot.
+    pub fn ray_between(&self, from: Vec3, to: Vec3) -> Option<(Ray3d, f32)> {
+        let origin = self.project(from);
+        let (direction, length) = Dir3::new_and_length(self.project(to) - origin).ok()?;
+        Some((Ray3d::new(origin, direction), length))
+    }
+}
+
+/// Put into a level to move its floor, and with it the [`ThrowingPlane`], to the height of this
+/// entity. For arenas that aren't at ground level. Tilting it tilts the plane too.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ArenaFloor;
+
+fn move_floor(
+    trigger: Trigger<OnAdd, ArenaFloor>,
+    floors: Query<&Transform>,
+    mut plane: ResMut<ThrowingPlane>,
+) {
+    if let Ok(floor) = floors.get(trigger.target()) {
+        plane.floor = floor.translation.y;
+        plane.normal = floor.up();
+    }
+}
+
+fn reset_throwing_plane(mut plane: ResMut<ThrowingPlane>) {
+    *plane = ThrowingPlane::default();
+}
@@ -0,0 +1,210 @@
+//! A "WANTED" poster that shows up for a moment at the start of a level, listing the named outlaws
+//! in it and the price on their heads.
+//! Outlaws are authored in the level scene by putting a [`Bounty`] next to an enemy spawn point.
+//! Killing one pays out the reward on top of the usual bounty and crosses them out on the poster,
+//! which comes back for a moment to show it.
+
+use crate::clock::UiTime;
+use crate::gameplay::Gameplay;
+use crate::gameplay::enemy::Enemy;
+use crate::gameplay::health_and_damage::DeathEvent;
+use crate::gameplay::score::ScoreEvent;
+use crate::localization::Localized;
+use crate::ui_assets::FontAssets;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Bounty>();
+    app.add_observer(put_bounty_on_outlaw)
+        .add_observer(pay_out_bounty);
+    app.add_systems(OnEnter(Gameplay::Normal), spawn_wanted_poster);
+    app.add_systems(
+        Update,
+        (list_outlaws, show_wanted_poster)
+            .chain()
+            .run_if(in_state(Gameplay::Normal)),
+    );
+}
+
+/// How long the poster stays up, including sliding away.
+const POSTER_SECONDS: f32 = 4.0;
+const SLIDE_SECONDS: f32 = 0.5;
+const POSTER_LEFT: f32 = 40.0;
+const POSTER_WIDTH: f32 = 260.0;
+
+const PAPER_COLOR: Color = Color::srgb(0.87, 0.78, 0.6);
+const INK_COLOR: Color = Color::srgb(0.25, 0.15, 0.08);
+const STRIKE_COLOR: Color = Color::srgb(0.7, 0.1, 0.05);
+
+/// A price on someone's head. Put this on an enemy spawn point in the level scene, the enemy
+/// coming out of it gets a copy.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct Bounty {
+    pub name: String,
+    /// Paid out on top of the usual bounty for a kill.
+    pub reward: f32,
+}
+
+impl Default for Bounty {
+    fn default() -> Self {
+        Self {
+            name: "Nameless Ned".to_string(),
+            reward: 500.0,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct WantedPoster {
+    shown: Timer,
+}
+
+/// The line through an outlaw's name, shown once they're dead.
+#[derive(Component, Debug)]
+struct Strike(Entity);
+
+/// Enemies come out right where their spawn point is, that's how we know which one to hand the
+/// [`Bounty`] to.
+fn put_bounty_on_outlaw(
+    trigger: Trigger<OnAdd, Enemy>,
+    enemies: Query<&Transform>,
+    spawn_points: Query<(&Bounty, &Transform), Without<Enemy>>,
+    mut commands: Commands,
+) {
+    let Ok(enemy) = enemies.get(trigger.target()) else {
+        return;
+    };
+    let position = enemy.translation.xz();
+    if let Some((bounty, _)) = spawn_points
+        .iter()
+        .find(|(_, spawn_point)| spawn_point.translation.xz().distance(position) < 0.01)
+    {
+        commands.entity(trigger.target()).insert(bounty.clone());
+    }
+}
+
+fn pay_out_bounty(
+    trigger: Trigger<DeathEvent>,
+    outlaws: Query<(&Bounty, &GlobalTransform), With<Enemy>>,
+    mut strikes: Query<(&Strike, &mut Visibility)>,
+    mut poster: Query<&mut WantedPoster>,
+    mut commands: Commands,
+) {
+    let Ok((bounty, transform)) = outlaws.get(trigger.target()) else {
+        return;
+    };
+    commands.trigger(ScoreEvent::AddScore(bounty.reward, transform.translation()));
+
+    for (strike, mut visibility) in &mut strikes {
+        if strike.0 == trigger.target() {
+            *visibility = Visibility::Inherited;
+        }
+    }
+    if let Ok(mut poster) = poster.single_mut() {
+        poster.shown.reset();
+    }
+}
+
+fn spawn_wanted_poster(font_assets: Res<FontAssets>, mut commands: Commands) {
+    let mut shown = Timer::from_seconds(POSTER_SECONDS, TimerMode::Once);
+    // stays hidden until there's someone on it
+    shown.tick(shown.duration());
+    commands.spawn((
+        Name::new("Wanted Poster"),
+        WantedPoster { shown },
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(POSTER_LEFT),
+            top: Val::Px(120.0),
+            width: Val::Px(POSTER_WIDTH),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(Val::Px(16.0)),
+            row_gap: Val::Px(6.0),
+            border: UiRect::all(Val::Px(3.0)),
+            ..default()
+        },
+        BackgroundColor(PAPER_COLOR),
+        BorderColor(INK_COLOR),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Gameplay::Normal),
+        children![
+            (
+                Name::new("Wanted Title"),
+                Text::new("WANTED"),
+                Localized("WANTED".to_string()),
+                TextFont::from_font_size(40.0).with_font(font_assets.header.clone()),
+                TextColor(INK_COLOR),
+            ),
+            (
+                Name::new("Wanted Subtitle"),
+                Text::new("DEAD OR ALIVE"),
+                Localized("DEAD OR ALIVE".to_string()),
+                TextFont::from_font_size(16.0).with_font(font_assets.content.clone()),
+                TextColor(INK_COLOR),
+            )
+        ],
+    ));
+}
+
+fn list_outlaws(
+    outlaws: Query<(Entity, &Bounty), (With<Enemy>, Added<Bounty>)>,
+    poster: Single<(Entity, &mut WantedPoster)>,
+    font_assets: Res<FontAssets>,
+    mut commands: Commands,
+) {
+    let (poster_entity, mut poster) = poster.into_inner();
+    for (outlaw, bounty) in &outlaws {
+        poster.shown.reset();
+        commands.spawn((
+            Name::new("Wanted Outlaw"),
+            Node {
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::SpaceBetween,
+                ..default()
+            },
+            ChildOf(poster_entity),
+            children![
+                (
+                    Text::new(bounty.name.clone()),
+                    TextFont::from_font_size(20.0).with_font(font_assets.content.clone()),
+                    TextColor(INK_COLOR),
+                ),
+                (
+                    Text::new(format!("$ {}", bounty.reward)),
+                    TextFont::from_font_size(20.0).with_font(font_assets.content.clone()),
+                    TextColor(INK_COLOR),
+                ),
+                (
+                    Strike(outlaw),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Percent(50.0),
+                        width: Val::Percent(100.0),
+                        height: Val::Px(3.0),
+                        ..default()
+                    },
+                    BackgroundColor(STRIKE_COLOR),
+                    Visibility::Hidden,
+                )
+            ],
+        ));
+    }
+}
+
+fn show_wanted_poster(
+    time: UiTime,
+    poster: Single<(&mut WantedPoster, &mut Node, &mut Visibility)>,
+) {
+    let (mut poster, mut node, mut visibility) = poster.into_inner();
+    if poster.shown.tick(time.delta()).finished() {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    // slides off to the left at the end
+    let slide = 1.0 - (poster.shown.remaining_secs() / SLIDE_SECONDS).min(1.0);
+    node.left = Val::Px(POSTER_LEFT - slide * (POSTER_LEFT + POSTER_WIDTH));
+    *visibility = Visibility::Inherited;
+}
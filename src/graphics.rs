@@ -0,0 +1,169 @@
+//! Graphics presets, picked in the settings menu. A [`GraphicsPreset`] decides in one place how
+//! expensive the picture gets: ambient occlusion, bloom, anti-aliasing, shadows and how many
+//! particles may be alive at once (see [`FxBudget`]).
+//! The preset is turned into [`GraphicsSettings`], which are applied to every 3D camera and
+//! directional light as it spawns, and to all of them again whenever the preset changes.
+
+use crate::settings::{Settings, SettingsApplied};
+use crate::theme::fx_budget::FxBudget;
+use bevy::core_pipeline::bloom::Bloom;
+use bevy::core_pipeline::fxaa::Fxaa;
+use bevy::core_pipeline::prepass::{DepthPrepass, NormalPrepass};
+use bevy::pbr::{DirectionalLightShadowMap, ScreenSpaceAmbientOcclusion};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<GraphicsSettings>()
+        .register_type::<GraphicsSettings>();
+    app.add_observer(apply_graphics_setting);
+    app.add_systems(Update, (configure_cameras, configure_lights));
+}
+
+/// How good the game looks, and how much it asks of the GPU for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+pub enum GraphicsPreset {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl GraphicsPreset {
+    pub fn next(self) -> Self {
+        match self {
+            GraphicsPreset::Low => GraphicsPreset::Medium,
+            GraphicsPreset::Medium => GraphicsPreset::High,
+            GraphicsPreset::High => GraphicsPreset::Low,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            GraphicsPreset::Low => GraphicsPreset::High,
+            GraphicsPreset::Medium => GraphicsPreset::Low,
+            GraphicsPreset::High => GraphicsPreset::Medium,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GraphicsPreset::Low => "Low",
+            GraphicsPreset::Medium => "Medium",
+            GraphicsPreset::High => "High",
+        }
+    }
+}
+
+/// What the current [`GraphicsPreset`] turns on and off.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct GraphicsSettings {
+    /// Screen space ambient occlusion. Doesn't work together with MSAA, so it comes with FXAA
+    /// instead.
+    pub ssao: bool,
+    pub bloom: bool,
+    /// MSAA samples, 1 turns it off.
+    pub msaa_samples: u32,
+    pub shadows: bool,
+    /// Resolution of the directional light shadow maps.
+    pub shadow_map_size: usize,
+    /// Multiplier on the [`FxBudget`], e.g. 0.5 for half as many particles at once.
+    pub particle_density: f32,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self::from(GraphicsPreset::default())
+    }
+}
+
+impl From<GraphicsPreset> for GraphicsSettings {
+    fn from(preset: GraphicsPreset) -> Self {
+        match preset {
+            GraphicsPreset::Low => Self {
+                ssao: false,
+                bloom: false,
+                msaa_samples: 1,
+                shadows: false,
+                shadow_map_size: 1024,
+                particle_density: 0.4,
+            },
+            GraphicsPreset::Medium => Self {
+                ssao: false,
+                bloom: true,
+                msaa_samples: 4,
+                shadows: true,
+                shadow_map_size: 2048,
+                particle_density: 1.0,
+            },
+            GraphicsPreset::High => Self {
+                ssao: true,
+                bloom: true,
+                msaa_samples: 1,
+                shadows: true,
+                shadow_map_size: 4096,
+                particle_density: 1.0,
+            },
+        }
+    }
+}
+
+fn apply_graphics_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut graphics: ResMut<GraphicsSettings>,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
+    mut fx_budget: ResMut<FxBudget>,
+) {
+    let wanted = GraphicsSettings::from(settings.graphics);
+    // the settings change for all kinds of reasons, only touch the cameras if these did
+    if *graphics != wanted {
+        *graphics = wanted;
+    }
+    if shadow_map.size != graphics.shadow_map_size {
+        shadow_map.size = graphics.shadow_map_size;
+    }
+    *fx_budget = FxBudget::default().scaled(graphics.particle_density);
+}
+
+fn configure_cameras(
+    graphics: Res<GraphicsSettings>,
+    all_cameras: Query<Entity, With<Camera3d>>,
+    new_cameras: Query<Entity, Added<Camera3d>>,
+    mut commands: Commands,
+) {
+    let cameras: Vec<Entity> = if graphics.is_changed() {
+        all_cameras.iter().collect()
+    } else {
+        new_cameras.iter().collect()
+    };
+    for camera in cameras {
+        let mut camera = commands.entity(camera);
+        camera.insert(Msaa::from_samples(graphics.msaa_samples));
+        if graphics.bloom {
+            camera.insert(Bloom::NATURAL);
+        } else {
+            camera.remove::<Bloom>();
+        }
+        if graphics.ssao {
+            camera.insert((ScreenSpaceAmbientOcclusion::default(), Fxaa::default()));
+        } else {
+            camera.remove::<(
+                ScreenSpaceAmbientOcclusion,
+                Fxaa,
+                DepthPrepass,
+                NormalPrepass,
+            )>();
+        }
+    }
+}
+
+fn configure_lights(graphics: Res<GraphicsSettings>, mut lights: Query<&mut DirectionalLight>) {
+    for mut light in &mut lights {
+        if (graphics.is_changed() || light.is_added()) && light.shadows_enabled != graphics.shadows
+        {
+            light.shadows_enabled = graphics.shadows;
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! The game without a window, renderer, audio or input devices, so it runs on machines without a
+//! GPU: balancing simulations, servers and the integration tests. Built by [`AppPlugin`] when
+//! `headless` is set.
+//!
+//! Only the parts of the gameplay that don't need any of those are added, i.e. combat, enemies,
+//! boomerangs and physics. There are no screens and no levels get loaded, whoever drives the app
+//! moves it into [`Screen::Gameplay`] and spawns what they need.
+//!
+//! [`AppPlugin`]: crate::AppPlugin
+
+use crate::asset_tracking::{self, LoadResource};
+use crate::gameplay;
+use crate::gameplay::mouse_position::MousePosition;
+use crate::localization::{Language, Translations};
+use crate::screens::Screen;
+use crate::ui_assets::FontAssets;
+use avian3d::PhysicsPlugins;
+use bevy::gizmos::GizmoPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy::state::app::StatesPlugin;
+use bevy::transform::TransformPlugin;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        AssetPlugin::default(),
+        TransformPlugin,
+        ScenePlugin,
+        GizmoPlugin,
+        PhysicsPlugins::default(),
+    ));
+
+    // Asset types the gameplay code hands out handles to. Nothing ever gets loaded.
+    app.init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<AudioSource>()
+        .init_asset::<Image>()
+        .init_asset::<Font>();
+
+    // Usually provided by plugins that need a window or input devices.
+    app.init_state::<Screen>();
+    app.init_resource::<MousePosition>()
+        .init_resource::<Language>()
+        .init_asset::<Translations>()
+        .load_resource::<FontAssets>();
+
+    app.add_plugins((asset_tracking::plugin, gameplay::headless_plugin));
+}
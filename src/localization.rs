@@ -0,0 +1,162 @@
+//! Translations for UI text. The English text in the code doubles as the key: every other
+//! language is an `assets/i18n/<language>.lang.ron` file mapping English strings to translated
+//! ones. Anything missing from a file just stays English.
+//!
+//! Text spawned through the [`crate::theme::widget`] constructors gets translated on its own.
+//! Other static text needs a [`Localized`] component, text that's put together at runtime should
+//! go through [`Locale::tr`].
+
+use crate::asset_tracking::{LoadResource, RonAsset, RonAssetLoader};
+use crate::settings::{Settings, SettingsApplied};
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Language>()
+        .register_type::<Language>()
+        .register_type::<Localized>();
+    app.add_observer(apply_language_setting);
+    app.init_asset::<Translations>()
+        .init_asset_loader::<RonAssetLoader<Translations>>();
+    app.register_type::<LocalizationAssets>()
+        .load_resource::<LocalizationAssets>();
+    app.add_systems(Update, localize_texts);
+}
+
+/// The language all UI text is shown in, picked in the settings menu.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+impl Language {
+    pub fn next(self) -> Self {
+        match self {
+            Language::English => Language::German,
+            Language::German => Language::Spanish,
+            Language::Spanish => Language::English,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            Language::English => Language::Spanish,
+            Language::German => Language::English,
+            Language::Spanish => Language::German,
+        }
+    }
+
+    /// The name of the language, in that language.
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+        }
+    }
+}
+
+fn apply_language_setting(
+    _: Trigger<SettingsApplied>,
+    settings: Res<Settings>,
+    mut language: ResMut<Language>,
+) {
+    *language = settings.language;
+}
+
+/// English text mapped to its translation.
+#[derive(Asset, TypePath, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct Translations(HashMap<String, String>);
+
+impl RonAsset for Translations {
+    const EXTENSIONS: &'static [&'static str] = &["lang.ron"];
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LocalizationAssets {
+    #[dependency]
+    german: Handle<Translations>,
+    #[dependency]
+    spanish: Handle<Translations>,
+}
+
+impl LocalizationAssets {
+    fn translations(&self, language: Language) -> Option<&Handle<Translations>> {
+        match language {
+            Language::English => None,
+            Language::German => Some(&self.german),
+            Language::Spanish => Some(&self.spanish),
+        }
+    }
+}
+
+impl FromWorld for LocalizationAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            german: assets.load("i18n/de.lang.ron"),
+            spanish: assets.load("i18n/es.lang.ron"),
+        }
+    }
+}
+
+/// Keeps the [`Text`] on the same entity translated to the current [`Language`].
+/// Holds the English text. Empty text is left alone, so labels that get filled in by some other
+/// system don't get wiped.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Localized(pub String);
+
+/// Looks up translations for the current [`Language`].
+#[derive(SystemParam)]
+pub struct Locale<'w> {
+    language: Res<'w, Language>,
+    assets: Option<Res<'w, LocalizationAssets>>,
+    translations: Res<'w, Assets<Translations>>,
+}
+
+impl Locale<'_> {
+    /// `text` in the current language, or `text` itself if there's no translation for it.
+    pub fn tr<'a>(&'a self, text: &'a str) -> &'a str {
+        self.assets
+            .as_ref()
+            .and_then(|assets| assets.translations(*self.language))
+            .and_then(|handle| self.translations.get(handle))
+            .and_then(|translations| translations.0.get(text))
+            .map_or(text, String::as_str)
+    }
+
+    /// Whether the language was switched this frame.
+    pub fn is_changed(&self) -> bool {
+        self.language.is_changed()
+    }
+}
+
+fn localize_texts(
+    locale: Locale,
+    mut translation_events: EventReader<AssetEvent<Translations>>,
+    mut texts: Query<(Ref<Localized>, &mut Text)>,
+) {
+    // also catches the translations finishing loading, or being edited in dev builds
+    let translations_changed = !translation_events.is_empty();
+    translation_events.clear();
+    let refresh_all = locale.is_changed() || translations_changed;
+
+    for (localized, mut text) in &mut texts {
+        if localized.0.is_empty() || !(refresh_all || localized.is_added()) {
+            continue;
+        }
+        let translated = locale.tr(&localized.0);
+        if text.0 != translated {
+            text.0 = translated.to_string();
+        }
+    }
+}
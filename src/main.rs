@@ -8,27 +8,36 @@
 mod ai;
 mod asset_tracking;
 mod audio;
+mod clock;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod framepace;
 mod gameplay;
+mod graphics;
+mod headless;
+mod localization;
 mod physics_layers;
+mod save;
 mod screens;
+mod settings;
 mod theme;
 mod ui_assets;
 
 use avian3d::PhysicsPlugins;
-use bevy::audio::Volume;
 use bevy::window::{PresentMode, WindowResolution};
 use bevy::{asset::AssetMetaCheck, prelude::*};
 use bevy_skein::SkeinPlugin;
 use oxidized_navigation::OxidizedNavigation;
 
 fn main() -> AppExit {
-    App::new().add_plugins(AppPlugin).run()
+    App::new().add_plugins(AppPlugin::default()).run()
 }
 
-pub struct AppPlugin;
+#[derive(Default)]
+pub struct AppPlugin {
+    /// Leaves out the window, rendering, audio and input, see [`headless`].
+    pub headless: bool,
+}
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
@@ -54,6 +63,11 @@ impl Plugin for AppPlugin {
                 .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
         );
 
+        if self.headless {
+            app.add_plugins(headless::plugin);
+            return;
+        }
+
         // Add Bevy plugins.
         app.add_plugins((
             DefaultPlugins
@@ -83,21 +97,19 @@ impl Plugin for AppPlugin {
         // Add other plugins.
         app.add_plugins((
             asset_tracking::plugin,
+            localization::plugin,
+            save::plugin,
+            settings::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             screens::plugin,
             theme::plugin,
+            graphics::plugin,
             framepace::plugin,
             gameplay::plugin,
             ai::plugin,
+            audio::plugin,
         ));
-
-        app.add_systems(Update, audio::update_sfx_speed);
-
-        // globally adjust max volume
-        app.add_systems(Startup, |mut global_volume: ResMut<GlobalVolume>| {
-            global_volume.volume = Volume::Linear(0.2);
-        });
     }
 }
 
@@ -115,3 +127,13 @@ enum AppSystems {
     /// Do everything else (consider splitting this into further variants).
     Update,
 }
+
+/// Systems that are expensive enough to keep an eye on.
+/// Not ordered relative to each other, the dev tools time each set separately.
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HotSystems {
+    Pathfinding,
+    EnemyAi,
+    BoomerangMovement,
+    Particles,
+}
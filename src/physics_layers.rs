@@ -12,5 +12,6 @@ pub enum GameLayer {
     Terrain,   // Layer 4
     Boomerang, // Layer 5
     DeadEnemy, // Layer 5
+    Civilian,  // Layer 7
                //NoCollision, // Layer ?
 }
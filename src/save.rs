@@ -0,0 +1,140 @@
+//! Persistent save data, like level progress, found collectibles and achievements.
+//! Stored as RON in a file next to the game on native builds, and in local storage on web.
+//! Just modify the [`SaveData`] resource, it gets written to disk automatically whenever it changes.
+//! [`read_persisted`] and [`write_persisted`] store other things the same way, like the settings.
+
+use crate::gameplay::cosmetics::{Cosmetics, PlayerHat};
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(SaveData::load());
+    app.add_systems(
+        Last,
+        write_save_data.run_if(resource_changed::<SaveData>.and(not(resource_added::<SaveData>))),
+    );
+}
+
+const SAVE_NAME: &str = "save";
+#[cfg(target_family = "wasm")]
+const LOCAL_STORAGE_PREFIX: &str = "fistful_of_boomerangs_";
+
+#[derive(Resource, Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct SaveData {
+    /// The highest level index the player has reached so far.
+    pub furthest_level: usize,
+    /// The best bounty claimed in each level, loaded back in when continuing from the title screen.
+    pub bounties: BTreeMap<usize, f32>,
+    pub levels: BTreeMap<usize, LevelProgress>,
+    pub achievements: BTreeSet<String>,
+    /// IDs of the tutorial hints that were already shown, each one only ever shows up once.
+    pub hints_shown: BTreeSet<String>,
+    /// IDs of the mechanics the player has used at least once, see
+    /// [`crate::gameplay::hints::Mechanic`].
+    pub mechanics_used: BTreeSet<String>,
+    /// Whether the player has beaten the last level at least once, which unlocks the mutators.
+    pub campaign_finished: bool,
+    /// How many hats the player picked up off fallen outlaws, over all playthroughs.
+    pub hats_collected: u32,
+    /// What the player picked in the wardrobe, see [`crate::gameplay::cosmetics`].
+    pub cosmetics: Cosmetics,
+    /// Where saves from before the skins kept the picked hat. Moved into [`SaveData::cosmetics`]
+    /// on load and never written back.
+    #[serde(
+        rename = "hat",
+        skip_serializing,
+        deserialize_with = "deserialize_legacy_hat"
+    )]
+    legacy_hat: Option<PlayerHat>,
+}
+
+/// Old saves wrote the hat as it is, not wrapped in `Some`.
+fn deserialize_legacy_hat<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<PlayerHat>, D::Error> {
+    PlayerHat::deserialize(deserializer).map(Some)
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct LevelProgress {
+    /// IDs of all golden horseshoes the player has found in this level.
+    pub horseshoes_found: BTreeSet<String>,
+    /// How many horseshoes there are in this level. Only known once the level has been played.
+    pub horseshoes_total: usize,
+}
+
+impl SaveData {
+    fn load() -> Self {
+        let Some(contents) = read_persisted(SAVE_NAME) else {
+            info!("No save data found, starting fresh");
+            return Self::default();
+        };
+        let mut save_data: Self = ron::from_str(&contents).unwrap_or_else(|e| {
+            warn!("Unable to parse save data, starting fresh: {e}");
+            Self::default()
+        });
+        if let Some(hat) = save_data.legacy_hat.take() {
+            save_data.cosmetics.hat = hat;
+        }
+        save_data
+    }
+
+    pub fn level(&self, level: usize) -> Option<&LevelProgress> {
+        self.levels.get(&level)
+    }
+
+    pub fn level_mut(&mut self, level: usize) -> &mut LevelProgress {
+        self.levels.entry(level).or_default()
+    }
+
+    /// Returns true if the achievement wasn't unlocked before.
+    pub fn unlock_achievement(&mut self, id: impl Into<String>) -> bool {
+        self.achievements.insert(id.into())
+    }
+}
+
+fn write_save_data(save_data: Res<SaveData>) {
+    match ron::ser::to_string_pretty(save_data.as_ref(), ron::ser::PrettyConfig::default()) {
+        Ok(contents) => write_persisted(SAVE_NAME, &contents),
+        Err(e) => error!("Unable to serialize save data: {e}"),
+    }
+}
+
+/// Reads what was stored under `name`, from `<name>.ron` or the local storage.
+#[cfg(not(target_family = "wasm"))]
+pub fn read_persisted(name: &str) -> Option<String> {
+    std::fs::read_to_string(format!("{name}.ron")).ok()
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn write_persisted(name: &str, contents: &str) {
+    if let Err(e) = std::fs::write(format!("{name}.ron"), contents) {
+        error!("Unable to write {name} file: {e}");
+    }
+}
+
+/// Reads what was stored under `name`, from `<name>.ron` or the local storage.
+#[cfg(target_family = "wasm")]
+pub fn read_persisted(name: &str) -> Option<String> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage
+        .get_item(&format!("{LOCAL_STORAGE_PREFIX}{name}"))
+        .ok()?
+}
+
+#[cfg(target_family = "wasm")]
+pub fn write_persisted(name: &str, contents: &str) {
+    let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) else {
+        error!("Local storage unavailable, unable to store {name}");
+        return;
+    };
+    if storage
+        .set_item(&format!("{LOCAL_STORAGE_PREFIX}{name}"), contents)
+        .is_err()
+    {
+        error!("Unable to write {name} to local storage");
+    }
+}
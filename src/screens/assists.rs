@@ -0,0 +1,109 @@
+//! The assists screen, reachable from the settings screen. Every [`Assist`] gets a row to switch it
+//! on or off, see [`crate::gameplay::assists`] for what they do.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::gameplay::assists::Assist;
+use crate::localization::Locale;
+use crate::settings::Settings;
+use crate::ui_assets::{FontAssets, PanelAssets};
+use crate::{screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Assists), spawn_assists_screen);
+
+    app.register_type::<AssistLabel>();
+    app.add_systems(
+        Update,
+        update_assist_labels.run_if(in_state(Screen::Assists)),
+    );
+}
+
+fn spawn_assists_screen(panel: Res<PanelAssets>, fonts: Res<FontAssets>, mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Assists Screen"),
+            StateScoped(Screen::Assists),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header_with_font("Assists", &fonts.header));
+            parent
+                .spawn((
+                    Name::new("Assists Grid"),
+                    Node {
+                        display: Display::Grid,
+                        row_gap: Px(10.0),
+                        column_gap: Px(30.0),
+                        grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|grid| {
+                    for assist in Assist::ALL {
+                        grid.spawn((
+                            widget::label(assist.label()),
+                            Node {
+                                justify_self: JustifySelf::End,
+                                ..default()
+                            },
+                        ));
+                        grid.spawn(toggle_widget(assist));
+                    }
+                });
+            parent.spawn(widget::label("Assists don't affect your bounties."));
+            parent.spawn((
+                widget::paneled_button("Back", enter_settings_screen, &panel, &fonts.header),
+                BackButton,
+            ));
+        });
+}
+
+fn toggle_widget(assist: Assist) -> impl Bundle {
+    let toggle = move |_: Trigger<Activate>, mut settings: ResMut<Settings>| {
+        settings.assists.toggle(assist);
+    };
+    (
+        Name::new(format!("{} Widget", assist.label())),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle),
+            (
+                Name::new(format!("Current {}", assist.label())),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), AssistLabel(assist))],
+            ),
+            widget::button_small(">", toggle),
+        ],
+    )
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AssistLabel(Assist);
+
+fn update_assist_labels(
+    settings: Res<Settings>,
+    mut labels: Query<(&mut Text, &AssistLabel)>,
+    locale: Locale,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = locale
+            .tr(if settings.assists.is_on(label.0) {
+                "On"
+            } else {
+                "Off"
+            })
+            .to_string();
+    }
+}
+
+fn enter_settings_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Settings);
+}
@@ -1,25 +1,90 @@
 //! A credits screen that can be accessed from the title screen.
+//! What it lists comes from `assets/game.credits.ron`, so attributions can be updated without
+//! touching any code. Changes to that file show up right away in dev builds.
 
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::{ecs::spawn::SpawnIter, prelude::*, ui::Val::*};
+use serde::Deserialize;
 
+use crate::asset_tracking::{LoadResource, RonAsset, RonAssetLoader};
 use crate::gameplay::level::LevelAssets;
 use crate::ui_assets::{FontAssets, PanelAssets};
-use crate::{asset_tracking::LoadResource, audio::music, screens::Screen, theme::prelude::*};
+use crate::{audio::music, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Credits>()
+        .init_asset_loader::<RonAssetLoader<Credits>>();
     app.add_systems(OnEnter(Screen::Credits), spawn_credits_screen);
+    app.add_systems(
+        Update,
+        (scroll_credits, reload_credits).run_if(in_state(Screen::Credits)),
+    );
 
     app.register_type::<CreditsAssets>();
     app.load_resource::<CreditsAssets>();
     app.add_systems(OnEnter(Screen::Credits), start_credits_music);
 }
 
+// ===== CREDITS FILE =====
+
+#[derive(Asset, TypePath, Debug, Deserialize)]
+struct Credits {
+    sections: Vec<CreditsSection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreditsSection {
+    title: String,
+    entries: Vec<CreditsEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreditsEntry {
+    name: String,
+    role: String,
+    #[serde(default)]
+    link: Option<String>,
+}
+
+impl RonAsset for Credits {
+    const EXTENSIONS: &'static [&'static str] = &["credits.ron"];
+}
+
+// ===== SCREEN =====
+
+/// The scrollable part of the screen the credits file gets rendered into.
+#[derive(Component)]
+struct CreditsList;
+
+/// Pixels to scroll per line on mouse wheels that scroll line by line.
+const SCROLL_LINE_HEIGHT: f32 = 34.0;
+
 fn spawn_credits_screen(
     panel: Res<PanelAssets>,
     level_assets: Res<LevelAssets>,
     fonts: Res<FontAssets>,
+    credits_assets: Res<CreditsAssets>,
+    credits: Res<Assets<Credits>>,
     mut commands: Commands,
 ) {
+    let list = commands
+        .spawn((
+            Name::new("Credits List"),
+            CreditsList,
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Px(20.0),
+                max_height: Vh(60.0),
+                overflow: Overflow::scroll_y(),
+                ..default()
+            },
+        ))
+        .id();
+    if let Some(credits) = credits.get(&credits_assets.credits) {
+        fill_credits_list(&mut commands, list, credits, &fonts);
+    }
+
     commands
         .spawn((
             widget::ui_root("Credits Screen"),
@@ -33,34 +98,38 @@ fn spawn_credits_screen(
                     &fonts.content,
                 ));
             }
-            parent.spawn(widget::header_with_font("Created by", &fonts.header));
-            parent.spawn(created_by());
-            parent.spawn(widget::header_with_font("Assets", &fonts.header));
-            parent.spawn(assets());
-            parent.spawn(widget::paneled_button(
-                "Back",
-                enter_title_screen,
-                &panel,
-                &fonts.header,
+        })
+        .add_child(list)
+        .with_children(|parent| {
+            parent.spawn((
+                widget::paneled_button("Back", enter_title_screen, &panel, &fonts.header),
+                BackButton,
             ));
         });
 }
 
-fn created_by() -> impl Bundle {
-    grid(vec![
-        ["Emily 'tigerplush' P.", "UI, SFX"],
-        ["Sam 'Sandman' F.", "Design, Programming"],
-        ["Martin 'mpwoz'", "Programming"],
-        ["Jacudibu", "Programming"],
-        ["BurnteToaster", "SFX"],
-    ])
-}
-
-fn assets() -> impl Bundle {
-    grid(vec![["Pistol Ricochet Sound", "CC0 by Diboz"]])
+fn fill_credits_list(commands: &mut Commands, list: Entity, credits: &Credits, fonts: &FontAssets) {
+    commands.entity(list).with_children(|parent| {
+        for section in &credits.sections {
+            parent.spawn(widget::header_with_font(
+                section.title.clone(),
+                &fonts.header,
+            ));
+            parent.spawn(grid(&section.entries));
+        }
+    });
 }
 
-fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
+fn grid(entries: &[CreditsEntry]) -> impl Bundle {
+    let cells: Vec<_> = entries
+        .iter()
+        .flat_map(|entry| {
+            [
+                (entry.name.clone(), None, JustifySelf::End),
+                (entry.role.clone(), entry.link.clone(), JustifySelf::Start),
+            ]
+        })
+        .collect();
     (
         Name::new("Grid"),
         Node {
@@ -70,25 +139,68 @@ fn grid(content: Vec<[&'static str; 2]>) -> impl Bundle {
             grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
-        Children::spawn(SpawnIter(content.into_iter().flatten().enumerate().map(
-            |(i, text)| {
+        Children::spawn(SpawnIter(cells.into_iter().map(
+            |(text, link, justify_self)| {
                 (
-                    widget::label(text),
                     Node {
-                        justify_self: if i % 2 == 0 {
-                            JustifySelf::End
-                        } else {
-                            JustifySelf::Start
-                        },
+                        flex_direction: FlexDirection::Column,
+                        justify_self,
                         ..default()
                     },
+                    Children::spawn((
+                        Spawn(widget::label(text)),
+                        SpawnIter(link.into_iter().map(link_label)),
+                    )),
                 )
             },
         ))),
     )
 }
 
-fn enter_title_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+/// A small line below an entry pointing to where the asset came from.
+fn link_label(url: String) -> impl Bundle {
+    (
+        Name::new("Link"),
+        Text(url),
+        TextFont::from_font_size(14.0),
+        TextColor(ui_palette::HEADER_TEXT),
+    )
+}
+
+fn scroll_credits(
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut list: Single<&mut ScrollPosition, With<CreditsList>>,
+) {
+    for event in mouse_wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        list.offset_y -= dy;
+    }
+}
+
+/// Rebuilds the list whenever the credits file changes on disk.
+fn reload_credits(
+    mut events: EventReader<AssetEvent<Credits>>,
+    credits_assets: Res<CreditsAssets>,
+    credits: Res<Assets<Credits>>,
+    fonts: Res<FontAssets>,
+    list: Single<Entity, With<CreditsList>>,
+    mut commands: Commands,
+) {
+    let modified = events
+        .read()
+        .any(|event| event.is_modified(&credits_assets.credits));
+    let Some(credits) = credits.get(&credits_assets.credits).filter(|_| modified) else {
+        return;
+    };
+    let list = list.into_inner();
+    commands.entity(list).despawn_related::<Children>();
+    fill_credits_list(&mut commands, list, credits, &fonts);
+}
+
+fn enter_title_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
 
@@ -97,6 +209,8 @@ fn enter_title_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextSt
 struct CreditsAssets {
     #[dependency]
     music: Handle<AudioSource>,
+    #[dependency]
+    credits: Handle<Credits>,
 }
 
 impl FromWorld for CreditsAssets {
@@ -104,6 +218,7 @@ impl FromWorld for CreditsAssets {
         let assets = world.resource::<AssetServer>();
         Self {
             music: assets.load("audio/music/EcstasyOfSka.ogg"),
+            credits: assets.load("game.credits.ron"),
         }
     }
 }
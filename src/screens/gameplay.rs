@@ -2,11 +2,15 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::gameplay::Gameplay;
+use crate::gameplay::outcome::{OutcomeEvent, OutcomeReason};
+use crate::gameplay::practice_range::PracticeRange;
 use crate::{gameplay::level::spawn_level, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_level.run_if(not(resource_exists::<PracticeRange>)),
+    );
 
     app.add_systems(
         Update,
@@ -15,6 +19,15 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn return_to_title_screen(mut next_screen: ResMut<NextState<Gameplay>>) {
-    next_screen.set(Gameplay::GameOver);
+fn return_to_title_screen(
+    practice: Option<Res<PracticeRange>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    // there's nothing to recap after practicing
+    if practice.is_some() {
+        next_screen.set(Screen::Title);
+    } else {
+        commands.trigger(OutcomeEvent::Lose(OutcomeReason::GaveUp));
+    }
 }
@@ -0,0 +1,85 @@
+//! A level select screen, showing the collectibles found per level.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::gameplay::level::LevelAssets;
+use crate::save::SaveData;
+use crate::ui_assets::{FontAssets, PanelAssets};
+use crate::{screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::LevelSelect), spawn_level_select_screen);
+}
+
+fn spawn_level_select_screen(
+    panel: Res<PanelAssets>,
+    fonts: Res<FontAssets>,
+    level_assets: Res<LevelAssets>,
+    save_data: Res<SaveData>,
+    mut commands: Commands,
+) {
+    let level_count = level_assets.levels.len();
+    let rows: Vec<_> = (0..level_count)
+        .map(|level| {
+            let unlocked = level <= save_data.furthest_level;
+            let details = match save_data.level(level) {
+                Some(progress) if progress.horseshoes_total > 0 => format!(
+                    "Horseshoes {}/{}",
+                    progress.horseshoes_found.len(),
+                    progress.horseshoes_total
+                ),
+                _ => "Horseshoes ?/?".to_string(),
+            };
+            (level, unlocked, details)
+        })
+        .collect();
+
+    commands
+        .spawn((
+            widget::ui_root("Level Select Screen"),
+            StateScoped(Screen::LevelSelect),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header_with_font("Pick yer trail", &fonts.header));
+            parent
+                .spawn((
+                    Name::new("Level Grid"),
+                    Node {
+                        display: Display::Grid,
+                        row_gap: Px(10.0),
+                        column_gap: Px(30.0),
+                        grid_template_columns: RepeatedGridTrack::px(2, 350.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                ))
+                .with_children(|grid| {
+                    for (level, unlocked, details) in rows {
+                        if unlocked {
+                            grid.spawn(widget::paneled_button(
+                                format!("Level {}", level + 1),
+                                move |_: Trigger<Activate>,
+                                      mut level_assets: ResMut<LevelAssets>,
+                                      mut next_screen: ResMut<NextState<Screen>>| {
+                                    level_assets.current_level = level;
+                                    next_screen.set(Screen::LevelLoading);
+                                },
+                                &panel,
+                                &fonts.header,
+                            ));
+                        } else {
+                            grid.spawn(widget::label_with_font("Locked", &fonts.header));
+                        }
+                        grid.spawn(widget::label_with_font(details, &fonts.content));
+                    }
+                });
+            parent.spawn((
+                widget::paneled_button("Back", enter_title_screen, &panel, &fonts.header),
+                BackButton,
+            ));
+        });
+}
+
+fn enter_title_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -1,8 +1,12 @@
 //! A loading screen during which game assets are loaded.
 //! This reduces stuttering, especially for audio on WASM.
+//! Levels are too big to load up front, so there's a second loading screen in front of each one.
 
 use bevy::prelude::*;
 
+use crate::gameplay::level::{current_level_loaded, load_current_level};
+use crate::gameplay::practice_range::PracticeRange;
+use crate::screens::tips::tip_label;
 use crate::{asset_tracking::ResourceHandles, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
@@ -12,16 +16,43 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         enter_gameplay_screen.run_if(in_state(Screen::Loading).and(all_assets_loaded)),
     );
+
+    app.add_systems(
+        OnEnter(Screen::LevelLoading),
+        (
+            spawn_level_loading_screen,
+            load_current_level.run_if(not(resource_exists::<PracticeRange>)),
+        ),
+    );
+    app.add_systems(
+        Update,
+        start_level.run_if(
+            in_state(Screen::LevelLoading)
+                .and(resource_exists::<PracticeRange>.or(current_level_loaded)),
+        ),
+    );
 }
 
 fn spawn_loading_screen(mut commands: Commands) {
     commands.spawn((
         widget::ui_root("Loading Screen"),
         StateScoped(Screen::Loading),
-        children![widget::label("Loading...")],
+        children![widget::label("Loading..."), tip_label()],
     ));
 }
 
+fn spawn_level_loading_screen(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Level Loading Screen"),
+        StateScoped(Screen::LevelLoading),
+        children![widget::label("Loading..."), tip_label()],
+    ));
+}
+
+fn start_level(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Gameplay);
+}
+
 fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
@@ -1,11 +1,16 @@
 //! The game's main screen states and transitions between them.
 
+mod assists;
 mod credits;
 mod gameplay;
+mod level_select;
 mod loading;
+mod mutators;
 pub mod settings;
 mod splash;
+pub mod tips;
 mod title;
+mod wardrobe;
 
 use bevy::prelude::*;
 
@@ -13,12 +18,17 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
     app.add_plugins((
+        assists::plugin,
         credits::plugin,
         gameplay::plugin,
+        level_select::plugin,
         loading::plugin,
+        mutators::plugin,
         settings::plugin,
         splash::plugin,
+        tips::plugin,
         title::plugin,
+        wardrobe::plugin,
     ));
 }
 
@@ -30,8 +40,14 @@ pub enum Screen {
     Splash,
     Title,
     Credits,
+    LevelSelect,
     Settings,
+    Assists,
+    Mutators,
+    Wardrobe,
     Loading,
+    /// Waits for the level that's about to be played to be loaded, then starts it.
+    LevelLoading,
     Gameplay,
     /// This state exists to make retrying a level easier
     Retry,
@@ -0,0 +1,119 @@
+//! The mutators screen, reachable from the title screen once the campaign is finished. Every
+//! [`Mutator`] gets a row to switch it on or off, see [`crate::gameplay::mutators`] for what they do.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::gameplay::mutators::{Mutator, Mutators};
+use crate::localization::Locale;
+use crate::ui_assets::{FontAssets, PanelAssets};
+use crate::{screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Mutators), spawn_mutators_screen);
+
+    app.register_type::<MutatorLabel>()
+        .register_type::<MultiplierLabel>();
+    app.add_systems(
+        Update,
+        (update_mutator_labels, update_multiplier_label).run_if(in_state(Screen::Mutators)),
+    );
+}
+
+fn spawn_mutators_screen(panel: Res<PanelAssets>, fonts: Res<FontAssets>, mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Mutators Screen"),
+            StateScoped(Screen::Mutators),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header_with_font("Mutators", &fonts.header));
+            parent
+                .spawn((
+                    Name::new("Mutators Grid"),
+                    Node {
+                        display: Display::Grid,
+                        row_gap: Px(10.0),
+                        column_gap: Px(30.0),
+                        grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|grid| {
+                    for mutator in Mutator::ALL {
+                        grid.spawn((
+                            widget::label(mutator.label()),
+                            Node {
+                                justify_self: JustifySelf::End,
+                                ..default()
+                            },
+                        ));
+                        grid.spawn(toggle_widget(mutator));
+                    }
+                });
+            parent.spawn((widget::label(""), MultiplierLabel));
+            parent.spawn((
+                widget::paneled_button("Back", enter_title_screen, &panel, &fonts.header),
+                BackButton,
+            ));
+        });
+}
+
+fn toggle_widget(mutator: Mutator) -> impl Bundle {
+    let toggle = move |_: Trigger<Activate>, mut mutators: ResMut<Mutators>| {
+        mutators.toggle(mutator);
+    };
+    (
+        Name::new(format!("{} Widget", mutator.label())),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle),
+            (
+                Name::new(format!("Current {}", mutator.label())),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), MutatorLabel(mutator))],
+            ),
+            widget::button_small(">", toggle),
+        ],
+    )
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MutatorLabel(Mutator);
+
+fn update_mutator_labels(
+    mutators: Res<Mutators>,
+    mut labels: Query<(&mut Text, &MutatorLabel)>,
+    locale: Locale,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = locale
+            .tr(if mutators.is_on(label.0) { "On" } else { "Off" })
+            .to_string();
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MultiplierLabel;
+
+fn update_multiplier_label(
+    mutators: Res<Mutators>,
+    mut label: Single<&mut Text, With<MultiplierLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale
+        .tr("Bounty multiplier: x{}")
+        .replace("{}", &format!("{:.2}", mutators.score_multiplier()));
+}
+
+fn enter_title_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -1,19 +1,52 @@
 //! A settings screen that can be accessed from the title screen.
 //!
-//! Settings and accessibility options should go here.
+//! Settings and accessibility options should go here. Everything here only changes [`Settings`],
+//! which takes care of applying and storing them.
 
-use bevy::{audio::Volume, prelude::*, ui::Val::*};
+use bevy::{
+    ecs::{spawn::SpawnableList, system::IntoObserverSystem},
+    prelude::*,
+    ui::Val::*,
+};
 
+use crate::gameplay::input::ControlSettings;
+use crate::localization::Locale;
+use crate::settings::Settings;
 use crate::ui_assets::{FontAssets, PanelAssets};
 use crate::{screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Settings), spawn_settings_screen);
 
-    app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<GlobalVolumeLabel>()
+        .register_type::<ScreenShakeLabel>()
+        .register_type::<AimAssistLabel>()
+        .register_type::<CrosshairStyleLabel>()
+        .register_type::<StickCurveLabel>()
+        .register_type::<StickDeadzoneLabel>()
+        .register_type::<InvertStickLabel>()
+        .register_type::<LanguageLabel>()
+        .register_type::<SkipSplashLabel>()
+        .register_type::<ViolenceLabel>()
+        .register_type::<GhostTrailLabel>()
+        .register_type::<AdaptiveDifficultyLabel>()
+        .register_type::<GraphicsLabel>();
     app.add_systems(
         Update,
-        update_volume_label.run_if(in_state(Screen::Settings)),
+        (
+            update_volume_label,
+            update_screen_shake_label,
+            update_aim_assist_label,
+            update_crosshair_style_label,
+            update_control_labels,
+            update_language_label,
+            update_skip_splash_label,
+            update_violence_label,
+            update_ghost_trail_label,
+            update_adaptive_difficulty_label,
+            update_graphics_label,
+        )
+            .run_if(in_state(Screen::Settings)),
     );
 }
 
@@ -24,7 +57,11 @@ fn spawn_settings_screen(panel: Res<PanelAssets>, fonts: Res<FontAssets>, mut co
         children![
             widget::header_with_font("Settings", &fonts.header),
             settings_grid(),
-            widget::paneled_button("Back", enter_title_screen, &panel, &fonts.header),
+            widget::paneled_button("Assists", enter_assists_screen, &panel, &fonts.header),
+            (
+                widget::paneled_button("Back", enter_title_screen, &panel, &fonts.header),
+                BackButton,
+            ),
         ],
     ));
 }
@@ -39,16 +76,51 @@ fn settings_grid() -> impl Bundle {
             grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
-        children![
-            (
-                widget::label("Audio Volume"),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
+        Children::spawn((
+            settings_row("Audio Volume", volume_widget()),
+            settings_row("Graphics", graphics_widget()),
+            settings_row("Screen Shake", screen_shake_widget()),
+            settings_row("Aim Assist", aim_assist_widget()),
+            settings_row("Crosshair", crosshair_style_widget()),
+            settings_row("Throw Trail", ghost_trail_widget()),
+            settings_row("Violence", violence_widget()),
+            settings_row("Adaptive Difficulty", adaptive_difficulty_widget()),
+            settings_row(
+                "Stick Curve",
+                stepper_widget(
+                    "Stick Curve",
+                    lower_stick_curve,
+                    StickCurveLabel,
+                    raise_stick_curve,
+                ),
             ),
-            volume_widget(),
-        ],
+            settings_row(
+                "Stick Deadzone",
+                stepper_widget(
+                    "Stick Deadzone",
+                    lower_stick_deadzone,
+                    StickDeadzoneLabel,
+                    raise_stick_deadzone,
+                ),
+            ),
+            settings_row("Invert Stick X", invert_stick_widget()),
+            settings_row("Language", language_widget()),
+            settings_row("Skip Intro", skip_splash_widget()),
+        )),
+    )
+}
+
+/// A label in the left column of the grid, and the widget to change the setting in the right one.
+fn settings_row(label: &'static str, control: impl Bundle) -> impl SpawnableList<ChildOf> {
+    (
+        Spawn((
+            widget::label(label),
+            Node {
+                justify_self: JustifySelf::End,
+                ..default()
+            },
+        )),
+        Spawn(control),
     )
 }
 
@@ -75,17 +147,12 @@ fn volume_widget() -> impl Bundle {
     )
 }
 
-pub const MIN_VOLUME: f32 = 0.0;
-pub const MAX_VOLUME: f32 = 3.0;
-
-fn lower_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let new_factor = global_volume.volume.to_linear() - 0.1;
-    global_volume.volume = Volume::Linear(new_factor.max(MIN_VOLUME));
+fn lower_volume(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.volume = (settings.volume - 0.1).max(Settings::MIN_VOLUME);
 }
 
-fn raise_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let new_factor = global_volume.volume.to_linear() + 0.1;
-    global_volume.volume = Volume::Linear(new_factor.min(MAX_VOLUME));
+fn raise_volume(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.volume = (settings.volume + 0.1).min(Settings::MAX_VOLUME);
 }
 
 #[derive(Component, Reflect)]
@@ -93,13 +160,512 @@ fn raise_volume(_: Trigger<Pointer<Click>>, mut global_volume: ResMut<GlobalVolu
 struct GlobalVolumeLabel;
 
 fn update_volume_label(
-    global_volume: Res<GlobalVolume>,
+    settings: Res<Settings>,
     mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
 ) {
-    let percent = 100.0 * global_volume.volume.to_linear();
+    let percent = 100.0 * settings.volume;
     label.0 = format!("{percent:3.0}%");
 }
 
-fn enter_title_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+fn screen_shake_widget() -> impl Bundle {
+    (
+        Name::new("Screen Shake Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_screen_shake),
+            (
+                Name::new("Current Screen Shake"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ScreenShakeLabel)],
+            ),
+            widget::button_small(">", toggle_screen_shake),
+        ],
+    )
+}
+
+fn toggle_screen_shake(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.screen_shake = !settings.screen_shake;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ScreenShakeLabel;
+
+fn update_screen_shake_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<ScreenShakeLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale
+        .tr(if settings.screen_shake { "On" } else { "Off" })
+        .to_string();
+}
+
+fn ghost_trail_widget() -> impl Bundle {
+    (
+        Name::new("Throw Trail Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_ghost_trail),
+            (
+                Name::new("Current Throw Trail"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), GhostTrailLabel)],
+            ),
+            widget::button_small(">", toggle_ghost_trail),
+        ],
+    )
+}
+
+fn toggle_ghost_trail(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.ghost_trail = !settings.ghost_trail;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GhostTrailLabel;
+
+fn update_ghost_trail_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<GhostTrailLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale
+        .tr(if settings.ghost_trail { "On" } else { "Off" })
+        .to_string();
+}
+
+fn adaptive_difficulty_widget() -> impl Bundle {
+    (
+        Name::new("Adaptive Difficulty Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_adaptive_difficulty),
+            (
+                Name::new("Current Adaptive Difficulty"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), AdaptiveDifficultyLabel)],
+            ),
+            widget::button_small(">", toggle_adaptive_difficulty),
+        ],
+    )
+}
+
+fn toggle_adaptive_difficulty(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.adaptive_difficulty = !settings.adaptive_difficulty;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AdaptiveDifficultyLabel;
+
+fn update_adaptive_difficulty_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<AdaptiveDifficultyLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale
+        .tr(if settings.adaptive_difficulty {
+            "On"
+        } else {
+            "Off"
+        })
+        .to_string();
+}
+
+fn aim_assist_widget() -> impl Bundle {
+    (
+        Name::new("Aim Assist Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", lower_aim_assist),
+            (
+                Name::new("Current Aim Assist"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), AimAssistLabel)],
+            ),
+            widget::button_small(">", raise_aim_assist),
+        ],
+    )
+}
+
+fn lower_aim_assist(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.aim_assist = settings.aim_assist.previous();
+}
+
+fn raise_aim_assist(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.aim_assist = settings.aim_assist.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AimAssistLabel;
+
+fn update_aim_assist_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<AimAssistLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale.tr(settings.aim_assist.label()).to_string();
+}
+
+fn crosshair_style_widget() -> impl Bundle {
+    (
+        Name::new("Crosshair Style Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_crosshair_style),
+            (
+                Name::new("Current Crosshair Style"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), CrosshairStyleLabel)],
+            ),
+            widget::button_small(">", next_crosshair_style),
+        ],
+    )
+}
+
+fn previous_crosshair_style(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.crosshair = settings.crosshair.previous();
+}
+
+fn next_crosshair_style(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.crosshair = settings.crosshair.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CrosshairStyleLabel;
+
+fn update_crosshair_style_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<CrosshairStyleLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale.tr(settings.crosshair.label()).to_string();
+}
+
+/// A `-`/`+` pair of buttons around a label showing the current value.
+fn stepper_widget<E, B, M, I1, I2>(
+    name: &'static str,
+    lower: I1,
+    label: impl Component,
+    raise: I2,
+) -> impl Bundle
+where
+    E: Event,
+    B: Bundle,
+    I1: IntoObserverSystem<E, B, M>,
+    I2: IntoObserverSystem<E, B, M>,
+{
+    (
+        Name::new(format!("{name} Widget")),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower),
+            (
+                Name::new(format!("Current {name}")),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), label)],
+            ),
+            widget::button_small("+", raise),
+        ],
+    )
+}
+
+fn invert_stick_widget() -> impl Bundle {
+    (
+        Name::new("Invert Stick X Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_invert_stick),
+            (
+                Name::new("Current Invert Stick X"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), InvertStickLabel)],
+            ),
+            widget::button_small(">", toggle_invert_stick),
+        ],
+    )
+}
+
+fn lower_stick_curve(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.stick_curve = (settings.stick_curve - 0.25).max(ControlSettings::MIN_STICK_CURVE);
+}
+
+fn raise_stick_curve(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.stick_curve = (settings.stick_curve + 0.25).min(ControlSettings::MAX_STICK_CURVE);
+}
+
+fn lower_stick_deadzone(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.stick_deadzone = (settings.stick_deadzone - 0.05).max(0.0);
+}
+
+fn raise_stick_deadzone(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.stick_deadzone =
+        (settings.stick_deadzone + 0.05).min(ControlSettings::MAX_STICK_DEADZONE);
+}
+
+fn toggle_invert_stick(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.invert_stick_x = !settings.invert_stick_x;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct StickCurveLabel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct StickDeadzoneLabel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct InvertStickLabel;
+
+fn update_control_labels(
+    settings: Res<Settings>,
+    mut curve_label: Single<&mut Text, With<StickCurveLabel>>,
+    mut deadzone_label: Single<&mut Text, (With<StickDeadzoneLabel>, Without<StickCurveLabel>)>,
+    mut invert_label: Single<
+        &mut Text,
+        (
+            With<InvertStickLabel>,
+            Without<StickCurveLabel>,
+            Without<StickDeadzoneLabel>,
+        ),
+    >,
+    locale: Locale,
+) {
+    curve_label.0 = format!("{:.2}", settings.stick_curve);
+    deadzone_label.0 = format!("{:3.0}%", settings.stick_deadzone * 100.0);
+    invert_label.0 = locale
+        .tr(if settings.invert_stick_x { "On" } else { "Off" })
+        .to_string();
+}
+
+fn language_widget() -> impl Bundle {
+    (
+        Name::new("Language Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_language),
+            (
+                Name::new("Current Language"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), LanguageLabel)],
+            ),
+            widget::button_small(">", next_language),
+        ],
+    )
+}
+
+fn previous_language(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.language = settings.language.previous();
+}
+
+fn next_language(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.language = settings.language.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LanguageLabel;
+
+fn update_language_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<LanguageLabel>>,
+) {
+    // always in its own language, so it can be found again
+    label.0 = settings.language.label().to_string();
+}
+
+fn violence_widget() -> impl Bundle {
+    (
+        Name::new("Violence Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", lower_violence),
+            (
+                Name::new("Current Violence"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ViolenceLabel)],
+            ),
+            widget::button_small(">", raise_violence),
+        ],
+    )
+}
+
+fn lower_violence(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.violence = settings.violence.previous();
+}
+
+fn raise_violence(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.violence = settings.violence.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ViolenceLabel;
+
+fn update_violence_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<ViolenceLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale.tr(settings.violence.label()).to_string();
+}
+
+fn graphics_widget() -> impl Bundle {
+    (
+        Name::new("Graphics Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", lower_graphics),
+            (
+                Name::new("Current Graphics"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), GraphicsLabel)],
+            ),
+            widget::button_small(">", raise_graphics),
+        ],
+    )
+}
+
+fn lower_graphics(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.graphics = settings.graphics.previous();
+}
+
+fn raise_graphics(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.graphics = settings.graphics.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GraphicsLabel;
+
+fn update_graphics_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<GraphicsLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale.tr(settings.graphics.label()).to_string();
+}
+
+fn skip_splash_widget() -> impl Bundle {
+    (
+        Name::new("Skip Intro Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_skip_splash),
+            (
+                Name::new("Current Skip Intro"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), SkipSplashLabel)],
+            ),
+            widget::button_small(">", toggle_skip_splash),
+        ],
+    )
+}
+
+fn toggle_skip_splash(_: Trigger<Activate>, mut settings: ResMut<Settings>) {
+    settings.skip_splash = !settings.skip_splash;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SkipSplashLabel;
+
+fn update_skip_splash_label(
+    settings: Res<Settings>,
+    mut label: Single<&mut Text, With<SkipSplashLabel>>,
+    locale: Locale,
+) {
+    label.0 = locale
+        .tr(if settings.skip_splash { "On" } else { "Off" })
+        .to_string();
+}
+
+fn enter_assists_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Assists);
+}
+
+fn enter_title_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
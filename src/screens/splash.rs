@@ -1,81 +1,213 @@
-//! A splash screen that plays briefly at startup.
+//! Splash screens that play briefly at startup, one logo after another.
+//! Which logos are shown and for how long comes from `assets/game.splash.ron`.
+//! Any input skips to the next logo, escape skips all of them. Players who've seen them enough can
+//! turn them off entirely with [`Settings::skip_splash`].
 
+use crate::clock::UiTime;
 use bevy::{
     image::{ImageLoaderSettings, ImageSampler},
     input::common_conditions::input_just_pressed,
     prelude::*,
 };
+use serde::Deserialize;
 
+use crate::asset_tracking::{RonAsset, RonAssetLoader};
+use crate::settings::Settings;
 use crate::{AppSystems, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<SplashSequence>()
+        .init_asset_loader::<RonAssetLoader<SplashSequence>>();
+
     // Spawn splash screen.
     app.insert_resource(ClearColor(SPLASH_BACKGROUND_COLOR));
     app.add_systems(OnEnter(Screen::Splash), spawn_splash_screen);
+    app.add_systems(OnExit(Screen::Splash), remove_splash_progress);
 
     // Animate splash screen.
+    app.register_type::<ImageNodeFadeInOut>();
     app.add_systems(
         Update,
         (
-            tick_fade_in_out.in_set(AppSystems::TickTimers),
-            apply_fade_in_out.in_set(AppSystems::Update),
-        )
-            .run_if(in_state(Screen::Splash)),
-    );
-
-    // Add splash timer.
-    app.register_type::<SplashTimer>();
-    app.add_systems(OnEnter(Screen::Splash), insert_splash_timer);
-    app.add_systems(OnExit(Screen::Splash), remove_splash_timer);
-    app.add_systems(
-        Update,
-        (
-            tick_splash_timer.in_set(AppSystems::TickTimers),
-            check_splash_timer.in_set(AppSystems::Update),
+            (tick_fade_in_out, tick_splash_progress).in_set(AppSystems::TickTimers),
+            skip_splash_logo.in_set(AppSystems::RecordInput),
+            (advance_splash_sequence, apply_fade_in_out)
+                .chain()
+                .in_set(AppSystems::Update),
         )
-            .run_if(in_state(Screen::Splash)),
+            .run_if(in_state(Screen::Splash).and(resource_exists::<SplashProgress>)),
     );
 
     // Exit the splash screen early if the player hits escape.
     app.add_systems(
         Update,
-        enter_title_screen
+        enter_loading_screen
             .run_if(input_just_pressed(KeyCode::Escape).and(in_state(Screen::Splash))),
     );
 }
 
 const SPLASH_BACKGROUND_COLOR: Color = Color::srgb(0.157, 0.157, 0.157);
-const SPLASH_DURATION_SECS: f32 = 1.8;
-const SPLASH_FADE_DURATION_SECS: f32 = 0.6;
+const SPLASH_SEQUENCE_PATH: &str = "game.splash.ron";
+
+// ===== SPLASH FILE =====
+
+#[derive(Asset, TypePath, Debug, Deserialize)]
+struct SplashSequence {
+    logos: Vec<SplashLogo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SplashLogo {
+    image: String,
+    /// How long the logo is shown in seconds, including fading in and out.
+    duration: f32,
+    /// How long fading takes in seconds, see [`ImageNodeFadeInOut::fade_duration`].
+    fade: f32,
+    /// Width of the logo in percent of the screen.
+    #[serde(default = "SplashLogo::default_width")]
+    width: f32,
+}
+
+impl SplashLogo {
+    fn default_width() -> f32 {
+        70.0
+    }
+}
+
+impl RonAsset for SplashSequence {
+    const EXTENSIONS: &'static [&'static str] = &["splash.ron"];
+}
+
+// ===== SCREEN =====
+
+/// Where in the splash sequence we are.
+#[derive(Resource, Debug)]
+struct SplashProgress {
+    sequence: Handle<SplashSequence>,
+    /// The logo that's shown right now, [None] while the sequence is still loading.
+    current: Option<usize>,
+    /// Runs out when it's time for the next logo.
+    timer: Timer,
+}
+
+/// Everything on the splash screen goes in here.
+#[derive(Component)]
+struct SplashRoot;
+
+#[derive(Component)]
+struct SplashImage;
+
+fn spawn_splash_screen(
+    settings: Res<Settings>,
+    asset_server: Res<AssetServer>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    if settings.skip_splash {
+        next_screen.set(Screen::Loading);
+        return;
+    }
 
-fn spawn_splash_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn((
         widget::ui_root("Splash Screen"),
+        SplashRoot,
         BackgroundColor(SPLASH_BACKGROUND_COLOR),
         StateScoped(Screen::Splash),
-        children![(
-            Name::new("Splash image"),
-            Node {
-                margin: UiRect::all(Val::Auto),
-                width: Val::Percent(70.0),
-                ..default()
-            },
-            ImageNode::new(asset_server.load_with_settings(
-                // This should be an embedded asset for instant loading, but that is
-                // currently [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
-                "images/splash.png",
-                |settings: &mut ImageLoaderSettings| {
-                    // Make an exception for the splash image in case
-                    // `ImagePlugin::default_nearest()` is used for pixel art.
-                    settings.sampler = ImageSampler::linear();
-                },
-            )),
-            ImageNodeFadeInOut {
-                total_duration: SPLASH_DURATION_SECS,
-                fade_duration: SPLASH_FADE_DURATION_SECS,
-                t: 0.0,
+    ));
+    commands.insert_resource(SplashProgress {
+        sequence: asset_server.load(SPLASH_SEQUENCE_PATH),
+        current: None,
+        timer: Timer::default(),
+    });
+}
+
+fn remove_splash_progress(mut commands: Commands) {
+    commands.remove_resource::<SplashProgress>();
+}
+
+fn tick_splash_progress(time: UiTime, mut progress: ResMut<SplashProgress>) {
+    progress.timer.tick(time.delta());
+}
+
+/// Cuts the current logo short on any key, mouse or gamepad button.
+fn skip_splash_logo(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut progress: ResMut<SplashProgress>,
+) {
+    let pressed = keys.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    if pressed && progress.current.is_some() {
+        let remaining = progress.timer.remaining();
+        progress.timer.tick(remaining);
+    }
+}
+
+/// Shows the first logo once the sequence has loaded, and every following one once the one before
+/// it is done. Moves on to loading the game after the last one.
+fn advance_splash_sequence(
+    mut progress: ResMut<SplashProgress>,
+    sequences: Res<Assets<SplashSequence>>,
+    asset_server: Res<AssetServer>,
+    root: Single<Entity, With<SplashRoot>>,
+    images: Query<Entity, With<SplashImage>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    let next = match progress.current {
+        Some(current) if progress.timer.finished() => current + 1,
+        Some(_) => return,
+        None => {
+            if asset_server.load_state(&progress.sequence).is_failed() {
+                warn!("Unable to load the splash sequence, skipping it");
+                next_screen.set(Screen::Loading);
+                return;
+            }
+            0
+        }
+    };
+    let Some(sequence) = sequences.get(&progress.sequence) else {
+        return;
+    };
+
+    for image in &images {
+        commands.entity(image).despawn();
+    }
+    let Some(logo) = sequence.logos.get(next) else {
+        next_screen.set(Screen::Loading);
+        return;
+    };
+
+    progress.current = Some(next);
+    progress.timer = Timer::from_seconds(logo.duration, TimerMode::Once);
+    commands.spawn((
+        Name::new("Splash image"),
+        SplashImage,
+        Node {
+            margin: UiRect::all(Val::Auto),
+            width: Val::Percent(logo.width),
+            ..default()
+        },
+        ImageNode::new(asset_server.load_with_settings(
+            // This should be an embedded asset for instant loading, but that is
+            // currently [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
+            logo.image.clone(),
+            |settings: &mut ImageLoaderSettings| {
+                // Make an exception for the splash image in case
+                // `ImagePlugin::default_nearest()` is used for pixel art.
+                settings.sampler = ImageSampler::linear();
             },
-        )],
+        )),
+        ImageNodeFadeInOut {
+            total_duration: logo.duration,
+            fade_duration: logo.fade,
+            t: 0.0,
+        },
+        ChildOf(*root),
     ));
 }
 
@@ -101,7 +233,7 @@ impl ImageNodeFadeInOut {
     }
 }
 
-fn tick_fade_in_out(time: Res<Time>, mut animation_query: Query<&mut ImageNodeFadeInOut>) {
+fn tick_fade_in_out(time: UiTime, mut animation_query: Query<&mut ImageNodeFadeInOut>) {
     for mut anim in &mut animation_query {
         anim.t += time.delta_secs();
     }
@@ -113,34 +245,6 @@ fn apply_fade_in_out(mut animation_query: Query<(&ImageNodeFadeInOut, &mut Image
     }
 }
 
-#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
-#[reflect(Resource)]
-struct SplashTimer(Timer);
-
-impl Default for SplashTimer {
-    fn default() -> Self {
-        Self(Timer::from_seconds(SPLASH_DURATION_SECS, TimerMode::Once))
-    }
-}
-
-fn insert_splash_timer(mut commands: Commands) {
-    commands.init_resource::<SplashTimer>();
-}
-
-fn remove_splash_timer(mut commands: Commands) {
-    commands.remove_resource::<SplashTimer>();
-}
-
-fn tick_splash_timer(time: Res<Time>, mut timer: ResMut<SplashTimer>) {
-    timer.0.tick(time.delta());
-}
-
-fn check_splash_timer(timer: ResMut<SplashTimer>, mut next_screen: ResMut<NextState<Screen>>) {
-    if timer.0.just_finished() {
-        next_screen.set(Screen::Loading);
-    }
-}
-
-fn enter_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
+fn enter_loading_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Loading);
 }
@@ -0,0 +1,125 @@
+//! Gameplay tips and western one-liners, shown on the loading screen and between levels.
+//! They come from `assets/game.tips.ron` and a new one comes up every couple of seconds.
+//! Tips about a [`Mechanic`] the player has never used are picked more often than the rest.
+
+use crate::clock::UiTime;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use serde::Deserialize;
+
+use crate::asset_tracking::{RonAsset, RonAssetLoader};
+use crate::gameplay::hints::Mechanic;
+use crate::localization::Locale;
+use crate::save::SaveData;
+use crate::theme::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Tips>()
+        .init_asset_loader::<RonAssetLoader<Tips>>();
+    app.init_resource::<TipAssets>();
+    app.add_systems(Update, rotate_tips);
+}
+
+/// How long each tip stays up, in real seconds.
+const TIP_SECONDS: f32 = 6.0;
+/// How much more likely a tip about a mechanic the player has never used is.
+const UNUSED_MECHANIC_WEIGHT: f32 = 4.0;
+
+// ===== TIPS FILE =====
+
+#[derive(Asset, TypePath, Debug, Deserialize)]
+struct Tips {
+    tips: Vec<Tip>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tip {
+    text: String,
+    /// What the tip is about, if it's about anything in particular.
+    #[serde(default)]
+    mechanic: Option<Mechanic>,
+}
+
+impl RonAsset for Tips {
+    const EXTENSIONS: &'static [&'static str] = &["tips.ron"];
+}
+
+/// Loaded right at startup instead of on the loading screen, so the loading screen can show them.
+#[derive(Resource)]
+struct TipAssets {
+    tips: Handle<Tips>,
+}
+
+impl FromWorld for TipAssets {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            tips: world.resource::<AssetServer>().load("game.tips.ron"),
+        }
+    }
+}
+
+// ===== LABEL =====
+
+#[derive(Component, Debug)]
+struct TipLabel {
+    timer: Timer,
+    current: Option<usize>,
+}
+
+/// A label that shows a new tip every couple of seconds. Empty until the tips have loaded.
+pub fn tip_label() -> impl Bundle {
+    (
+        Name::new("Tip"),
+        TipLabel {
+            timer: Timer::from_seconds(TIP_SECONDS, TimerMode::Repeating),
+            current: None,
+        },
+        Text::default(),
+        TextFont::from_font_size(20.0),
+        TextColor(ui_palette::LABEL_TEXT),
+        Node {
+            max_width: Val::Px(600.0),
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+    )
+}
+
+fn rotate_tips(
+    // real time, tips also show up while the game is slowed down or paused
+    time: UiTime,
+    tip_assets: Res<TipAssets>,
+    tips: Res<Assets<Tips>>,
+    save_data: Res<SaveData>,
+    locale: Locale,
+    mut labels: Query<(&mut TipLabel, &mut Text)>,
+) {
+    let Some(tips) = tips.get(&tip_assets.tips) else {
+        return;
+    };
+    let weight = |tip: &Tip| match tip.mechanic {
+        Some(mechanic) if !save_data.mechanics_used.contains(mechanic.id()) => {
+            UNUSED_MECHANIC_WEIGHT
+        }
+        _ => 1.0,
+    };
+
+    let mut rng = thread_rng();
+    for (mut label, mut text) in &mut labels {
+        let due = label.timer.tick(time.delta()).just_finished();
+        if label.current.is_some() && !due {
+            continue;
+        }
+        // never the same one twice in a row
+        let candidates: Vec<_> = (0..tips.tips.len())
+            .filter(|index| tips.tips.len() < 2 || Some(*index) != label.current)
+            .collect();
+        let Ok(index) = candidates.choose_weighted(&mut rng, |index| weight(&tips.tips[*index]))
+        else {
+            continue;
+        };
+        label.current = Some(*index);
+        text.0 = locale.tr(&tips.tips[*index].text).to_string();
+    }
+}
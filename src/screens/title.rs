@@ -3,7 +3,10 @@
 use bevy::prelude::*;
 
 use crate::audio::music;
+use crate::gameplay::cosmetics::PlayerSkin;
 use crate::gameplay::level::LevelAssets;
+use crate::gameplay::practice_range::PracticeRange;
+use crate::save::SaveData;
 use crate::ui_assets::{FontAssets, PanelAssets};
 use crate::{asset_tracking::LoadResource, screens::Screen, theme::prelude::*};
 
@@ -18,28 +21,84 @@ pub(super) fn plugin(app: &mut App) {
         );
 }
 
-fn spawn_title_screen(panel: Res<PanelAssets>, fonts: Res<FontAssets>, mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Title Screen"),
-        StateScoped(Screen::Title),
-        #[cfg(not(target_family = "wasm"))]
-        children![
-            widget::label_with_font("4D4XFUN Presents", &fonts.header),
-            widget::header_with_font("A FISTFUL OF BOOMERANGS", &fonts.header),
-            widget::header(""),
-            widget::paneled_button("Play", enter_gameplay_screen, &panel, &fonts.header),
-            widget::paneled_button("Credits", enter_credits_screen, &panel, &fonts.header),
-            widget::paneled_button("Exit", exit_app, &panel, &fonts.header),
-        ],
-        #[cfg(target_family = "wasm")]
-        children![
-            widget::label_with_font("4D4XFUN Presents", &fonts.header),
-            widget::header_with_font("A FISTFUL OF BOOMERANGS", &fonts.header),
-            widget::header(""),
-            widget::paneled_button("Play", enter_gameplay_screen, &panel, &fonts.header),
-            widget::paneled_button("Credits", enter_credits_screen, &panel, &fonts.header),
-        ],
-    ));
+fn spawn_title_screen(
+    panel: Res<PanelAssets>,
+    fonts: Res<FontAssets>,
+    save_data: Res<SaveData>,
+    mut commands: Commands,
+) {
+    let has_progress = save_data.furthest_level > 0 || !save_data.bounties.is_empty();
+    commands
+        .spawn((widget::ui_root("Title Screen"), StateScoped(Screen::Title)))
+        .with_children(|parent| {
+            parent.spawn(widget::label_with_font("4D4XFUN Presents", &fonts.header));
+            parent.spawn(widget::header_with_font(
+                "A FISTFUL OF BOOMERANGS",
+                &fonts.header,
+            ));
+            parent.spawn(widget::header(""));
+            // first, so it's what gamepads start out on
+            if has_progress {
+                parent.spawn(widget::paneled_button(
+                    "Continue",
+                    continue_campaign,
+                    &panel,
+                    &fonts.header,
+                ));
+            }
+            parent.spawn(widget::paneled_button(
+                "Play",
+                enter_level_select_screen,
+                &panel,
+                &fonts.header,
+            ));
+            parent.spawn(widget::paneled_button(
+                "Practice Range",
+                enter_practice_range,
+                &panel,
+                &fonts.header,
+            ));
+            // nothing to pick from until something other than the defaults is unlocked
+            if save_data.hats_collected > 0
+                || PlayerSkin::ALL
+                    .into_iter()
+                    .any(|skin| skin != PlayerSkin::default() && skin.is_unlocked(&save_data))
+            {
+                parent.spawn(widget::paneled_button(
+                    "Wardrobe",
+                    enter_wardrobe_screen,
+                    &panel,
+                    &fonts.header,
+                ));
+            }
+            if save_data.campaign_finished {
+                parent.spawn(widget::paneled_button(
+                    "Mutators",
+                    enter_mutators_screen,
+                    &panel,
+                    &fonts.header,
+                ));
+            }
+            parent.spawn(widget::paneled_button(
+                "Settings",
+                enter_settings_screen,
+                &panel,
+                &fonts.header,
+            ));
+            parent.spawn(widget::paneled_button(
+                "Credits",
+                enter_credits_screen,
+                &panel,
+                &fonts.header,
+            ));
+            #[cfg(not(target_family = "wasm"))]
+            parent.spawn(widget::paneled_button(
+                "Exit",
+                exit_app,
+                &panel,
+                &fonts.header,
+            ));
+        });
 }
 
 fn start_credits_music(mut commands: Commands, assets: Res<AssetServer>) {
@@ -50,23 +109,60 @@ fn start_credits_music(mut commands: Commands, assets: Res<AssetServer>) {
     ));
 }
 
-fn enter_gameplay_screen(
-    _: Trigger<Pointer<Click>>,
+/// Jumps straight into the furthest level the player has unlocked, with the bounty of every level
+/// before it.
+fn continue_campaign(
+    _: Trigger<Activate>,
+    save_data: Res<SaveData>,
+    mut level_assets: ResMut<LevelAssets>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    level_assets.current_level = save_data
+        .furthest_level
+        .min(level_assets.levels.len().saturating_sub(1));
+    level_assets.all_bounties = save_data
+        .bounties
+        .iter()
+        .map(|(level, bounty)| (*level, *bounty))
+        .collect();
+    next_screen.set(Screen::LevelLoading);
+}
+
+fn enter_level_select_screen(
+    _: Trigger<Activate>,
     mut level_assets: ResMut<LevelAssets>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
     level_assets.all_bounties.clear();
+    next_screen.set(Screen::LevelSelect);
+}
+
+/// Always open, no matter how far the player got.
+fn enter_practice_range(
+    _: Trigger<Activate>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.insert_resource(PracticeRange);
     next_screen.set(Screen::Gameplay);
 }
 
-fn _enter_settings_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+fn enter_mutators_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Mutators);
+}
+
+fn enter_wardrobe_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Wardrobe);
+}
+
+fn enter_settings_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Settings);
 }
 
-fn enter_credits_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+fn enter_credits_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Credits);
 }
 #[cfg(not(target_family = "wasm"))]
-fn exit_app(_: Trigger<Pointer<Click>>, mut app_exit: EventWriter<AppExit>) {
+fn exit_app(_: Trigger<Activate>, mut app_exit: EventWriter<AppExit>) {
     app_exit.write(AppExit::Success);
 }
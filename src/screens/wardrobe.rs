@@ -0,0 +1,184 @@
+//! The wardrobe, reachable from the title screen once there's something to wear. Players pick their
+//! hat and skin here, see [`crate::gameplay::cosmetics`] for how those get unlocked.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::gameplay::cosmetics::PlayerSkin;
+use crate::localization::Locale;
+use crate::save::SaveData;
+use crate::ui_assets::{FontAssets, PanelAssets};
+use crate::{screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Wardrobe), spawn_wardrobe_screen);
+
+    app.register_type::<WardrobeLabel>();
+    app.add_systems(
+        Update,
+        (update_wardrobe_labels, update_preview).run_if(in_state(Screen::Wardrobe)),
+    );
+}
+
+fn spawn_wardrobe_screen(panel: Res<PanelAssets>, fonts: Res<FontAssets>, mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Wardrobe Screen"),
+            StateScoped(Screen::Wardrobe),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header_with_font("Wardrobe", &fonts.header));
+            parent.spawn(preview());
+            parent
+                .spawn((
+                    Name::new("Wardrobe Grid"),
+                    Node {
+                        display: Display::Grid,
+                        row_gap: Px(10.0),
+                        column_gap: Px(30.0),
+                        grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|grid| {
+                    grid.spawn((
+                        widget::label("Hat"),
+                        Node {
+                            justify_self: JustifySelf::End,
+                            ..default()
+                        },
+                    ));
+                    grid.spawn(carousel(WardrobeLabel::Hat));
+                    grid.spawn((
+                        widget::label("Skin"),
+                        Node {
+                            justify_self: JustifySelf::End,
+                            ..default()
+                        },
+                    ));
+                    grid.spawn(carousel(WardrobeLabel::Skin));
+                });
+            parent.spawn((widget::label(""), WardrobeLabel::HatsCollected));
+            parent.spawn((widget::label(""), WardrobeLabel::NextSkin));
+            parent.spawn((
+                widget::paneled_button("Back", enter_title_screen, &panel, &fonts.header),
+                BackButton,
+            ));
+        });
+}
+
+/// A swatch in the color of the picked skin, with the picked hat's name on it.
+fn preview() -> impl Bundle {
+    (
+        Name::new("Wardrobe Preview"),
+        Node {
+            width: Px(160.0),
+            height: Px(160.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BorderRadius::all(Px(80.0)),
+        BackgroundColor(PlayerSkin::default().color()),
+        WardrobePreview,
+        children![(widget::label(""), WardrobeLabel::Hat)],
+    )
+}
+
+/// Steps through what's unlocked for the hat or the skin, depending on `label`.
+fn carousel(label: WardrobeLabel) -> impl Bundle {
+    let previous = move |_: Trigger<Activate>, mut save_data: ResMut<SaveData>| {
+        wear(label, false, &mut save_data);
+    };
+    let next = move |_: Trigger<Activate>, mut save_data: ResMut<SaveData>| {
+        wear(label, true, &mut save_data);
+    };
+    (
+        Name::new(format!("{label:?} Widget")),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous),
+            (
+                Name::new(format!("Current {label:?}")),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), label)],
+            ),
+            widget::button_small(">", next),
+        ],
+    )
+}
+
+#[derive(Component)]
+struct WardrobePreview;
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+enum WardrobeLabel {
+    Hat,
+    Skin,
+    HatsCollected,
+    /// What it takes to unlock the next locked skin, empty once they're all unlocked.
+    NextSkin,
+}
+
+fn update_wardrobe_labels(
+    save_data: Res<SaveData>,
+    mut labels: Query<(&mut Text, &WardrobeLabel)>,
+    locale: Locale,
+) {
+    let cosmetics = save_data.cosmetics;
+    for (mut text, label) in &mut labels {
+        text.0 = match label {
+            WardrobeLabel::Hat => locale.tr(cosmetics.hat.label()).to_string(),
+            WardrobeLabel::Skin => locale.tr(cosmetics.skin.label()).to_string(),
+            WardrobeLabel::HatsCollected => locale
+                .tr("Hats collected: {}")
+                .replace("{}", &save_data.hats_collected.to_string()),
+            WardrobeLabel::NextSkin => PlayerSkin::ALL
+                .into_iter()
+                .find(|skin| !skin.is_unlocked(&save_data))
+                .map(|skin| locale.tr(skin.unlock_hint()).to_string())
+                .unwrap_or_default(),
+        };
+    }
+}
+
+fn update_preview(
+    save_data: Res<SaveData>,
+    mut previews: Query<&mut BackgroundColor, With<WardrobePreview>>,
+) {
+    for mut background in &mut previews {
+        background.0 = save_data.cosmetics.skin.color();
+    }
+}
+
+fn wear(label: WardrobeLabel, forward: bool, save_data: &mut SaveData) {
+    let cosmetics = save_data.cosmetics;
+    match label {
+        WardrobeLabel::Hat => {
+            save_data.cosmetics.hat = if forward {
+                cosmetics.hat.next(save_data)
+            } else {
+                cosmetics.hat.previous(save_data)
+            };
+        }
+        WardrobeLabel::Skin => {
+            save_data.cosmetics.skin = if forward {
+                cosmetics.skin.next(save_data)
+            } else {
+                cosmetics.skin.previous(save_data)
+            };
+        }
+        WardrobeLabel::HatsCollected | WardrobeLabel::NextSkin => {}
+    }
+}
+
+fn enter_title_screen(_: Trigger<Activate>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
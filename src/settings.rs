@@ -0,0 +1,155 @@
+//! Everything players can set up in the settings menu, from the volume to the language, kept in a
+//! single [`Settings`] resource and stored next to the save data (see [`crate::save`]).
+//! The settings are loaded at startup, before the title screen shows up. Whenever they change,
+//! [`SettingsApplied`] is triggered and every subsystem copies over the part it cares about, then
+//! they're written to disk.
+//!
+//! The file is versioned. Files written by an older build are brought up to date in
+//! [`Settings::parse`]. Files written by a newer build only load if they don't use anything this
+//! build can't read: settings it doesn't know about are skipped and dropped the next time it writes
+//! the file, but an option it doesn't know, like a graphics preset added later, fails the whole
+//! file. The defaults are used then, and the unreadable file is kept under [`SETTINGS_BACKUP_NAME`]
+//! before it gets overwritten.
+
+use crate::gameplay::aim_mode::AimAssist;
+use crate::gameplay::assists::Assists;
+use crate::gameplay::camera::ScreenShakeSettings;
+use crate::gameplay::crosshair::CrosshairStyle;
+use crate::gameplay::death_fx::ViolenceLevel;
+use crate::gameplay::enemy::difficulty::AdaptiveDifficulty;
+use crate::gameplay::ghost_trail::GhostTrailSettings;
+use crate::gameplay::input::ControlSettings;
+use crate::graphics::GraphicsPreset;
+use crate::localization::Language;
+use crate::save::{read_persisted, write_persisted};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Settings::load());
+    app.add_systems(
+        Update,
+        trigger_settings_applied.run_if(resource_changed::<Settings>),
+    );
+    app.add_systems(
+        Last,
+        write_settings.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+    );
+}
+
+const SETTINGS_NAME: &str = "settings";
+/// Where a settings file that couldn't be read is kept, so going back to a newer build doesn't
+/// lose it.
+const SETTINGS_BACKUP_NAME: &str = "settings.backup";
+
+/// The layout [`Settings`] are written with. Bump it whenever a setting gets renamed or changes
+/// meaning, and convert the old layout in [`Settings::parse`]. Settings that are only added don't
+/// need a new version, they fall back to their default.
+const SETTINGS_VERSION: u32 = 1;
+
+/// Triggered once the [`Settings`] are loaded and every time they change afterwards. Subsystems
+/// observe it and update their own resources from the settings.
+#[derive(Event, Debug)]
+pub struct SettingsApplied;
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// The layout these settings were written with, see [`SETTINGS_VERSION`].
+    pub version: u32,
+    /// Linear global volume, between [`Settings::MIN_VOLUME`] and [`Settings::MAX_VOLUME`].
+    pub volume: f32,
+    pub screen_shake: bool,
+    pub aim_assist: AimAssist,
+    pub crosshair: CrosshairStyle,
+    /// See [`ControlSettings::stick_curve`].
+    pub stick_curve: f32,
+    /// See [`ControlSettings::stick_deadzone`].
+    pub stick_deadzone: f32,
+    pub invert_stick_x: bool,
+    pub language: Language,
+    /// Goes straight to the game at startup, without showing any splash screens.
+    pub skip_splash: bool,
+    pub violence: ViolenceLevel,
+    /// Shows the path the last boomerang flew once it's done.
+    pub ghost_trail: bool,
+    /// See [`AdaptiveDifficulty`].
+    pub adaptive_difficulty: bool,
+    pub graphics: GraphicsPreset,
+    pub assists: Assists,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let controls = ControlSettings::default();
+        Self {
+            version: SETTINGS_VERSION,
+            volume: 0.2,
+            screen_shake: ScreenShakeSettings::default().enabled,
+            aim_assist: AimAssist::default(),
+            crosshair: CrosshairStyle::default(),
+            stick_curve: controls.stick_curve,
+            stick_deadzone: controls.stick_deadzone,
+            invert_stick_x: controls.invert_stick_x,
+            language: Language::default(),
+            skip_splash: false,
+            violence: ViolenceLevel::default(),
+            ghost_trail: GhostTrailSettings::default().enabled,
+            adaptive_difficulty: AdaptiveDifficulty::default().enabled,
+            graphics: GraphicsPreset::default(),
+            assists: Assists::default(),
+        }
+    }
+}
+
+/// Just the version of a settings file, to know how to read the rest of it.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SettingsVersion {
+    version: u32,
+}
+
+impl Settings {
+    pub const MIN_VOLUME: f32 = 0.0;
+    pub const MAX_VOLUME: f32 = 3.0;
+
+    fn load() -> Self {
+        let Some(contents) = read_persisted(SETTINGS_NAME) else {
+            info!("No settings found, using the defaults");
+            return Self::default();
+        };
+        Self::parse(&contents).unwrap_or_else(|e| {
+            warn!("Unable to parse settings, using the defaults and backing them up: {e}");
+            write_persisted(SETTINGS_BACKUP_NAME, &contents);
+            Self::default()
+        })
+    }
+
+    fn parse(contents: &str) -> Result<Self, ron::error::SpannedError> {
+        let SettingsVersion { version } = ron::from_str(contents)?;
+        if version > SETTINGS_VERSION {
+            warn!(
+                "Settings were written by a newer version of the game ({version}), \
+                 some of them might not be used"
+            );
+        }
+        // Every version so far shares the same layout. Once it changes, read older files as
+        // their own type here and turn them into the current one.
+        let settings: Self = ron::from_str(contents)?;
+        Ok(Self {
+            version: SETTINGS_VERSION,
+            ..settings
+        })
+    }
+}
+
+fn trigger_settings_applied(mut commands: Commands) {
+    commands.trigger(SettingsApplied);
+}
+
+fn write_settings(settings: Res<Settings>) {
+    match ron::ser::to_string_pretty(settings.as_ref(), ron::ser::PrettyConfig::default()) {
+        Ok(contents) => write_persisted(SETTINGS_NAME, &contents),
+        Err(e) => error!("Unable to serialize settings: {e}"),
+    }
+}
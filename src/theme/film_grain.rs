@@ -1,3 +1,4 @@
+use crate::clock::UiTime;
 use bevy::{
     core_pipeline::{
         core_2d::graph::{Core2d, Node2d},
@@ -313,7 +314,7 @@ impl FromWorld for FilmGrainPipeline {
 }
 
 // Helper system to update time
-pub fn update_film_grain_time(time: Res<Time>, mut query: Query<&mut FilmGrainSettings>) {
+pub fn update_film_grain_time(time: UiTime, mut query: Query<&mut FilmGrainSettings>) {
     for mut settings in &mut query {
         settings.time += time.delta_secs() * settings.grain_speed;
     }
@@ -362,7 +363,7 @@ impl FilmGrainSettingsTween {
 
     pub fn update(
         mut query: Query<(&mut FilmGrainSettings, &mut FilmGrainSettingsTween)>,
-        time: Res<Time>,
+        time: UiTime,
     ) {
         for (mut settings, mut settings_tween) in query.iter_mut() {
             // tick the timer
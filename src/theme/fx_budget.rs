@@ -0,0 +1,120 @@
+//! Keeps purely visual effects (smoke, sparks, confetti, explosion and muzzle flashes) from piling
+//! up in big fights.
+//! Every effect entity is tagged with an [`Fx`] kind, and each kind may only have so many alive at
+//! once. Once there are more, the oldest ones make room for the new ones.
+//! How many are alive and how many had to go show up in the system budget dev tool.
+
+use crate::HotSystems;
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<FxBudget>().register_type::<FxBudget>();
+    app.register_diagnostic(Diagnostic::new(FX_ALIVE).with_max_history_length(HISTORY_LENGTH))
+        .register_diagnostic(Diagnostic::new(FX_RECYCLED).with_max_history_length(HISTORY_LENGTH));
+    app.add_observer(number_fx);
+    app.add_systems(Update, enforce_fx_budget.in_set(HotSystems::Particles));
+}
+
+pub const FX_ALIVE: DiagnosticPath = DiagnosticPath::const_new("fx/alive");
+pub const FX_RECYCLED: DiagnosticPath = DiagnosticPath::const_new("fx/recycled");
+const HISTORY_LENGTH: usize = 120;
+
+/// What kind of effect an entity is. Add it to anything that's only there for looks and can go
+/// away early without anyone noticing.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fx {
+    Smoke,
+    Sparks,
+    Confetti,
+    Explosion,
+    /// Comes with a light, which is a lot more expensive than a particle.
+    MuzzleFlash,
+}
+
+/// How many effects of each kind may be alive at once.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct FxBudget {
+    pub smoke: usize,
+    pub sparks: usize,
+    pub confetti: usize,
+    pub explosions: usize,
+    pub muzzle_flashes: usize,
+}
+
+impl Default for FxBudget {
+    fn default() -> Self {
+        Self {
+            smoke: 150,
+            sparks: 120,
+            confetti: 200,
+            explosions: 8,
+            muzzle_flashes: 6,
+        }
+    }
+}
+
+impl FxBudget {
+    /// The same budget with every cap multiplied by `factor`, always leaving room for one effect.
+    pub fn scaled(&self, factor: f32) -> Self {
+        let scale = |cap: usize| ((cap as f32 * factor).round() as usize).max(1);
+        Self {
+            smoke: scale(self.smoke),
+            sparks: scale(self.sparks),
+            confetti: scale(self.confetti),
+            explosions: scale(self.explosions),
+            muzzle_flashes: scale(self.muzzle_flashes),
+        }
+    }
+
+    fn cap(&self, fx: Fx) -> usize {
+        match fx {
+            Fx::Smoke => self.smoke,
+            Fx::Sparks => self.sparks,
+            Fx::Confetti => self.confetti,
+            Fx::Explosion => self.explosions,
+            Fx::MuzzleFlash => self.muzzle_flashes,
+        }
+    }
+}
+
+/// When an effect was spawned, relative to all the others.
+#[derive(Component, Debug)]
+struct FxAge(u64);
+
+fn number_fx(trigger: Trigger<OnAdd, Fx>, mut spawned: Local<u64>, mut commands: Commands) {
+    commands.entity(trigger.target()).insert(FxAge(*spawned));
+    *spawned += 1;
+}
+
+fn enforce_fx_budget(
+    budget: Res<FxBudget>,
+    effects: Query<(Entity, &Fx, &FxAge)>,
+    mut diagnostics: Diagnostics,
+    mut commands: Commands,
+) {
+    let mut by_kind: HashMap<Fx, Vec<(u64, Entity)>> = HashMap::default();
+    for (entity, fx, age) in &effects {
+        by_kind.entry(*fx).or_default().push((age.0, entity));
+    }
+
+    let mut alive = 0;
+    let mut recycled = 0;
+    for (fx, mut instances) in by_kind {
+        let cap = budget.cap(fx);
+        if instances.len() > cap {
+            instances.sort_unstable();
+            let over = instances.len() - cap;
+            for (_, entity) in instances.drain(..over) {
+                // might be fading out this very frame anyway
+                commands.entity(entity).try_despawn();
+            }
+            recycled += over;
+        }
+        alive += instances.len();
+    }
+    diagnostics.add_measurement(&FX_ALIVE, || alive as f64);
+    diagnostics.add_measurement(&FX_RECYCLED, || recycled as f64);
+}
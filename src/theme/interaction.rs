@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::{
+    asset_tracking::LoadResource, audio::sound_effect, theme::navigation::Activate,
+    theme::navigation::MenuFocus,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
@@ -9,6 +12,7 @@ pub(super) fn plugin(app: &mut App) {
         (
             apply_interaction_palette,
             apply_interaction_palette_to_image_node,
+            play_on_focus_sound_effect.run_if(resource_changed::<MenuFocus>),
         ),
     );
 
@@ -29,34 +33,50 @@ pub struct InteractionPalette {
     pub pressed: Color,
 }
 
+impl InteractionPalette {
+    /// A button with the [`MenuFocus`] looks hovered, so players without a mouse see where they are.
+    fn color(&self, interaction: Interaction, focused: bool) -> Color {
+        match interaction {
+            Interaction::None if focused => self.hovered,
+            Interaction::None => self.none,
+            Interaction::Hovered => self.hovered,
+            Interaction::Pressed => self.pressed,
+        }
+    }
+}
+
 fn apply_interaction_palette(
+    focus: Res<MenuFocus>,
     mut palette_query: Query<
-        (&Interaction, &InteractionPalette, &mut BackgroundColor),
-        (Changed<Interaction>, Without<ImageNode>),
+        (
+            Entity,
+            Ref<Interaction>,
+            &InteractionPalette,
+            &mut BackgroundColor,
+        ),
+        Without<ImageNode>,
     >,
 ) {
-    for (interaction, palette, mut background) in &mut palette_query {
-        *background = match interaction {
-            Interaction::None => palette.none,
-            Interaction::Hovered => palette.hovered,
-            Interaction::Pressed => palette.pressed,
+    for (entity, interaction, palette, mut background) in &mut palette_query {
+        if interaction.is_changed() || focus.is_changed() {
+            *background = palette.color(*interaction, focus.0 == Some(entity)).into();
         }
-        .into();
     }
 }
 
 fn apply_interaction_palette_to_image_node(
-    mut palette_query: Query<
-        (&Interaction, &InteractionPalette, &mut ImageNode),
-        Changed<Interaction>,
-    >,
+    focus: Res<MenuFocus>,
+    mut palette_query: Query<(
+        Entity,
+        Ref<Interaction>,
+        &InteractionPalette,
+        &mut ImageNode,
+    )>,
 ) {
-    for (interaction, palette, mut image_node) in &mut palette_query {
-        image_node.color = match interaction {
-            Interaction::None => palette.none,
-            Interaction::Hovered => palette.hovered,
-            Interaction::Pressed => palette.pressed,
-        };
+    for (entity, interaction, palette, mut image_node) in &mut palette_query {
+        if interaction.is_changed() || focus.is_changed() {
+            image_node.color = palette.color(*interaction, focus.0 == Some(entity));
+        }
     }
 }
 
@@ -94,8 +114,28 @@ fn play_on_hover_sound_effect(
     }
 }
 
+/// The mouse already plays the hover sound on its own, this is for focus moved by the keyboard or a
+/// gamepad.
+fn play_on_focus_sound_effect(
+    mut commands: Commands,
+    focus: Res<MenuFocus>,
+    interaction_assets: Option<Res<InteractionAssets>>,
+    interaction_query: Query<&Interaction>,
+) {
+    let Some(interaction_assets) = interaction_assets else {
+        return;
+    };
+
+    let focused = focus
+        .0
+        .and_then(|entity| interaction_query.get(entity).ok());
+    if focused == Some(&Interaction::None) {
+        commands.spawn(sound_effect(interaction_assets.hover.clone()));
+    }
+}
+
 fn play_on_click_sound_effect(
-    trigger: Trigger<Pointer<Click>>,
+    trigger: Trigger<Activate>,
     mut commands: Commands,
     interaction_assets: Option<Res<InteractionAssets>>,
     interaction_query: Query<(), With<Interaction>>,
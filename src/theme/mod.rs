@@ -4,26 +4,41 @@
 #![allow(dead_code)]
 
 pub mod film_grain;
+pub mod fx_budget;
 pub mod interaction;
+pub mod navigation;
 pub mod palette;
 pub mod particles;
+pub mod toast;
+pub mod virtual_cursor;
 pub mod widget;
 
 #[allow(unused_imports)]
 pub mod prelude {
-    pub use super::{interaction::InteractionPalette, palette as ui_palette, widget};
+    pub use super::{
+        interaction::InteractionPalette,
+        navigation::{Activate, BackButton},
+        palette as ui_palette, widget,
+    };
 }
 
 use crate::theme::film_grain::{FilmGrainPlugin, update_film_grain_time};
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((
+        interaction::plugin,
+        navigation::plugin,
+        virtual_cursor::plugin,
+    ));
 
     // grain
     app.add_plugins(FilmGrainPlugin);
     app.add_systems(Update, update_film_grain_time);
 
     // particles
-    app.add_plugins(particles::plugin);
+    app.add_plugins((particles::plugin, fx_budget::plugin));
+
+    // notifications
+    app.add_plugins(toast::plugin);
 }
@@ -0,0 +1,195 @@
+//! Menus work without a mouse too: the arrow keys, the d-pad or the left stick move a focus
+//! between buttons, enter/space or A presses the focused one, escape or B goes back.
+//! Buttons react to [`Activate`] rather than clicks, clicking a button triggers it as well.
+//! The focused button is highlighted like a hovered one, see [`InteractionPalette`].
+
+use crate::clock::UiTime;
+use crate::gameplay::input::LastInputKind;
+use crate::theme::interaction::InteractionPalette;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MenuFocus>();
+    app.add_systems(Update, navigate_menus);
+    app.add_observer(focus_hovered_button)
+        .add_observer(unfocus_left_button);
+}
+
+/// Stick input past this counts as pushing it in a direction.
+const STICK_THRESHOLD: f32 = 0.5;
+/// How long the stick has to stay pushed before the focus moves on to the next button.
+const STICK_REPEAT_SECONDS: f32 = 0.25;
+/// Buttons to either side of the direction count this much more than the ones straight ahead.
+const SIDEWAYS_PENALTY: f32 = 2.0;
+
+/// Triggered on a button when it's clicked, or pressed while it has the focus.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Activate;
+
+/// The button that activates on enter or A, if any.
+#[derive(Resource, Debug, Default)]
+pub struct MenuFocus(pub Option<Entity>);
+
+/// Put this next to a button that leaves the current menu, it gets activated on escape or B.
+#[derive(Component, Debug, Default)]
+pub struct BackButton;
+
+/// Turns any click on a button into an [`Activate`]. Added to every button by the widgets.
+pub fn activate_on_click(trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.entity(trigger.target()).trigger(Activate);
+}
+
+fn focus_hovered_button(
+    trigger: Trigger<Pointer<Over>>,
+    buttons: Query<(), (With<Button>, With<InteractionPalette>)>,
+    mut focus: ResMut<MenuFocus>,
+) {
+    if buttons.contains(trigger.target()) {
+        focus.0 = Some(trigger.target());
+    }
+}
+
+fn unfocus_left_button(trigger: Trigger<Pointer<Out>>, mut focus: ResMut<MenuFocus>) {
+    if focus.0 == Some(trigger.target()) {
+        focus.0 = None;
+    }
+}
+
+fn navigate_menus(
+    // real time, menus on top of the game shouldn't get sluggish in slow motion
+    time: UiTime,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    last_input: Res<LastInputKind>,
+    buttons: Query<
+        (Entity, &GlobalTransform, &InheritedVisibility),
+        (With<Button>, With<InteractionPalette>),
+    >,
+    back_buttons: Query<&Children, With<BackButton>>,
+    mut focus: ResMut<MenuFocus>,
+    mut stick_cooldown: Local<f32>,
+    mut commands: Commands,
+) {
+    let visible: Vec<_> = buttons
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation().xy()))
+        .collect();
+    if focus
+        .0
+        .is_some_and(|focused| !visible.iter().any(|(entity, _)| *entity == focused))
+    {
+        focus.0 = None;
+    }
+    if visible.is_empty() {
+        return;
+    }
+
+    let direction = navigation_direction(&keys, &gamepads, &time, &mut stick_cooldown);
+    // gamepads start out on the first button, everyone else once they start navigating
+    if focus.0.is_none() && (direction.is_some() || *last_input == LastInputKind::Gamepad) {
+        focus.0 = first_button(&visible);
+        return;
+    }
+    if let (Some(direction), Some(focused)) = (direction, focus.0) {
+        if let Some(next) = neighbor(focused, direction, &visible) {
+            focus.0 = Some(next);
+        }
+        return;
+    }
+
+    let pressed = keys.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Space])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+    if let Some(focused) = focus.0.filter(|_| pressed) {
+        commands.entity(focused).trigger(Activate);
+        return;
+    }
+
+    let back = keys.just_pressed(KeyCode::Escape)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::East));
+    if !back {
+        return;
+    }
+    if let Some(button) = back_buttons
+        .iter()
+        .flat_map(|children| children.iter())
+        .find(|child| buttons.contains(*child))
+    {
+        commands.entity(button).trigger(Activate);
+    }
+}
+
+/// Which way the player wants the focus to go this frame, in UI coordinates (y points down).
+fn navigation_direction(
+    keys: &ButtonInput<KeyCode>,
+    gamepads: &Query<&Gamepad>,
+    time: &Time<Real>,
+    stick_cooldown: &mut f32,
+) -> Option<Vec2> {
+    let pressed = |key: KeyCode, button: GamepadButton| {
+        keys.just_pressed(key) || gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+    };
+    if pressed(KeyCode::ArrowUp, GamepadButton::DPadUp) {
+        return Some(Vec2::NEG_Y);
+    }
+    if pressed(KeyCode::ArrowDown, GamepadButton::DPadDown) {
+        return Some(Vec2::Y);
+    }
+    if pressed(KeyCode::ArrowLeft, GamepadButton::DPadLeft) {
+        return Some(Vec2::NEG_X);
+    }
+    if pressed(KeyCode::ArrowRight, GamepadButton::DPadRight) {
+        return Some(Vec2::X);
+    }
+
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| gamepad.left_stick())
+        .find(|stick| stick.length() > STICK_THRESHOLD);
+    let Some(stick) = stick else {
+        *stick_cooldown = 0.0;
+        return None;
+    };
+    *stick_cooldown -= time.delta_secs();
+    if *stick_cooldown > 0.0 {
+        return None;
+    }
+    *stick_cooldown = STICK_REPEAT_SECONDS;
+    // only the main direction, and the stick's y points up
+    Some(if stick.x.abs() > stick.y.abs() {
+        Vec2::new(stick.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, -stick.y.signum())
+    })
+}
+
+/// The top left button.
+fn first_button(buttons: &[(Entity, Vec2)]) -> Option<Entity> {
+    buttons
+        .iter()
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .map(|(entity, _)| *entity)
+}
+
+/// The closest button in `direction` from the `focused` one, preferring ones straight ahead.
+fn neighbor(focused: Entity, direction: Vec2, buttons: &[(Entity, Vec2)]) -> Option<Entity> {
+    let (_, from) = buttons.iter().find(|(entity, _)| *entity == focused)?;
+    buttons
+        .iter()
+        .filter_map(|(entity, position)| {
+            let offset = *position - *from;
+            let ahead = offset.dot(direction);
+            // anything roughly level counts as being next to it, not ahead of it
+            if ahead < 1.0 {
+                return None;
+            }
+            let sideways = offset.perp_dot(direction).abs();
+            Some((*entity, ahead + sideways * SIDEWAYS_PENALTY))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
@@ -1,150 +1,434 @@
+//! Particle effects. Most of them come from the [`Vfx`] catalog: trigger a [`SpawnVfxEvent`] with
+//! the effect and where it goes, e.g. `SpawnVfxEvent::new(Vfx::Puff, position)`. Every effect is
+//! a [`VfxRecipe`] of how many particles it has, how they move and what they look like, so a new
+//! one is a new variant and its recipe.
+//! Confetti is the odd one out, with particles of many colors and a hat, see
+//! [`SpawnConfettiEvent`].
+
+use crate::HotSystems;
+use crate::clock::GameTime;
+use crate::theme::fx_budget::Fx;
 use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.init_resource::<SmokeParticleConfig>()
-        .add_observer(spawn_gun_smoke)
-        .add_systems(Update, update_smoke_particles);
+    app.init_resource::<VfxAssets>()
+        .add_observer(spawn_vfx)
+        .add_observer(spawn_confetti)
+        .add_systems(
+            Update,
+            (update_vfx_particles, update_confetti_particles).in_set(HotSystems::Particles),
+        );
 
     // reflection
-    app.register_type::<SmokeParticle>()
-        .register_type::<SmokeParticleConfig>();
+    app.register_type::<VfxParticle>()
+        .register_type::<ConfettiParticle>();
+}
 
-    // dev tool
-    // use bevy_inspector_egui::quick::ResourceInspectorPlugin;
-    // app.add_plugins(ResourceInspectorPlugin::<SmokeParticleConfig>::default());
+/// Every effect in the catalog, see [`Vfx::recipe`] for what they look like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Vfx {
+    /// Blown out of a gun barrel, along the direction of the shot.
+    GunSmoke,
+    /// A round cloud of smoke, e.g. where something went *poof*.
+    Puff,
+    /// Kicked up where a boomerang hits a wall, away from the wall.
+    ImpactDust,
+    /// A big cloud of dust where an outlaw bit it.
+    DeathPoof,
+    /// Glittering where something was picked up.
+    PickupSparkle,
+    /// A shower of sparks, e.g. when two pieces of metal hit each other.
+    Sparks,
 }
 
-#[derive(Component, Debug, Reflect)]
-#[reflect(Component)]
-pub struct SmokeParticle {
-    pub velocity: Vec3,
+/// How an effect's particles look and move.
+#[derive(Debug, Clone, Copy)]
+pub struct VfxRecipe {
+    pub particles: usize,
+    /// How long each particle lives, in seconds.
     pub lifetime: f32,
+    /// How far from the effect's position particles start out, at most.
+    pub spread: f32,
+    /// How fast particles fly along the effect's direction.
+    pub push: f32,
+    /// How fast particles fly in a random direction, picked from this range.
+    pub scatter: (f32, f32),
+    /// How much the random direction leans up, picked from this range. 0.0 is flat, below 0.0 is
+    /// down.
+    pub rise: (f32, f32),
+    /// Pulls particles down, negative values make them float up instead.
+    pub gravity: f32,
+    /// How quickly particles slow down, higher is quicker.
+    pub drag: f32,
+    /// How big particles are when they're spawned and when they die.
+    pub size: (f32, f32),
+    pub size_ease: EaseFunction,
+    pub color: Color,
+    /// Makes particles glow, black for none.
+    pub emissive: LinearRgba,
+    /// Fades particles out over their lifetime. Every particle needs a material of its own for
+    /// that, the others share one per effect.
+    pub fade: bool,
+    pub shape: VfxShape,
+    pub fx: Fx,
 }
 
-#[derive(Event, Debug, Copy, Clone)]
-pub struct SpawnGunshotSmokeEvent {
+/// What a particle is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VfxShape {
+    /// A square that always faces the camera.
+    Billboard,
+    /// A tiny cube, e.g. for sparks.
+    Cube,
+}
+
+impl Vfx {
+    pub fn recipe(self) -> VfxRecipe {
+        let smoke = VfxRecipe {
+            particles: 15,
+            lifetime: 2.0,
+            spread: 0.6,
+            push: 0.0,
+            scatter: (2.5, 2.5),
+            rise: (-0.25, 0.25),
+            gravity: -0.5,
+            drag: 3.0,
+            size: (0.5, 1.0),
+            size_ease: EaseFunction::ExponentialOut,
+            color: Color::WHITE,
+            emissive: LinearRgba::BLACK,
+            fade: true,
+            shape: VfxShape::Billboard,
+            fx: Fx::Smoke,
+        };
+        let sparks = VfxRecipe {
+            particles: 12,
+            lifetime: 0.4,
+            spread: 0.0,
+            push: 0.0,
+            scatter: (4.0, 10.0),
+            rise: (0.0, 0.5),
+            gravity: 9.81,
+            drag: 0.0,
+            size: (0.08, 0.0),
+            size_ease: EaseFunction::Linear,
+            color: Color::srgb(1.0, 0.8, 0.3),
+            emissive: LinearRgba::rgb(8.0, 5.0, 1.0),
+            fade: false,
+            shape: VfxShape::Cube,
+            fx: Fx::Sparks,
+        };
+        match self {
+            Vfx::GunSmoke => VfxRecipe {
+                spread: 1.0,
+                push: 2.0,
+                scatter: (0.6, 1.2),
+                rise: (1.0, 2.0),
+                ..smoke
+            },
+            Vfx::Puff => smoke,
+            Vfx::ImpactDust => VfxRecipe {
+                particles: 8,
+                lifetime: 0.8,
+                spread: 0.3,
+                push: 3.0,
+                scatter: (0.5, 1.5),
+                rise: (0.0, 0.5),
+                gravity: 1.0,
+                drag: 4.0,
+                size: (0.3, 0.8),
+                color: Color::srgb(0.8, 0.7, 0.5),
+                ..smoke
+            },
+            Vfx::DeathPoof => VfxRecipe {
+                particles: 24,
+                lifetime: 1.2,
+                spread: 0.8,
+                scatter: (3.0, 4.5),
+                rise: (0.0, 0.6),
+                gravity: -1.0,
+                drag: 4.0,
+                size: (0.8, 1.6),
+                color: Color::srgb(0.75, 0.68, 0.6),
+                ..smoke
+            },
+            Vfx::PickupSparkle => VfxRecipe {
+                particles: 10,
+                lifetime: 0.6,
+                spread: 0.8,
+                scatter: (1.0, 2.5),
+                rise: (0.5, 1.5),
+                gravity: 0.0,
+                drag: 2.0,
+                size: (0.15, 0.0),
+                color: Color::srgb(1.0, 0.9, 0.5),
+                emissive: LinearRgba::rgb(6.0, 5.0, 1.5),
+                shape: VfxShape::Billboard,
+                ..sparks
+            },
+            Vfx::Sparks => sparks,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SpawnVfxEvent {
+    pub vfx: Vfx,
     pub position: Vec3,
+    /// Which way the effect goes, if it has a [`VfxRecipe::push`].
     pub direction: Vec3,
 }
 
-#[derive(Resource, Debug, Reflect)]
-#[reflect(Resource)]
-pub struct SmokeParticleConfig {
-    lifetime: f32,
-    max_lifetime: f32,
-    min_size: f32,
-    max_size: f32,
-    ease_function: EaseFunction,
-    particles_per_shot: usize,
-    particle_spread: f32,
-}
-impl Default for SmokeParticleConfig {
-    fn default() -> Self {
+impl SpawnVfxEvent {
+    pub fn new(vfx: Vfx, position: Vec3) -> Self {
         Self {
-            lifetime: 0.0,
-            max_lifetime: 2.0,
-            min_size: 0.5,
-            max_size: 1.0,
-            ease_function: EaseFunction::ExponentialOut,
-            particles_per_shot: 15,
-            particle_spread: 1.0,
+            vfx,
+            position,
+            direction: Vec3::ZERO,
         }
     }
+
+    pub fn toward(self, direction: Vec3) -> Self {
+        Self { direction, ..self }
+    }
 }
-impl SmokeParticleConfig {
-    pub fn tween_size(&self, lifetime: f32) -> f32 {
-        let progress = lifetime / self.max_lifetime;
-        EasingCurve::new(self.min_size, self.max_size, self.ease_function).sample_clamped(progress)
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VfxParticle {
+    pub vfx: Vfx,
+    pub velocity: Vec3,
+    pub lifetime: f32,
+}
+
+/// The meshes every particle shares.
+#[derive(Resource, Debug)]
+struct VfxAssets {
+    billboard: Handle<Mesh>,
+    cube: Handle<Mesh>,
+}
+
+impl FromWorld for VfxAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        Self {
+            billboard: meshes.add(Rectangle::new(1.0, 1.0)),
+            cube: meshes.add(Cuboid::from_length(1.0)),
+        }
     }
 }
 
-fn spawn_gun_smoke(
-    trigger: Trigger<SpawnGunshotSmokeEvent>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
+fn spawn_vfx(
+    trigger: Trigger<SpawnVfxEvent>,
+    assets: Res<VfxAssets>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    particle_configs: Res<SmokeParticleConfig>,
+    mut commands: Commands,
 ) {
     let event = trigger.event();
-
-    let quad_handle = meshes.add(Plane3d::default().mesh().size(1.0, 1.0));
-
-    for _ in 0..particle_configs.particles_per_shot {
-        let random_offset = Vec3::new(
-            (rand::random::<f32>() - 0.5) * particle_configs.particle_spread,
-            (rand::random::<f32>() - 0.5) * particle_configs.particle_spread,
-            (rand::random::<f32>() - 0.5) * particle_configs.particle_spread,
-        );
-
-        let velocity = event.direction * 2.0
-            + Vec3::new(
-                (rand::random::<f32>() - 0.5) * 1.0,
-                rand::random::<f32>() * 0.5 + 0.5,
-                (rand::random::<f32>() - 0.5) * 1.0,
-            );
-
-        let material = materials.add(StandardMaterial {
-            base_color: Color::srgba(1.0, 1.0, 1.0, 1.0),
-            alpha_mode: AlphaMode::Blend,
+    let recipe = event.vfx.recipe();
+    let mesh = match recipe.shape {
+        VfxShape::Billboard => assets.billboard.clone(),
+        VfxShape::Cube => assets.cube.clone(),
+    };
+    let new_material = |materials: &mut Assets<StandardMaterial>| {
+        materials.add(StandardMaterial {
+            base_color: recipe.color,
+            emissive: recipe.emissive,
+            alpha_mode: if recipe.fade {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            unlit: recipe.emissive != LinearRgba::BLACK,
             double_sided: true,
+            cull_mode: None,
             ..default()
-        });
+        })
+    };
+    let shared_material = (!recipe.fade).then(|| new_material(&mut materials));
+    let random = |range: (f32, f32)| range.0 + rand::random::<f32>() * (range.1 - range.0);
+
+    for _ in 0..recipe.particles {
+        let offset =
+            (Vec3::new(rand::random(), rand::random(), rand::random()) - 0.5) * recipe.spread;
+        let direction = Vec3::new(
+            rand::random::<f32>() - 0.5,
+            random(recipe.rise),
+            rand::random::<f32>() - 0.5,
+        )
+        .normalize_or(Vec3::Y);
+        let material = shared_material
+            .clone()
+            .unwrap_or_else(|| new_material(&mut materials));
 
         commands.spawn((
-            Name::new("SmokeParticle"),
-            Mesh3d(quad_handle.clone()),
+            Name::new(format!("{:?} Particle", event.vfx)),
+            Mesh3d(mesh.clone()),
             MeshMaterial3d(material),
-            // Transform::from_translation(event.position + random_offset)
-            //     .with_scale(Vec3::splat(0.5)),
-            Transform::from_translation(event.position + random_offset)
-                .with_scale(Vec3::splat(2.0))
-                .looking_at(event.position + event.direction, Vec3::Y),
-            SmokeParticle {
-                velocity,
+            Transform::from_translation(event.position + offset)
+                .with_scale(Vec3::splat(recipe.size.0)),
+            VfxParticle {
+                vfx: event.vfx,
+                velocity: event.direction * recipe.push + direction * random(recipe.scatter),
                 lifetime: 0.0,
             },
+            recipe.fx,
             NotShadowCaster,
             NotShadowReceiver,
         ));
     }
 }
 
-fn update_smoke_particles(
+fn update_vfx_particles(
     mut commands: Commands,
-    time: Res<Time>,
+    time: GameTime,
+    cameras: Query<&GlobalTransform, With<Camera3d>>,
     mut particles: Query<(
         Entity,
         &mut Transform,
         &MeshMaterial3d<StandardMaterial>,
-        &mut SmokeParticle,
+        &mut VfxParticle,
     )>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    particle_config: Res<SmokeParticleConfig>,
 ) {
     let dt = time.delta_secs();
+    let facing_camera = cameras.iter().next().map(GlobalTransform::rotation);
 
     for (entity, mut transform, material_handle, mut particle) in &mut particles {
+        let recipe = particle.vfx.recipe();
         particle.lifetime += dt;
-
-        if particle.lifetime > particle_config.max_lifetime {
+        if particle.lifetime > recipe.lifetime {
             commands.entity(entity).despawn();
             continue;
         }
+        let progress = particle.lifetime / recipe.lifetime;
 
-        // Move particles
         transform.translation += particle.velocity * dt;
-        particle.velocity *= 0.95;
-        particle.velocity.y += dt * 0.5;
+        particle.velocity *= (-recipe.drag * dt).exp();
+        particle.velocity.y -= recipe.gravity * dt;
 
-        // Scale over time
-        let size = particle_config.tween_size(particle.lifetime);
+        let size = EasingCurve::new(recipe.size.0, recipe.size.1, recipe.size_ease)
+            .sample_clamped(progress);
         transform.scale = Vec3::splat(size);
+        if recipe.shape == VfxShape::Billboard {
+            if let Some(rotation) = facing_camera {
+                transform.rotation = rotation;
+            }
+        }
 
-        // Fade out linearly over time
-        if let Some(material) = materials.get_mut(&material_handle.0) {
-            let alpha = 0.7 * (1.0 - particle.lifetime / particle_config.max_lifetime);
-            material.base_color = Color::srgba(1.0, 1.0, 1.0, alpha);
+        if recipe.fade {
+            if let Some(material) = materials.get_mut(&material_handle.0) {
+                material.base_color = recipe.color.with_alpha(0.7 * (1.0 - progress));
+            }
         }
     }
 }
+
+// ===== CONFETTI =====
+
+const CONFETTI_PER_BURST: usize = 30;
+const CONFETTI_LIFETIME: f32 = 2.0;
+/// Confetti drifts down a lot slower than things fall.
+const CONFETTI_GRAVITY: f32 = 3.0;
+const CONFETTI_DRAG: f32 = 2.0;
+const CONFETTI_COLORS: [Color; 5] = [
+    Color::srgb(0.9, 0.2, 0.2),
+    Color::srgb(0.95, 0.8, 0.2),
+    Color::srgb(0.2, 0.6, 0.9),
+    Color::srgb(0.3, 0.8, 0.3),
+    Color::srgb(0.9, 0.4, 0.8),
+];
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ConfettiParticle {
+    pub velocity: Vec3,
+    pub spin: Vec3,
+    pub lifetime: f32,
+}
+
+/// A burst of paper confetti, with a hat flying out of it.
+#[derive(Event, Debug, Copy, Clone)]
+pub struct SpawnConfettiEvent {
+    pub position: Vec3,
+}
+
+fn spawn_confetti(
+    trigger: Trigger<SpawnConfettiEvent>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let position = trigger.event().position;
+    let random_spin = || Vec3::new(rand::random(), rand::random(), rand::random()) * 12.0;
+
+    let mesh = meshes.add(Plane3d::default().mesh().size(0.12, 0.2));
+    for index in 0..CONFETTI_PER_BURST {
+        let direction = Vec3::new(
+            rand::random::<f32>() - 0.5,
+            0.5 + rand::random::<f32>(),
+            rand::random::<f32>() - 0.5,
+        )
+        .normalize_or(Vec3::Y);
+        let material = materials.add(StandardMaterial {
+            base_color: CONFETTI_COLORS[index % CONFETTI_COLORS.len()],
+            double_sided: true,
+            cull_mode: None,
+            unlit: true,
+            ..default()
+        });
+        commands.spawn((
+            Name::new("ConfettiParticle"),
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material),
+            Transform::from_translation(position),
+            ConfettiParticle {
+                velocity: direction * (6.0 + rand::random::<f32>() * 6.0),
+                spin: random_spin(),
+                lifetime: 0.0,
+            },
+            Fx::Confetti,
+            NotShadowCaster,
+            NotShadowReceiver,
+        ));
+    }
+
+    // the hat goes last, and highest
+    commands.spawn((
+        Name::new("HatParticle"),
+        Mesh3d(meshes.add(Cylinder::new(0.4, 0.3))),
+        MeshMaterial3d(materials.add(Color::srgb(0.35, 0.2, 0.1))),
+        Transform::from_translation(position + Vec3::Y * 0.5),
+        ConfettiParticle {
+            velocity: Vec3::Y * 14.0,
+            spin: random_spin() * 0.5,
+            lifetime: 0.0,
+        },
+        Fx::Confetti,
+        NotShadowReceiver,
+    ));
+}
+
+fn update_confetti_particles(
+    mut commands: Commands,
+    time: GameTime,
+    mut particles: Query<(Entity, &mut Transform, &mut ConfettiParticle)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut transform, mut particle) in &mut particles {
+        particle.lifetime += dt;
+
+        if particle.lifetime > CONFETTI_LIFETIME || transform.translation.y < 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let drag = particle.velocity * CONFETTI_DRAG * dt;
+        particle.velocity -= drag;
+        particle.velocity.y -= CONFETTI_GRAVITY * dt;
+        transform.translation += particle.velocity * dt;
+        transform.rotate(Quat::from_scaled_axis(particle.spin * dt));
+    }
+}
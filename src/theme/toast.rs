@@ -0,0 +1,92 @@
+//! Short notification messages at the bottom of the screen.
+//! Trigger a [`Toast`] from anywhere, e.g. `commands.trigger(Toast::new("Achievement unlocked!"))`.
+
+use crate::clock::UiTime;
+use crate::localization::Localized;
+use crate::theme::palette::HEADER_TEXT;
+use crate::ui_assets::FontAssets;
+use bevy::color::palettes::css::BLACK;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, spawn_toast_container);
+    app.add_systems(Update, fade_out_toasts);
+    app.add_observer(show_toast);
+}
+
+const TOAST_DURATION_SECS: f32 = 3.0;
+const TOAST_FADE_SECS: f32 = 0.5;
+
+#[derive(Event, Debug, Clone)]
+pub struct Toast(pub String);
+
+impl Toast {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(text.into())
+    }
+}
+
+#[derive(Component)]
+struct ToastContainer;
+
+#[derive(Component)]
+struct ToastTimer(Timer);
+
+fn spawn_toast_container(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Toasts"),
+        ToastContainer,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(40.0),
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::ColumnReverse,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        // Don't block picking events for other UI roots.
+        Pickable::IGNORE,
+        GlobalZIndex(10),
+    ));
+}
+
+fn show_toast(
+    trigger: Trigger<Toast>,
+    container: Single<Entity, With<ToastContainer>>,
+    font_assets: Option<Res<FontAssets>>,
+    mut commands: Commands,
+) {
+    let font = font_assets.map(|f| f.content.clone()).unwrap_or_default();
+    let toast = commands
+        .spawn((
+            Name::new("Toast"),
+            Text(trigger.event().0.clone()),
+            Localized(trigger.event().0.clone()),
+            TextFont::from_font_size(28.0).with_font(font),
+            TextColor(HEADER_TEXT),
+            TextShadow {
+                color: BLACK.into(),
+                ..default()
+            },
+            ToastTimer(Timer::from_seconds(TOAST_DURATION_SECS, TimerMode::Once)),
+            Pickable::IGNORE,
+        ))
+        .id();
+    commands.entity(container.into_inner()).add_child(toast);
+}
+
+fn fade_out_toasts(
+    time: UiTime,
+    mut toasts: Query<(Entity, &mut ToastTimer, &mut TextColor)>,
+    mut commands: Commands,
+) {
+    for (entity, mut timer, mut color) in &mut toasts {
+        timer.0.tick(time.delta());
+        let remaining = timer.0.remaining_secs();
+        color.0.set_alpha((remaining / TOAST_FADE_SECS).min(1.0));
+        if timer.0.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
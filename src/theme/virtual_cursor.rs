@@ -0,0 +1,205 @@
+//! A cursor for gamepad players, moved with the right stick. It shows up as soon as the right stick
+//! is pushed in a menu, and works like hovering with the mouse: the button under it gets the
+//! [`MenuFocus`], so A presses it. The d-pad and left stick keep working as before, see
+//! [`crate::theme::navigation`].
+//!
+//! The longer the stick is held, the faster the cursor gets, so it can cross the screen quickly and
+//! still be placed precisely. Letting go of the stick next to a button snaps the cursor onto it.
+
+use crate::clock::UiTime;
+use crate::gameplay::input::LastInputKind;
+use crate::theme::interaction::InteractionPalette;
+use crate::theme::navigation::MenuFocus;
+use crate::theme::palette::BUTTON_HOVERED_BACKGROUND;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<VirtualCursor>();
+    app.add_systems(Startup, spawn_cursor);
+    app.add_systems(Update, (move_cursor, hover_buttons, draw_cursor).chain());
+}
+
+/// The stick has to be pushed further than this to move the cursor.
+const DEADZONE: f32 = 0.2;
+/// Speed in logical pixels per second with the stick fully pushed, right after pushing it.
+const BASE_SPEED: f32 = 500.0;
+/// How much faster the cursor gets for every second the stick is held.
+const ACCELERATION: f32 = 1.5;
+/// The cursor never gets faster than this many times [`BASE_SPEED`].
+const MAX_SPEED_FACTOR: f32 = 3.0;
+/// Letting go of the stick closer than this (in logical pixels) to a button snaps onto it.
+const SNAP_RADIUS: f32 = 80.0;
+/// How quickly the cursor glides onto the button it snaps to, higher is faster.
+const SNAP_RATE: f32 = 20.0;
+const CURSOR_SIZE: f32 = 20.0;
+
+#[derive(Resource, Debug, Default)]
+pub struct VirtualCursor {
+    /// In logical pixels, from the top left of the window.
+    pub position: Vec2,
+    /// Hidden until the right stick is pushed, and again once the mouse or keyboard are used.
+    pub visible: bool,
+    /// How long the stick has been pushed for.
+    held_seconds: f32,
+    /// The button under the cursor, if any.
+    hovered: Option<Entity>,
+}
+
+#[derive(Component, Debug)]
+struct VirtualCursorNode;
+
+type MenuButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static GlobalTransform,
+        &'static ComputedNode,
+        &'static InheritedVisibility,
+    ),
+    (With<Button>, With<InteractionPalette>),
+>;
+
+fn spawn_cursor(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Virtual Cursor"),
+        VirtualCursorNode,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(CURSOR_SIZE),
+            height: Val::Px(CURSOR_SIZE),
+            border: UiRect::all(Val::Px(3.0)),
+            ..default()
+        },
+        BorderColor(BUTTON_HOVERED_BACKGROUND),
+        BorderRadius::MAX,
+        Visibility::Hidden,
+        // Don't block picking events for other UI roots.
+        Pickable::IGNORE,
+        GlobalZIndex(100),
+    ));
+}
+
+/// Every visible menu button with its center and half size, in logical pixels.
+fn visible_buttons(buttons: &MenuButtons) -> Vec<(Entity, Vec2, Vec2)> {
+    buttons
+        .iter()
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(entity, transform, node, _)| {
+            let scale = node.inverse_scale_factor();
+            (
+                entity,
+                transform.translation().xy() * scale,
+                node.size() * scale / 2.0,
+            )
+        })
+        .collect()
+}
+
+fn move_cursor(
+    time: UiTime,
+    gamepads: Query<&Gamepad>,
+    last_input: Res<LastInputKind>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    buttons: MenuButtons,
+    mut cursor: ResMut<VirtualCursor>,
+) {
+    let buttons = visible_buttons(&buttons);
+    if buttons.is_empty() || *last_input != LastInputKind::Gamepad {
+        if cursor.visible {
+            cursor.visible = false;
+        }
+        return;
+    }
+
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| gamepad.right_stick())
+        .find(|stick| stick.length() > DEADZONE);
+    let Some(stick) = stick else {
+        cursor.held_seconds = 0.0;
+        if cursor.visible {
+            snap_to_nearest_button(&mut cursor, &buttons, time.delta_secs());
+        }
+        return;
+    };
+
+    if !cursor.visible {
+        // start out in the middle, where the menus are
+        cursor.visible = true;
+        cursor.position = window.size() / 2.0;
+    }
+    cursor.held_seconds += time.delta_secs();
+    let speed = BASE_SPEED * (1.0 + ACCELERATION * cursor.held_seconds).min(MAX_SPEED_FACTOR);
+    // squared, so small pushes are precise; the stick's y points up, the UI's down
+    let velocity = Vec2::new(stick.x, -stick.y) * stick.length() * speed;
+    cursor.position =
+        (cursor.position + velocity * time.delta_secs()).clamp(Vec2::ZERO, window.size());
+}
+
+fn snap_to_nearest_button(
+    cursor: &mut VirtualCursor,
+    buttons: &[(Entity, Vec2, Vec2)],
+    delta: f32,
+) {
+    let nearest = buttons
+        .iter()
+        .map(|(_, center, half_size)| {
+            // distance to the edge of the button, 0 inside of it
+            let outside = ((cursor.position - *center).abs() - *half_size).max(Vec2::ZERO);
+            (*center, outside.length())
+        })
+        .filter(|(_, distance)| *distance < SNAP_RADIUS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((center, _)) = nearest {
+        let position = cursor.position;
+        cursor.position = position.lerp(center, (SNAP_RATE * delta).min(1.0));
+    }
+}
+
+fn hover_buttons(
+    buttons: MenuButtons,
+    mut cursor: ResMut<VirtualCursor>,
+    mut focus: ResMut<MenuFocus>,
+) {
+    if !cursor.visible {
+        if cursor.hovered.is_some() {
+            cursor.hovered = None;
+        }
+        return;
+    }
+    let under_cursor = visible_buttons(&buttons)
+        .into_iter()
+        .find(|(_, center, half_size)| {
+            let offset = (cursor.position - *center).abs();
+            offset.x <= half_size.x && offset.y <= half_size.y
+        })
+        .map(|(entity, ..)| entity);
+    if under_cursor == cursor.hovered {
+        return;
+    }
+    // unlike with the mouse, the focus stays on the last button when leaving it, otherwise the
+    // gamepad navigation would jump back to the first one
+    if let Some(hovered) = under_cursor {
+        focus.0 = Some(hovered);
+    }
+    cursor.hovered = under_cursor;
+}
+
+fn draw_cursor(
+    cursor: Res<VirtualCursor>,
+    node: Single<(&mut Node, &mut Visibility), With<VirtualCursorNode>>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+    let (mut node, mut visibility) = node.into_inner();
+    node.left = Val::Px(cursor.position.x - CURSOR_SIZE / 2.0);
+    node.top = Val::Px(cursor.position.y - CURSOR_SIZE / 2.0);
+    *visibility = if cursor.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
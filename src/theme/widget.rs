@@ -2,7 +2,8 @@
 
 use std::borrow::Cow;
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::localization::Localized;
+use crate::theme::{interaction::InteractionPalette, navigation::activate_on_click, palette::*};
 use crate::ui_assets::PanelAssets;
 use bevy::{
     ecs::{spawn::SpawnWith, system::IntoObserverSystem},
@@ -31,18 +32,22 @@ pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
 
 /// A simple header label. Bigger than [`label`].
 pub fn header(text: impl Into<String>) -> impl Bundle {
+    let text = text.into();
     (
         Name::new("Header"),
-        Text(text.into()),
+        Text(text.clone()),
+        Localized(text),
         TextFont::from_font_size(40.0),
         TextColor(HEADER_TEXT),
     )
 }
 
 pub fn header_with_font(text: impl Into<String>, font: &Handle<Font>) -> impl Bundle {
+    let text = text.into();
     (
         Name::new("Header"),
-        Text(text.into()),
+        Text(text.clone()),
+        Localized(text),
         TextFont::from_font_size(40.0).with_font(font.clone()),
         TextColor(HEADER_TEXT),
     )
@@ -50,9 +55,11 @@ pub fn header_with_font(text: impl Into<String>, font: &Handle<Font>) -> impl Bu
 
 /// A simple text label.
 pub fn label(text: impl Into<String>) -> impl Bundle {
+    let text = text.into();
     (
         Name::new("Label"),
-        Text(text.into()),
+        Text(text.clone()),
+        Localized(text),
         TextFont::from_font_size(24.0),
         TextColor(LABEL_TEXT),
     )
@@ -60,9 +67,11 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
 
 /// A simple text label.
 pub fn label_with_font(text: impl Into<String>, font: &Handle<Font>) -> impl Bundle {
+    let text = text.into();
     (
         Name::new("Label"),
-        Text(text.into()),
+        Text(text.clone()),
+        Localized(text),
         TextFont::from_font_size(24.0).with_font(font.clone()),
         TextColor(LABEL_TEXT),
     )
@@ -140,7 +149,8 @@ where
                     },
                     children![(
                         Name::new("Button Text"),
-                        Text(text),
+                        Text(text.clone()),
+                        Localized(text),
                         TextFont::from_font_size(40.0),
                         TextColor(BUTTON_TEXT),
                         // Don't bubble picking events from the text up to the button.
@@ -148,6 +158,7 @@ where
                     )],
                 ))
                 .insert(button_bundle)
+                .observe(activate_on_click)
                 .observe(action);
         })),
     )
@@ -210,7 +221,8 @@ where
                     },
                     children![(
                         Name::new("Button Text"),
-                        Text(text),
+                        Text(text.clone()),
+                        Localized(text),
                         TextFont::from_font_size(40.0).with_font(font),
                         TextColor(BUTTON_TEXT),
                         // Don't bubble picking events from the text up to the button.
@@ -218,6 +230,7 @@ where
                     )],
                 ))
                 .insert(button_bundle)
+                .observe(activate_on_click)
                 .observe(action);
         })),
     )